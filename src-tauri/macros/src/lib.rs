@@ -0,0 +1,73 @@
+//! Attribute macros for the app crate.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Block, Ident, ItemFn, ReturnType, Type};
+
+/// Times a Tauri command with `utils::metrics`: how long it took and, when it
+/// returns a `Result`, the error variant it failed with.
+/// `#[metrics::timed(response_size)]` also buckets the response by its
+/// `metrics::ResponseSize`, for commands that return transcript text.
+///
+/// Goes below `#[specta::specta]` on every command registered in
+/// `bindings.rs`. The signature is left alone; only the body is wrapped.
+#[proc_macro_attribute]
+pub fn timed(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let sized = !attr.is_empty();
+    if sized {
+        let option = parse_macro_input!(attr as Ident);
+        if option != "response_size" {
+            return syn::Error::new(option.span(), "expected `response_size`")
+                .to_compile_error()
+                .into();
+        }
+    }
+    let mut function = parse_macro_input!(item as ItemFn);
+
+    let command = function.sig.ident.to_string();
+    let outcome = match (returns_result(&function.sig.output), sized) {
+        (true, false) => quote!(of_result),
+        (true, true) => quote!(of_sized_result),
+        (false, false) => quote!(of_value),
+        (false, true) => quote!(of_sized_value),
+    };
+    let output = match &function.sig.output {
+        ReturnType::Default => quote!(()),
+        ReturnType::Type(_, ty) => quote!(#ty),
+    };
+    let body = &function.block;
+    let timed: Block = if function.sig.asyncness.is_some() {
+        syn::parse_quote!({
+            crate::utils::metrics::time_async(
+                #command,
+                async move #body,
+                crate::utils::metrics::Outcome::#outcome,
+            )
+            .await
+        })
+    } else {
+        syn::parse_quote!({
+            crate::utils::metrics::time(
+                #command,
+                move || -> #output #body,
+                crate::utils::metrics::Outcome::#outcome,
+            )
+        })
+    };
+    *function.block = timed;
+    quote!(#function).into()
+}
+
+/// Whether the command returns a `Result` (any type path ending in `Result`).
+fn returns_result(output: &ReturnType) -> bool {
+    let ReturnType::Type(_, ty) = output else {
+        return false;
+    };
+    let Type::Path(path) = ty.as_ref() else {
+        return false;
+    };
+    path.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Result")
+}
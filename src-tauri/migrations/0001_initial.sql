@@ -0,0 +1,63 @@
+-- Drafts, transcripts with their search index, and the audit log
+
+CREATE TABLE IF NOT EXISTS drafts (
+    draft_id    TEXT PRIMARY KEY NOT NULL,
+    content     TEXT NOT NULL,
+    cursor_meta TEXT,
+    created_at  INTEGER NOT NULL,
+    updated_at  INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS transcripts (
+    id            TEXT PRIMARY KEY NOT NULL,
+    title         TEXT NOT NULL DEFAULT '',
+    text          TEXT NOT NULL,
+    language      TEXT,
+    model_id      TEXT,
+    duration_secs REAL NOT NULL DEFAULT 0,
+    note_id       TEXT,
+    created_at    INTEGER NOT NULL,
+    updated_at    INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_transcripts_created_at ON transcripts(created_at);
+CREATE INDEX IF NOT EXISTS idx_transcripts_note_id ON transcripts(note_id);
+
+-- Full-text index over transcripts, kept in sync by the triggers below.
+-- Keyed by id rather than rowid: rowids of a TEXT-keyed table can change on VACUUM.
+CREATE VIRTUAL TABLE IF NOT EXISTS transcripts_fts USING fts5(
+    id UNINDEXED,
+    title,
+    text,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_insert AFTER INSERT ON transcripts BEGIN
+    INSERT INTO transcripts_fts (id, title, text) VALUES (new.id, new.title, new.text);
+END;
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_delete AFTER DELETE ON transcripts BEGIN
+    DELETE FROM transcripts_fts WHERE id = old.id;
+END;
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_update AFTER UPDATE OF title, text ON transcripts BEGIN
+    UPDATE transcripts_fts SET title = new.title, text = new.text WHERE id = old.id;
+END;
+
+-- Remove deleted transcripts' tokens from the index instead of only marking them deleted
+INSERT INTO transcripts_fts (transcripts_fts, rank) VALUES ('secure-delete', 1);
+
+-- Append-only access log. Entries can't be edited, and entries younger than
+-- MIN_AUDIT_RETENTION_DAYS (365) can't be deleted; only audit::trim_expired removes older ones.
+CREATE TABLE IF NOT EXISTS audit_log (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp_ms INTEGER NOT NULL,
+    actor        TEXT NOT NULL,
+    kind         TEXT NOT NULL,
+    subject_id   TEXT,
+    detail       TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_audit_log_timestamp ON audit_log(timestamp_ms);
+CREATE TRIGGER IF NOT EXISTS audit_log_no_update BEFORE UPDATE ON audit_log BEGIN
+    SELECT RAISE(ABORT, 'audit log is append-only');
+END;
+CREATE TRIGGER IF NOT EXISTS audit_log_retention_only BEFORE DELETE ON audit_log
+WHEN old.timestamp_ms > (CAST(strftime('%s', 'now') AS INTEGER) - 365 * 86400) * 1000 BEGIN
+    SELECT RAISE(ABORT, 'audit entries are only removed by audit retention');
+END;
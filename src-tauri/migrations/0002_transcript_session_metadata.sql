@@ -0,0 +1,4 @@
+-- Word count and session label; existing word counts are filled in from Rust
+
+ALTER TABLE transcripts ADD COLUMN word_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE transcripts ADD COLUMN session_label TEXT;
@@ -0,0 +1,4 @@
+-- Soft delete: transcripts in the trash have a deletion time
+
+ALTER TABLE transcripts ADD COLUMN deleted_at INTEGER;
+CREATE INDEX IF NOT EXISTS idx_transcripts_deleted_at ON transcripts(deleted_at);
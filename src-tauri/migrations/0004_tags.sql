@@ -0,0 +1,13 @@
+-- Tag names are stored normalized (trimmed, lowercase)
+
+CREATE TABLE IF NOT EXISTS tags (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    name       TEXT NOT NULL UNIQUE,
+    created_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS transcript_tags (
+    transcript_id TEXT NOT NULL REFERENCES transcripts(id) ON DELETE CASCADE,
+    tag_id        INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (transcript_id, tag_id)
+);
+CREATE INDEX IF NOT EXISTS idx_transcript_tags_tag_id ON transcript_tags(tag_id);
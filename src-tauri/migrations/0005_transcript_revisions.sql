@@ -0,0 +1,11 @@
+-- Earlier versions of each transcript, pruned to the newest few
+
+CREATE TABLE IF NOT EXISTS transcript_revisions (
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    transcript_id TEXT NOT NULL REFERENCES transcripts(id) ON DELETE CASCADE,
+    title         TEXT NOT NULL,
+    text          TEXT NOT NULL,
+    created_at    INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_transcript_revisions_transcript_id
+    ON transcript_revisions(transcript_id, id);
@@ -0,0 +1,11 @@
+-- One row per successful transcription, saved or not. Timing only, no content.
+
+CREATE TABLE IF NOT EXISTS transcription_stats (
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    model_id      TEXT NOT NULL,
+    audio_secs    REAL NOT NULL,
+    processing_ms INTEGER NOT NULL,
+    completed_at  INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_transcription_stats_completed_at
+    ON transcription_stats(completed_at);
@@ -0,0 +1,3 @@
+-- Rule set a transcript was de-identified with on save (NULL when it wasn't)
+
+ALTER TABLE transcripts ADD COLUMN deidentified_with TEXT;
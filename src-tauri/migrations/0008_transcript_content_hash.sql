@@ -0,0 +1,4 @@
+-- Normalized-content hash for duplicate detection on save
+
+ALTER TABLE transcripts ADD COLUMN content_hash TEXT;
+CREATE INDEX IF NOT EXISTS idx_transcripts_content_hash ON transcripts(content_hash);
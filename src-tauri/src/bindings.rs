@@ -1,44 +1,264 @@
-use tauri_specta::{collect_commands, Builder};
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+use tauri_specta::{collect_commands, collect_events, Builder};
+
+use crate::utils::metrics;
+
+/// Committed bindings, relative to the crate
+const BINDINGS_PATH: &str = "../src/lib/bindings.ts";
+
+/// Generated constant holding their schema hash, relative to the crate
+const VERSION_PATH: &str = "src/bindings_version.rs";
+
+/// Hex digits of the schema hash kept
+const SCHEMA_HASH_LEN: usize = 16;
+
+// `BINDINGS_VERSION`: the schema hash of the committed bindings, written by
+// `export_ts_bindings` so it's known at build time
+include!("bindings_version.rs");
 
 pub fn generate_bindings() -> Builder<tauri::Wry> {
     use crate::commands::{
-        audio, llm, notifications, preferences, quick_pane, recovery, transcription,
+        app_lock, audio, audit, backup, backup_exclusion, cache, compliance, diagnostics,
+        emergency, export, health, jobs, llm, navigation, notifications, output, pipeline,
+        preferences, quick_pane, recovery, revisions, screen_capture, stats, storage, tags,
+        transcription, transcripts, updates,
     };
 
-    Builder::<tauri::Wry>::new().commands(collect_commands![
-        preferences::greet,
-        preferences::load_preferences,
-        preferences::save_preferences,
-        notifications::send_native_notification,
-        recovery::save_emergency_data,
-        recovery::load_emergency_data,
-        recovery::cleanup_old_recovery_files,
-        quick_pane::show_quick_pane,
-        quick_pane::dismiss_quick_pane,
-        quick_pane::toggle_quick_pane,
-        quick_pane::get_default_quick_pane_shortcut,
-        quick_pane::update_quick_pane_shortcut,
-        audio::start_recording,
-        audio::stop_recording,
-        transcription::list_whisper_models,
-        transcription::download_whisper_model,
-        transcription::transcribe_and_delete,
-        llm::check_llm_model,
-        llm::download_llm_model,
-        llm::generate_note_stream,
-    ])
-}
-
-/// Export TypeScript bindings to the frontend.
+    Builder::<tauri::Wry>::new()
+        .commands(collect_commands![
+            preferences::greet,
+            preferences::load_preferences,
+            preferences::save_preferences,
+            app_lock::get_app_lock_status,
+            app_lock::set_app_lock_passcode,
+            app_lock::unlock_app,
+            app_lock::get_biometric_availability,
+            app_lock::unlock_with_biometrics,
+            app_lock::lock_app,
+            app_lock::report_user_activity,
+            notifications::send_native_notification,
+            notifications::handle_transcription_notification,
+            notifications::send_test_notification,
+            recovery::save_emergency_data,
+            recovery::load_emergency_data,
+            recovery::cleanup_old_recovery_files,
+            recovery::list_recovery_sessions,
+            recovery::recover_session,
+            recovery::purge_recovery,
+            recovery::get_recovery_usage,
+            recovery::repair_recording,
+            recovery::export_recovery_audio,
+            recovery::save_draft,
+            recovery::load_drafts,
+            recovery::delete_draft,
+            transcripts::get_database_status,
+            storage::get_frontend_database_url,
+            storage::get_storage_usage,
+            storage::clear_category,
+            transcripts::save_transcript,
+            transcripts::deidentify_text,
+            transcripts::get_transcript,
+            transcripts::list_transcripts,
+            transcripts::update_transcript,
+            transcripts::update_session_label,
+            revisions::list_revisions,
+            revisions::restore_revision,
+            transcripts::delete_transcript,
+            transcripts::list_trash,
+            transcripts::restore_transcript,
+            transcripts::purge_transcript,
+            stats::get_usage_stats,
+            transcripts::search_transcripts,
+            tags::set_transcript_tags,
+            tags::list_tags,
+            tags::rename_tag,
+            tags::delete_tag,
+            export::export_transcript,
+            export::export_all_transcripts,
+            export::cancel_transcript_export,
+            export::decrypt_export,
+            backup::backup_database,
+            backup::restore_database,
+            backup::run_integrity_check,
+            compliance::generate_compliance_report,
+            cache::secure_wipe_cache,
+            emergency::emergency_wipe,
+            audit::list_audit_events,
+            audit::export_audit_log,
+            quick_pane::show_quick_pane,
+            output::paste_into_previous_app,
+            output::check_paste_permission,
+            output::type_out_text,
+            output::format_transcript,
+            output::cancel_type_out,
+            quick_pane::dismiss_quick_pane,
+            quick_pane::force_dismiss_quick_pane,
+            quick_pane::set_quick_pane_dirty,
+            quick_pane::toggle_quick_pane,
+            quick_pane::get_quick_pane_visible,
+            quick_pane::get_default_quick_pane_shortcut,
+            quick_pane::update_quick_pane_shortcut,
+            quick_pane::update_double_tap_modifier,
+            quick_pane::check_double_tap_permission,
+            quick_pane::update_quick_pane_appearance,
+            quick_pane::set_quick_pane_zoom,
+            quick_pane::zoom_quick_pane_in,
+            quick_pane::zoom_quick_pane_out,
+            screen_capture::get_screen_capture_state,
+            backup_exclusion::get_backup_exclusion_status,
+            audio::start_recording,
+            audio::stop_recording,
+            pipeline::get_pipeline_status,
+            transcription::list_whisper_models,
+            transcription::verify_all_models,
+            transcription::download_whisper_model,
+            transcription::resume_model_download,
+            transcription::transcribe_and_delete,
+            llm::check_llm_model,
+            llm::download_llm_model,
+            llm::generate_note_stream,
+            navigation::open_main_window,
+            navigation::main_window_ready,
+            updates::check_for_updates,
+            updates::download_and_install_update,
+            updates::set_update_channel,
+            updates::restart_to_update,
+            updates::cancel_pending_update_install,
+            get_bindings_version,
+            diagnostics::get_command_metrics,
+            diagnostics::get_diagnostics,
+            diagnostics::export_log_bundle,
+            jobs::get_job,
+            jobs::list_jobs,
+            jobs::cancel_job,
+            health::run_health_check,
+        ])
+        .events(collect_events![
+            crate::types::QuickPaneVisibilityChanged,
+            crate::types::QuickPaneHideRequested,
+            crate::types::Navigate,
+            crate::types::PreferencesChanged,
+            crate::types::PipelineStatus,
+            crate::types::JobProgress,
+            crate::types::HealthCheckProgress,
+            crate::types::RecoveryDegraded,
+            crate::types::RecoveryEvicted,
+            crate::types::RecoveryExpired,
+            crate::types::DraftsAvailable,
+            crate::types::PreviousSessionCrashed,
+            crate::types::RecordingsRepaired,
+            crate::types::ModelDownloadResumable,
+            crate::types::WhisperModelDownloadProgress,
+            crate::types::ModelDownloadComplete,
+            crate::types::ModelDownloadFailed,
+            crate::types::LowDiskSpace,
+            crate::types::LlmModelDownloadProgress,
+            crate::types::LlmChunk,
+            crate::types::LlmDone,
+            crate::types::LlmError,
+            crate::types::UpdateAvailable,
+            crate::types::UpdateDownloadProgress,
+            crate::types::UpdateInstallCountdown,
+            crate::types::NotificationSuppressed,
+            crate::types::TranscriptSaved,
+            crate::types::TranscriptCopied,
+            crate::types::ClipboardCleared,
+            crate::types::PasteFallback,
+            crate::types::FileAppendFailed,
+            crate::types::AppLocked,
+            crate::types::AutoStoppedAndLocked,
+            crate::types::DataDirCloudSynced,
+            crate::types::TranscriptExportProgress,
+            crate::types::DatabaseTransferProgress,
+            crate::types::DatabaseRecovered,
+            crate::types::DatabaseUnavailable,
+            crate::types::ShutdownBlocked,
+        ])
+}
+
+/// Generates the bindings without a header. The exporter only writes files,
+/// so this goes through a temp file.
+fn render_bindings() -> Result<String, String> {
+    let path =
+        std::env::temp_dir().join(format!("privacyscribe-bindings-{}.ts", std::process::id()));
+    generate_bindings()
+        .export(specta_typescript::Typescript::default(), &path)
+        .map_err(|e| format!("Failed to export TypeScript bindings: {e}"))?;
+    let rendered = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read generated bindings: {e}"));
+    let _ = std::fs::remove_file(&path);
+    rendered
+}
+
+/// Short hash of generated bindings, which change whenever a command, type
+/// or event signature does.
+fn schema_hash(rendered: &str) -> String {
+    Sha256::digest(rendered.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>()[..SCHEMA_HASH_LEN]
+        .to_string()
+}
+
+/// The bindings file: header with the schema hash, the generated code, and
+/// the hash again as a constant the frontend can compare with
+/// `get_bindings_version`.
+fn bindings_file(rendered: &str) -> String {
+    let hash = schema_hash(rendered);
+    format!(
+        "// @ts-nocheck\n// Auto-generated by tauri-specta. DO NOT EDIT.\n// Schema hash: {hash}\n\n{rendered}\nexport const BINDINGS_VERSION = \"{hash}\";\n"
+    )
+}
+
+/// The Rust side of `bindings_file`: the same hash as `BINDINGS_VERSION`,
+/// compiled into the app so `get_bindings_version` needs no exporter.
+fn version_file(rendered: &str) -> String {
+    let hash = schema_hash(rendered);
+    format!(
+        "// Auto-generated by `export_ts_bindings`. DO NOT EDIT.\n\n/// Schema hash of the committed TypeScript bindings\npub(crate) const BINDINGS_VERSION: &str = \"{hash}\";\n"
+    )
+}
+
+fn crate_path(relative: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(relative)
+}
+
+/// Writes `contents` to `path` unless it's already there. Returns whether
+/// the file changed.
+fn write_if_changed(path: &Path, contents: &str) -> bool {
+    if std::fs::read_to_string(path).is_ok_and(|committed| committed == contents) {
+        return false;
+    }
+    std::fs::write(path, contents)
+        .unwrap_or_else(|e| panic!("Failed to write {}: {e}", path.display()));
+    true
+}
+
+/// Export TypeScript bindings to the frontend, and their schema hash to
+/// `bindings_version.rs`. Files are only rewritten when the schema changed.
 /// Run with: cargo test export_bindings -- --ignored
 pub fn export_ts_bindings() {
-    generate_bindings()
-        .export(
-            specta_typescript::Typescript::default()
-                .header("// @ts-nocheck\n// Auto-generated by tauri-specta. DO NOT EDIT.\n\n"),
-            "../src/lib/bindings.ts",
-        )
-        .expect("Failed to export TypeScript bindings");
+    let rendered = render_bindings().expect("Failed to render bindings");
+    let bindings_changed = write_if_changed(&crate_path(BINDINGS_PATH), &bindings_file(&rendered));
+    let version_changed = write_if_changed(&crate_path(VERSION_PATH), &version_file(&rendered));
+    if !bindings_changed && !version_changed {
+        return;
+    }
+    println!(
+        "TypeScript bindings updated to schema {}",
+        schema_hash(&rendered)
+    );
+}
+
+/// Reports the schema hash of the commands, types and events this build
+/// exposes, to compare with `BINDINGS_VERSION` in the frontend's bindings.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn get_bindings_version() -> String {
+    BINDINGS_VERSION.to_string()
 }
 
 #[cfg(test)]
@@ -54,4 +274,63 @@ mod tests {
         export_ts_bindings();
         println!("✓ TypeScript bindings exported to ../src/lib/bindings.ts");
     }
+
+    /// Fails when the committed bindings are stale relative to the Rust
+    /// commands, types and events. Run before release builds with:
+    /// cargo test check_bindings -- --ignored
+    #[test]
+    #[ignore]
+    fn check_bindings() {
+        let rendered = render_bindings().unwrap();
+        for (path, expected) in [
+            (BINDINGS_PATH, bindings_file(&rendered)),
+            (VERSION_PATH, version_file(&rendered)),
+        ] {
+            let committed = std::fs::read_to_string(crate_path(path)).unwrap_or_default();
+            assert!(
+                committed == expected,
+                "{path} is stale; regenerate it with `npm run rust:bindings`"
+            );
+        }
+    }
+
+    #[test]
+    fn version_matches_the_committed_bindings() {
+        let committed = std::fs::read_to_string(crate_path(BINDINGS_PATH)).unwrap();
+        assert!(committed.contains(&format!("// Schema hash: {BINDINGS_VERSION}\n")));
+    }
+
+    #[test]
+    fn bindings_file_carries_the_schema_hash() {
+        let file = bindings_file("export const commands = {}\n");
+        let hash = schema_hash("export const commands = {}\n");
+        assert_eq!(hash.len(), SCHEMA_HASH_LEN);
+        assert!(file.contains(&format!("// Schema hash: {hash}\n")));
+        assert!(file.ends_with(&format!("export const BINDINGS_VERSION = \"{hash}\";\n")));
+        assert_ne!(hash, schema_hash("export const commands = { greet }\n"));
+        assert!(
+            version_file("export const commands = {}\n").contains(&format!(
+                "pub(crate) const BINDINGS_VERSION: &str = \"{hash}\";\n"
+            ))
+        );
+    }
+
+    /// Commands emit only the typed events registered above, so every event
+    /// name and payload ends up in the bindings. Raw `Emitter` calls with
+    /// string names and `json!` payloads would bypass them.
+    #[test]
+    fn commands_emit_only_typed_events() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/commands");
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let source = std::fs::read_to_string(&path).unwrap();
+            for raw in ["Emitter", ".emit(\"", ".emit_to(\"", "emit_filter("] {
+                assert!(
+                    !source.contains(raw),
+                    "{} emits a raw event ({raw})",
+                    path.display()
+                );
+            }
+        }
+    }
 }
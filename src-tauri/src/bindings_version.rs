@@ -0,0 +1,4 @@
+// Auto-generated by `export_ts_bindings`. DO NOT EDIT.
+
+/// Schema hash of the committed TypeScript bindings
+pub(crate) const BINDINGS_VERSION: &str = "a2f6a65bb619ca06";
@@ -0,0 +1,633 @@
+//! App lock: a passcode screen enforced by the backend.
+//!
+//! The passcode is stored as an Argon2 hash in the OS keychain. With one set,
+//! the app starts locked and locks again after `auto_lock_minutes` without
+//! activity (input the frontend reports via `report_user_activity`, plus any
+//! gated command) or on `lock_app`. While locked, every command that reads or
+//! exports PHI fails with a `Locked` error through `ensure_unlocked`, so
+//! hiding the lock screen in the webview exposes nothing. So does saving
+//! preferences beyond appearance, which would reach the lock, retention and
+//! de-identification settings. Saving recovery data and drafts stays allowed
+//! so a lock mid-dictation loses no work.
+//!
+//! After `FREE_ATTEMPTS` wrong passcodes, each further attempt waits twice as
+//! long as the one before, up to `MAX_RETRY_DELAY`. Attempts run one at a
+//! time, and the failure count and retry deadline are kept in the keychain
+//! next to the passcode, so neither parallel calls nor a restart reset them.
+//! While locked, `load_preferences` leaves out the de-identification names
+//! and patterns.
+//!
+//! With `biometric_unlock` on, `unlock_with_biometrics` unlocks through Touch
+//! ID or Windows Hello instead; the OS limits those attempts itself. When the
+//! prompt can't be shown or is cancelled the error says why, and the lock
+//! screen falls back to the passcode.
+//!
+//! When the app starts locked the database stays closed, its key unread, until
+//! the first unlock by either path.
+//!
+//! Recording keeps the app from idle-locking, but a recording that runs
+//! `max_idle_while_recording_minutes` with no input and no gated command is
+//! treated as a forgotten hot mic: `session_timeout` stops it, saves the
+//! transcription as a draft and locks.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use tauri::{AppHandle, Manager, State};
+use tauri_specta::Event;
+
+use crate::commands::audio::{self, RecordingPhase};
+use crate::commands::{
+    audit, cache, deep_link, preferences, recovery, session_timeout, transcripts,
+};
+use crate::db::Database;
+use crate::types::{
+    AppError, AppLockError, AppLockReason, AppLockStatus, AppLocked, AuditEventKind,
+    BiometricAvailability, BiometricFallbackReason, EmergencyWipeError, RecoveryError,
+    TranscriptError, TypeOutError, MIN_APP_LOCK_PASSCODE_LEN,
+};
+use crate::utils::{biometrics, metrics, secrets};
+
+/// Keychain entry holding the passcode hash
+pub(crate) const PASSCODE_SECRET_NAME: &str = "app-lock-passcode";
+
+/// Keychain entry holding the failed attempt count and retry deadline
+const ATTEMPTS_SECRET_NAME: &str = "app-lock-attempts";
+
+/// Error returned by gated commands whose errors are plain strings
+pub const LOCKED_MESSAGE: &str = "Locked";
+
+/// Shown in the OS biometric prompt
+const BIOMETRIC_PROMPT_REASON: &str = "unlock PrivacyScribe";
+
+/// How often the idle timer checks for inactivity
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Wrong passcodes allowed before attempts are delayed
+const FREE_ATTEMPTS: u32 = 3;
+
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(5);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+struct LockInner {
+    /// A passcode is set
+    enabled: bool,
+    locked: bool,
+    last_activity: Instant,
+    /// Like `last_activity`, but not refreshed by a running recording
+    last_input: Instant,
+    failed_attempts: u32,
+    retry_at: Option<Instant>,
+}
+
+/// Lock state, managed by Tauri.
+pub struct AppLockState {
+    inner: Mutex<LockInner>,
+    /// Held for a whole passcode check, so parallel attempts can't all pass
+    /// the rate limit before any failure is recorded
+    attempt: tokio::sync::Mutex<()>,
+}
+
+impl Default for AppLockState {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(LockInner {
+                enabled: false,
+                locked: false,
+                last_activity: Instant::now(),
+                last_input: Instant::now(),
+                failed_attempts: 0,
+                retry_at: None,
+            }),
+            attempt: tokio::sync::Mutex::new(()),
+        }
+    }
+}
+
+impl AppLockState {
+    fn with<T>(&self, f: impl FnOnce(&mut LockInner) -> T) -> T {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut inner)
+    }
+
+    fn touch(&self) {
+        self.with(|inner| inner.last_activity = Instant::now());
+    }
+
+    fn touch_input(&self) {
+        self.with(|inner| {
+            inner.last_activity = Instant::now();
+            inner.last_input = inner.last_activity;
+        });
+    }
+
+    fn retry_after(inner: &LockInner) -> Duration {
+        inner
+            .retry_at
+            .map(|at| at.saturating_duration_since(Instant::now()))
+            .unwrap_or_default()
+    }
+
+    fn check_rate_limit(&self) -> Result<(), AppLockError> {
+        self.with(|inner| match Self::retry_after(inner) {
+            wait if wait.is_zero() => Ok(()),
+            wait => Err(AppLockError::RateLimited {
+                retry_after_secs: secs_ceil(wait),
+            }),
+        })
+    }
+
+    fn record_failure(&self) -> AppLockError {
+        let (failed_attempts, delay) = self.with(|inner| {
+            inner.failed_attempts += 1;
+            let delay = retry_delay(inner.failed_attempts);
+            inner.retry_at = (!delay.is_zero()).then(|| Instant::now() + delay);
+            (inner.failed_attempts, delay)
+        });
+        let retry_at_ms = unix_ms(SystemTime::now() + delay);
+        if let Err(e) = secrets::set_secret_string(
+            ATTEMPTS_SECRET_NAME,
+            &encode_attempts(failed_attempts, retry_at_ms),
+        ) {
+            log::error!("Failed to persist app lock attempts: {e}");
+        }
+        AppLockError::WrongPasscode {
+            failed_attempts,
+            retry_after_secs: secs_ceil(delay),
+        }
+    }
+
+    fn record_success(&self) {
+        self.with(|inner| {
+            inner.failed_attempts = 0;
+            inner.retry_at = None;
+            inner.locked = false;
+            inner.last_activity = Instant::now();
+            inner.last_input = inner.last_activity;
+        });
+        clear_persisted_attempts();
+    }
+}
+
+/// Returned by `ensure_unlocked`; converts into each gated command's error type.
+#[derive(Debug)]
+pub struct Locked;
+
+impl From<Locked> for String {
+    fn from(_: Locked) -> Self {
+        LOCKED_MESSAGE.to_string()
+    }
+}
+
+impl From<Locked> for AppError {
+    fn from(_: Locked) -> Self {
+        AppError::Locked
+    }
+}
+
+impl From<Locked> for TranscriptError {
+    fn from(_: Locked) -> Self {
+        TranscriptError::Locked
+    }
+}
+
+impl From<Locked> for RecoveryError {
+    fn from(_: Locked) -> Self {
+        RecoveryError::Locked
+    }
+}
+
+impl From<Locked> for EmergencyWipeError {
+    fn from(_: Locked) -> Self {
+        EmergencyWipeError::Locked
+    }
+}
+
+impl From<Locked> for TypeOutError {
+    fn from(_: Locked) -> Self {
+        TypeOutError::Locked
+    }
+}
+
+/// Fails while the app is locked; otherwise counts as activity for the idle
+/// timer. Called first by every command that reads or exports PHI.
+pub fn ensure_unlocked(app: &AppHandle) -> Result<(), Locked> {
+    let state = app.state::<AppLockState>();
+    state.with(|inner| {
+        if inner.locked {
+            return Err(Locked);
+        }
+        inner.last_activity = Instant::now();
+        inner.last_input = inner.last_activity;
+        Ok(())
+    })
+}
+
+fn unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// `"<failed attempts> <retry deadline in unix ms>"`
+fn encode_attempts(failed_attempts: u32, retry_at_ms: u64) -> String {
+    format!("{failed_attempts} {retry_at_ms}")
+}
+
+fn decode_attempts(value: &str) -> Option<(u32, u64)> {
+    let (failed_attempts, retry_at_ms) = value.split_once(' ')?;
+    Some((failed_attempts.parse().ok()?, retry_at_ms.parse().ok()?))
+}
+
+/// Reads the attempt count and retry deadline persisted by `record_failure`.
+fn load_persisted_attempts() -> (u32, Option<Instant>) {
+    let value = match secrets::get_secret_string(ATTEMPTS_SECRET_NAME) {
+        Ok(value) => value,
+        Err(e) => {
+            log::error!("Failed to read app lock attempts: {e}");
+            None
+        }
+    };
+    let Some((failed_attempts, retry_at_ms)) = value.as_deref().and_then(decode_attempts) else {
+        return (0, None);
+    };
+    let wait = Duration::from_millis(retry_at_ms.saturating_sub(unix_ms(SystemTime::now())));
+    (
+        failed_attempts,
+        (!wait.is_zero()).then(|| Instant::now() + wait),
+    )
+}
+
+fn clear_persisted_attempts() {
+    if let Err(e) = secrets::delete_secret(ATTEMPTS_SECRET_NAME) {
+        log::error!("Failed to clear app lock attempts: {e}");
+    }
+}
+
+/// Whether an app lock passcode is set.
+pub fn is_enabled(app: &AppHandle) -> bool {
+    app.state::<AppLockState>().with(|inner| inner.enabled)
+}
+
+/// Whether the app is locked right now.
+pub fn is_locked(app: &AppHandle) -> bool {
+    app.state::<AppLockState>().with(|inner| inner.locked)
+}
+
+/// Startup work that needs the database: announcing its status, importing
+/// the legacy audit log and offering leftover drafts.
+pub fn database_opened(app: &AppHandle) {
+    transcripts::announce_database_status(app);
+    audit::import_legacy_log(app);
+    recovery::announce_drafts(app);
+}
+
+/// Opens the database if startup deferred it, off the async runtime.
+async fn open_deferred_database(app: &AppHandle) {
+    let app = app.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        if app.state::<Database>().open_deferred(&app) {
+            database_opened(&app);
+        }
+    })
+    .await;
+    if let Err(e) = result {
+        log::error!("Opening the database after unlock panicked: {e}");
+    }
+}
+
+/// Catches up on what waited behind the lock: the deferred database, then
+/// links received meanwhile.
+async fn unlocked(app: &AppHandle) {
+    open_deferred_database(app).await;
+    deep_link::run_queued(app);
+}
+
+fn secs_ceil(duration: Duration) -> u32 {
+    duration.as_millis().div_ceil(1000) as u32
+}
+
+/// Wait before the next attempt after `failed_attempts` wrong passcodes:
+/// none for the first few, then doubling.
+fn retry_delay(failed_attempts: u32) -> Duration {
+    if failed_attempts < FREE_ATTEMPTS {
+        return Duration::ZERO;
+    }
+    let doublings = (failed_attempts - FREE_ATTEMPTS).min(16);
+    (FIRST_RETRY_DELAY * 2u32.pow(doublings)).min(MAX_RETRY_DELAY)
+}
+
+fn hash_passcode(passcode: &str) -> Result<String, String> {
+    use chacha20poly1305::aead::{rand_core::RngCore, OsRng};
+
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let salt = SaltString::encode_b64(&salt).map_err(|e| e.to_string())?;
+    Argon2::default()
+        .hash_password(passcode.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| format!("Failed to hash passcode: {e}"))
+}
+
+fn passcode_matches(hash: &str, passcode: &str) -> bool {
+    PasswordHash::new(hash).is_ok_and(|parsed| {
+        Argon2::default()
+            .verify_password(passcode.as_bytes(), &parsed)
+            .is_ok()
+    })
+}
+
+fn storage_error(message: String) -> AppLockError {
+    AppLockError::StorageError { message }
+}
+
+/// Checks `passcode` against the stored hash, applying the attempt limit.
+/// Attempts are serialized so each sees the failures recorded before it.
+async fn verify(app: &AppHandle, passcode: String) -> Result<(), AppLockError> {
+    let state = app.state::<AppLockState>();
+    let _attempt = state.attempt.lock().await;
+    state.check_rate_limit()?;
+    let hash = secrets::get_secret_string(PASSCODE_SECRET_NAME)
+        .map_err(storage_error)?
+        .ok_or(AppLockError::NotEnabled)?;
+    // Argon2 is deliberately slow; keep it off the async runtime
+    let matches = tokio::task::spawn_blocking(move || passcode_matches(&hash, &passcode))
+        .await
+        .map_err(|e| storage_error(format!("Passcode check panicked: {e}")))?;
+    if matches {
+        state.record_success();
+        unlocked(app).await;
+        Ok(())
+    } else {
+        log::warn!("Wrong app lock passcode");
+        Err(state.record_failure())
+    }
+}
+
+/// Locks the app if a passcode is set, wiping the cache and emitting
+/// `app-locked`. Returns whether the app is locked afterwards.
+pub(crate) fn lock(app: &AppHandle, reason: AppLockReason) -> bool {
+    let (locked, newly_locked) = app.state::<AppLockState>().with(|inner| {
+        let newly_locked = inner.enabled && !inner.locked;
+        if newly_locked {
+            inner.locked = true;
+        }
+        (inner.locked, newly_locked)
+    });
+    if !newly_locked {
+        return locked;
+    }
+    log::info!("App locked ({reason:?})");
+    cache::wipe_in_background(app);
+    if let Err(e) = (AppLocked { reason }).emit(app) {
+        log::warn!("Failed to emit app-locked: {e}");
+    }
+    true
+}
+
+/// Reads whether a passcode is set. With one, the app starts locked, with
+/// the attempt limit carried over from the last run. Called once at startup.
+pub fn init(app: &AppHandle) {
+    let enabled = match secrets::get_secret_string(PASSCODE_SECRET_NAME) {
+        Ok(hash) => hash.is_some(),
+        Err(e) => {
+            // Fail closed: a passcode we can't read may still be set
+            log::error!("Failed to read app lock passcode, starting locked: {e}");
+            true
+        }
+    };
+    let (failed_attempts, retry_at) = load_persisted_attempts();
+    app.state::<AppLockState>().with(|inner| {
+        inner.enabled = enabled;
+        inner.locked = enabled;
+        inner.failed_attempts = failed_attempts;
+        inner.retry_at = retry_at;
+    });
+}
+
+fn from_minutes(minutes: u32) -> Duration {
+    Duration::from_secs(u64::from(minutes) * 60)
+}
+
+/// Locks the app after `auto_lock_minutes` without activity. Recording
+/// counts as activity, up to `max_idle_while_recording_minutes` without
+/// input, after which the recording is stopped and the app locked.
+pub fn spawn_idle_timer(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut was_recording = false;
+        loop {
+            tokio::time::sleep(IDLE_CHECK_INTERVAL).await;
+            let state = app.state::<AppLockState>();
+            if audio::recording_phase() != RecordingPhase::Idle {
+                // Starting a recording (possibly by global shortcut) is input
+                if was_recording {
+                    state.touch();
+                } else {
+                    state.touch_input();
+                }
+                was_recording = true;
+                let limit =
+                    preferences::load_saved_preferences(&app).max_idle_while_recording_minutes;
+                let idle = state.with(|inner| inner.last_input.elapsed());
+                if limit > 0 && idle >= from_minutes(limit) {
+                    session_timeout::stop_and_lock(&app, limit).await;
+                }
+                continue;
+            }
+            was_recording = false;
+            let minutes = preferences::load_saved_preferences(&app).auto_lock_minutes;
+            if minutes == 0 {
+                continue;
+            }
+            let idle = state.with(|inner| inner.last_activity.elapsed());
+            if idle >= from_minutes(minutes) {
+                lock(&app, AppLockReason::Idle);
+            }
+        }
+    });
+}
+
+/// Returns whether a passcode is set, whether the app is locked, and how
+/// long until another unlock attempt is accepted.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn get_app_lock_status(state: State<'_, AppLockState>) -> AppLockStatus {
+    state.with(|inner| AppLockStatus {
+        enabled: inner.enabled,
+        locked: inner.locked,
+        retry_after_secs: secs_ceil(AppLockState::retry_after(inner)),
+    })
+}
+
+/// Sets, changes or (with `passcode` None) removes the app lock passcode.
+/// When one is already set, `current_passcode` must match it, with the same
+/// attempt limit as `unlock_app`.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn set_app_lock_passcode(
+    app: AppHandle,
+    current_passcode: Option<String>,
+    passcode: Option<String>,
+) -> Result<(), AppLockError> {
+    let was_enabled = app.state::<AppLockState>().with(|inner| inner.enabled);
+    if was_enabled {
+        verify(&app, current_passcode.unwrap_or_default()).await?;
+    }
+
+    match &passcode {
+        Some(passcode) => {
+            if passcode.chars().count() < MIN_APP_LOCK_PASSCODE_LEN {
+                return Err(AppLockError::PasscodeTooShort {
+                    min_length: MIN_APP_LOCK_PASSCODE_LEN as u32,
+                });
+            }
+            let passcode = passcode.clone();
+            let hash = tokio::task::spawn_blocking(move || hash_passcode(&passcode))
+                .await
+                .map_err(|e| storage_error(format!("Passcode hashing panicked: {e}")))?
+                .map_err(storage_error)?;
+            secrets::set_secret_string(PASSCODE_SECRET_NAME, &hash).map_err(storage_error)?;
+        }
+        None => {
+            secrets::delete_secret(PASSCODE_SECRET_NAME).map_err(storage_error)?;
+            clear_persisted_attempts();
+        }
+    }
+    let enabled = passcode.is_some();
+    app.state::<AppLockState>().with(|inner| {
+        inner.enabled = enabled;
+        inner.locked = false;
+        inner.last_activity = Instant::now();
+    });
+
+    let state = |on: bool| if on { "on" } else { "off" };
+    audit::record(
+        &app,
+        AuditEventKind::PrivacySettingChanged,
+        None,
+        &format!("app_lock={}->{}", state(was_enabled), state(enabled)),
+    );
+    log::info!(
+        "App lock passcode {}",
+        if enabled { "set" } else { "removed" }
+    );
+    Ok(())
+}
+
+/// Unlocks the app. Wrong passcodes are counted; after a few, attempts are
+/// refused with `RateLimited` until the delay has passed.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn unlock_app(app: AppHandle, passcode: String) -> Result<(), AppLockError> {
+    verify(&app, passcode).await?;
+    log::info!("App unlocked");
+    Ok(())
+}
+
+/// Whether biometric unlock can be offered on this device, and with which
+/// sensor. The settings screen only shows the `biometric_unlock` toggle when
+/// this is `available`.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn get_biometric_availability() -> BiometricAvailability {
+    tokio::task::spawn_blocking(biometrics::availability)
+        .await
+        .unwrap_or(BiometricAvailability::Unavailable {
+            reason: BiometricFallbackReason::Unavailable,
+        })
+}
+
+/// Unlocks the app with the OS biometric prompt. Fails with
+/// `BiometricsUnavailable` (and the reason) when the preference is off, the
+/// device can't do it, or the prompt is cancelled or fails; the lock screen
+/// then asks for the passcode. Success also clears passcode attempt delays.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn unlock_with_biometrics(app: AppHandle) -> Result<(), AppLockError> {
+    if !app.state::<AppLockState>().with(|inner| inner.enabled) {
+        return Err(AppLockError::NotEnabled);
+    }
+    if !preferences::load_saved_preferences(&app).biometric_unlock {
+        return Err(AppLockError::BiometricsUnavailable {
+            reason: BiometricFallbackReason::Disabled,
+        });
+    }
+
+    let result = tokio::task::spawn_blocking(|| biometrics::authenticate(BIOMETRIC_PROMPT_REASON))
+        .await
+        .unwrap_or(Err(BiometricFallbackReason::Failed));
+    if let Err(reason) = result {
+        log::info!("Biometric unlock not used: {reason:?}");
+        return Err(AppLockError::BiometricsUnavailable { reason });
+    }
+    app.state::<AppLockState>().record_success();
+    unlocked(&app).await;
+    log::info!("App unlocked with biometrics");
+    Ok(())
+}
+
+/// Locks the app now and emits `app-locked`.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn lock_app(app: AppHandle) -> Result<(), AppLockError> {
+    if !app.state::<AppLockState>().with(|inner| inner.enabled) {
+        return Err(AppLockError::NotEnabled);
+    }
+    lock(&app, AppLockReason::Manual);
+    Ok(())
+}
+
+/// Resets the idle timers. The frontend calls this (throttled) on focus and
+/// input events. Ignored while locked.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn report_user_activity(state: State<'_, AppLockState>) {
+    state.with(|inner| {
+        if !inner.locked {
+            inner.last_activity = Instant::now();
+            inner.last_input = inner.last_activity;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_after_free_attempts() {
+        assert_eq!(retry_delay(1), Duration::ZERO);
+        assert_eq!(retry_delay(2), Duration::ZERO);
+        assert_eq!(retry_delay(3), Duration::from_secs(5));
+        assert_eq!(retry_delay(4), Duration::from_secs(10));
+        assert_eq!(retry_delay(6), Duration::from_secs(40));
+        assert_eq!(retry_delay(50), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn persisted_attempts_round_trip() {
+        let value = encode_attempts(4, 1_700_000_000_000);
+        assert_eq!(decode_attempts(&value), Some((4, 1_700_000_000_000)));
+        assert_eq!(decode_attempts("4"), None);
+        assert_eq!(decode_attempts("four 1700000000000"), None);
+    }
+
+    #[test]
+    fn passcode_hash_round_trips() {
+        let hash = hash_passcode("2468").unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(passcode_matches(&hash, "2468"));
+        assert!(!passcode_matches(&hash, "2469"));
+        assert!(!passcode_matches("not a hash", "2468"));
+    }
+}
@@ -6,19 +6,50 @@
 //!
 //! Because cpal::Stream is !Send, all stream operations happen on a dedicated
 //! recording thread. The async commands signal this thread via atomics.
+//!
+//! Start/stop are serialized through a small phase state machine so that
+//! overlapping calls (e.g. a mashed shortcut) can't race each other. A start
+//! only succeeds once the recording thread's stream is running, so a missing
+//! or refused microphone is reported as such.
+//!
+//! While recording, the recording thread appends captured samples to a
+//! recovery file every few seconds (see `recovery::RecoverySession`). The
+//! audio callback hands it each buffer through a bounded channel, so the
+//! flush never takes the capture buffer's lock.
+//!
+//! Samples are only ever held in buffers that zero their memory when
+//! dropped (`SampleBuffer`, `Zeroizing`), and the capture buffer is moved
+//! out on stop rather than copied.
 
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    mpsc::{Receiver, SyncSender, TrySendError},
     Arc, Mutex, OnceLock,
 };
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
+use zeroize::Zeroizing;
+
+use crate::commands::recovery::{self, RecoverySession};
+use crate::commands::{notifications, pipeline};
+use crate::types::{AppError, DiskSpaceContext, LowDiskSpace, PipelineErrorKind, PipelineStage};
+use crate::utils::audio::{
+    downmix_to_mono, resample_to_16k, write_wav_16k, SampleBuffer, TARGET_SAMPLE_RATE,
+};
+use crate::utils::disk::{self, DiskHeadroom};
+use crate::utils::metrics;
+use crate::utils::paths;
 
-const TARGET_SAMPLE_RATE: u32 = 16_000;
+/// How often captured samples are flushed to the recovery file
+const RECOVERY_FLUSH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Callback buffers the recovery channel holds. The recording thread drains
+/// it every 50ms, so it only fills up if a flush stalls for seconds.
+const RECOVERY_QUEUE_BUFFERS: usize = 1024;
 
 struct RawAudio {
-    samples: Vec<f32>,
+    samples: SampleBuffer,
     native_sample_rate: u32,
     native_channels: u16,
 }
@@ -27,10 +58,74 @@ static RAW_AUDIO: OnceLock<Arc<Mutex<RawAudio>>> = OnceLock::new();
 static IS_RECORDING: AtomicBool = AtomicBool::new(false);
 static STOP_SIGNAL: AtomicBool = AtomicBool::new(false);
 
+/// Set by the audio callback when the recovery channel was full and a buffer
+/// never reached the recovery file
+static RECOVERY_OVERRUN: AtomicBool = AtomicBool::new(false);
+
+/// Id of the current recording session; names both its recovery file and its WAV.
+static SESSION_ID: Mutex<Option<String>> = Mutex::new(None);
+
+/// Recording lifecycle phase. Only the transitions in `begin_transition` are allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingPhase {
+    Idle,
+    Starting,
+    Recording,
+    Stopping,
+}
+
+static RECORDING_PHASE: Mutex<RecordingPhase> = Mutex::new(RecordingPhase::Idle);
+
+/// Returns the current recording phase.
+pub fn recording_phase() -> RecordingPhase {
+    RECORDING_PHASE
+        .lock()
+        .map(|phase| *phase)
+        .unwrap_or(RecordingPhase::Idle)
+}
+
+/// Returns the session id of the recording in progress, if any.
+pub fn active_session_id() -> Option<String> {
+    if recording_phase() == RecordingPhase::Idle {
+        return None;
+    }
+    SESSION_ID.lock().ok().and_then(|current| current.clone())
+}
+
+/// Returns true while a start or stop is in flight.
+pub fn is_recording_transitioning() -> bool {
+    matches!(
+        recording_phase(),
+        RecordingPhase::Starting | RecordingPhase::Stopping
+    )
+}
+
+/// Atomically moves from `from` to `to`, failing with `busy_error` from any other phase.
+fn begin_transition(
+    from: RecordingPhase,
+    to: RecordingPhase,
+    busy_error: AppError,
+) -> Result<(), AppError> {
+    let mut phase = RECORDING_PHASE
+        .lock()
+        .map_err(|e| AppError::internal(format!("Lock poisoned: {e}")))?;
+    if *phase != from {
+        return Err(busy_error);
+    }
+    *phase = to;
+    Ok(())
+}
+
+fn set_phase(to: RecordingPhase) {
+    if let Ok(mut phase) = RECORDING_PHASE.lock() {
+        *phase = to;
+    }
+}
+
 fn get_raw_audio() -> &'static Arc<Mutex<RawAudio>> {
     RAW_AUDIO.get_or_init(|| {
         Arc::new(Mutex::new(RawAudio {
-            samples: Vec::new(),
+            samples: SampleBuffer::default(),
             native_sample_rate: 0,
             native_channels: 0,
         }))
@@ -41,19 +136,145 @@ fn get_raw_audio() -> &'static Arc<Mutex<RawAudio>> {
 /// Spawns a dedicated thread that creates and owns the cpal::Stream.
 #[tauri::command]
 #[specta::specta]
-pub async fn start_recording() -> Result<(), String> {
+#[metrics::timed]
+pub async fn start_recording(app: AppHandle) -> Result<(), AppError> {
+    begin_transition(
+        RecordingPhase::Idle,
+        RecordingPhase::Starting,
+        AppError::AlreadyRecording,
+    )?;
+
+    let result = start_recording_inner(&app).await;
+    match &result {
+        Ok(()) => {
+            set_phase(RecordingPhase::Recording);
+            pipeline::begin_job(&app, PipelineStage::Recording);
+        }
+        Err(_) => {
+            set_phase(RecordingPhase::Idle);
+            pipeline::publish(
+                &app,
+                PipelineStage::Error {
+                    kind: PipelineErrorKind::Recording,
+                },
+            );
+        }
+    }
+    result
+}
+
+/// Warns when free space covers less than `low_disk_warning_minutes` of recording
+/// and refuses to start below `low_disk_block_minutes`. If free space can't be
+/// determined, recording proceeds.
+fn check_disk_space(app: &AppHandle, sample_rate: u32, channels: u16) -> Result<(), AppError> {
+    let data_dir = paths::app_data_dir(app)
+        .map_err(|e| AppError::internal(format!("Failed to get app data dir: {e}")))?;
+    let available = match disk::available_bytes(&data_dir) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            log::warn!("Could not check free disk space: {e}");
+            return Ok(());
+        }
+    };
+
+    let prefs = crate::commands::preferences::load_saved_preferences(app);
+    let per_minute = disk::recording_bytes_per_minute(sample_rate, channels);
+    let warn_below = per_minute * u64::from(prefs.low_disk_warning_minutes);
+    let block_below = per_minute * u64::from(prefs.low_disk_block_minutes);
+
+    let headroom = disk::classify_headroom(available, warn_below, block_below);
+    if headroom == DiskHeadroom::Sufficient {
+        return Ok(());
+    }
+    let blocked = headroom == DiskHeadroom::Insufficient;
+    notifications::report_low_disk_space(
+        app,
+        LowDiskSpace {
+            context: DiskSpaceContext::Recording,
+            available_bytes: available as f64,
+            required_bytes: warn_below as f64,
+            blocked,
+        },
+    );
+    if blocked {
+        return Err(AppError::DiskFull {
+            required: Some(block_below as f64),
+            available: Some(available as f64),
+        });
+    }
+    Ok(())
+}
+
+/// Sample rate and channel count the default input device records at, if
+/// there is one.
+pub(crate) fn default_input_format() -> Option<(u32, u16)> {
+    let device = cpal::default_host().default_input_device()?;
+    let config = device.default_input_config().ok()?;
+    Some((config.sample_rate().0, config.channels()))
+}
+
+/// Opens the default input device for `duration` and counts the samples it
+/// delivers, without keeping them. Returns the device name and the count.
+/// Blocks; must not run while recording.
+pub(crate) fn probe_input_device(
+    duration: std::time::Duration,
+) -> Result<(String, usize), AppError> {
+    let device = cpal::default_host()
+        .default_input_device()
+        .ok_or(AppError::NoInputDevice)?;
+    let name = device
+        .name()
+        .unwrap_or_else(|_| "Unknown device".to_string());
+    let config = device.default_input_config()?;
+
+    let received = Arc::new(AtomicUsize::new(0));
+    let err_fn = |err: cpal::StreamError| {
+        log::warn!("Audio probe stream error: {err}");
+    };
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => {
+            let received = received.clone();
+            device.build_input_stream(
+                &config.into(),
+                move |data: &[f32], _: &_| {
+                    received.fetch_add(data.len(), Ordering::Relaxed);
+                },
+                err_fn,
+                None,
+            )
+        }
+        cpal::SampleFormat::I16 => {
+            let received = received.clone();
+            device.build_input_stream(
+                &config.into(),
+                move |data: &[i16], _: &_| {
+                    received.fetch_add(data.len(), Ordering::Relaxed);
+                },
+                err_fn,
+                None,
+            )
+        }
+        fmt => {
+            return Err(AppError::internal(format!(
+                "Unsupported sample format: {fmt:?}"
+            )))
+        }
+    }?;
+    stream.play()?;
+    std::thread::sleep(duration);
+    drop(stream);
+    Ok((name, received.load(Ordering::Relaxed)))
+}
+
+async fn start_recording_inner(app: &AppHandle) -> Result<(), AppError> {
     if IS_RECORDING.load(Ordering::SeqCst) {
-        return Err("Already recording".into());
+        return Err(AppError::AlreadyRecording);
     }
 
     // Pre-check that we have an input device
     let host = cpal::default_host();
-    let device = host
-        .default_input_device()
-        .ok_or("No input device available")?;
-    let config = device
-        .default_input_config()
-        .map_err(|e| format!("Failed to get input config: {e}"))?;
+    let device = host.default_input_device().ok_or(AppError::NoInputDevice)?;
+    let config = device.default_input_config()?;
 
     let native_rate = config.sample_rate().0;
     let native_channels = config.channels();
@@ -65,44 +286,48 @@ pub async fn start_recording() -> Result<(), String> {
         config.sample_format()
     );
 
+    check_disk_space(app, native_rate, native_channels)?;
+
     // Reset the shared buffer
     {
         let mut raw = get_raw_audio()
             .lock()
-            .map_err(|e| format!("Lock poisoned: {e}"))?;
+            .map_err(|e| AppError::internal(format!("Lock poisoned: {e}")))?;
         raw.samples.clear();
         raw.samples.reserve(native_rate as usize * 60);
         raw.native_sample_rate = native_rate;
         raw.native_channels = native_channels;
     }
 
+    let session_id = format!("rec_{}", timestamp_hex());
+    if let Ok(mut current) = SESSION_ID.lock() {
+        *current = Some(session_id.clone());
+    }
+
     let sample_format = config.sample_format();
+    let app = app.clone();
     STOP_SIGNAL.store(false, Ordering::SeqCst);
     IS_RECORDING.store(true, Ordering::SeqCst);
 
+    // The thread reports whether its stream started, so the start fails with the reason
+    let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+
     // The stream must be created and kept alive on the same thread (it's !Send).
     // We spawn a dedicated thread that creates the stream, records, and waits
     // for the stop signal.
     std::thread::spawn(move || {
         let host = cpal::default_host();
-        let device = match host.default_input_device() {
-            Some(d) => d,
-            None => {
-                log::error!("No input device on recording thread");
-                IS_RECORDING.store(false, Ordering::SeqCst);
-                return;
-            }
+        let Some(device) = host.default_input_device() else {
+            return fail_start(started_tx, AppError::NoInputDevice);
         };
         let config = match device.default_input_config() {
             Ok(c) => c,
-            Err(e) => {
-                log::error!("Failed to get input config on recording thread: {e}");
-                IS_RECORDING.store(false, Ordering::SeqCst);
-                return;
-            }
+            Err(e) => return fail_start(started_tx, e.into()),
         };
 
         let buf = get_raw_audio().clone();
+        let (recovery_tx, recovery_rx) = std::sync::mpsc::sync_channel(RECOVERY_QUEUE_BUFFERS);
+        RECOVERY_OVERRUN.store(false, Ordering::SeqCst);
         let err_fn = |err: cpal::StreamError| {
             log::error!("Audio stream error: {err}");
         };
@@ -114,8 +339,9 @@ pub async fn start_recording() -> Result<(), String> {
                     &config.into(),
                     move |data: &[f32], _: &_| {
                         if let Ok(mut raw) = buf.try_lock() {
-                            raw.samples.extend_from_slice(data);
+                            raw.samples.extend(data.iter().copied());
                         }
+                        queue_for_recovery(&recovery_tx, Zeroizing::new(data.to_vec()));
                     },
                     err_fn,
                     None,
@@ -126,60 +352,200 @@ pub async fn start_recording() -> Result<(), String> {
                 device.build_input_stream(
                     &config.into(),
                     move |data: &[i16], _: &_| {
+                        let samples: Zeroizing<Vec<f32>> = Zeroizing::new(
+                            data.iter().map(|&s| s as f32 / i16::MAX as f32).collect(),
+                        );
                         if let Ok(mut raw) = buf.try_lock() {
-                            for &s in data {
-                                raw.samples.push(s as f32 / i16::MAX as f32);
-                            }
+                            raw.samples.extend(samples.iter().copied());
                         }
+                        queue_for_recovery(&recovery_tx, samples);
                     },
                     err_fn,
                     None,
                 )
             }
             fmt => {
-                log::error!("Unsupported sample format: {fmt:?}");
-                IS_RECORDING.store(false, Ordering::SeqCst);
-                return;
+                let error = AppError::internal(format!("Unsupported sample format: {fmt:?}"));
+                return fail_start(started_tx, error);
             }
         };
 
         let stream = match stream {
             Ok(s) => s,
-            Err(e) => {
-                log::error!("Failed to build input stream: {e}");
-                IS_RECORDING.store(false, Ordering::SeqCst);
-                return;
-            }
+            Err(e) => return fail_start(started_tx, e.into()),
         };
 
         if let Err(e) = stream.play() {
-            log::error!("Failed to start stream: {e}");
-            IS_RECORDING.store(false, Ordering::SeqCst);
-            return;
+            return fail_start(started_tx, e.into());
         }
 
         log::info!("Recording stream active");
-
-        // Keep stream alive until stop signal
+        let _ = started_tx.send(Ok(()));
+
+        let mut recovery_session =
+            match RecoverySession::open(&app, &session_id, native_rate, native_channels) {
+                Ok(session) => Some(session),
+                Err(e) => {
+                    recovery::emit_degraded(&app, &session_id, e);
+                    None
+                }
+            };
+        let mut pending = SampleBuffer::default();
+        let mut last_flush = std::time::Instant::now();
+
+        // 0 disables the long-recording reminder
+        let reminder_minutes =
+            crate::commands::preferences::load_saved_preferences(&app).recording_reminder_minutes;
+        let reminder_interval = std::time::Duration::from_secs(u64::from(reminder_minutes) * 60);
+        let started_at = std::time::Instant::now();
+        let mut next_reminder = reminder_interval;
+
+        // Keep stream alive until stop signal, flushing to the recovery file periodically
         while !STOP_SIGNAL.load(Ordering::SeqCst) {
             std::thread::sleep(std::time::Duration::from_millis(50));
+            drain_recovery_queue(&recovery_rx, &mut pending);
+            if last_flush.elapsed() >= RECOVERY_FLUSH_INTERVAL {
+                flush_recovery(&app, &mut recovery_session, &mut pending);
+                last_flush = std::time::Instant::now();
+            }
+            if reminder_minutes > 0 && started_at.elapsed() >= next_reminder {
+                notifications::notify_still_recording(&app, next_reminder);
+                next_reminder += reminder_interval;
+            }
         }
 
         drop(stream);
+        drain_recovery_queue(&recovery_rx, &mut pending);
+        flush_recovery(&app, &mut recovery_session, &mut pending);
+        if let Some(session) = recovery_session {
+            if let Err(e) = session.finalize() {
+                log::warn!("Failed to finalize recovery file: {e}");
+            }
+        }
         IS_RECORDING.store(false, Ordering::SeqCst);
         log::info!("Recording stream dropped");
     });
 
+    started_rx
+        .await
+        .unwrap_or_else(|_| Err(AppError::internal("Recording thread exited")))?;
     log::info!("Recording started");
     Ok(())
 }
 
+/// Reports from the recording thread that its stream didn't start.
+fn fail_start(started_tx: tokio::sync::oneshot::Sender<Result<(), AppError>>, error: AppError) {
+    log::error!("Recording stream failed to start: {error}");
+    IS_RECORDING.store(false, Ordering::SeqCst);
+    let _ = started_tx.send(Err(error));
+}
+
+/// Hands a captured buffer to the recording thread without blocking the
+/// audio callback. A full channel is flagged rather than waited on.
+fn queue_for_recovery(queue: &SyncSender<Zeroizing<Vec<f32>>>, samples: Zeroizing<Vec<f32>>) {
+    if let Err(TrySendError::Full(_)) = queue.try_send(samples) {
+        RECOVERY_OVERRUN.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Moves the buffers queued by the audio callback into `pending`.
+fn drain_recovery_queue(queue: &Receiver<Zeroizing<Vec<f32>>>, pending: &mut SampleBuffer) {
+    for samples in queue.try_iter() {
+        pending.extend(samples.iter().copied());
+    }
+}
+
+/// Appends the samples received since the last flush to the recovery file.
+/// They come through the recovery channel, so the capture buffer is never
+/// locked here. If the channel overflowed or a write fails (e.g. disk full),
+/// the file would have a gap: the session is dropped and recording continues
+/// memory-only.
+fn flush_recovery(
+    app: &AppHandle,
+    session: &mut Option<RecoverySession>,
+    pending: &mut SampleBuffer,
+) {
+    let Some(active) = session.as_mut() else {
+        pending.clear();
+        return;
+    };
+
+    let result = if RECOVERY_OVERRUN.swap(false, Ordering::SeqCst) {
+        Err(std::io::Error::other("Recovery channel overflowed"))
+    } else if pending.is_empty() {
+        return;
+    } else {
+        active.append(pending).and_then(|()| {
+            if !active.needs_roll() {
+                return Ok(());
+            }
+            // Over the per-session cap: keep only the most recent window
+            active.roll()
+        })
+    };
+    pending.clear();
+
+    if let Err(e) = result {
+        recovery::emit_degraded(app, active.id(), e.to_string());
+        *session = None;
+    }
+}
+
+/// Stops a recording without keeping it: the stream ends, captured samples
+/// are dropped and no WAV is written. The recovery file is left to the
+/// caller. Returns whether a recording was running.
+pub async fn abort_recording(app: &AppHandle) -> bool {
+    if !IS_RECORDING.load(Ordering::SeqCst) {
+        return false;
+    }
+    STOP_SIGNAL.store(true, Ordering::SeqCst);
+    for _ in 0..100 {
+        if !IS_RECORDING.load(Ordering::SeqCst) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+
+    if let Ok(mut raw) = get_raw_audio().lock() {
+        raw.samples.clear();
+    }
+    if let Ok(mut current) = SESSION_ID.lock() {
+        current.take();
+    }
+    set_phase(RecordingPhase::Idle);
+    pipeline::publish(app, PipelineStage::Idle);
+    log::info!("Recording aborted");
+    true
+}
+
 /// Stop recording, resample to 16kHz mono WAV, and return the file path.
 #[tauri::command]
 #[specta::specta]
-pub async fn stop_recording(app: AppHandle) -> Result<String, String> {
+#[metrics::timed(response_size)]
+pub async fn stop_recording(app: AppHandle) -> Result<String, AppError> {
+    begin_transition(
+        RecordingPhase::Recording,
+        RecordingPhase::Stopping,
+        AppError::NotRecording,
+    )?;
+
+    pipeline::publish(&app, PipelineStage::Stopping);
+    let result = stop_recording_inner(&app).await;
+    set_phase(RecordingPhase::Idle);
+    if result.is_err() {
+        pipeline::publish(
+            &app,
+            PipelineStage::Error {
+                kind: PipelineErrorKind::Recording,
+            },
+        );
+    }
+    result
+}
+
+async fn stop_recording_inner(app: &AppHandle) -> Result<String, AppError> {
     if !IS_RECORDING.load(Ordering::SeqCst) {
-        return Err("Not recording".into());
+        return Err(AppError::NotRecording);
     }
 
     STOP_SIGNAL.store(true, Ordering::SeqCst);
@@ -192,19 +558,30 @@ pub async fn stop_recording(app: AppHandle) -> Result<String, String> {
         tokio::time::sleep(std::time::Duration::from_millis(20)).await;
     }
 
+    // Taken rather than copied: one buffer of the recording in memory, zeroed on drop
     let (raw_samples, native_rate, native_channels) = {
-        let raw = get_raw_audio()
+        let mut raw = get_raw_audio()
             .lock()
-            .map_err(|e| format!("Lock poisoned: {e}"))?;
+            .map_err(|e| AppError::internal(format!("Lock poisoned: {e}")))?;
         (
-            raw.samples.clone(),
+            std::mem::take(&mut raw.samples),
             raw.native_sample_rate,
             raw.native_channels,
         )
     };
 
+    let session_id = SESSION_ID
+        .lock()
+        .ok()
+        .and_then(|mut current| current.take())
+        .unwrap_or_else(|| format!("rec_{}", timestamp_hex()));
+
     if raw_samples.is_empty() {
-        return Err("No audio data captured".into());
+        // Nothing worth recovering
+        if let Err(e) = recovery::discard_session(app, &session_id) {
+            log::warn!("Failed to discard empty recovery file: {e}");
+        }
+        return Err(AppError::NoAudio);
     }
 
     log::info!(
@@ -214,13 +591,11 @@ pub async fn stop_recording(app: AppHandle) -> Result<String, String> {
         native_channels
     );
 
-    // Downmix to mono
-    let mono: Vec<f32> = raw_samples
-        .chunks(native_channels as usize)
-        .map(|frame: &[f32]| frame.iter().sum::<f32>() / frame.len() as f32)
-        .collect();
+    let mono = downmix_to_mono(&raw_samples, native_channels);
+    drop(raw_samples);
 
     // Resample to 16kHz if needed
+    pipeline::publish(app, PipelineStage::Resampling);
     let resampled = if native_rate == TARGET_SAMPLE_RATE {
         mono
     } else {
@@ -228,39 +603,18 @@ pub async fn stop_recording(app: AppHandle) -> Result<String, String> {
     };
 
     // Write WAV to cache directory
-    let cache_dir = app
-        .path()
-        .app_cache_dir()
-        .map_err(|e| format!("Failed to get cache dir: {e}"))?;
-    std::fs::create_dir_all(&cache_dir).map_err(|e| format!("Failed to create cache dir: {e}"))?;
-
-    let wav_path = cache_dir.join(format!("rec_{}.wav", timestamp_hex()));
-
-    let spec = hound::WavSpec {
-        channels: 1,
-        sample_rate: TARGET_SAMPLE_RATE,
-        bits_per_sample: 16,
-        sample_format: hound::SampleFormat::Int,
-    };
+    let cache_dir = paths::app_cache_dir(app)
+        .map_err(|e| AppError::internal(format!("Failed to get cache dir: {e}")))?;
+    std::fs::create_dir_all(&cache_dir)?;
 
-    let mut writer = hound::WavWriter::create(&wav_path, spec)
-        .map_err(|e| format!("Failed to create WAV: {e}"))?;
+    // Named after the session so a successful transcription can discard its recovery file
+    let wav_path = cache_dir.join(format!("{session_id}.wav"));
 
-    for &sample in &resampled {
-        let clamped: f32 = sample.clamp(-1.0, 1.0);
-        let as_i16 = (clamped * i16::MAX as f32) as i16;
-        writer
-            .write_sample(as_i16)
-            .map_err(|e| format!("Failed to write sample: {e}"))?;
-    }
-
-    writer
-        .finalize()
-        .map_err(|e| format!("Failed to finalize WAV: {e}"))?;
+    write_wav_16k(&wav_path, &resampled)?;
 
     let path_str = wav_path
         .to_str()
-        .ok_or("Path is not valid UTF-8")?
+        .ok_or_else(|| AppError::internal("Path is not valid UTF-8"))?
         .to_string();
 
     log::info!(
@@ -270,65 +624,6 @@ pub async fn stop_recording(app: AppHandle) -> Result<String, String> {
     Ok(path_str)
 }
 
-/// Resample audio from `source_rate` to 16kHz using an FFT-based resampler.
-fn resample_to_16k(mono: &[f32], source_rate: u32) -> Result<Vec<f32>, String> {
-    use rubato::{FftFixedIn, Resampler};
-
-    let ratio = TARGET_SAMPLE_RATE as f64 / source_rate as f64;
-    let chunk_size = 1024;
-    let sub_chunks = 2;
-
-    let mut resampler = FftFixedIn::<f64>::new(
-        source_rate as usize,
-        TARGET_SAMPLE_RATE as usize,
-        chunk_size,
-        sub_chunks,
-        1, // mono
-    )
-    .map_err(|e| format!("Failed to create resampler: {e}"))?;
-
-    let mono_f64: Vec<f64> = mono.iter().map(|&s| s as f64).collect();
-    let mut output = Vec::with_capacity((mono.len() as f64 * ratio * 1.1) as usize);
-
-    let frames_needed = resampler.input_frames_next();
-    let mut pos = 0;
-
-    while pos + frames_needed <= mono_f64.len() {
-        let chunk = &mono_f64[pos..pos + frames_needed];
-        let result = resampler
-            .process(&[chunk], None)
-            .map_err(|e| format!("Resample error: {e}"))?;
-        if let Some(channel) = result.first() {
-            output.extend(channel.iter().map(|&s| s as f32));
-        }
-        pos += frames_needed;
-    }
-
-    // Process remaining samples with zero-padding
-    if pos < mono_f64.len() {
-        let remaining = &mono_f64[pos..];
-        let mut padded = remaining.to_vec();
-        padded.resize(frames_needed, 0.0);
-        let result = resampler
-            .process(&[&padded], None)
-            .map_err(|e| format!("Resample error (tail): {e}"))?;
-        if let Some(channel) = result.first() {
-            let expected_tail = ((mono_f64.len() - pos) as f64 * ratio).ceil() as usize;
-            let take = expected_tail.min(channel.len());
-            output.extend(channel[..take].iter().map(|&s| s as f32));
-        }
-    }
-
-    log::info!(
-        "Resampled {} -> {} samples ({}Hz -> {}Hz)",
-        mono.len(),
-        output.len(),
-        source_rate,
-        TARGET_SAMPLE_RATE
-    );
-    Ok(output)
-}
-
 fn timestamp_hex() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
     let nanos = SystemTime::now()
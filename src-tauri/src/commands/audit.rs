@@ -0,0 +1,384 @@
+//! Append-only audit log of PHI-touching operations.
+//!
+//! Entries record who did what to which subject and when — never content.
+//! They live in the `audit_log` table of the encrypted backend database, which
+//! triggers keep append-only: no command can edit or delete an entry, and only
+//! `trim_expired` removes entries past the (long, separate) audit retention.
+//! Earlier versions wrote JSON lines to `app_data/audit.log`; those are
+//! imported on first start.
+
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use rusqlite::{params_from_iter, types::Value as SqlValue};
+use serde::Deserialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_fs::FsExt;
+
+use crate::db::Database;
+use crate::types::{
+    AuditEvent, AuditEventKind, AuditEventPage, AuditExportFormat, AuditFilter, Pagination,
+    MAX_TRANSCRIPT_PAGE_SIZE, MIN_AUDIT_RETENTION_DAYS,
+};
+use crate::utils::metrics;
+use crate::utils::paths;
+
+/// Legacy JSON-lines log, imported into the table once
+const LEGACY_AUDIT_LOG_FILENAME: &str = "audit.log";
+
+pub(crate) const MS_PER_DAY: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// OS account the app runs under, recorded as the actor of every entry
+static ACTOR: LazyLock<String> = LazyLock::new(|| {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+});
+
+/// The OS account the app runs under, when known.
+pub(crate) fn os_account() -> Option<&'static str> {
+    Some(ACTOR.as_str()).filter(|actor| *actor != "unknown")
+}
+
+fn insert_entry(
+    database: &Database,
+    timestamp_ms: f64,
+    kind: AuditEventKind,
+    subject_id: Option<&str>,
+    detail: &str,
+) -> Result<(), String> {
+    database.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO audit_log (timestamp_ms, actor, kind, subject_id, detail)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![
+                timestamp_ms as i64,
+                ACTOR.as_str(),
+                kind.as_str(),
+                subject_id,
+                detail
+            ],
+        )
+        .map(|_| ())
+    })
+}
+
+/// Records an audit event. `detail` must never contain PHI (ids and reasons only).
+/// Failures are logged rather than returned so auditing never blocks the operation.
+pub fn record(app: &AppHandle, kind: AuditEventKind, subject_id: Option<&str>, detail: &str) {
+    let timestamp_ms = crate::commands::pipeline::now_ms();
+    if let Err(e) = insert_entry(
+        &app.state::<Database>(),
+        timestamp_ms,
+        kind,
+        subject_id,
+        detail,
+    ) {
+        log::error!("Failed to record audit event {kind:?}: {e}");
+    }
+}
+
+/// A line of the legacy `audit.log`.
+#[derive(Deserialize)]
+struct LegacyEntry {
+    timestamp_ms: f64,
+    kind: AuditEventKind,
+    subject_id: Option<String>,
+    detail: String,
+}
+
+/// Moves entries from the legacy `audit.log` into the table, then removes the file.
+/// A file with unreadable lines is left in place rather than partially imported.
+pub fn import_legacy_log(app: &AppHandle) {
+    let Ok(dir) = paths::app_data_dir(app) else {
+        return;
+    };
+    let path = dir.join(LEGACY_AUDIT_LOG_FILENAME);
+    let Ok(file) = std::fs::File::open(&path) else {
+        return;
+    };
+
+    let entries: Result<Vec<LegacyEntry>, String> = std::io::BufReader::new(file)
+        .lines()
+        .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|line| {
+            let line = line.map_err(|e| e.to_string())?;
+            serde_json::from_str(&line).map_err(|e| e.to_string())
+        })
+        .collect();
+    let entries = match entries {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::error!("Legacy audit log is unreadable, leaving it in place: {e}");
+            return;
+        }
+    };
+
+    let imported = app.state::<Database>().with_conn(|conn| {
+        let tx = conn.transaction()?;
+        for entry in &entries {
+            tx.execute(
+                "INSERT INTO audit_log (timestamp_ms, actor, kind, subject_id, detail)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![
+                    entry.timestamp_ms as i64,
+                    ACTOR.as_str(),
+                    entry.kind.as_str(),
+                    entry.subject_id,
+                    entry.detail
+                ],
+            )?;
+        }
+        tx.commit()
+    });
+    if let Err(e) = imported {
+        log::error!("Failed to import legacy audit log: {e}");
+        return;
+    }
+
+    if let Err(e) = std::fs::remove_file(&path) {
+        log::warn!("Failed to remove imported legacy audit log: {e}");
+    }
+    log::info!("Imported {} legacy audit entries", entries.len());
+}
+
+/// Removes entries older than `audit_retention_days` (never less than
+/// `MIN_AUDIT_RETENTION_DAYS`), recording the trim itself. Run by the retention sweep.
+pub fn trim_expired(app: &AppHandle) {
+    let retention_days = crate::commands::preferences::load_saved_preferences(app)
+        .audit_retention_days
+        .max(MIN_AUDIT_RETENTION_DAYS);
+    let cutoff_ms = crate::commands::pipeline::now_ms() - f64::from(retention_days) * MS_PER_DAY;
+
+    let removed = app.state::<Database>().with_conn(|conn| {
+        conn.execute(
+            "DELETE FROM audit_log WHERE timestamp_ms < ?1",
+            [cutoff_ms as i64],
+        )
+    });
+    match removed {
+        Ok(0) => {}
+        Ok(removed) => {
+            log::info!("Trimmed {removed} audit entries older than {retention_days} days");
+            record(
+                app,
+                AuditEventKind::AuditLogTrimmed,
+                None,
+                &format!("retention_days={retention_days} entries={removed}"),
+            );
+        }
+        Err(e) => log::warn!("Failed to trim audit log: {e}"),
+    }
+}
+
+/// Builds the WHERE clause and its parameters for an audit filter.
+fn filter_clause(filter: &AuditFilter) -> (String, Vec<SqlValue>) {
+    let mut conditions = Vec::new();
+    let mut params = Vec::new();
+    if let Some(kind) = filter.kind {
+        conditions.push("kind = ?");
+        params.push(SqlValue::Text(kind.as_str().to_string()));
+    }
+    if let Some(subject_id) = &filter.subject_id {
+        conditions.push("subject_id = ?");
+        params.push(SqlValue::Text(subject_id.clone()));
+    }
+    if let Some(from) = filter.from_ms {
+        conditions.push("timestamp_ms >= ?");
+        params.push(SqlValue::Integer(from as i64));
+    }
+    if let Some(to) = filter.to_ms {
+        conditions.push("timestamp_ms < ?");
+        params.push(SqlValue::Integer(to as i64));
+    }
+
+    if conditions.is_empty() {
+        (String::new(), params)
+    } else {
+        (format!(" WHERE {}", conditions.join(" AND ")), params)
+    }
+}
+
+fn row_to_event(row: &rusqlite::Row) -> rusqlite::Result<AuditEvent> {
+    let kind: String = row.get(2)?;
+    Ok(AuditEvent {
+        timestamp_ms: row.get::<_, i64>(0)? as f64,
+        actor: row.get(1)?,
+        kind: serde_json::from_value(serde_json::Value::String(kind)).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, Box::new(e))
+        })?,
+        subject_id: row.get(3)?,
+        detail: row.get(4)?,
+    })
+}
+
+/// Queries entries newest first. `page` of None returns every match.
+fn query_events(
+    database: &Database,
+    filter: &AuditFilter,
+    page: Option<(u32, u32)>,
+) -> Result<(Vec<AuditEvent>, u32), String> {
+    let (clause, mut params) = filter_clause(filter);
+    database.with_conn(|conn| {
+        let total: u32 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM audit_log{clause}"),
+            params_from_iter(params.iter()),
+            |row| row.get(0),
+        )?;
+
+        let mut sql = format!(
+            "SELECT timestamp_ms, actor, kind, subject_id, detail FROM audit_log{clause}
+             ORDER BY timestamp_ms DESC, id DESC"
+        );
+        if let Some((limit, offset)) = page {
+            sql.push_str(" LIMIT ? OFFSET ?");
+            params.push(SqlValue::Integer(i64::from(limit)));
+            params.push(SqlValue::Integer(i64::from(offset)));
+        }
+        let mut stmt = conn.prepare(&sql)?;
+        let events = stmt
+            .query_map(params_from_iter(params.iter()), row_to_event)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok((events, total))
+    })
+}
+
+/// Lists audit entries matching `filter`, newest first.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn list_audit_events(
+    app: AppHandle,
+    filter: Option<AuditFilter>,
+    pagination: Option<Pagination>,
+) -> Result<AuditEventPage, String> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let pagination = pagination.unwrap_or_default();
+    let limit = pagination.limit.clamp(1, MAX_TRANSCRIPT_PAGE_SIZE);
+    let (events, total) = query_events(
+        &app.state::<Database>(),
+        &filter.unwrap_or_default(),
+        Some((limit, pagination.offset)),
+    )?;
+    Ok(AuditEventPage { events, total })
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn events_to_csv(events: &[AuditEvent]) -> String {
+    let mut csv = String::from("timestamp_ms,actor,kind,subject_id,detail\n");
+    for event in events {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            event.timestamp_ms,
+            csv_field(&event.actor),
+            event.kind.as_str(),
+            csv_field(event.subject_id.as_deref().unwrap_or("")),
+            csv_field(&event.detail),
+        ));
+    }
+    csv
+}
+
+/// Exports the whole audit log, newest first. `path` must come from the save
+/// dialog (which grants fs scope) and end in the format's extension. The export
+/// is itself audited. Returns the number of entries written.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn export_audit_log(
+    app: AppHandle,
+    path: String,
+    format: AuditExportFormat,
+) -> Result<u32, String> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let destination = PathBuf::from(&path);
+    let has_extension = destination
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(format.extension()));
+    if !destination.is_absolute() || !has_extension {
+        return Err(format!(
+            "Destination must be an absolute path to a .{} file",
+            format.extension()
+        ));
+    }
+    if !app.fs_scope().is_allowed(&destination) {
+        return Err("Destination was not chosen through the save dialog".to_string());
+    }
+
+    let (events, _) = query_events(&app.state::<Database>(), &AuditFilter::default(), None)?;
+    let contents = match format {
+        AuditExportFormat::Csv => events_to_csv(&events),
+        AuditExportFormat::Json => serde_json::to_string_pretty(&events)
+            .map_err(|e| format!("Failed to serialize audit log: {e}"))?,
+    };
+
+    let mut file = std::fs::File::create(&destination)
+        .map_err(|e| format!("Failed to create audit export: {e}"))?;
+    file.write_all(contents.as_bytes())
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Failed to write audit export: {e}"))?;
+
+    record(
+        &app,
+        AuditEventKind::AuditLogExported,
+        None,
+        &format!(
+            "destination={} entries={}",
+            destination.display(),
+            events.len()
+        ),
+    );
+    log::info!("Exported {} audit entries", events.len());
+    Ok(events.len() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_field("post_success"), "post_success");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn csv_export_has_header_and_one_row_per_event() {
+        let events = vec![AuditEvent {
+            timestamp_ms: 1_700_000_000_000.0,
+            actor: "clinician".into(),
+            kind: AuditEventKind::TranscriptDeleted,
+            subject_id: Some("tr_1".into()),
+            detail: String::new(),
+        }];
+        assert_eq!(
+            events_to_csv(&events),
+            "timestamp_ms,actor,kind,subject_id,detail\n\
+             1700000000000,clinician,transcript_deleted,tr_1,\n"
+        );
+    }
+
+    #[test]
+    fn kind_strings_match_serde_names() {
+        for kind in [
+            AuditEventKind::RecoveryPurged,
+            AuditEventKind::TranscriptViewed,
+            AuditEventKind::PrivacySettingChanged,
+        ] {
+            assert_eq!(
+                serde_json::to_value(kind).unwrap(),
+                serde_json::Value::String(kind.as_str().to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Database backup, restore and integrity commands.
+//!
+//! Backups are timestamped, encrypted copies of the backend database made
+//! while the app runs. They open only with this installation's keychain key.
+//! Restoring needs an explicit confirmation from the UI, because it replaces
+//! every transcript and draft. Both operations are audited and report
+//! progress through `database-transfer-progress`.
+
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_fs::FsExt;
+use tauri_specta::Event;
+
+use crate::commands::audit;
+use crate::db::{Database, MAX_REPORTED_PROBLEMS};
+use crate::types::{AuditEventKind, DatabaseOperation, DatabaseTransferProgress, IntegrityReport};
+use crate::utils::metrics;
+
+/// Returns a callback that emits progress for `operation`, skipping repeats.
+fn progress_reporter(app: &AppHandle, operation: DatabaseOperation) -> impl FnMut(u32) {
+    let app = app.clone();
+    let mut last = None;
+    move |percent| {
+        if last == Some(percent) {
+            return;
+        }
+        last = Some(percent);
+        let payload = DatabaseTransferProgress { operation, percent };
+        if let Err(e) = payload.emit(&app) {
+            log::warn!("Failed to emit database progress: {e}");
+        }
+    }
+}
+
+/// Backup filename for the current local time, e.g. `privacyscribe-backup-20250314-092600.db`.
+fn backup_filename() -> String {
+    format!(
+        "privacyscribe-backup-{}.db",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    )
+}
+
+/// Checks `path` is absolute and was granted by a file dialog.
+fn require_dialog_path(app: &AppHandle, path: &Path) -> Result<(), String> {
+    if !path.is_absolute() {
+        return Err("Path must be absolute".to_string());
+    }
+    if !app.fs_scope().is_allowed(path) {
+        return Err("Path was not chosen through a file dialog".to_string());
+    }
+    Ok(())
+}
+
+/// Writes a timestamped backup of the database into `destination_path`
+/// (a directory from the folder picker) and returns the backup's path.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn backup_database(app: AppHandle, destination_path: String) -> Result<String, String> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let dir = PathBuf::from(&destination_path);
+    if !dir.is_dir() {
+        return Err("Backup destination must be a folder".to_string());
+    }
+    let destination = dir.join(backup_filename());
+    require_dialog_path(&app, &destination)?;
+    if destination.exists() {
+        return Err("A backup with this name already exists".to_string());
+    }
+
+    let backup_app = app.clone();
+    let backup_path = destination.clone();
+    tokio::task::spawn_blocking(move || {
+        let progress = progress_reporter(&backup_app, DatabaseOperation::Backup);
+        backup_app
+            .state::<Database>()
+            .backup_to(&backup_path, progress)
+            .inspect_err(|_| {
+                // Don't leave a partial backup that looks usable
+                let _ = std::fs::remove_file(&backup_path);
+            })
+    })
+    .await
+    .map_err(|e| format!("Backup task panicked: {e}"))??;
+
+    audit::record(
+        &app,
+        AuditEventKind::DatabaseBackedUp,
+        None,
+        &format!("destination={}", destination.display()),
+    );
+    log::info!("Database backed up");
+    destination
+        .to_str()
+        .map(str::to_string)
+        .ok_or_else(|| "Backup path is not valid UTF-8".to_string())
+}
+
+/// Replaces the database with the backup at `source_path` (from the file
+/// picker). `confirm` must be true, i.e. the user accepted the warning that
+/// current data will be replaced. The replaced database is kept next to the
+/// live one as `.pre-restore`.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn restore_database(
+    app: AppHandle,
+    source_path: String,
+    confirm: bool,
+) -> Result<(), String> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    if !confirm {
+        return Err("Restoring a backup requires confirmation".to_string());
+    }
+    let source = PathBuf::from(&source_path);
+    require_dialog_path(&app, &source)?;
+    if !source.is_file() {
+        return Err(format!("Backup not found: {source_path}"));
+    }
+
+    let restore_app = app.clone();
+    let restore_source = source.clone();
+    let kept = tokio::task::spawn_blocking(move || {
+        let progress = progress_reporter(&restore_app, DatabaseOperation::Restore);
+        restore_app
+            .state::<Database>()
+            .restore_from(&restore_app, &restore_source, progress)
+    })
+    .await
+    .map_err(|e| format!("Restore task panicked: {e}"))??;
+
+    // Recorded in the restored database's log; the previous log is in the kept copy
+    audit::record(
+        &app,
+        AuditEventKind::DatabaseRestored,
+        None,
+        &format!("source={} previous={}", source.display(), kept.display()),
+    );
+    log::info!("Database restored");
+    Ok(())
+}
+
+/// Runs a full integrity check on the database for the diagnostics screen.
+/// Slower than the startup quick check; runs off the main thread.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn run_integrity_check(app: AppHandle) -> Result<IntegrityReport, String> {
+    let problems =
+        tokio::task::spawn_blocking(move || app.state::<Database>().integrity_problems())
+            .await
+            .map_err(|e| format!("Integrity check task panicked: {e}"))??;
+    if !problems.is_empty() {
+        log::warn!("Database integrity check found {} problems", problems.len());
+    }
+    Ok(IntegrityReport {
+        ok: problems.is_empty(),
+        problems: problems.into_iter().take(MAX_REPORTED_PROBLEMS).collect(),
+    })
+}
@@ -0,0 +1,140 @@
+//! Keeping PHI out of backups.
+//!
+//! Time Machine and iCloud would otherwise copy the cache and the encrypted
+//! recovery files to a backup disk or the cloud, outside the app's retention
+//! sweep. Both are marked excluded (see `utils::backup_exclusion`) at startup;
+//! the database directory too when `exclude_database_from_backups` is on,
+//! reapplied when it changes. On macOS that directory also holds the
+//! preferences, so excluding it means a restore starts from scratch.
+//!
+//! Windows has no such flag, but a data directory inside a OneDrive folder
+//! is synced to the cloud: that's reported once at startup through
+//! `data-dir-cloud-synced`.
+
+use std::path::PathBuf;
+
+use tauri::AppHandle;
+
+use crate::commands::{navigation, preferences, recovery};
+use crate::db;
+use crate::types::{
+    BackupExclusionState, BackupExclusionStatus, CloudSyncedDirectory, DataDirCloudSynced,
+    DataDirectory, DirectoryBackupExclusion,
+};
+use crate::utils::{backup_exclusion, metrics, paths};
+
+/// Where a data directory lives. Creates it if missing.
+fn directory_path(app: &AppHandle, directory: DataDirectory) -> Result<PathBuf, String> {
+    match directory {
+        DataDirectory::Cache => {
+            let dir = paths::app_cache_dir(app)
+                .map_err(|e| format!("Failed to get app cache directory: {e}"))?;
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create app cache directory: {e}"))?;
+            Ok(dir)
+        }
+        DataDirectory::Recovery => recovery::get_recovery_dir(app),
+        DataDirectory::Database => db::database_dir(app).map_err(|status| status.to_string()),
+    }
+}
+
+/// Each data directory with whether it should be excluded from backups.
+fn wanted_exclusions(app: &AppHandle) -> [(DataDirectory, bool); 3] {
+    let prefs = preferences::load_saved_preferences(app);
+    [
+        (DataDirectory::Cache, true),
+        (DataDirectory::Recovery, true),
+        (DataDirectory::Database, prefs.exclude_database_from_backups),
+    ]
+}
+
+/// Marks the data directories as excluded from (or included in) backups per
+/// the preferences. Does nothing where the platform has no exclusion.
+pub fn apply(app: &AppHandle) {
+    if let Some(reason) = backup_exclusion::UNSUPPORTED_REASON {
+        log::debug!("Backup exclusion not applied: {reason}");
+        return;
+    }
+    for (directory, excluded) in wanted_exclusions(app) {
+        let result = directory_path(app, directory)
+            .and_then(|path| backup_exclusion::set_excluded(&path, excluded));
+        match result {
+            Ok(()) => log::debug!("Backup exclusion of {directory:?} directory: {excluded}"),
+            Err(e) => log::warn!("Failed to set backup exclusion of {directory:?} directory: {e}"),
+        }
+    }
+}
+
+/// Data directories inside a cloud-synced folder.
+fn cloud_synced(app: &AppHandle) -> Vec<CloudSyncedDirectory> {
+    [
+        DataDirectory::Cache,
+        DataDirectory::Recovery,
+        DataDirectory::Database,
+    ]
+    .into_iter()
+    .filter_map(|directory| {
+        let path = directory_path(app, directory).ok()?;
+        let provider = backup_exclusion::cloud_synced_by(&path)?;
+        Some(CloudSyncedDirectory {
+            directory,
+            path: path.to_string_lossy().into_owned(),
+            provider: provider.to_string(),
+        })
+    })
+    .collect()
+}
+
+/// Emits `data-dir-cloud-synced` when any data directory is inside a
+/// cloud-synced folder.
+pub fn warn_if_cloud_synced(app: &AppHandle) {
+    let directories = cloud_synced(app);
+    if directories.is_empty() {
+        return;
+    }
+    log::warn!(
+        "{} data directories are inside a cloud-synced folder",
+        directories.len()
+    );
+    let payload = DataDirCloudSynced { directories };
+    if let Err(e) = navigation::emit_to_main_when_ready(app, payload) {
+        log::warn!("Failed to emit data-dir-cloud-synced: {e}");
+    }
+}
+
+fn exclusion_state(app: &AppHandle, directory: DataDirectory) -> DirectoryBackupExclusion {
+    let path = directory_path(app, directory);
+    let state = match (&path, backup_exclusion::UNSUPPORTED_REASON) {
+        (Err(e), _) => BackupExclusionState::Error { message: e.clone() },
+        (Ok(_), Some(reason)) => BackupExclusionState::Unsupported {
+            reason: reason.to_string(),
+        },
+        (Ok(path), None) => match backup_exclusion::is_excluded(path) {
+            Ok(true) => BackupExclusionState::Excluded,
+            Ok(false) => BackupExclusionState::Included,
+            Err(message) => BackupExclusionState::Error { message },
+        },
+    };
+    DirectoryBackupExclusion {
+        directory,
+        path: path
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        state,
+    }
+}
+
+/// What the OS reports for each data directory, and which of them are
+/// inside a cloud-synced folder.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn get_backup_exclusion_status(app: AppHandle) -> BackupExclusionStatus {
+    BackupExclusionStatus {
+        directories: wanted_exclusions(&app)
+            .into_iter()
+            .map(|(directory, _)| exclusion_state(&app, directory))
+            .collect(),
+        cloud_synced: cloud_synced(&app),
+    }
+}
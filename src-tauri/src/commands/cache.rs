@@ -0,0 +1,275 @@
+//! Secure wiping of the app cache directory.
+//!
+//! The cache briefly holds recording WAVs, recovery temp files and webview
+//! artifacts. They're overwritten and removed with `secure_delete` on quit,
+//! when the app locks, and on demand through `secure_wipe_cache`.
+//!
+//! While a recording or transcription is running its audio (`rec_*` files) is
+//! left alone, as is the webview's own cache while the app runs. The quit
+//! wipe has a time budget so a large cache can't stall shutdown; what's left
+//! is recorded in a marker and wiped at the next start, limited to files
+//! older than the quit so a later session's audio isn't touched.
+//!
+//! Recordings stranded by a crash and not yet transcribed are wiped too;
+//! their encrypted recovery file stays the fallback.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use tauri::AppHandle;
+
+use crate::commands::{audio, pipeline};
+use crate::types::CacheWipeReport;
+use crate::utils::metrics;
+use crate::utils::paths;
+use crate::utils::secure_delete::secure_delete;
+
+/// How long the quit wipe may delay shutdown
+const QUIT_BUDGET: Duration = Duration::from_secs(2);
+
+/// Marker in the app data dir holding the time of a quit wipe that ran out
+/// of budget (milliseconds since the Unix epoch)
+const PENDING_WIPE_FILENAME: &str = "cache-wipe-pending";
+
+/// Prefix of recording and recovered WAVs in the cache
+const RECORDING_PREFIX: &str = "rec_";
+
+/// Webview cache directories (WKWebView, WebView2), wiped only on quit
+const WEBVIEW_DIRS: &[&str] = &["WebKit", "EBWebView"];
+
+static WIPING: AtomicBool = AtomicBool::new(false);
+
+/// Limits on a single wipe.
+struct WipeScope {
+    /// Stop starting new files after this
+    deadline: Option<Instant>,
+    /// Only wipe files last modified before this
+    modified_before: Option<SystemTime>,
+    /// The app keeps running afterwards, so its webview cache is in use
+    running: bool,
+    /// A recording or transcription holds its audio
+    busy: bool,
+}
+
+impl WipeScope {
+    fn new(deadline: Option<Instant>, modified_before: Option<SystemTime>, running: bool) -> Self {
+        Self {
+            deadline,
+            modified_before,
+            running,
+            busy: pipeline::is_busy() || audio::recording_phase() != audio::RecordingPhase::Idle,
+        }
+    }
+}
+
+/// Whether `name` (a file or directory directly in the cache, or below it)
+/// must be left in place.
+fn is_in_use(name: &str, at_top_level: bool, scope: &WipeScope) -> bool {
+    (scope.busy && name.starts_with(RECORDING_PREFIX))
+        || (scope.running && at_top_level && WEBVIEW_DIRS.contains(&name))
+}
+
+fn wipe_dir(dir: &Path, at_top_level: bool, scope: &WipeScope, report: &mut CacheWipeReport) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(Result::ok) {
+        if scope
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            report.complete = false;
+            return;
+        }
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        // Symlinks are unlinked, never followed
+        let Ok(metadata) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if is_in_use(&name, at_top_level, scope) {
+            report.files_skipped += 1;
+            continue;
+        }
+        if metadata.is_dir() {
+            wipe_dir(&path, false, scope, report);
+            // Fails harmlessly while something inside was skipped
+            let _ = std::fs::remove_dir(&path);
+            continue;
+        }
+        let too_new = scope
+            .modified_before
+            .is_some_and(|before| !metadata.modified().is_ok_and(|modified| modified < before));
+        if too_new {
+            continue;
+        }
+        let result = if metadata.file_type().is_symlink() {
+            std::fs::remove_file(&path)
+        } else {
+            secure_delete(&path)
+        };
+        match result {
+            Ok(()) => {
+                report.files_wiped += 1;
+                report.bytes_wiped += metadata.len() as f64;
+            }
+            Err(e) => {
+                log::warn!("Failed to wipe cached file {path:?}: {e}");
+                report.files_skipped += 1;
+            }
+        }
+    }
+}
+
+fn wipe(app: &AppHandle, scope: WipeScope) -> Result<CacheWipeReport, String> {
+    let dir = paths::app_cache_dir(app).map_err(|e| format!("Failed to get cache dir: {e}"))?;
+    if WIPING.swap(true, Ordering::SeqCst) {
+        return Err("A cache wipe is already running".to_string());
+    }
+    let started = Instant::now();
+    let mut report = CacheWipeReport {
+        complete: true,
+        ..Default::default()
+    };
+    wipe_dir(&dir, true, &scope, &mut report);
+    report.duration_ms = started.elapsed().as_millis() as u32;
+    WIPING.store(false, Ordering::SeqCst);
+
+    log::info!(
+        "Wiped {} cached files ({} bytes) in {}ms, {} skipped{}",
+        report.files_wiped,
+        report.bytes_wiped,
+        report.duration_ms,
+        report.files_skipped,
+        if report.complete { "" } else { ", out of time" }
+    );
+    Ok(report)
+}
+
+fn pending_wipe_path(app: &AppHandle) -> Result<PathBuf, String> {
+    paths::app_data_dir(app)
+        .map(|dir| dir.join(PENDING_WIPE_FILENAME))
+        .map_err(|e| format!("Failed to get app data dir: {e}"))
+}
+
+/// Wipes the cache within `QUIT_BUDGET`, leaving a marker for the next start
+/// if it runs out. Called when the app exits.
+pub fn wipe_on_quit(app: &AppHandle) {
+    let started_at_ms = pipeline::now_ms();
+    let scope = WipeScope::new(Some(Instant::now() + QUIT_BUDGET), None, false);
+    let complete = match wipe(app, scope) {
+        Ok(report) => report.complete,
+        Err(e) => {
+            log::warn!("Cache wipe on quit skipped: {e}");
+            false
+        }
+    };
+    if complete {
+        return;
+    }
+    match pending_wipe_path(app) {
+        Ok(path) => {
+            if let Err(e) = std::fs::write(&path, started_at_ms.to_string()) {
+                log::warn!("Failed to record pending cache wipe: {e}");
+            }
+        }
+        Err(e) => log::warn!("Failed to record pending cache wipe: {e}"),
+    }
+}
+
+/// Finishes a quit wipe that ran out of time, for files that were already
+/// there at quit. Called once at startup, off the main thread.
+pub fn finish_pending_wipe(app: &AppHandle) {
+    let Ok(path) = pending_wipe_path(app) else {
+        return;
+    };
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return;
+    };
+    let quit_at = contents
+        .trim()
+        .parse::<f64>()
+        .map(|ms| UNIX_EPOCH + Duration::from_millis(ms as u64))
+        .unwrap_or_else(|_| SystemTime::now());
+    let scope = WipeScope::new(None, Some(quit_at), true);
+    match wipe(app, scope) {
+        Ok(_) => {
+            if let Err(e) = std::fs::remove_file(&path) {
+                log::warn!("Failed to remove pending cache wipe marker: {e}");
+            }
+        }
+        Err(e) => log::warn!("Pending cache wipe skipped: {e}"),
+    }
+}
+
+/// Wipes everything in the cache except the webview's own files, including
+/// audio of a running job. Used by the emergency wipe.
+pub fn wipe_everything(app: &AppHandle) -> Result<CacheWipeReport, String> {
+    let scope = WipeScope {
+        busy: false,
+        ..WipeScope::new(None, None, true)
+    };
+    wipe(app, scope)
+}
+
+/// Wipes the cache in the background. Called when the app locks.
+pub fn wipe_in_background(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = wipe(&app, WipeScope::new(None, None, true)) {
+            log::warn!("Cache wipe on lock skipped: {e}");
+        }
+    });
+}
+
+/// Wipes the cache now, skipping what's in use. Blocks.
+pub fn wipe_now(app: &AppHandle) -> Result<CacheWipeReport, String> {
+    wipe(app, WipeScope::new(None, None, true))
+}
+
+/// Securely wipes the cache directory now and reports what was removed.
+/// Audio in use by a recording or transcription, and the webview's cache,
+/// are skipped.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn secure_wipe_cache(app: AppHandle) -> Result<CacheWipeReport, String> {
+    tokio::task::spawn_blocking(move || wipe_now(&app))
+        .await
+        .map_err(|e| format!("Cache wipe task panicked: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(running: bool, busy: bool) -> WipeScope {
+        WipeScope {
+            deadline: None,
+            modified_before: None,
+            running,
+            busy,
+        }
+    }
+
+    #[test]
+    fn recordings_are_kept_only_while_busy() {
+        assert!(is_in_use("rec_1a2b.wav", true, &scope(true, true)));
+        assert!(is_in_use(
+            "rec_1a2b_recovered.wav",
+            true,
+            &scope(false, true)
+        ));
+        assert!(!is_in_use("rec_1a2b.wav", true, &scope(true, false)));
+        assert!(!is_in_use("export.tmp", true, &scope(true, true)));
+    }
+
+    #[test]
+    fn webview_cache_is_kept_while_running() {
+        assert!(is_in_use("WebKit", true, &scope(true, false)));
+        assert!(!is_in_use("WebKit", true, &scope(false, false)));
+        // Only the top-level webview directory is special
+        assert!(!is_in_use("WebKit", false, &scope(true, false)));
+    }
+}
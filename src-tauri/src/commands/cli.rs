@@ -0,0 +1,315 @@
+//! Command-line arguments, so scripts and hardware buttons can drive the app.
+//!
+//! Read at launch, and forwarded by the single-instance plugin when the app
+//! is started again while running:
+//!
+//! - `--start-recording` starts a recording, or stops the one in progress and
+//!   transcribes it (auto-saved, and delivered per the preferences)
+//! - `--quick-pane` toggles the quick pane
+//! - `--transcribe <path>` transcribes a 16kHz mono WAV, leaving the file in
+//!   place, then copies the text to the clipboard (never printed, since
+//!   stdout ends up in terminal scrollback and logs)
+//! - `--hidden` starts without the main window (see the `start_hidden`
+//!   preference)
+//!
+//! Unknown arguments are logged and ignored; `privacyscribe://` links are
+//! left to `deep_link`, which runs its actions through here too. A recording
+//! or transcription that would collide with the job in flight is queued until
+//! the pipeline is idle, with a notification saying so.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::AppHandle;
+
+use crate::commands::audio::{self, RecordingPhase};
+use crate::commands::{
+    app_lock, clipboard, deep_link, notifications, pipeline, quick_pane, transcription,
+};
+use crate::types::NotificationCategory;
+use crate::utils::audio::TARGET_SAMPLE_RATE;
+use crate::utils::paths::PORTABLE_ARG;
+
+/// Command-line argument that starts the app without showing the main window
+pub const HIDDEN_ARG: &str = "--hidden";
+
+/// How often queued actions check whether the pipeline went idle
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Something asked for on the command line or by a link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum CliAction {
+    /// Start a recording, or stop and transcribe the one in progress.
+    /// `language` applies to a recording this starts.
+    ToggleRecording {
+        language: Option<String>,
+    },
+    ToggleQuickPane,
+    /// Transcribe a WAV file without deleting it
+    Transcribe(PathBuf),
+}
+
+impl CliAction {
+    /// What the action does, to finish "PrivacyScribe will …". Leaves out
+    /// file names, which can identify a patient.
+    fn describe(&self) -> &'static str {
+        match self {
+            CliAction::ToggleRecording { .. } => "start or stop recording",
+            CliAction::ToggleQuickPane => "toggle the quick pane",
+            CliAction::Transcribe(_) => "transcribe the file",
+        }
+    }
+}
+
+struct Queue {
+    actions: VecDeque<CliAction>,
+    /// Whether a task is waiting to run `actions`
+    draining: bool,
+}
+
+/// Actions waiting for the job in flight to finish
+static QUEUE: Mutex<Queue> = Mutex::new(Queue {
+    actions: VecDeque::new(),
+    draining: false,
+});
+
+/// Session id and language of the recording a request started, for its transcription
+static RECORDING_LANGUAGE: Mutex<Option<(String, Option<String>)>> = Mutex::new(None);
+
+/// The actions in `args` (without the executable), in order, and the
+/// arguments that weren't understood. Relative paths resolve against `cwd`.
+fn parse(args: &[String], cwd: &Path) -> (Vec<CliAction>, Vec<String>) {
+    let mut actions = Vec::new();
+    let mut unknown = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--start-recording" => actions.push(CliAction::ToggleRecording { language: None }),
+            "--quick-pane" => actions.push(CliAction::ToggleQuickPane),
+            "--transcribe" => match args.next() {
+                Some(path) => actions.push(CliAction::Transcribe(cwd.join(path))),
+                None => unknown.push(arg.clone()),
+            },
+            // Read by `utils::paths` and setup at startup
+            PORTABLE_ARG | HIDDEN_ARG => {}
+            // Delivered to `deep_link` by the deep-link plugin
+            arg if deep_link::is_deep_link(arg) => {}
+            _ => unknown.push(arg.clone()),
+        }
+    }
+    (actions, unknown)
+}
+
+/// Runs the actions in `args` (without the executable), one after another.
+/// `cwd` is where the command was run, for relative paths. Returns whether
+/// the arguments asked for anything, links and `--hidden` included.
+pub fn handle_args(app: &AppHandle, args: &[String], cwd: &Path) -> bool {
+    let (actions, unknown) = parse(args, cwd);
+    for arg in unknown {
+        log::warn!("Ignoring unknown command-line argument: {arg}");
+    }
+    let has_link_or_hidden = args
+        .iter()
+        .any(|arg| arg == HIDDEN_ARG || deep_link::is_deep_link(arg));
+    if actions.is_empty() {
+        return has_link_or_hidden;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        for action in actions {
+            dispatch(&app, action).await;
+        }
+    });
+    true
+}
+
+/// Whether `action` has to wait for the job in flight. Stopping the
+/// recording in progress never waits.
+fn must_wait(action: &CliAction) -> bool {
+    match action {
+        CliAction::ToggleQuickPane => false,
+        CliAction::ToggleRecording { .. }
+            if audio::recording_phase() == RecordingPhase::Recording =>
+        {
+            false
+        }
+        CliAction::ToggleRecording { .. } | CliAction::Transcribe(_) => {
+            pipeline::is_busy() || audio::recording_phase() != RecordingPhase::Idle
+        }
+    }
+}
+
+/// Runs `action` now, or queues it behind the job in flight.
+pub(crate) async fn dispatch(app: &AppHandle, action: CliAction) {
+    if must_wait(&action) {
+        enqueue(app, action);
+    } else {
+        run(app, action).await;
+    }
+}
+
+/// Queues `action` behind the job in flight and says so.
+fn enqueue(app: &AppHandle, action: CliAction) {
+    let body = format!(
+        "PrivacyScribe will {} once the current recording or transcription finishes.",
+        action.describe()
+    );
+    log::info!("Queued request: {}", action.describe());
+
+    let mut queue = QUEUE.lock().unwrap_or_else(|e| e.into_inner());
+    queue.actions.push_back(action);
+    if !queue.draining {
+        queue.draining = true;
+        let drain_app = app.clone();
+        tauri::async_runtime::spawn(async move { drain(&drain_app).await });
+    }
+    drop(queue);
+
+    notifications::maybe_notify(
+        app,
+        NotificationCategory::Automation,
+        "Request queued",
+        &body,
+    );
+}
+
+/// Runs queued actions in order, each once the pipeline allows it.
+async fn drain(app: &AppHandle) {
+    loop {
+        tokio::time::sleep(QUEUE_POLL_INTERVAL).await;
+        let next = {
+            let mut queue = QUEUE.lock().unwrap_or_else(|e| e.into_inner());
+            match queue.actions.front() {
+                None => {
+                    queue.draining = false;
+                    return;
+                }
+                Some(action) if must_wait(action) => None,
+                Some(_) => queue.actions.pop_front(),
+            }
+        };
+        if let Some(action) = next {
+            run(app, action).await;
+        }
+    }
+}
+
+async fn run(app: &AppHandle, action: CliAction) {
+    let description = action.describe();
+    let result = match action {
+        CliAction::ToggleRecording { language } => toggle_recording(app, language).await,
+        CliAction::ToggleQuickPane => {
+            quick_pane::handle_quick_pane_activation(app);
+            Ok(())
+        }
+        CliAction::Transcribe(path) => transcribe(app, path).await,
+    };
+    if let Err(e) = result {
+        log::error!("Request to {description} failed: {e}");
+        notifications::maybe_notify(
+            app,
+            NotificationCategory::Automation,
+            &format!("Couldn't {description}"),
+            &e,
+        );
+    }
+}
+
+async fn toggle_recording(app: &AppHandle, language: Option<String>) -> Result<(), String> {
+    if audio::recording_phase() != RecordingPhase::Recording {
+        audio::start_recording(app.clone()).await?;
+        *RECORDING_LANGUAGE.lock().unwrap_or_else(|e| e.into_inner()) =
+            audio::active_session_id().map(|session_id| (session_id, language));
+        return Ok(());
+    }
+    let session_id = audio::active_session_id();
+    let file_path = audio::stop_recording(app.clone()).await?;
+    // Only if this is the recording the request started
+    let language = RECORDING_LANGUAGE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+        .filter(|(started, _)| Some(started) == session_id.as_ref())
+        .and_then(|(_, language)| language);
+    transcription::transcribe_and_delete(
+        app.clone(),
+        file_path,
+        language,
+        transcription::DEFAULT_MODEL_ID.to_string(),
+        Some(true),
+        None,
+        None,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Checks `path` is a WAV whisper can take as is.
+fn check_wav(path: &Path) -> Result<(), String> {
+    let spec = hound::WavReader::open(path)
+        .map_err(|e| format!("Not a readable WAV file: {e}"))?
+        .spec();
+    if spec.sample_rate != TARGET_SAMPLE_RATE || spec.channels != 1 {
+        return Err(format!(
+            "Expected a 16kHz mono WAV, got {}Hz with {} channels",
+            spec.sample_rate, spec.channels
+        ));
+    }
+    Ok(())
+}
+
+/// Transcribes the WAV at `path`, leaving it in place, then copies the text
+/// to the clipboard. Nothing is shown while the app is locked.
+async fn transcribe(app: &AppHandle, path: PathBuf) -> Result<(), String> {
+    app_lock::ensure_unlocked(app)?;
+    check_wav(&path)?;
+    let transcribed =
+        transcription::transcribe_file(app, path, None, transcription::DEFAULT_MODEL_ID).await?;
+    app_lock::ensure_unlocked(app)?;
+    clipboard::copy_transcript(app, &transcribed.text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_actions_in_order_and_collects_unknown_arguments() {
+        let cwd = Path::new("/Users/dana");
+        let (actions, unknown) = parse(
+            &args(&[
+                "--quick-pane",
+                "--portable",
+                "--hidden",
+                "--transcribe",
+                "dictation.wav",
+                "--start-recording",
+                "privacyscribe://quick-pane",
+                "--bogus",
+            ]),
+            cwd,
+        );
+        assert_eq!(
+            actions,
+            vec![
+                CliAction::ToggleQuickPane,
+                CliAction::Transcribe(PathBuf::from("/Users/dana/dictation.wav")),
+                CliAction::ToggleRecording { language: None },
+            ]
+        );
+        assert_eq!(unknown, args(&["--bogus"]));
+    }
+
+    #[test]
+    fn transcribe_without_a_path_is_unknown() {
+        let (actions, unknown) = parse(&args(&["--transcribe"]), Path::new("/"));
+        assert!(actions.is_empty());
+        assert_eq!(unknown, args(&["--transcribe"]));
+    }
+}
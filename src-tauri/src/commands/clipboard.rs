@@ -0,0 +1,154 @@
+//! Clipboard delivery of finished transcripts.
+//!
+//! The usual flow is dictate, then paste into the EHR. Copying from the
+//! backend the moment transcription succeeds keeps that flow working when the
+//! quick pane loses focus mid-transcription or the webview reloads.
+//!
+//! A transcript left on the clipboard can be read by any app and kept by
+//! clipboard-history tools. Copies made here are written natively with the
+//! concealed/transient markers those tools honour (see `utils::pasteboard`)
+//! unless `conceal_clipboard_from_history` is off, and are cleared again after
+//! `clipboard_clear_seconds` — unless the clipboard no longer holds exactly
+//! that transcript, in which case the user copied something else and it's
+//! left alone. A clear empties every flavor written (plain and, in rich
+//! mode, HTML).
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_specta::Event;
+
+use crate::commands::preferences;
+use crate::types::{ClipboardCleared, ClipboardFormat, TranscriptCopied};
+use crate::utils::{pasteboard, rich_text};
+
+/// How often a pending clear checks whether the user copied something else
+const CLEAR_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Bumped on every copy; a pending clear only runs for the latest one.
+static CLEAR_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Fingerprint of the last transcript the app copied
+static LAST_COPIED: Mutex<Option<[u8; 32]>> = Mutex::new(None);
+
+/// Hash of copied text, so a pending clear doesn't keep the transcript in memory
+fn fingerprint(text: &str) -> [u8; 32] {
+    Sha256::digest(text.as_bytes()).into()
+}
+
+fn clipboard_holds(app: &AppHandle, expected: &[u8; 32]) -> bool {
+    app.clipboard()
+        .read_text()
+        .is_ok_and(|text| fingerprint(&text) == *expected)
+}
+
+/// Clears the clipboard after the configured delay if it still holds `text`.
+/// Stops early when the clipboard changes or another copy replaces this one.
+fn schedule_clear(app: &AppHandle, text: &str) {
+    let generation = CLEAR_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let delay_secs = preferences::load_saved_preferences(app).clipboard_clear_seconds;
+    if delay_secs == 0 {
+        return;
+    }
+
+    let expected = fingerprint(text);
+    let app = app.clone();
+    let deadline = Instant::now() + Duration::from_secs(u64::from(delay_secs));
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            tokio::time::sleep(remaining.min(CLEAR_CHECK_INTERVAL)).await;
+            if CLEAR_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            if !clipboard_holds(&app, &expected) {
+                log::debug!("Clipboard changed since the transcript was copied; not clearing");
+                return;
+            }
+            if Instant::now() >= deadline {
+                break;
+            }
+        }
+
+        if let Err(e) = app.clipboard().clear() {
+            log::warn!("Failed to clear the clipboard: {e}");
+            return;
+        }
+        log::info!("Cleared copied transcript from the clipboard");
+        let payload = ClipboardCleared {
+            after_secs: delay_secs,
+        };
+        if let Err(e) = payload.emit(&app) {
+            log::warn!("Failed to emit clipboard-cleared: {e}");
+        }
+    });
+}
+
+/// Writes `text`, with an HTML flavor in rich mode, marked for clipboard
+/// history tools to skip when the preference is on and the platform supports
+/// it. The plain flavor is always exactly `text`, which the pending clear
+/// compares against.
+fn write_text(app: &AppHandle, text: &str) -> Result<(), String> {
+    let preferences = preferences::load_saved_preferences(app);
+    let html =
+        (preferences.clipboard_format == ClipboardFormat::Rich).then(|| rich_text::to_html(text));
+    if preferences.conceal_clipboard_from_history && pasteboard::SUPPORTED {
+        return pasteboard::write_concealed(text, html.as_deref());
+    }
+    match html {
+        Some(html) => app.clipboard().write_html(html.as_str(), Some(text)),
+        None => app.clipboard().write_text(text),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Writes a transcript to the clipboard, announces it with
+/// `transcript-copied` and schedules it to be cleared.
+pub(crate) fn copy_transcript(app: &AppHandle, text: &str) -> Result<(), String> {
+    write_text(app, text).map_err(|e| format!("Failed to copy transcript: {e}"))?;
+    if let Ok(mut last) = LAST_COPIED.lock() {
+        *last = Some(fingerprint(text));
+    }
+    schedule_clear(app, text);
+    let payload = TranscriptCopied {
+        characters: text.chars().count() as u32,
+    };
+    if let Err(e) = payload.emit(app) {
+        log::warn!("Failed to emit transcript-copied: {e}");
+    }
+    Ok(())
+}
+
+/// Clears the clipboard if it still holds the last transcript the app
+/// copied. Returns whether it did.
+pub(crate) fn clear_if_ours(app: &AppHandle) -> Result<bool, String> {
+    let Some(expected) = LAST_COPIED.lock().ok().and_then(|last| *last) else {
+        return Ok(false);
+    };
+    if !clipboard_holds(app, &expected) {
+        return Ok(false);
+    }
+    app.clipboard()
+        .clear()
+        .map_err(|e| format!("Failed to clear the clipboard: {e}"))?;
+    Ok(true)
+}
+
+/// Copies a just-finished transcript when `requested`, or when the caller
+/// didn't say and the `auto_copy_to_clipboard` preference is on. A failed
+/// copy is logged; the transcript is still returned to the caller.
+pub(crate) fn auto_copy(app: &AppHandle, text: &str, requested: Option<bool>) {
+    let enabled = requested
+        .unwrap_or_else(|| preferences::load_saved_preferences(app).auto_copy_to_clipboard);
+    if !enabled || text.trim().is_empty() {
+        return;
+    }
+    match copy_transcript(app, text) {
+        Ok(()) => log::info!("Copied transcript to the clipboard"),
+        Err(e) => log::error!("{e}"),
+    }
+}
@@ -0,0 +1,167 @@
+//! Appending finished transcriptions to a plain-text daily note.
+//!
+//! Some users keep a running "day sheet" outside the app. When
+//! `append_to_file` is on, each delivered transcription is rendered through
+//! the template and appended to the chosen file under an advisory lock, so
+//! an editor or sync tool that honours locks doesn't interleave writes. The
+//! file is created if missing; a `{date}` in its name rolls to a new file
+//! each day.
+//!
+//! The file must have been chosen through a dialog (persisted by the
+//! persisted-scope plugin). Any failure leaves the transcription intact:
+//! `file-append-failed` is emitted and the caller copies the text instead.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use tauri::AppHandle;
+use tauri_plugin_fs::FsExt;
+use tauri_specta::Event;
+
+use crate::commands::{audit, preferences};
+use crate::types::{AppendToFileOptions, AuditEventKind, FileAppendFailed};
+
+/// How long to wait for another process to release the file lock
+const LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The target for `now`, with `{date}` in the file name replaced.
+fn resolve_path(path: &str, now: &DateTime<Local>) -> PathBuf {
+    PathBuf::from(path.replace("{date}", &now.format("%Y-%m-%d").to_string()))
+}
+
+/// One entry for the file. The text is appended after the template if the
+/// template has no `{text}`, so a header-only template still records it.
+fn render_entry(template: &str, text: &str, now: &DateTime<Local>) -> String {
+    let entry = template
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{time}", &now.format("%H:%M").to_string());
+    if entry.contains("{text}") {
+        entry.replace("{text}", text)
+    } else {
+        format!("{entry}{text}\n")
+    }
+}
+
+fn lock_with_timeout(file: &std::fs::File) -> Result<(), String> {
+    let deadline = Instant::now() + LOCK_TIMEOUT;
+    loop {
+        match fs2::FileExt::try_lock_exclusive(file) {
+            Ok(()) => return Ok(()),
+            Err(_) if Instant::now() < deadline => std::thread::sleep(LOCK_RETRY_INTERVAL),
+            Err(e) => return Err(format!("File is locked by another program: {e}")),
+        }
+    }
+}
+
+/// Appends `entry` to `path` under an exclusive advisory lock, creating the
+/// file if needed. The lock is released when the file is closed.
+fn append_locked(path: &Path, entry: &str) -> Result<(), String> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+    lock_with_timeout(&file)?;
+    file.write_all(entry.as_bytes())
+        .and_then(|()| file.sync_data())
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+fn append_to(
+    app: &AppHandle,
+    options: &AppendToFileOptions,
+    path: &Path,
+    text: &str,
+) -> Result<(), String> {
+    if !path.is_absolute() || !app.fs_scope().is_allowed(path) {
+        return Err("The daily note file was not chosen through a file dialog".to_string());
+    }
+    let entry = render_entry(&options.template, text, &Local::now());
+    append_locked(path, &entry)
+}
+
+/// Appends `text` to the daily note if `requested` (or the `append_to_file`
+/// preference) says so. Returns false when appending was wanted but failed,
+/// after emitting `file-append-failed`.
+pub(crate) async fn append_transcript(
+    app: &AppHandle,
+    text: &str,
+    requested: Option<bool>,
+) -> bool {
+    let options = preferences::load_saved_preferences(app).append_to_file;
+    if !requested.unwrap_or(options.enabled) || text.trim().is_empty() {
+        return true;
+    }
+    let Some(configured) = options.path.clone() else {
+        log::warn!("Appending to a daily note is on but no file is set");
+        return true;
+    };
+    let path = resolve_path(&configured, &Local::now());
+
+    let result = {
+        let (app, path, text) = (app.clone(), path.clone(), text.to_string());
+        tokio::task::spawn_blocking(move || append_to(&app, &options, &path, &text))
+            .await
+            .unwrap_or_else(|e| Err(format!("Append task panicked: {e}")))
+    };
+    match result {
+        Ok(()) => {
+            audit::record(
+                app,
+                AuditEventKind::TranscriptExported,
+                None,
+                &format!("format=daily_note destination={}", path.display()),
+            );
+            true
+        }
+        Err(message) => {
+            log::error!("Failed to append transcription to daily note: {message}");
+            let payload = FileAppendFailed {
+                path: path.display().to_string(),
+                message,
+            };
+            if let Err(e) = payload.emit(app) {
+                log::warn!("Failed to emit file-append-failed: {e}");
+            }
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at_noon() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 3, 7, 12, 5, 0).unwrap()
+    }
+
+    #[test]
+    fn date_in_file_name_rolls_daily() {
+        assert_eq!(
+            resolve_path("/notes/day-{date}.txt", &at_noon()),
+            PathBuf::from("/notes/day-2024-03-07.txt")
+        );
+        assert_eq!(
+            resolve_path("/notes/day.txt", &at_noon()),
+            PathBuf::from("/notes/day.txt")
+        );
+    }
+
+    #[test]
+    fn entries_follow_the_template() {
+        assert_eq!(
+            render_entry(crate::types::DEFAULT_APPEND_TEMPLATE, "Seen.", &at_noon()),
+            "--- 2024-03-07 12:05 ---\nSeen.\n\n"
+        );
+        assert_eq!(
+            render_entry("{time} ", "Seen.", &at_noon()),
+            "12:05 Seen.\n"
+        );
+    }
+}
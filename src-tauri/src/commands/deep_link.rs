@@ -0,0 +1,230 @@
+//! `privacyscribe://` links, so text expanders and browser-based EHRs can
+//! trigger dictation.
+//!
+//! Links can only do low-risk things:
+//!
+//! - `privacyscribe://record`, optionally `?language=no`, starts a recording
+//!   or stops and transcribes the one in progress
+//! - `privacyscribe://quick-pane` toggles the quick pane
+//! - `privacyscribe://open/transcript/<id>` opens a transcript in the main window
+//!
+//! Anything else, exporting and deleting included, is rejected and logged.
+//! Recording and quick pane links queue behind a busy pipeline like their
+//! command-line counterparts (see `cli`). Links arriving while the app is
+//! locked wait for it to be unlocked.
+
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::commands::cli::{self, CliAction};
+use crate::commands::{app_lock, navigation, transcripts};
+
+/// URL scheme registered for the app (matches tauri.conf.json)
+pub const DEEP_LINK_SCHEME: &str = "privacyscribe";
+
+/// Main window route that shows one transcript
+const TRANSCRIPT_ROUTE: &str = "/transcripts";
+
+/// What a link may ask for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DeepLink {
+    Action(CliAction),
+    OpenTranscript { id: String },
+}
+
+/// Links received while the app was locked, run in order on unlock
+static QUEUED_WHILE_LOCKED: Mutex<Vec<DeepLink>> = Mutex::new(Vec::new());
+
+/// Whether a command-line argument is one of the app's links.
+pub fn is_deep_link(arg: &str) -> bool {
+    arg.strip_prefix(DEEP_LINK_SCHEME)
+        .is_some_and(|rest| rest.starts_with("://"))
+}
+
+/// An ISO 639-1 (or -2) language code like whisper takes.
+fn is_language_code(code: &str) -> bool {
+    (2..=3).contains(&code.len()) && code.bytes().all(|b| b.is_ascii_lowercase())
+}
+
+/// Maps a link to what it asks for, or says why it's refused.
+fn parse(url: &Url) -> Result<DeepLink, String> {
+    if url.scheme() != DEEP_LINK_SCHEME {
+        return Err(format!("unexpected scheme {}", url.scheme()));
+    }
+    let action = url.host_str().unwrap_or_default();
+    let path: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+
+    match (action, path.as_slice()) {
+        ("record", []) => {
+            let language = url
+                .query_pairs()
+                .find(|(key, _)| key == "language")
+                .map(|(_, value)| value.into_owned())
+                .filter(|language| language != "auto");
+            if let Some(language) = &language {
+                if !is_language_code(language) {
+                    return Err(format!("invalid language {language:?}"));
+                }
+            }
+            Ok(DeepLink::Action(CliAction::ToggleRecording { language }))
+        }
+        ("quick-pane", []) => Ok(DeepLink::Action(CliAction::ToggleQuickPane)),
+        ("open", ["transcript", id]) => {
+            transcripts::validate_id(id).map_err(|e| e.to_string())?;
+            Ok(DeepLink::OpenTranscript { id: id.to_string() })
+        }
+        _ => Err(format!("unsupported action {action:?}")),
+    }
+}
+
+async fn run(app: &AppHandle, link: DeepLink) {
+    match link {
+        DeepLink::Action(action) => cli::dispatch(app, action).await,
+        DeepLink::OpenTranscript { id } => {
+            let payload = serde_json::json!({ "transcript_id": id });
+            let result =
+                navigation::open_main_window(app.clone(), TRANSCRIPT_ROUTE.into(), Some(payload))
+                    .await;
+            if let Err(e) = result {
+                log::error!("Failed to open transcript from a link: {e}");
+            }
+        }
+    }
+}
+
+/// Runs the links in `urls`, in order. Rejected links are logged (without
+/// their query, which may carry PHI) and otherwise ignored.
+pub fn handle_urls(app: &AppHandle, urls: Vec<Url>) {
+    let links: Vec<DeepLink> = urls
+        .iter()
+        .filter_map(|url| match parse(url) {
+            Ok(link) => Some(link),
+            Err(reason) => {
+                log::warn!(
+                    "Rejected link {}://{}{}: {reason}",
+                    url.scheme(),
+                    url.host_str().unwrap_or_default(),
+                    url.path()
+                );
+                None
+            }
+        })
+        .collect();
+    if links.is_empty() {
+        return;
+    }
+
+    if app_lock::is_locked(app) {
+        log::info!("App locked; {} link(s) wait for unlock", links.len());
+        QUEUED_WHILE_LOCKED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend(links);
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        for link in links {
+            run(&app, link).await;
+        }
+    });
+}
+
+/// Runs the links that arrived while the app was locked. Called on unlock.
+pub fn run_queued(app: &AppHandle) {
+    let links = std::mem::take(
+        &mut *QUEUED_WHILE_LOCKED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()),
+    );
+    if links.is_empty() {
+        return;
+    }
+    log::info!("Running {} link(s) received while locked", links.len());
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        for link in links {
+            run(&app, link).await;
+        }
+    });
+}
+
+/// Listens for links, and runs the one the app was launched with. Must be
+/// called from setup().
+pub fn init(app: &AppHandle) {
+    let deep_link = app.deep_link();
+
+    // Installers register the scheme; Linux and Windows dev builds do it here
+    #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+    if let Err(e) = deep_link.register_all() {
+        log::warn!("Failed to register the {DEEP_LINK_SCHEME}:// scheme: {e}");
+    }
+
+    let handler_app = app.clone();
+    deep_link.on_open_url(move |event| handle_urls(&handler_app, event.urls()));
+
+    match deep_link.get_current() {
+        Ok(Some(urls)) => handle_urls(app, urls),
+        Ok(None) => {}
+        Err(e) => log::warn!("Failed to read the launch link: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(url: &str) -> Result<DeepLink, String> {
+        parse(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn low_risk_actions_are_understood() {
+        assert_eq!(
+            parse_str("privacyscribe://record?language=no"),
+            Ok(DeepLink::Action(CliAction::ToggleRecording {
+                language: Some("no".into())
+            }))
+        );
+        assert_eq!(
+            parse_str("privacyscribe://record?language=auto"),
+            Ok(DeepLink::Action(CliAction::ToggleRecording {
+                language: None
+            }))
+        );
+        assert_eq!(
+            parse_str("privacyscribe://quick-pane/"),
+            Ok(DeepLink::Action(CliAction::ToggleQuickPane))
+        );
+        assert_eq!(
+            parse_str("privacyscribe://open/transcript/123"),
+            Ok(DeepLink::OpenTranscript { id: "123".into() })
+        );
+    }
+
+    #[test]
+    fn anything_else_is_rejected() {
+        for url in [
+            "privacyscribe://export/all",
+            "privacyscribe://delete/transcript/123",
+            "privacyscribe://open/transcript/../db",
+            "privacyscribe://record/now",
+            "privacyscribe://record?language=en;rm",
+            "https://record",
+        ] {
+            assert!(parse_str(url).is_err(), "{url} was accepted");
+        }
+    }
+
+    #[test]
+    fn recognizes_link_arguments() {
+        assert!(is_deep_link("privacyscribe://record"));
+        assert!(!is_deep_link("privacyscribe:record"));
+        assert!(!is_deep_link("--start-recording"));
+    }
+}
@@ -0,0 +1,318 @@
+//! Data for the diagnostics screen, so support questions start from facts.
+//!
+//! `get_command_metrics` summarizes the command timings `utils::metrics`
+//! keeps in memory while the `command_metrics_enabled` preference is on.
+//! `get_diagnostics` snapshots the app, machine, model, storage, permissions
+//! and privacy settings; each reading that can't be taken is reported as
+//! unknown (see `utils::diagnostics`). `export_log_bundle` packs that
+//! snapshot with the recent, scrubbed log files into a ZIP for a bug report.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_fs::FsExt;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::commands::{
+    app_lock, audit, notifications, pipeline, preferences, recovery, transcription,
+};
+use crate::db::{self, Database};
+use crate::types::{
+    AppDiagnostics, AppError, AppPreferences, AuditEventKind, CommandMetrics, DiagnosticsReport,
+    LogBundleSummary, PermissionDiagnostics, PrivacyDiagnostics, StorageDiagnostics, TableRowCount,
+};
+use crate::utils::diagnostics::{dir_size, probe, system_diagnostics};
+use crate::utils::log_scrub::{self, ScrubCounts};
+use crate::utils::{disk, metrics, paths, permissions};
+
+/// Days of log files a bundle covers
+const LOG_BUNDLE_DAYS: u64 = 7;
+
+/// Most scrubbed log text a bundle holds; the oldest is left out first
+const MAX_BUNDLE_LOG_BYTES: usize = 20 * 1024 * 1024;
+
+/// Backend tables whose row counts the report includes
+const COUNTED_TABLES: &[&str] = &[
+    "transcripts",
+    "transcript_revisions",
+    "drafts",
+    "tags",
+    "audit_log",
+];
+
+/// Applies the `command_metrics_enabled` preference. Called from setup() and
+/// when preferences are saved.
+pub fn apply_metrics_preference(app: &AppHandle) {
+    metrics::set_enabled(preferences::load_saved_preferences(app).command_metrics_enabled);
+}
+
+/// Count, p50/p95 duration and error rate of each timed command, over the
+/// most recent calls. Empty while command metrics are off.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn get_command_metrics() -> Vec<CommandMetrics> {
+    metrics::summarize()
+}
+
+fn row_counts(app: &AppHandle) -> Result<Vec<TableRowCount>, String> {
+    app.state::<Database>().with_conn(|conn| {
+        COUNTED_TABLES
+            .iter()
+            .map(|&table| {
+                let rows = conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                    row.get(0)
+                })?;
+                Ok(TableRowCount {
+                    table: table.to_string(),
+                    rows,
+                })
+            })
+            .collect()
+    })
+}
+
+fn storage_diagnostics(app: &AppHandle) -> StorageDiagnostics {
+    let recovery_bytes = recovery::get_recovery_dir(app).and_then(|dir| dir_size(&dir));
+    let cache_bytes = paths::app_cache_dir(app)
+        .map_err(|e| e.to_string())
+        .and_then(|dir| dir_size(&dir));
+    let free_disk_bytes = paths::app_data_dir(app)
+        .map_err(|e| e.to_string())
+        .and_then(|dir| disk::available_bytes(&dir).map_err(|e| e.to_string()));
+    StorageDiagnostics {
+        database_bytes: probe("the database size", db::file_size(app).map(|b| b as f64)),
+        row_counts: probe("the database row counts", row_counts(app)),
+        recovery_bytes: probe("recovery usage", recovery_bytes.map(|b| b as f64)),
+        cache_bytes: probe("cache usage", cache_bytes.map(|b| b as f64)),
+        free_disk_bytes: probe("free disk space", free_disk_bytes.map(|b| b as f64)),
+    }
+}
+
+fn privacy_diagnostics(prefs: &AppPreferences, app_lock_enabled: bool) -> PrivacyDiagnostics {
+    PrivacyDiagnostics {
+        app_lock_enabled,
+        auto_lock_minutes: prefs.auto_lock_minutes,
+        biometric_unlock: prefs.biometric_unlock,
+        block_screen_capture: prefs.block_screen_capture,
+        auto_copy_to_clipboard: prefs.auto_copy_to_clipboard,
+        auto_paste_into_previous_app: prefs.auto_paste_into_previous_app,
+        clipboard_clear_seconds: prefs.clipboard_clear_seconds,
+        conceal_clipboard_from_history: prefs.conceal_clipboard_from_history,
+        append_to_file: prefs.append_to_file.enabled,
+        deidentify_auto_saved: prefs.deidentification.apply_to_auto_saved,
+        exclude_database_from_backups: prefs.exclude_database_from_backups,
+        recovery_retention_hours: prefs.recovery_retention_hours,
+        trash_retention_days: prefs.trash_retention_days,
+        audit_retention_days: prefs.audit_retention_days,
+        max_idle_while_recording_minutes: prefs.max_idle_while_recording_minutes,
+        check_updates_automatically: prefs.check_updates_automatically,
+        command_metrics_enabled: prefs.command_metrics_enabled,
+    }
+}
+
+/// Snapshot of everything the report covers. Blocks on disk walks.
+pub(crate) fn collect(app: &AppHandle) -> DiagnosticsReport {
+    let prefs = preferences::load_saved_preferences(app);
+    DiagnosticsReport {
+        generated_at_ms: pipeline::now_ms(),
+        app: AppDiagnostics {
+            version: app.package_info().version.to_string(),
+            update_channel: prefs.update_channel,
+            portable: paths::is_portable(),
+        },
+        system: system_diagnostics(),
+        model: transcription::default_model_diagnostics(app),
+        storage: storage_diagnostics(app),
+        permissions: PermissionDiagnostics {
+            microphone: probe("microphone access", permissions::microphone_permission()),
+            notifications: probe(
+                "notification permission",
+                notifications::notification_permission(app),
+            ),
+        },
+        privacy: privacy_diagnostics(&prefs, app_lock::is_enabled(app)),
+    }
+}
+
+/// App version and channel, OS and hardware, the default model, database and
+/// disk usage, permissions and privacy-relevant preferences, for support.
+/// Readings that fail are reported as unknown; the command itself only fails
+/// if the collection task dies.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn get_diagnostics(app: AppHandle) -> Result<DiagnosticsReport, AppError> {
+    tokio::task::spawn_blocking(move || collect(&app))
+        .await
+        .map_err(|e| AppError::internal(format!("Diagnostics task panicked: {e}")))
+}
+
+/// Log files modified within `LOG_BUNDLE_DAYS`, newest first.
+fn recent_log_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let cutoff = SystemTime::now() - Duration::from_secs(LOG_BUNDLE_DAYS * 24 * 60 * 60);
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to list log files: {e}")),
+    };
+    let mut files: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let path = entry.path();
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            (path.extension().is_some_and(|ext| ext == "log") && modified >= cutoff)
+                .then_some((modified, path))
+        })
+        .collect();
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    Ok(files.into_iter().map(|(_, path)| path).collect())
+}
+
+/// The last `budget` bytes of `text` or less, starting at a line.
+fn tail_within(text: &str, budget: usize) -> &str {
+    if text.len() <= budget {
+        return text;
+    }
+    let from = text.len() - budget;
+    let start = text.as_bytes()[from..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(text.len(), |newline| from + newline + 1);
+    &text[start..]
+}
+
+/// Writes the bundle ZIP to `destination`. Blocks.
+fn write_log_bundle(app: &AppHandle, destination: &Path) -> Result<LogBundleSummary, String> {
+    let log_dir = paths::app_log_dir(app).map_err(|e| format!("Failed to get log dir: {e}"))?;
+    let account = audit::os_account();
+    let mut counts = ScrubCounts::default();
+    let mut logs: Vec<(String, String)> = Vec::new();
+    let mut budget = MAX_BUNDLE_LOG_BYTES;
+    let mut truncated = false;
+    for path in recent_log_files(&log_dir)? {
+        if budget == 0 {
+            truncated = true;
+            break;
+        }
+        let text = match std::fs::read(&path) {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(e) => {
+                log::warn!("Skipping unreadable log file in bundle: {e}");
+                continue;
+            }
+        };
+        let scrubbed = log_scrub::scrub(&text, account, &mut counts);
+        let kept = tail_within(&scrubbed, budget);
+        truncated |= kept.len() < scrubbed.len();
+        budget -= kept.len();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        logs.push((name, kept.to_string()));
+    }
+
+    let diagnostics = serde_json::to_string_pretty(&collect(app))
+        .map_err(|e| format!("Failed to serialize diagnostics: {e}"))?;
+
+    let file = std::fs::File::create(destination)
+        .map_err(|e| format!("Failed to create log bundle: {e}"))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let entries = std::iter::once(("diagnostics.json".to_string(), diagnostics)).chain(
+        logs.iter()
+            .map(|(name, text)| (format!("logs/{name}"), text.clone())),
+    );
+    for (name, contents) in entries {
+        zip.start_file(name, options)
+            .and_then(|_| zip.write_all(contents.as_bytes()).map_err(Into::into))
+            .map_err(|e| format!("Failed to write log bundle entry: {e}"))?;
+    }
+    let file = zip
+        .finish()
+        .map_err(|e| format!("Failed to finish log bundle: {e}"))?;
+    file.sync_all()
+        .map_err(|e| format!("Failed to finish log bundle: {e}"))?;
+    let bytes = file.metadata().map_or(0, |metadata| metadata.len());
+
+    Ok(LogBundleSummary {
+        files: logs.into_iter().map(|(name, _)| name).collect(),
+        bytes: bytes as f64,
+        account_names_scrubbed: counts.account_names,
+        lines_redacted: counts.redacted_lines,
+        lines_dropped: counts.dropped_lines,
+        truncated,
+    })
+}
+
+/// Writes a ZIP for a bug report to `destination_path`, which must come from
+/// the save dialog and end in `.zip`: the last `LOG_BUNDLE_DAYS` days of log
+/// files, scrubbed (see `utils::log_scrub`), and the `get_diagnostics` report
+/// as `diagnostics.json`. Older log content is left out past
+/// `MAX_BUNDLE_LOG_BYTES`. The export is audited. Returns what went in, so
+/// the user can see what they're about to send.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn export_log_bundle(
+    app: AppHandle,
+    destination_path: String,
+) -> Result<LogBundleSummary, AppError> {
+    app_lock::ensure_unlocked(&app)?;
+    let destination = PathBuf::from(&destination_path);
+    let is_zip = destination
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+    if !destination.is_absolute() || !is_zip {
+        return Err(AppError::invalid_input(
+            "Destination must be an absolute path to a .zip file",
+        ));
+    }
+    if !app.fs_scope().is_allowed(&destination) {
+        return Err(AppError::invalid_input(
+            "Destination was not chosen through the save dialog",
+        ));
+    }
+
+    let bundle_app = app.clone();
+    let bundle_destination = destination.clone();
+    let summary =
+        tokio::task::spawn_blocking(move || write_log_bundle(&bundle_app, &bundle_destination))
+            .await
+            .map_err(|e| AppError::internal(format!("Log bundle task panicked: {e}")))??;
+
+    audit::record(
+        &app,
+        AuditEventKind::LogBundleExported,
+        None,
+        &format!(
+            "destination={} files={} bytes={}",
+            destination.display(),
+            summary.files.len(),
+            summary.bytes
+        ),
+    );
+    log::info!(
+        "Exported log bundle: {} files, {} bytes",
+        summary.files.len(),
+        summary.bytes
+    );
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tails_start_at_a_line() {
+        let text = "first line\nsecond line\nthird\n";
+        assert_eq!(tail_within(text, 100), text);
+        assert_eq!(tail_within(text, 14), "third\n");
+        assert_eq!(tail_within(text, 3), "");
+    }
+}
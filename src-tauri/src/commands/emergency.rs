@@ -0,0 +1,214 @@
+//! Emergency wipe: remove all PHI from this machine now.
+//!
+//! `emergency_wipe` runs only with `EMERGENCY_WIPE_PHRASE` typed exactly and
+//! the app unlocked. It stops any recording without saving it, then wipes
+//! each `WipeCategory` in turn, carrying on past failures so one stuck file
+//! doesn't leave the rest in place. Keychain entries are removed even when
+//! files couldn't be, so what's left can't be decrypted.
+//!
+//! The audit log lives in the database being wiped, so the wipe itself is
+//! recorded in a separate minimal log (`emergency-wipe.log`, no PHI), kept
+//! along with downloaded models. The app then restarts into its first-run
+//! state.
+
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::{app_lock, audio, cache, clipboard, pipeline, recovery};
+use crate::db::{self, Database};
+use crate::types::{
+    EmergencyWipeError, EmergencyWipeReport, WipeCategory, WipeCategoryResult,
+    EMERGENCY_WIPE_PHRASE,
+};
+use crate::utils::metrics;
+use crate::utils::paths;
+use crate::utils::secrets;
+use crate::utils::secure_delete::{secure_delete, secure_delete_dir};
+
+/// Log of emergency wipes in the app data dir, which survives them
+const WIPE_LOG_FILENAME: &str = "emergency-wipe.log";
+
+/// App data entries the wipe keeps (models hold no PHI and are large)
+const KEPT_APP_DATA: &[&str] = &["models", WIPE_LOG_FILENAME];
+
+/// Keychain entries the app owns
+const OWNED_SECRETS: &[&str] = &[
+    db::DATABASE_KEY_NAME,
+    recovery::RECOVERY_KEY_NAME,
+    app_lock::PASSCODE_SECRET_NAME,
+];
+
+/// Time for the report to reach the frontend before the restart
+const RESTART_DELAY: Duration = Duration::from_secs(2);
+
+/// Set once a wipe starts; the app restarts afterwards, so it's never reset
+static WIPED: AtomicBool = AtomicBool::new(false);
+
+fn outcome(category: WipeCategory, result: Result<(), String>) -> WipeCategoryResult {
+    if let Err(message) = &result {
+        log::error!("Emergency wipe of {category:?} failed: {message}");
+    }
+    WipeCategoryResult {
+        category,
+        success: result.is_ok(),
+        message: result.err(),
+    }
+}
+
+fn wipe_drafts(database: &Database) -> Result<(), String> {
+    database.with_conn(|conn| conn.execute("DELETE FROM drafts", []).map(|_| ()))
+}
+
+fn wipe_cache(app: &AppHandle) -> Result<(), String> {
+    let report = cache::wipe_everything(app)?;
+    match report.files_skipped {
+        0 => Ok(()),
+        skipped => Err(format!("{skipped} cached files could not be wiped")),
+    }
+}
+
+fn wipe_clipboard(app: &AppHandle) -> Result<(), String> {
+    clipboard::clear_if_ours(app).map(|_| ())
+}
+
+fn wipe_secrets() -> Result<(), String> {
+    let failures: Vec<String> = OWNED_SECRETS
+        .iter()
+        .filter_map(|name| secrets::delete_secret(name).err())
+        .collect();
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("; "))
+    }
+}
+
+/// Securely deletes everything in the app data dir but `KEPT_APP_DATA`.
+fn wipe_app_data(app: &AppHandle) -> Result<(), String> {
+    let dir =
+        paths::app_data_dir(app).map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(format!("Failed to list app data: {e}")),
+    };
+
+    let mut failures = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if KEPT_APP_DATA.contains(&name.as_str()) {
+            continue;
+        }
+        let path = entry.path();
+        let result = match entry.file_type() {
+            Ok(file_type) if file_type.is_symlink() => std::fs::remove_file(&path),
+            Ok(file_type) if file_type.is_dir() => secure_delete_dir(&path),
+            _ => secure_delete(&path),
+        };
+        if let Err(e) = result {
+            failures.push(format!("{name}: {e}"));
+        }
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed to delete {}", failures.join("; ")))
+    }
+}
+
+/// Everything after stopping the recording, in `WipeCategory` order.
+fn wipe_stored_data(app: &AppHandle) -> Vec<WipeCategoryResult> {
+    let database = app.state::<Database>();
+    vec![
+        outcome(WipeCategory::Drafts, wipe_drafts(&database)),
+        outcome(WipeCategory::Database, database.destroy(app)),
+        outcome(WipeCategory::Recovery, recovery::wipe_recovery_dir(app)),
+        outcome(WipeCategory::Cache, wipe_cache(app)),
+        outcome(WipeCategory::Clipboard, wipe_clipboard(app)),
+        outcome(WipeCategory::Keychain, wipe_secrets()),
+        outcome(WipeCategory::Settings, wipe_app_data(app)),
+    ]
+}
+
+/// A line of `emergency-wipe.log`.
+#[derive(Serialize)]
+struct WipeLogEntry<'a> {
+    timestamp_ms: f64,
+    event: &'static str,
+    results: &'a [WipeCategoryResult],
+}
+
+fn append_wipe_log(dir: &Path, results: &[WipeCategoryResult]) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let entry = WipeLogEntry {
+        timestamp_ms: pipeline::now_ms(),
+        event: "emergency_wipe",
+        results,
+    };
+    let line = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(WIPE_LOG_FILENAME))
+        .and_then(|mut file| writeln!(file, "{line}").and_then(|()| file.sync_data()))
+        .map_err(|e| e.to_string())
+}
+
+/// Wipes all PHI from this machine and restarts the app into its first-run
+/// state. Requires `confirmation_phrase` to be exactly `EMERGENCY_WIPE_PHRASE`.
+/// Returns how each category went; a failed category doesn't stop the rest.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn emergency_wipe(
+    app: AppHandle,
+    confirmation_phrase: String,
+) -> Result<EmergencyWipeReport, EmergencyWipeError> {
+    if confirmation_phrase != EMERGENCY_WIPE_PHRASE {
+        return Err(EmergencyWipeError::ConfirmationMismatch {
+            expected: EMERGENCY_WIPE_PHRASE.to_string(),
+        });
+    }
+    app_lock::ensure_unlocked(&app)?;
+    if WIPED.swap(true, Ordering::SeqCst) {
+        return Err(EmergencyWipeError::AlreadyRunning);
+    }
+    log::warn!("Emergency wipe started");
+
+    if audio::abort_recording(&app).await {
+        log::info!("Stopped the active recording for the emergency wipe");
+    }
+    let mut results = vec![outcome(WipeCategory::Recording, Ok(()))];
+
+    let wipe_app = app.clone();
+    let stored = tokio::task::spawn_blocking(move || wipe_stored_data(&wipe_app))
+        .await
+        .unwrap_or_else(|e| {
+            vec![outcome(
+                WipeCategory::Database,
+                Err(format!("Wipe task panicked: {e}")),
+            )]
+        });
+    results.extend(stored);
+
+    let log_written = paths::app_data_dir(&app)
+        .map_err(|e| e.to_string())
+        .and_then(|dir| append_wipe_log(&dir, &results));
+    if let Err(e) = log_written {
+        log::error!("Failed to write the emergency wipe log: {e}");
+    }
+    let failed = results.iter().filter(|result| !result.success).count();
+    log::warn!("Emergency wipe finished ({failed} categories failed); restarting");
+
+    let restart_app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(RESTART_DELAY).await;
+        restart_app.restart();
+    });
+    Ok(EmergencyWipeReport { results })
+}
@@ -0,0 +1,640 @@
+//! Transcript export commands.
+//!
+//! Renders saved transcripts as plain text, Markdown (with front-matter) or
+//! PDF and writes them where the user chose in the save dialog. Every export
+//! is recorded in the audit log with its destination.
+//!
+//! `export_all_transcripts` writes every transcript (optionally within a date
+//! range) into one ZIP archive, one entry at a time so memory stays flat, and
+//! can be cancelled between entries.
+//!
+//! With `ExportEncryption`, either kind of export is wrapped in a
+//! passphrase-encrypted `.psenc` envelope (see `utils::envelope`) instead,
+//! so it can leave the machine by email or USB stick. `decrypt_export` opens
+//! one again. The passphrase is only held for the call; it's never stored or
+//! logged.
+
+use std::collections::HashSet;
+use std::io::{Cursor, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_fs::FsExt;
+use tauri_specta::Event;
+use zip::write::FileOptions;
+use zip::{AesMode, CompressionMethod, ZipWriter};
+
+use crate::commands::notifications::format_audio_duration;
+use crate::commands::{audit, transcripts};
+use crate::db::Database;
+use crate::types::{
+    AuditEventKind, BulkExportSummary, DateRange, ExportEncryption, Transcript, TranscriptError,
+    TranscriptExportFormat, TranscriptExportProgress, TranscriptFilter,
+};
+use crate::utils::{envelope, metrics, pdf};
+
+/// Title used when a transcript was saved without one
+const UNTITLED: &str = "Untitled transcript";
+
+fn io_error(message: String) -> TranscriptError {
+    TranscriptError::IoError { message }
+}
+
+/// Checks that `path` is absolute, has one of `extensions`, and was granted by
+/// the save dialog.
+pub(crate) fn validate_export_destination(
+    app: &AppHandle,
+    path: &str,
+    extensions: &[&str],
+) -> Result<PathBuf, String> {
+    let destination = PathBuf::from(path);
+    let has_extension = destination.extension().is_some_and(|ext| {
+        extensions
+            .iter()
+            .any(|allowed| ext.eq_ignore_ascii_case(allowed))
+    });
+    if !destination.is_absolute() || !has_extension {
+        return Err(format!(
+            "Destination must be an absolute path to a .{} file",
+            extensions.join(" or .")
+        ));
+    }
+    if !app.fs_scope().is_allowed(&destination) {
+        return Err("Destination was not chosen through the save dialog".to_string());
+    }
+    Ok(destination)
+}
+
+fn display_title(transcript: &Transcript) -> &str {
+    if transcript.title.trim().is_empty() {
+        UNTITLED
+    } else {
+        &transcript.title
+    }
+}
+
+/// Local date and time of a millisecond timestamp, e.g. "2025-03-14 09:26".
+pub(crate) fn format_timestamp(ms: f64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms as i64)
+        .map(|utc| {
+            utc.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+/// "Created …", "Duration …" and similar lines shown above the body.
+fn metadata_lines(transcript: &Transcript) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(label) = &transcript.session_label {
+        lines.push(format!("Session: {label}"));
+    }
+    lines.push(format!(
+        "Created: {}",
+        format_timestamp(transcript.created_at_ms)
+    ));
+    if transcript.duration_secs > 0.0 {
+        lines.push(format!(
+            "Duration: {}",
+            format_audio_duration(transcript.duration_secs)
+        ));
+    }
+    if let Some(language) = &transcript.language {
+        lines.push(format!("Language: {language}"));
+    }
+    if !transcript.tags.is_empty() {
+        lines.push(format!("Tags: {}", transcript.tags.join(", ")));
+    }
+    lines
+}
+
+fn render_txt(transcript: &Transcript) -> String {
+    let mut out = format!("{}\n", display_title(transcript));
+    for line in metadata_lines(transcript) {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out.push('\n');
+    out.push_str(&transcript.text);
+    out.push('\n');
+    out
+}
+
+fn render_md(transcript: &Transcript) -> String {
+    // JSON strings are valid YAML scalars, which takes care of quoting
+    let quote = |value: &str| serde_json::to_string(value).unwrap_or_default();
+    let mut out = String::from("---\n");
+    out.push_str(&format!("title: {}\n", quote(display_title(transcript))));
+    out.push_str(&format!("id: {}\n", transcript.id));
+    out.push_str(&format!(
+        "created: {}\n",
+        quote(&format_timestamp(transcript.created_at_ms))
+    ));
+    out.push_str(&format!(
+        "duration_secs: {}\n",
+        transcript.duration_secs.round()
+    ));
+    if let Some(language) = &transcript.language {
+        out.push_str(&format!("language: {}\n", quote(language)));
+    }
+    if let Some(model_id) = &transcript.model_id {
+        out.push_str(&format!("model: {}\n", quote(model_id)));
+    }
+    out.push_str(&format!("word_count: {}\n", transcript.word_count));
+    if let Some(label) = &transcript.session_label {
+        out.push_str(&format!("session: {}\n", quote(label)));
+    }
+    if !transcript.tags.is_empty() {
+        let tags = serde_json::to_string(&transcript.tags).unwrap_or_default();
+        out.push_str(&format!("tags: {tags}\n"));
+    }
+    out.push_str("---\n\n");
+    out.push_str(&format!("# {}\n\n", display_title(transcript)));
+    out.push_str(&transcript.text);
+    out.push('\n');
+    out
+}
+
+/// Renders a transcript in `format`. Shared by single and bulk exports.
+pub(crate) fn render_transcript(
+    transcript: &Transcript,
+    format: TranscriptExportFormat,
+) -> Result<Vec<u8>, String> {
+    match format {
+        TranscriptExportFormat::Txt => Ok(render_txt(transcript).into_bytes()),
+        TranscriptExportFormat::Md => Ok(render_md(transcript).into_bytes()),
+        TranscriptExportFormat::Pdf => pdf::render_document(
+            display_title(transcript),
+            &metadata_lines(transcript),
+            &transcript.text,
+        ),
+    }
+}
+
+pub(crate) fn write_export(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let mut file =
+        std::fs::File::create(path).map_err(|e| format!("Failed to create export: {e}"))?;
+    file.write_all(bytes)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| {
+            // Don't leave a half-written export behind
+            let _ = std::fs::remove_file(path);
+            format!("Failed to write export: {e}")
+        })
+}
+
+/// The passphrase of `encrypted`, refusing an empty one.
+fn envelope_passphrase(
+    encrypted: Option<ExportEncryption>,
+) -> Result<Option<String>, TranscriptError> {
+    match encrypted {
+        Some(encryption) if encryption.passphrase.is_empty() => {
+            Err(TranscriptError::ValidationError {
+                message: "Passphrase cannot be empty".to_string(),
+            })
+        }
+        encrypted => Ok(encrypted.map(|encryption| encryption.passphrase)),
+    }
+}
+
+/// Exports a saved transcript to `path` (from the save dialog, ending in the
+/// format's extension, or `.psenc` when `encrypted`). Returns the number of
+/// bytes written.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn export_transcript(
+    app: AppHandle,
+    id: String,
+    format: TranscriptExportFormat,
+    path: String,
+    encrypted: Option<ExportEncryption>,
+) -> Result<f64, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let passphrase = envelope_passphrase(encrypted)?;
+    let extension = match passphrase {
+        Some(_) => envelope::EXTENSION,
+        None => format.extension(),
+    };
+    let destination = validate_export_destination(&app, &path, &[extension])
+        .map_err(|message| TranscriptError::ValidationError { message })?;
+    let transcript = transcripts::query_transcript(&app.state::<Database>(), &id)
+        .map_err(|message| TranscriptError::DatabaseError { message })?
+        .ok_or_else(|| TranscriptError::NotFound { id: id.clone() })?;
+
+    let export_path = destination.clone();
+    let encrypted = passphrase.is_some();
+    let bytes_written = tokio::task::spawn_blocking(move || {
+        let mut bytes = render_transcript(&transcript, format)?;
+        if let Some(passphrase) = passphrase {
+            bytes = envelope::seal(&bytes, &passphrase, format.extension())?;
+        }
+        write_export(&export_path, &bytes)?;
+        Ok::<_, String>(bytes.len())
+    })
+    .await
+    .map_err(|e| io_error(format!("Export task panicked: {e}")))?
+    .map_err(io_error)?;
+
+    audit::record(
+        &app,
+        AuditEventKind::TranscriptExported,
+        Some(&id),
+        &format!(
+            "format={} encrypted={encrypted} destination={}",
+            format.extension(),
+            destination.display()
+        ),
+    );
+    log::info!("Exported transcript {id} ({bytes_written} bytes)");
+    Ok(bytes_written as f64)
+}
+
+// ============================================================================
+// Bulk Export
+// ============================================================================
+
+/// Transcripts loaded from the database at a time during a bulk export
+const BULK_EXPORT_BATCH: u32 = 100;
+
+/// Longest title fragment used in archive entry names
+const MAX_ENTRY_TITLE_CHARS: usize = 50;
+
+/// Whether a bulk export is running (only one at a time)
+static BULK_EXPORT_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Set by `cancel_transcript_export`; checked between archive entries
+static BULK_EXPORT_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Archive entry name `YYYY-MM-DD_title_id.ext`, made unique against `used`
+/// by appending `_2`, `_3`, …. The title part keeps letters, digits, `-` and
+/// `_` (spaces become `_`) and is dropped when `include_title` is false.
+fn entry_name(
+    transcript: &Transcript,
+    extension: &str,
+    include_title: bool,
+    used: &mut HashSet<String>,
+) -> String {
+    let date = chrono::DateTime::from_timestamp_millis(transcript.created_at_ms as i64)
+        .map(|utc| {
+            utc.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+                .to_string()
+        })
+        .unwrap_or_else(|| "undated".to_string());
+    let title: String = transcript
+        .title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("_")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .take(MAX_ENTRY_TITLE_CHARS)
+        .collect();
+
+    let stem = if include_title && !title.is_empty() {
+        format!("{date}_{title}_{}", transcript.id)
+    } else {
+        format!("{date}_{}", transcript.id)
+    };
+    let mut name = format!("{stem}.{extension}");
+    let mut suffix = 2;
+    while !used.insert(name.clone()) {
+        name = format!("{stem}_{suffix}.{extension}");
+        suffix += 1;
+    }
+    name
+}
+
+/// Writes matching transcripts as a ZIP into `out`, newest first. Returns
+/// (exported, total, the finished writer), without a writer when cancelled.
+fn write_archive<W: Write + Seek>(
+    app: &AppHandle,
+    out: W,
+    format: TranscriptExportFormat,
+    filter: &TranscriptFilter,
+    passphrase: Option<&str>,
+) -> Result<(u32, u32, Option<W>), String> {
+    let database = app.state::<Database>();
+    let mut zip = ZipWriter::new(out);
+
+    // Typed with the passphrase's lifetime (SimpleFileOptions alone would need 'static)
+    let base_options: FileOptions<'_, ()> =
+        FileOptions::default().compression_method(CompressionMethod::Deflated);
+    let options = match passphrase {
+        Some(passphrase) => base_options.with_aes_encryption(AesMode::Aes256, passphrase),
+        None => base_options,
+    };
+
+    let mut used_names = HashSet::new();
+    let mut exported = 0;
+    let mut total;
+    loop {
+        let page =
+            transcripts::query_transcript_page(&database, filter, BULK_EXPORT_BATCH, exported)?;
+        total = page.total;
+        if page.transcripts.is_empty() {
+            break;
+        }
+
+        for transcript in page.transcripts {
+            if BULK_EXPORT_CANCELLED.load(Ordering::SeqCst) {
+                return Ok((exported, total, None));
+            }
+
+            // ZIP encryption covers contents, not names, so encrypted archives
+            // leave titles (which can identify patients) out of entry names
+            let name = entry_name(
+                &transcript,
+                format.extension(),
+                passphrase.is_none(),
+                &mut used_names,
+            );
+            let bytes = render_transcript(&transcript, format)?;
+            zip.start_file(name, options)
+                .and_then(|_| zip.write_all(&bytes).map_err(Into::into))
+                .map_err(|e| format!("Failed to write archive entry: {e}"))?;
+
+            exported += 1;
+            let progress = TranscriptExportProgress {
+                done: exported,
+                total,
+            };
+            if let Err(e) = progress.emit(app) {
+                log::warn!("Failed to emit export progress: {e}");
+            }
+        }
+    }
+
+    let out = zip
+        .finish()
+        .map_err(|e| format!("Failed to finish archive: {e}"))?;
+    Ok((exported, total, Some(out)))
+}
+
+/// Writes the archive to `destination`, sealed in an envelope under
+/// `envelope_passphrase` if given. The sealed archive is built in memory so
+/// no plaintext copy touches the disk. Returns (exported, total, cancelled).
+fn export_archive(
+    app: &AppHandle,
+    destination: &Path,
+    format: TranscriptExportFormat,
+    filter: &TranscriptFilter,
+    zip_passphrase: Option<&str>,
+    envelope_passphrase: Option<&str>,
+) -> Result<(u32, u32, bool), String> {
+    let Some(envelope_passphrase) = envelope_passphrase else {
+        let file = std::fs::File::create(destination)
+            .map_err(|e| format!("Failed to create archive: {e}"))?;
+        let (exported, total, file) = write_archive(app, file, format, filter, zip_passphrase)?;
+        if let Some(file) = &file {
+            file.sync_all()
+                .map_err(|e| format!("Failed to finish archive: {e}"))?;
+        }
+        return Ok((exported, total, file.is_none()));
+    };
+
+    let (exported, total, buffer) =
+        write_archive(app, Cursor::new(Vec::new()), format, filter, zip_passphrase)?;
+    if let Some(buffer) = &buffer {
+        let sealed = envelope::seal(buffer.get_ref(), envelope_passphrase, "zip")?;
+        write_export(destination, &sealed)?;
+    }
+    Ok((exported, total, buffer.is_none()))
+}
+
+/// Exports all transcripts created within `date_range` into a ZIP archive at
+/// `path` (from the save dialog), one file per transcript in `format`. With a
+/// `passphrase`, entries are AES-256 encrypted. With `encrypted`, the whole
+/// archive, entry names included, is sealed into a `.psenc` envelope at
+/// `path` instead. Emits `transcript-export-progress` after each entry;
+/// `cancel_transcript_export` stops the export and deletes the partial archive.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn export_all_transcripts(
+    app: AppHandle,
+    path: String,
+    format: TranscriptExportFormat,
+    date_range: Option<DateRange>,
+    passphrase: Option<String>,
+    encrypted: Option<ExportEncryption>,
+) -> Result<BulkExportSummary, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let envelope_passphrase = envelope_passphrase(encrypted)?;
+    let extension = match envelope_passphrase {
+        Some(_) => envelope::EXTENSION,
+        None => "zip",
+    };
+    let destination = validate_export_destination(&app, &path, &[extension])
+        .map_err(|message| TranscriptError::ValidationError { message })?;
+    let date_range = date_range.unwrap_or_default();
+    let filter = TranscriptFilter {
+        note_id: None,
+        created_after_ms: date_range.from_ms,
+        created_before_ms: date_range.to_ms,
+        ..Default::default()
+    };
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+
+    if BULK_EXPORT_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err(TranscriptError::ValidationError {
+            message: "An export is already running".to_string(),
+        });
+    }
+    BULK_EXPORT_CANCELLED.store(false, Ordering::SeqCst);
+
+    let export_app = app.clone();
+    let export_path = destination.clone();
+    let encrypted = passphrase.is_some() || envelope_passphrase.is_some();
+    let result = tokio::task::spawn_blocking(move || {
+        export_archive(
+            &export_app,
+            &export_path,
+            format,
+            &filter,
+            passphrase.as_deref(),
+            envelope_passphrase.as_deref(),
+        )
+    })
+    .await;
+    BULK_EXPORT_RUNNING.store(false, Ordering::SeqCst);
+
+    let (exported, total, cancelled) = match result {
+        Ok(Ok(outcome)) => outcome,
+        Ok(Err(message)) => {
+            let _ = std::fs::remove_file(&destination);
+            return Err(io_error(message));
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&destination);
+            return Err(io_error(format!("Export task panicked: {e}")));
+        }
+    };
+
+    if cancelled {
+        let _ = std::fs::remove_file(&destination);
+        log::info!("Bulk export cancelled after {exported} of {total} transcripts");
+        return Ok(BulkExportSummary {
+            exported: 0,
+            total,
+            cancelled: true,
+        });
+    }
+
+    audit::record(
+        &app,
+        AuditEventKind::TranscriptExported,
+        None,
+        &format!(
+            "format={} entries={exported} encrypted={encrypted} destination={}",
+            format.extension(),
+            destination.display()
+        ),
+    );
+    log::info!("Exported {exported} transcripts to archive");
+    Ok(BulkExportSummary {
+        exported,
+        total,
+        cancelled: false,
+    })
+}
+
+/// Cancels the running bulk export, if any.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn cancel_transcript_export() {
+    BULK_EXPORT_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+// ============================================================================
+// Decryption
+// ============================================================================
+
+/// Decrypts a `.psenc` export at `path` (from the open dialog) and writes the
+/// file it holds to `destination` (from the save dialog, ending in the
+/// wrapped file's extension). Fails with `WrongPassphrase` when the
+/// passphrase doesn't open it. Returns the number of bytes written.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn decrypt_export(
+    app: AppHandle,
+    path: String,
+    passphrase: String,
+    destination: String,
+) -> Result<f64, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let source = PathBuf::from(&path);
+    if !source.is_absolute() || !app.fs_scope().is_allowed(&source) {
+        return Err(TranscriptError::ValidationError {
+            message: "Encrypted export was not chosen through the open dialog".to_string(),
+        });
+    }
+    let data = std::fs::read(&source)
+        .map_err(|e| io_error(format!("Failed to read encrypted export: {e}")))?;
+
+    // Argon2 is deliberately slow; keep it off the async runtime
+    let opened = tokio::task::spawn_blocking(move || envelope::open(&data, &passphrase))
+        .await
+        .map_err(|e| io_error(format!("Decryption task panicked: {e}")))?
+        .map_err(|e| match e {
+            envelope::OpenError::WrongPassphrase => TranscriptError::WrongPassphrase,
+            envelope::OpenError::NotAnEnvelope => TranscriptError::ValidationError {
+                message: "Not an encrypted PrivacyScribe export".to_string(),
+            },
+            envelope::OpenError::Unsupported => TranscriptError::ValidationError {
+                message: "Encrypted with a newer version of PrivacyScribe".to_string(),
+            },
+        })?;
+
+    let destination = validate_export_destination(&app, &destination, &[&opened.extension])
+        .map_err(|message| TranscriptError::ValidationError { message })?;
+    let write_path = destination.clone();
+    let bytes_written = opened.plaintext.len();
+    tokio::task::spawn_blocking(move || write_export(&write_path, &opened.plaintext))
+        .await
+        .map_err(|e| io_error(format!("Decryption task panicked: {e}")))?
+        .map_err(io_error)?;
+
+    audit::record(
+        &app,
+        AuditEventKind::ExportDecrypted,
+        None,
+        &format!(
+            "source={} destination={}",
+            source.display(),
+            destination.display()
+        ),
+    );
+    log::info!("Decrypted export ({bytes_written} bytes)");
+    Ok(bytes_written as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript() -> Transcript {
+        Transcript {
+            id: "tr_1".into(),
+            title: "Knee \"follow-up\"".into(),
+            text: "Pain improved.\n\nContinue physio.".into(),
+            language: Some("no".into()),
+            model_id: None,
+            duration_secs: 134.4,
+            word_count: 4,
+            session_label: None,
+            note_id: None,
+            tags: vec!["knee".into(), "physio".into()],
+            deidentified_with: None,
+            created_at_ms: 1_700_000_000_000.0,
+            updated_at_ms: 1_700_000_000_000.0,
+            deleted_at_ms: None,
+        }
+    }
+
+    #[test]
+    fn markdown_has_quoted_front_matter_then_body() {
+        let md = render_md(&transcript());
+        assert!(md.starts_with("---\ntitle: \"Knee \\\"follow-up\\\"\"\nid: tr_1\n"));
+        assert!(md.contains(
+            "duration_secs: 134\nlanguage: \"no\"\nword_count: 4\ntags: [\"knee\",\"physio\"]\n---\n\n# Knee"
+        ));
+        assert!(md.ends_with("Pain improved.\n\nContinue physio.\n"));
+    }
+
+    #[test]
+    fn entry_names_are_sanitized_and_unique() {
+        let mut used = HashSet::new();
+        let mut transcript = transcript();
+        transcript.title = "Knee / follow-up: 2nd".into();
+        let first = entry_name(&transcript, "md", true, &mut used);
+        assert!(first.ends_with("_Knee__follow-up_2nd_tr_1.md"), "{first}");
+        let second = entry_name(&transcript, "md", true, &mut used);
+        assert_eq!(second, first.replace(".md", "_2.md"));
+    }
+
+    #[test]
+    fn entry_names_can_omit_titles() {
+        let name = entry_name(&transcript(), "txt", false, &mut HashSet::new());
+        assert!(
+            name.ends_with("_tr_1.txt") && !name.contains("Knee"),
+            "{name}"
+        );
+    }
+
+    #[test]
+    fn text_export_falls_back_to_untitled() {
+        let mut untitled = transcript();
+        untitled.title = "  ".into();
+        let txt = render_txt(&untitled);
+        assert!(txt.starts_with("Untitled transcript\nCreated: "));
+        assert!(
+            txt.contains("Duration: 2m 14s\nLanguage: no\nTags: knee, physio\n\nPain improved.")
+        );
+    }
+}
@@ -0,0 +1,362 @@
+//! End-to-end health check, so "dictation doesn't work" can be narrowed down
+//! to the part that's broken.
+//!
+//! `run_health_check` walks the path a dictation takes — microphone, model,
+//! whisper, database, crash recovery, disk, shortcut, notifications — one
+//! check at a time, and announces each result with `health-check-progress`
+//! as it lands. Every check has its own time limit; one that runs over is
+//! reported as failed and the next one starts (the blocking work it started
+//! is left to finish in the background).
+
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::commands::audio::{self, RecordingPhase};
+use crate::commands::{notifications, preferences, recovery, transcription};
+use crate::db::Database;
+use crate::types::{
+    AppError, DatabaseStatus, HealthCheckKind, HealthCheckProgress, HealthCheckResult,
+    HealthStatus, PermissionStatus,
+};
+use crate::utils::audio::TARGET_SAMPLE_RATE;
+use crate::utils::disk::{self, DiskHeadroom};
+use crate::utils::model_integrity::{self, Verdict};
+use crate::utils::{metrics, paths, permissions};
+
+/// The checks, in the order they run
+const CHECKS: [HealthCheckKind; 8] = [
+    HealthCheckKind::Microphone,
+    HealthCheckKind::Model,
+    HealthCheckKind::Whisper,
+    HealthCheckKind::Database,
+    HealthCheckKind::RecoveryDirectory,
+    HealthCheckKind::DiskSpace,
+    HealthCheckKind::Shortcut,
+    HealthCheckKind::Notifications,
+];
+
+/// How long a check may take
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long loading the model into whisper may take; large models on slow
+/// disks need a while
+const WHISPER_CHECK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long the microphone is listened to
+const MICROPHONE_PROBE: Duration = Duration::from_millis(200);
+
+/// Name of the file written to prove the recovery directory is writable
+const RECOVERY_PROBE_FILE: &str = ".health-check";
+
+type Outcome = (HealthStatus, String);
+
+fn time_limit(check: HealthCheckKind) -> Duration {
+    match check {
+        HealthCheckKind::Whisper => WHISPER_CHECK_TIMEOUT,
+        _ => CHECK_TIMEOUT + MICROPHONE_PROBE,
+    }
+}
+
+/// A permission read without asking for it: denied fails, not asked yet warns.
+fn permission_outcome(what: &str, permission: Result<PermissionStatus, String>) -> Outcome {
+    match permission {
+        Ok(PermissionStatus::Granted) => (HealthStatus::Pass, format!("{what} access is granted")),
+        Ok(PermissionStatus::NotDetermined) => (
+            HealthStatus::Warn,
+            format!("{what} access hasn't been asked for yet"),
+        ),
+        Ok(PermissionStatus::Denied) => (
+            HealthStatus::Fail,
+            format!("{what} access is denied in system settings"),
+        ),
+        Ok(PermissionStatus::Restricted) => (
+            HealthStatus::Fail,
+            format!("{what} access is blocked by a device policy"),
+        ),
+        Err(e) => (
+            HealthStatus::Warn,
+            format!("{what} access couldn't be read: {e}"),
+        ),
+    }
+}
+
+/// The microphone permission, then whether the default input device
+/// delivers audio. The device isn't opened while recording, nor before the
+/// user was asked for access (opening it would ask).
+fn check_microphone() -> Outcome {
+    let permission = permissions::microphone_permission();
+    let asked = !matches!(permission, Ok(PermissionStatus::NotDetermined));
+    let (status, detail) = permission_outcome("Microphone", permission);
+    if status == HealthStatus::Fail || !asked {
+        return (status, detail);
+    }
+    if audio::recording_phase() != RecordingPhase::Idle {
+        return (
+            status,
+            format!("{detail}; the microphone is in use by a recording"),
+        );
+    }
+    match audio::probe_input_device(MICROPHONE_PROBE) {
+        Ok((device, 0)) => (
+            HealthStatus::Fail,
+            format!("{device} opened but delivered no audio"),
+        ),
+        Ok((device, _)) => (status, format!("{detail}; {device} delivers audio")),
+        Err(e) => (HealthStatus::Fail, e.to_string()),
+    }
+}
+
+/// Whether the default model is downloaded and matches its checksum. Only
+/// re-hashes a model that changed since it was verified; a corrupt model is
+/// reported, not deleted.
+fn check_model(app: &AppHandle) -> Outcome {
+    let path = match transcription::default_model_path(app) {
+        Ok(Some(path)) => path,
+        Ok(None) => {
+            return (
+                HealthStatus::Fail,
+                "The default model isn't downloaded".to_string(),
+            )
+        }
+        Err(e) => return (HealthStatus::Fail, e.to_string()),
+    };
+    match model_integrity::check_before_load(&path) {
+        Ok(Verdict::Intact) => (
+            HealthStatus::Pass,
+            "The default model is downloaded and intact".to_string(),
+        ),
+        Ok(Verdict::Corrupt) => (
+            HealthStatus::Fail,
+            "The default model doesn't match its checksum; download it again".to_string(),
+        ),
+        Err(e) => (
+            HealthStatus::Warn,
+            format!("The default model couldn't be verified: {e}"),
+        ),
+    }
+}
+
+/// Whether whisper loads the default model. A missing or corrupt model isn't
+/// loaded.
+fn check_whisper(app: &AppHandle) -> Outcome {
+    let path = match transcription::default_model_path(app) {
+        Ok(Some(path)) => path,
+        Ok(None) => {
+            return (
+                HealthStatus::Fail,
+                "Skipped: the default model isn't downloaded".to_string(),
+            )
+        }
+        Err(e) => return (HealthStatus::Fail, e.to_string()),
+    };
+    if let Ok(Verdict::Corrupt) = model_integrity::check_before_load(&path) {
+        return (
+            HealthStatus::Fail,
+            "Skipped: the default model doesn't match its checksum".to_string(),
+        );
+    }
+    match transcription::try_load_model(&path) {
+        Ok(()) => (
+            HealthStatus::Pass,
+            "whisper loads the default model".to_string(),
+        ),
+        Err(e) => (
+            HealthStatus::Fail,
+            format!("whisper couldn't load the default model: {e}"),
+        ),
+    }
+}
+
+fn check_database(app: &AppHandle) -> Outcome {
+    let db = app.state::<Database>();
+    match db.status() {
+        DatabaseStatus::Ready => {}
+        DatabaseStatus::Locked => return (HealthStatus::Warn, DatabaseStatus::Locked.to_string()),
+        status => {
+            return (
+                HealthStatus::Fail,
+                format!("The database isn't open: {status}"),
+            )
+        }
+    }
+    match db.quick_check_problems() {
+        Ok(problems) if problems.is_empty() => (
+            HealthStatus::Pass,
+            "The database is open and passes its quick check".to_string(),
+        ),
+        Ok(problems) => (
+            HealthStatus::Fail,
+            format!(
+                "The database quick check found {} problem(s): {}",
+                problems.len(),
+                problems[0]
+            ),
+        ),
+        Err(e) => (HealthStatus::Fail, e),
+    }
+}
+
+/// Writes and removes a file where recordings are streamed for crash recovery.
+fn check_recovery_directory(app: &AppHandle) -> Outcome {
+    let result = recovery::get_recovery_dir(app).and_then(|dir| {
+        let probe = dir.join(RECOVERY_PROBE_FILE);
+        std::fs::write(&probe, b"ok")
+            .and_then(|()| std::fs::remove_file(&probe))
+            .map_err(|e| format!("Failed to write to the recovery directory: {e}"))
+    });
+    match result {
+        Ok(()) => (
+            HealthStatus::Pass,
+            "The recovery directory is writable".to_string(),
+        ),
+        Err(e) => (HealthStatus::Fail, e),
+    }
+}
+
+/// Free space against the low-disk thresholds, measured in minutes of
+/// recording at the default input device's format.
+fn check_disk_space(app: &AppHandle) -> Outcome {
+    let available = match paths::app_data_dir(app)
+        .map_err(|e| e.to_string())
+        .and_then(|dir| disk::available_bytes(&dir).map_err(|e| e.to_string()))
+    {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return (
+                HealthStatus::Warn,
+                format!("Free disk space couldn't be read: {e}"),
+            )
+        }
+    };
+    let (sample_rate, channels) = audio::default_input_format().unwrap_or((TARGET_SAMPLE_RATE, 1));
+    let prefs = preferences::load_saved_preferences(app);
+    let per_minute = disk::recording_bytes_per_minute(sample_rate, channels);
+    let headroom = disk::classify_headroom(
+        available,
+        per_minute * u64::from(prefs.low_disk_warning_minutes),
+        per_minute * u64::from(prefs.low_disk_block_minutes),
+    );
+    let minutes = available / per_minute.max(1);
+    let detail = format!("Free space holds about {minutes} minutes of recording");
+    (headroom_status(headroom), detail)
+}
+
+fn headroom_status(headroom: DiskHeadroom) -> HealthStatus {
+    match headroom {
+        DiskHeadroom::Sufficient => HealthStatus::Pass,
+        DiskHeadroom::Low => HealthStatus::Warn,
+        DiskHeadroom::Insufficient => HealthStatus::Fail,
+    }
+}
+
+#[cfg(desktop)]
+fn check_shortcut(app: &AppHandle) -> Outcome {
+    match crate::commands::quick_pane::registered_quick_pane_shortcut(app) {
+        Some(shortcut) => (
+            HealthStatus::Pass,
+            format!("{shortcut} is registered with the system"),
+        ),
+        None => (
+            HealthStatus::Fail,
+            "The quick pane shortcut isn't registered; another app may have taken it".to_string(),
+        ),
+    }
+}
+
+#[cfg(not(desktop))]
+fn check_shortcut(_app: &AppHandle) -> Outcome {
+    (
+        HealthStatus::Pass,
+        "Global shortcuts aren't used on this platform".to_string(),
+    )
+}
+
+/// Runs `check`. Blocks.
+fn run_check(app: &AppHandle, check: HealthCheckKind) -> Outcome {
+    match check {
+        HealthCheckKind::Microphone => check_microphone(),
+        HealthCheckKind::Model => check_model(app),
+        HealthCheckKind::Whisper => check_whisper(app),
+        HealthCheckKind::Database => check_database(app),
+        HealthCheckKind::RecoveryDirectory => check_recovery_directory(app),
+        HealthCheckKind::DiskSpace => check_disk_space(app),
+        HealthCheckKind::Shortcut => check_shortcut(app),
+        HealthCheckKind::Notifications => {
+            permission_outcome("Notification", notifications::notification_permission(app))
+        }
+    }
+}
+
+/// Runs `check` on a blocking thread within its time limit.
+async fn run_with_time_limit(app: &AppHandle, check: HealthCheckKind) -> HealthCheckResult {
+    let limit = time_limit(check);
+    let task_app = app.clone();
+    let task = tokio::task::spawn_blocking(move || run_check(&task_app, check));
+    let (status, detail) = match tokio::time::timeout(limit, task).await {
+        Ok(Ok(outcome)) => outcome,
+        Ok(Err(e)) => (HealthStatus::Fail, format!("The check crashed: {e}")),
+        Err(_) => (
+            HealthStatus::Fail,
+            format!("Didn't finish within {} seconds", limit.as_secs()),
+        ),
+    };
+    if status != HealthStatus::Pass {
+        log::warn!("Health check {check:?}: {status:?}");
+    }
+    HealthCheckResult {
+        check,
+        status,
+        detail,
+    }
+}
+
+/// Checks, one after the other, everything a dictation depends on. Each
+/// result is also sent as `health-check-progress` when its check finishes.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn run_health_check(app: AppHandle) -> Result<Vec<HealthCheckResult>, AppError> {
+    let mut results = Vec::with_capacity(CHECKS.len());
+    for check in CHECKS {
+        let result = run_with_time_limit(&app, check).await;
+        results.push(result.clone());
+        let progress = HealthCheckProgress {
+            result,
+            done: results.len() as u32,
+            total: CHECKS.len() as u32,
+        };
+        if let Err(e) = progress.emit(&app) {
+            log::warn!("Failed to emit health-check-progress: {e}");
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disk_headroom_maps_to_status() {
+        assert_eq!(
+            headroom_status(DiskHeadroom::Sufficient),
+            HealthStatus::Pass
+        );
+        assert_eq!(headroom_status(DiskHeadroom::Low), HealthStatus::Warn);
+        assert_eq!(
+            headroom_status(DiskHeadroom::Insufficient),
+            HealthStatus::Fail
+        );
+    }
+
+    #[test]
+    fn permissions_not_asked_for_warn_and_denied_fail() {
+        let status = |permission| permission_outcome("Microphone", Ok(permission)).0;
+        assert_eq!(status(PermissionStatus::Granted), HealthStatus::Pass);
+        assert_eq!(status(PermissionStatus::NotDetermined), HealthStatus::Warn);
+        assert_eq!(status(PermissionStatus::Denied), HealthStatus::Fail);
+        assert_eq!(status(PermissionStatus::Restricted), HealthStatus::Fail);
+    }
+}
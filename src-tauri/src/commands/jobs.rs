@@ -0,0 +1,320 @@
+//! Long-running work as jobs the frontend can list, follow and cancel.
+//!
+//! Transcriptions and model downloads register a job with `start` and report
+//! through the `JobHandle` it returns. Every change is announced with one
+//! event shape, `job-progress`, so the UI can keep a single activity list.
+//! Finished jobs stay retrievable with `get_job` for `FINISHED_JOB_TTL` (at
+//! most `MAX_FINISHED_JOBS` of them).
+//!
+//! Transcriptions still publish `pipeline-status` and downloads
+//! `whisper-model-download-progress` alongside, until the frontend has moved
+//! over to jobs.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::commands::pipeline;
+use crate::types::{AppError, Job, JobKind, JobOutput, JobProgress, JobStatus};
+use crate::utils::metrics;
+
+/// How long a finished job can still be looked up
+const FINISHED_JOB_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Finished jobs kept at most; the oldest are dropped first
+const MAX_FINISHED_JOBS: usize = 50;
+
+/// Tells apart jobs started in the same millisecond
+static NEXT_SEQUENCE: AtomicU32 = AtomicU32::new(0);
+
+struct Entry {
+    job: Job,
+    cancel: Arc<AtomicBool>,
+    /// When the job finished; None while it runs
+    finished_at: Option<Instant>,
+}
+
+/// Jobs running and recently finished, by id.
+#[derive(Default)]
+pub struct JobManager {
+    jobs: Mutex<HashMap<String, Entry>>,
+}
+
+impl JobManager {
+    fn with<T>(&self, f: impl FnOnce(&mut HashMap<String, Entry>) -> T) -> T {
+        let mut jobs = self.jobs.lock().unwrap_or_else(|e| e.into_inner());
+        prune(&mut jobs, Instant::now());
+        f(&mut jobs)
+    }
+}
+
+/// Drops finished jobs past `FINISHED_JOB_TTL`, then the oldest beyond
+/// `MAX_FINISHED_JOBS`. Running jobs are never dropped.
+fn prune(jobs: &mut HashMap<String, Entry>, now: Instant) {
+    jobs.retain(|_, entry| {
+        entry
+            .finished_at
+            .is_none_or(|finished| now.duration_since(finished) < FINISHED_JOB_TTL)
+    });
+    let mut finished: Vec<(Instant, String)> = jobs
+        .iter()
+        .filter_map(|(id, entry)| Some((entry.finished_at?, id.clone())))
+        .collect();
+    if finished.len() > MAX_FINISHED_JOBS {
+        finished.sort();
+        for (_, id) in &finished[..finished.len() - MAX_FINISHED_JOBS] {
+            jobs.remove(id);
+        }
+    }
+}
+
+/// Whether `cancel_job` can stop jobs of `kind`. A transcription can't be
+/// interrupted once whisper is running.
+fn is_cancellable(kind: JobKind) -> bool {
+    match kind {
+        JobKind::Transcription => false,
+        JobKind::ModelDownload => true,
+    }
+}
+
+fn is_finished(status: &JobStatus) -> bool {
+    !matches!(status, JobStatus::Running { .. })
+}
+
+fn announce(app: &AppHandle, job: Job) {
+    if let Err(e) = JobProgress(job).emit(app) {
+        log::warn!("Failed to emit job-progress: {e}");
+    }
+}
+
+/// Moves job `id` to `status` and announces it. Finished jobs don't change
+/// any more, and a repeat of the current status isn't announced.
+fn set_status(app: &AppHandle, id: &str, status: JobStatus) {
+    let changed = app.state::<JobManager>().with(|jobs| {
+        let entry = jobs.get_mut(id)?;
+        if entry.finished_at.is_some() || entry.job.status == status {
+            return None;
+        }
+        if is_finished(&status) {
+            entry.finished_at = Some(Instant::now());
+        }
+        entry.job.status = status;
+        entry.job.updated_at_ms = pipeline::now_ms();
+        Some(entry.job.clone())
+    });
+    if let Some(job) = changed {
+        announce(app, job);
+    }
+}
+
+/// Registers a running job of `kind` and announces it.
+pub fn start(app: &AppHandle, kind: JobKind) -> JobHandle {
+    let now = pipeline::now_ms();
+    let sequence = NEXT_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let id = format!("job_{:x}_{sequence}", now as u64);
+    let job = Job {
+        id: id.clone(),
+        kind,
+        status: JobStatus::Running { percent: None },
+        cancellable: is_cancellable(kind),
+        created_at_ms: now,
+        updated_at_ms: now,
+    };
+    let cancel = Arc::new(AtomicBool::new(false));
+    app.state::<JobManager>().with(|jobs| {
+        jobs.insert(
+            id.clone(),
+            Entry {
+                job: job.clone(),
+                cancel: cancel.clone(),
+                finished_at: None,
+            },
+        )
+    });
+    announce(app, job);
+    JobHandle {
+        app: app.clone(),
+        id,
+        cancel,
+        finished: false,
+    }
+}
+
+/// A running job, for the work it tracks. Dropping it unfinished fails the job.
+pub struct JobHandle {
+    app: AppHandle,
+    id: String,
+    cancel: Arc<AtomicBool>,
+    finished: bool,
+}
+
+impl JobHandle {
+    /// Reports progress, 0–100.
+    pub fn progress(&self, percent: u32) {
+        set_status(
+            &self.app,
+            &self.id,
+            JobStatus::Running {
+                percent: Some(percent.min(100)),
+            },
+        );
+    }
+
+    /// `progress` for work that runs on another thread.
+    pub fn reporter(&self) -> impl Fn(u32) + Send + 'static {
+        let app = self.app.clone();
+        let id = self.id.clone();
+        move |percent| {
+            set_status(
+                &app,
+                &id,
+                JobStatus::Running {
+                    percent: Some(percent.min(100)),
+                },
+            )
+        }
+    }
+
+    /// Whether `cancel_job` asked for the job to stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+
+    /// Finishes the job with the outcome of its work. `AppError::Cancelled`
+    /// finishes it as cancelled.
+    pub fn finish<T>(mut self, result: &Result<T, AppError>, output: impl FnOnce(&T) -> JobOutput) {
+        self.finished = true;
+        let status = match result {
+            Ok(value) => JobStatus::Succeeded {
+                output: output(value),
+            },
+            Err(AppError::Cancelled) => JobStatus::Cancelled,
+            Err(error) => JobStatus::Failed {
+                error: error.clone(),
+            },
+        };
+        set_status(&self.app, &self.id, status);
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        if !self.finished {
+            set_status(
+                &self.app,
+                &self.id,
+                JobStatus::Failed {
+                    error: AppError::internal("The job ended without a result"),
+                },
+            );
+        }
+    }
+}
+
+/// Returns a running or recently finished job.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn get_job(app: AppHandle, id: String) -> Result<Job, AppError> {
+    app.state::<JobManager>()
+        .with(|jobs| jobs.get(&id).map(|entry| entry.job.clone()))
+        .ok_or_else(|| AppError::invalid_input(format!("Unknown or expired job: {id}")))
+}
+
+/// Lists running and recently finished jobs, oldest first.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn list_jobs(app: AppHandle) -> Vec<Job> {
+    let mut jobs: Vec<Job> = app
+        .state::<JobManager>()
+        .with(|jobs| jobs.values().map(|entry| entry.job.clone()).collect());
+    jobs.sort_by(|a, b| a.created_at_ms.total_cmp(&b.created_at_ms));
+    jobs
+}
+
+/// Asks a running job to stop. The job finishes as cancelled once its work
+/// notices, announced with `job-progress`. Cancelling a finished job does
+/// nothing.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn cancel_job(app: AppHandle, id: String) -> Result<(), AppError> {
+    app.state::<JobManager>().with(|jobs| {
+        let entry = jobs
+            .get(&id)
+            .ok_or_else(|| AppError::invalid_input(format!("Unknown or expired job: {id}")))?;
+        if !entry.job.cancellable {
+            return Err(AppError::invalid_input(format!(
+                "A {:?} job can't be cancelled",
+                entry.job.kind
+            )));
+        }
+        if entry.finished_at.is_none() {
+            log::info!("Cancelling job {id}");
+            entry.cancel.store(true, Ordering::SeqCst);
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, finished_at: Option<Instant>) -> (String, Entry) {
+        let job = Job {
+            id: id.to_string(),
+            kind: JobKind::ModelDownload,
+            status: JobStatus::Running { percent: None },
+            cancellable: true,
+            created_at_ms: 0.0,
+            updated_at_ms: 0.0,
+        };
+        let entry = Entry {
+            job,
+            cancel: Arc::new(AtomicBool::new(false)),
+            finished_at,
+        };
+        (id.to_string(), entry)
+    }
+
+    #[test]
+    fn finished_jobs_expire_and_running_ones_stay() {
+        let now = Instant::now() + FINISHED_JOB_TTL * 2;
+        let mut jobs: HashMap<String, Entry> = [
+            entry("running", None),
+            entry("recent", Some(now - Duration::from_secs(1))),
+            entry("expired", Some(now - FINISHED_JOB_TTL)),
+        ]
+        .into_iter()
+        .collect();
+        prune(&mut jobs, now);
+        let mut ids: Vec<&str> = jobs.keys().map(String::as_str).collect();
+        ids.sort();
+        assert_eq!(ids, ["recent", "running"]);
+    }
+
+    #[test]
+    fn only_the_newest_finished_jobs_are_kept() {
+        let now = Instant::now() + Duration::from_secs(60);
+        let mut jobs: HashMap<String, Entry> = (0..MAX_FINISHED_JOBS as u64 + 5)
+            .map(|i| {
+                entry(
+                    &format!("job_{i}"),
+                    Some(now - Duration::from_millis(1000 - i)),
+                )
+            })
+            .chain([entry("running", None)])
+            .collect();
+        prune(&mut jobs, now);
+        assert_eq!(jobs.len(), MAX_FINISHED_JOBS + 1);
+        assert!(jobs.contains_key("running"));
+        assert!(!jobs.contains_key("job_4"));
+        assert!(jobs.contains_key("job_5"));
+    }
+}
@@ -13,7 +13,12 @@ use llama_cpp_2::llama_batch::LlamaBatch;
 use llama_cpp_2::model::params::LlamaModelParams;
 use llama_cpp_2::model::LlamaModel;
 use llama_cpp_2::sampling::LlamaSampler;
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::types::{LlmChunk, LlmDone, LlmError, LlmModelDownloadProgress};
+use crate::utils::metrics;
+use crate::utils::paths;
 
 const MODEL_FILENAME: &str = "Meta-Llama-3.1-8B-Instruct-Q5_K_M.gguf";
 const MODEL_URL: &str = "https://huggingface.co/bartowski/Meta-Llama-3.1-8B-Instruct-GGUF/resolve/main/Meta-Llama-3.1-8B-Instruct-Q5_K_M.gguf";
@@ -39,6 +44,7 @@ static IS_GENERATING: AtomicBool = AtomicBool::new(false);
 /// Check whether the LLM model file exists locally and is valid.
 #[tauri::command]
 #[specta::specta]
+#[metrics::timed]
 pub async fn check_llm_model(app: AppHandle) -> Result<bool, String> {
     let model_path = get_model_path(&app)?;
 
@@ -55,6 +61,7 @@ pub async fn check_llm_model(app: AppHandle) -> Result<bool, String> {
 /// Emits `llm-model-download-progress` events with `{ percent: number }`.
 #[tauri::command]
 #[specta::specta]
+#[metrics::timed]
 pub async fn download_llm_model(app: AppHandle) -> Result<(), String> {
     let model_path = get_model_path(&app)?;
 
@@ -70,10 +77,7 @@ pub async fn download_llm_model(app: AppHandle) -> Result<(), String> {
     }
 
     log::info!("Downloading LLM model from {MODEL_URL}");
-    let _ = app.emit(
-        "llm-model-download-progress",
-        serde_json::json!({ "percent": 0 }),
-    );
+    let _ = LlmModelDownloadProgress { percent: 0 }.emit(&app);
 
     let response = reqwest::get(MODEL_URL)
         .await
@@ -100,10 +104,7 @@ pub async fn download_llm_model(app: AppHandle) -> Result<(), String> {
         downloaded += chunk.len() as u64;
         if total_size > 0 {
             let percent = (downloaded as f64 / total_size as f64 * 100.0) as u32;
-            let _ = app.emit(
-                "llm-model-download-progress",
-                serde_json::json!({ "percent": percent }),
-            );
+            let _ = LlmModelDownloadProgress { percent }.emit(&app);
         }
     }
 
@@ -115,10 +116,7 @@ pub async fn download_llm_model(app: AppHandle) -> Result<(), String> {
     std::fs::rename(&tmp_path, &model_path)
         .map_err(|e| format!("Failed to finalize model file: {e}"))?;
 
-    let _ = app.emit(
-        "llm-model-download-progress",
-        serde_json::json!({ "percent": 100 }),
-    );
+    let _ = LlmModelDownloadProgress { percent: 100 }.emit(&app);
     log::info!("LLM model downloaded: {}", model_path.display());
 
     Ok(())
@@ -130,11 +128,13 @@ pub async fn download_llm_model(app: AppHandle) -> Result<(), String> {
 /// or `llm-error` on failure. Uses GBNF grammar to force valid JSON output.
 #[tauri::command]
 #[specta::specta]
+#[metrics::timed]
 pub async fn generate_note_stream(
     app: AppHandle,
     system_prompt: String,
     user_content: String,
 ) -> Result<(), String> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
     // Prevent concurrent generation
     if IS_GENERATING.swap(true, Ordering::SeqCst) {
         return Err("already_generating".into());
@@ -160,11 +160,11 @@ pub async fn generate_note_stream(
 
     match result {
         Ok(()) => {
-            let _ = app.emit("llm-done", ());
+            let _ = LlmDone.emit(&app);
             Ok(())
         }
         Err(e) => {
-            let _ = app.emit("llm-error", &e);
+            let _ = LlmError(e.clone()).emit(&app);
             Err(e)
         }
     }
@@ -282,7 +282,7 @@ fn run_generation(
             .unwrap_or_default();
 
         if !token_str.is_empty() {
-            let _ = app.emit("llm-chunk", &token_str);
+            let _ = LlmChunk(token_str).emit(app);
         }
 
         // Prepare next batch with just the new token
@@ -303,9 +303,7 @@ fn run_generation(
 
 /// Get the expected model file path in the app data directory.
 fn get_model_path(app: &AppHandle) -> Result<PathBuf, String> {
-    let models_dir = app
-        .path()
-        .app_data_dir()
+    let models_dir = paths::app_data_dir(app)
         .map_err(|e| format!("Failed to get app data dir: {e}"))?
         .join("models");
 
@@ -3,10 +3,38 @@
 //! Each submodule contains related commands and their helper functions.
 //! Import specific commands via their submodule (e.g., `commands::preferences::greet`).
 
+pub mod app_lock;
 pub mod audio;
+pub mod audit;
+pub mod backup;
+pub mod backup_exclusion;
+pub mod cache;
+pub mod cli;
+pub mod clipboard;
+pub mod compliance;
+pub mod daily_note;
+pub mod deep_link;
+pub mod diagnostics;
+pub mod emergency;
+pub mod export;
+pub mod health;
+pub mod jobs;
 pub mod llm;
+pub mod navigation;
 pub mod notifications;
+pub mod output;
+pub mod pipeline;
 pub mod preferences;
 pub mod quick_pane;
 pub mod recovery;
+pub mod revisions;
+pub mod screen_capture;
+pub mod session_timeout;
+pub mod shutdown;
+pub mod stats;
+pub mod storage;
+pub mod tags;
 pub mod transcription;
+pub mod transcripts;
+pub mod tray;
+pub mod updates;
@@ -0,0 +1,190 @@
+//! Main window navigation commands.
+//!
+//! Lets other windows (e.g. the quick pane) open the main window at a specific
+//! view. Navigation requests are delivered as `navigate` events; if the main
+//! window is cold-started or still loading, the request is held in managed
+//! state until the frontend reports that its listeners are ready. Other
+//! backend events meant for the main window can be queued the same way.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::types::{Navigate, NavigatePayload};
+use crate::utils::metrics;
+
+/// Window label for the main window (matches tauri.conf.json)
+pub const MAIN_WINDOW_LABEL: &str = "main";
+
+/// An event held back for the main window, emitted when called
+type PendingEvent = Box<dyn FnOnce(&AppHandle) -> tauri::Result<()> + Send>;
+
+/// Managed state tracking main window readiness and any queued navigation.
+#[derive(Default)]
+pub struct MainWindowState {
+    /// True once the main window frontend has registered its event listeners.
+    ready: AtomicBool,
+    /// Navigation waiting for the main window to become ready (latest wins).
+    pending_navigation: Mutex<Option<NavigatePayload>>,
+    /// Other events waiting for the main window, delivered in order.
+    pending_events: Mutex<Vec<(&'static str, PendingEvent)>>,
+}
+
+impl MainWindowState {
+    /// Marks the main window as not ready (called when its page starts loading).
+    pub fn mark_loading(&self) {
+        self.ready.store(false, Ordering::SeqCst);
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+}
+
+/// Returns the main window, creating it from the app config if it was closed.
+fn ensure_main_window(app: &AppHandle) -> Result<tauri::WebviewWindow, String> {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+        return Ok(window);
+    }
+
+    log::info!("Main window not open, creating it");
+
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|w| w.label == MAIN_WINDOW_LABEL)
+        .cloned()
+        .ok_or_else(|| "Main window configuration not found".to_string())?;
+
+    // A fresh webview must report readiness before it can receive events
+    if let Some(state) = app.try_state::<MainWindowState>() {
+        state.mark_loading();
+    }
+
+    tauri::WebviewWindowBuilder::from_config(app, &config)
+        .map_err(|e| format!("Failed to configure main window: {e}"))?
+        .build()
+        .map_err(|e| format!("Failed to create main window: {e}"))
+}
+
+/// Shows, unminimizes and focuses the main window, creating it if needed.
+pub fn focus_main_window(app: &AppHandle) -> Result<(), String> {
+    let window = ensure_main_window(app)?;
+    window
+        .show()
+        .map_err(|e| format!("Failed to show main window: {e}"))?;
+    if let Err(e) = window.unminimize() {
+        log::warn!("Failed to unminimize main window: {e}");
+    }
+    window
+        .set_focus()
+        .map_err(|e| format!("Failed to focus main window: {e}"))?;
+    Ok(())
+}
+
+/// Delivers a navigation to the main window, or queues it until the window is ready.
+fn deliver_navigation(app: &AppHandle, navigation: NavigatePayload) -> Result<(), String> {
+    let state = app.state::<MainWindowState>();
+
+    if state.is_ready() {
+        log::debug!("Navigating main window to {}", navigation.route);
+        return Navigate(navigation)
+            .emit_to(app, MAIN_WINDOW_LABEL)
+            .map_err(|e| format!("Failed to emit navigate event: {e}"));
+    }
+
+    log::debug!(
+        "Main window not ready, queueing navigation to {}",
+        navigation.route
+    );
+    let mut pending = state
+        .pending_navigation
+        .lock()
+        .map_err(|e| format!("Failed to lock pending navigation: {e}"))?;
+    *pending = Some(navigation);
+    Ok(())
+}
+
+/// Emits `event` to the main window, or queues it until the window reports ready
+/// (e.g. startup notices that would otherwise be lost on cold start).
+pub fn emit_to_main_when_ready<E>(app: &AppHandle, event: E) -> Result<(), String>
+where
+    E: Event + serde::Serialize + Clone + Send + 'static,
+{
+    let state = app.state::<MainWindowState>();
+
+    if state.is_ready() {
+        return event
+            .emit_to(app, MAIN_WINDOW_LABEL)
+            .map_err(|e| format!("Failed to emit {}: {e}", E::NAME));
+    }
+
+    log::debug!("Main window not ready, queueing {}", E::NAME);
+    state
+        .pending_events
+        .lock()
+        .map_err(|e| format!("Failed to lock pending events: {e}"))?
+        .push((
+            E::NAME,
+            Box::new(move |app: &AppHandle| event.emit_to(app, MAIN_WINDOW_LABEL)),
+        ));
+    Ok(())
+}
+
+/// Opens the main window (creating it if it was closed) and navigates to `route`.
+/// The optional `payload` is forwarded untouched with the `navigate` event.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn open_main_window(
+    app: AppHandle,
+    route: String,
+    payload: Option<serde_json::Value>,
+) -> Result<(), String> {
+    log::info!("Opening main window at route: {route}");
+
+    focus_main_window(&app)?;
+    deliver_navigation(&app, NavigatePayload { route, payload })
+}
+
+/// Called by the main window once its event listeners are registered.
+/// Flushes any events and navigation that were queued while the window was loading.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn main_window_ready(app: AppHandle) -> Result<(), String> {
+    let state = app.state::<MainWindowState>();
+    state.ready.store(true, Ordering::SeqCst);
+
+    let events = std::mem::take(
+        &mut *state
+            .pending_events
+            .lock()
+            .map_err(|e| format!("Failed to lock pending events: {e}"))?,
+    );
+    for (name, emit) in events {
+        log::debug!("Delivering queued {name}");
+        if let Err(e) = emit(&app) {
+            log::warn!("Failed to emit queued {name}: {e}");
+        }
+    }
+
+    let pending = state
+        .pending_navigation
+        .lock()
+        .map_err(|e| format!("Failed to lock pending navigation: {e}"))?
+        .take();
+
+    if let Some(navigation) = pending {
+        log::debug!("Delivering queued navigation to {}", navigation.route);
+        Navigate(navigation)
+            .emit_to(&app, MAIN_WINDOW_LABEL)
+            .map_err(|e| format!("Failed to emit navigate event: {e}"))?;
+    }
+
+    Ok(())
+}
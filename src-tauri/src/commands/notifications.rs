@@ -1,18 +1,221 @@
 //! Native notification commands.
 //!
 //! Provides cross-platform native notification support using the Tauri notification plugin.
+//!
+//! Backend-initiated notifications go through `maybe_notify`, which applies the
+//! user's notification preferences. They carry metadata only, never PHI.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::Timelike;
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::commands::navigation::MAIN_WINDOW_LABEL;
+use crate::commands::quick_pane::QUICK_PANE_LABEL;
+use crate::types::{
+    AppError, DiskSpaceContext, LowDiskSpace, ModelDownloadComplete, ModelDownloadErrorKind,
+    ModelDownloadFailed, NotificationCategory, NotificationSound, NotificationSuppressed,
+    PermissionStatus, TestNotificationResult,
+};
+use crate::utils::dnd;
+use crate::utils::metrics;
+
+type NotificationBuilder = tauri_plugin_notification::NotificationBuilder<tauri::Wry>;
+
+// ============================================================================
+// Window Focus
+// ============================================================================
+
+/// Labels of the windows that currently have focus, kept up to date from
+/// `WindowEvent::Focused` so the answer doesn't depend on when we ask.
+#[derive(Default)]
+pub struct WindowFocusState {
+    focused: Mutex<HashSet<String>>,
+}
+
+impl WindowFocusState {
+    /// Called from the app-wide window event handler.
+    pub fn set_focused(&self, label: &str, focused: bool) {
+        if let Ok(mut labels) = self.focused.lock() {
+            if focused {
+                labels.insert(label.to_string());
+            } else {
+                labels.remove(label);
+            }
+        }
+    }
+
+    /// Whether the main window or the quick pane has focus.
+    pub fn app_focused(&self) -> bool {
+        self.focused.lock().is_ok_and(|labels| {
+            labels.contains(MAIN_WINDOW_LABEL) || labels.contains(QUICK_PANE_LABEL)
+        })
+    }
+}
+
+// ============================================================================
+// Held Transcripts
+// ============================================================================
+
+/// Action type attached to transcription-complete notifications
+const TRANSCRIPTION_ACTION_TYPE: &str = "transcription-complete";
+
+/// Notification action that copies the transcript
+const ACTION_COPY: &str = "copy";
+
+/// Action type attached to long-recording reminders
+const RECORDING_REMINDER_ACTION_TYPE: &str = "recording-reminder";
+
+/// Action type attached to model download failures
+const MODEL_DOWNLOAD_FAILED_ACTION_TYPE: &str = "model-download-failed";
+
+/// Main window route that shows a completed transcription
+const TRANSCRIPTION_RESULT_ROUTE: &str = "/transcription";
+
+/// How long a completed transcript stays in memory for notification actions
+const TRANSCRIPT_HOLD_DURATION: Duration = Duration::from_secs(5 * 60);
+
+/// Completed transcripts keyed by pipeline job id, kept briefly so notification
+/// actions can use them. Entries are removed when consumed or after
+/// `TRANSCRIPT_HOLD_DURATION`, whichever comes first.
+#[derive(Default)]
+pub struct HeldTranscripts {
+    transcripts: Mutex<HashMap<String, String>>,
+}
+
+impl HeldTranscripts {
+    fn take(&self, job_id: &str) -> Option<String> {
+        self.transcripts.lock().ok()?.remove(job_id)
+    }
+}
+
+/// Holds `text` for `job_id` and schedules its removal.
+fn hold_transcript(app: &AppHandle, job_id: &str, text: String) {
+    if let Ok(mut transcripts) = app.state::<HeldTranscripts>().transcripts.lock() {
+        transcripts.insert(job_id.to_string(), text);
+    }
+
+    let app = app.clone();
+    let job_id = job_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(TRANSCRIPT_HOLD_DURATION).await;
+        if app.state::<HeldTranscripts>().take(&job_id).is_some() {
+            log::debug!("Released unused transcript held for {job_id}");
+        }
+    });
+}
+
+/// Registers the notification action buttons. Only mobile platforms deliver
+/// actions through the notification plugin; elsewhere this is a no-op and a
+/// notification click arrives without an action id (treated as Open).
+pub fn register_notification_actions(app: &AppHandle) {
+    #[cfg(mobile)]
+    {
+        use tauri_plugin_notification::{Action, ActionType, NotificationExt};
+
+        /// Opens the transcript; the handler treats any non-copy action this way
+        const ACTION_OPEN: &str = "open";
+        /// Reminder action the frontend maps to the `stop_recording` command
+        const ACTION_STOP_RECORDING: &str = "stop-recording";
+        /// Failure action the frontend maps to `resume_model_download`
+        const ACTION_RETRY: &str = "retry";
 
-use tauri::AppHandle;
+        let transcription = ActionType {
+            id: TRANSCRIPTION_ACTION_TYPE.to_string(),
+            actions: vec![
+                Action {
+                    id: ACTION_COPY.to_string(),
+                    title: "Copy text".to_string(),
+                    ..Default::default()
+                },
+                Action {
+                    id: ACTION_OPEN.to_string(),
+                    title: "Open".to_string(),
+                    foreground: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let reminder = ActionType {
+            id: RECORDING_REMINDER_ACTION_TYPE.to_string(),
+            actions: vec![Action {
+                id: ACTION_STOP_RECORDING.to_string(),
+                title: "Stop recording".to_string(),
+                destructive: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let download_failed = ActionType {
+            id: MODEL_DOWNLOAD_FAILED_ACTION_TYPE.to_string(),
+            actions: vec![Action {
+                id: ACTION_RETRY.to_string(),
+                title: "Retry".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let action_types = vec![transcription, reminder, download_failed];
+        if let Err(e) = app.notification().register_action_types(action_types) {
+            log::warn!("Failed to register notification actions: {e}");
+        }
+    }
+
+    #[cfg(not(mobile))]
+    let _ = app;
+}
+
+/// Handles a click on a transcription-complete notification. `action_id` is
+/// `"copy"` to put the transcript on the clipboard; anything else (including a
+/// plain click, which carries no action id) opens it in the main window.
+/// The held transcript is released either way.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn handle_transcription_notification(
+    app: AppHandle,
+    job_id: String,
+    action_id: Option<String>,
+) -> Result<(), AppError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let text = app.state::<HeldTranscripts>().take(&job_id);
+
+    if action_id.as_deref() == Some(ACTION_COPY) {
+        let text =
+            text.ok_or_else(|| AppError::internal("The transcript is no longer available"))?;
+        crate::commands::clipboard::copy_transcript(&app, &text)?;
+        log::info!("Copied transcript for {job_id} from notification");
+        return Ok(());
+    }
+
+    crate::commands::navigation::open_main_window(
+        app,
+        TRANSCRIPTION_RESULT_ROUTE.to_string(),
+        Some(serde_json::json!({ "job_id": job_id, "text": text })),
+    )
+    .await?;
+    Ok(())
+}
+
+// ============================================================================
+// Notifications
+// ============================================================================
 
 /// Sends a native system notification.
 /// On mobile platforms, returns an error as notifications are not yet supported.
 #[tauri::command]
 #[specta::specta]
+#[metrics::timed]
 pub async fn send_native_notification(
     app: AppHandle,
     title: String,
     body: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     log::info!("Sending native notification: {title}");
 
     #[cfg(not(mobile))]
@@ -32,7 +235,9 @@ pub async fn send_native_notification(
             }
             Err(e) => {
                 log::error!("Failed to send native notification: {e}");
-                Err(format!("Failed to send notification: {e}"))
+                Err(AppError::internal(format!(
+                    "Failed to send notification: {e}"
+                )))
             }
         }
     }
@@ -41,6 +246,422 @@ pub async fn send_native_notification(
     {
         let _ = (app, body);
         log::warn!("Native notifications not supported on mobile");
-        Err("Native notifications not supported on mobile".to_string())
+        Err(AppError::Unsupported)
+    }
+}
+
+/// Sends a notification in `category` unless the user's preferences hold it back
+/// (notifications off, category muted, or quiet hours). Non-critical categories
+/// are also held back while the OS is in Do Not Disturb; those are emitted as
+/// `notification-suppressed` instead so nothing is silently lost.
+/// Returns whether it was sent.
+pub fn maybe_notify(
+    app: &AppHandle,
+    category: NotificationCategory,
+    title: &str,
+    body: &str,
+) -> bool {
+    notify_with(app, category, title, body, |builder| builder)
+}
+
+/// `maybe_notify` with a hook to add actions or extras to the notification.
+fn notify_with(
+    app: &AppHandle,
+    category: NotificationCategory,
+    title: &str,
+    body: &str,
+    customize: impl FnOnce(NotificationBuilder) -> NotificationBuilder,
+) -> bool {
+    let prefs = crate::commands::preferences::load_saved_preferences(app).notifications;
+    if !prefs.enabled || prefs.muted_categories.contains(&category) {
+        log::debug!("Notification {category:?} suppressed by preferences");
+        return false;
+    }
+    let now = chrono::Local::now();
+    let minute_of_day = now.hour() * 60 + now.minute();
+    if !prefs.quiet_hours_exempt.contains(&category)
+        && prefs.quiet_hours.is_some_and(|q| q.contains(minute_of_day))
+    {
+        log::debug!("Notification {category:?} suppressed by quiet hours");
+        return false;
+    }
+    if !category.is_critical() && dnd::do_not_disturb_active() {
+        log::debug!("Notification {category:?} held back by OS Do Not Disturb");
+        let payload = NotificationSuppressed {
+            category,
+            title: title.to_string(),
+            body: body.to_string(),
+        };
+        if let Err(e) = payload.emit(app) {
+            log::warn!("Failed to emit suppressed notification: {e}");
+        }
+        return false;
+    }
+
+    show_notification(app, category, &prefs.sound, title, body, customize)
+}
+
+/// Builds and shows a notification with the user's sound, bypassing the preference gate.
+fn show_notification(
+    app: &AppHandle,
+    category: NotificationCategory,
+    sound: &NotificationSound,
+    title: &str,
+    body: &str,
+    customize: impl FnOnce(NotificationBuilder) -> NotificationBuilder,
+) -> bool {
+    #[cfg(not(mobile))]
+    {
+        use tauri_plugin_notification::NotificationExt;
+
+        let mut builder = app.notification().builder().title(title).body(body);
+        if let Some(sound_name) = sound_name(sound) {
+            builder = builder.sound(sound_name);
+        }
+        match customize(builder).show() {
+            Ok(_) => {
+                log::info!("Sent {category:?} notification");
+                true
+            }
+            Err(e) => {
+                log::error!("Failed to send {category:?} notification: {e}");
+                false
+            }
+        }
+    }
+
+    #[cfg(mobile)]
+    {
+        let _ = (app, sound, title, body, customize);
+        false
+    }
+}
+
+/// The platform sound name for a sound preference, or None for silence.
+/// Named sounds only exist on macOS; elsewhere they fall back to the default.
+fn sound_name(sound: &NotificationSound) -> Option<String> {
+    match sound {
+        NotificationSound::None => None,
+        NotificationSound::Named { name } if cfg!(target_os = "macos") => Some(name.clone()),
+        NotificationSound::Default | NotificationSound::Named { .. } => Some("default".to_string()),
+    }
+}
+
+/// Title and body of the sample notification for `category`.
+fn sample_notification(category: NotificationCategory) -> (&'static str, String) {
+    match category {
+        NotificationCategory::TranscriptionComplete => (
+            "Transcription ready",
+            format!("{} of audio, 312 words", format_audio_duration(134.0)),
+        ),
+        NotificationCategory::LowDiskSpace => (
+            "Low disk space",
+            "Only 512 MB free. Long recordings may not fit.".to_string(),
+        ),
+        NotificationCategory::RecordingReminder => (
+            "Still recording",
+            format!("{} elapsed", format_elapsed(Duration::from_secs(15 * 60))),
+        ),
+        NotificationCategory::ModelDownload => (
+            "Speech model ready",
+            "You can now transcribe offline".to_string(),
+        ),
+        NotificationCategory::UpdateAvailable => (
+            "PrivacyScribe update available",
+            "Bug fixes and performance improvements.".to_string(),
+        ),
+        NotificationCategory::Automation => (
+            "Request queued",
+            "PrivacyScribe will start or stop recording once the current recording or \
+             transcription finishes."
+                .to_string(),
+        ),
+    }
+}
+
+/// Sends a sample notification for `category` so the settings screen can show
+/// what it looks and sounds like. Ignores mutes and quiet hours, and reports
+/// whether the OS allows notifications so the UI can link to system settings.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn send_test_notification(
+    app: AppHandle,
+    category: NotificationCategory,
+) -> Result<TestNotificationResult, AppError> {
+    let permission_granted = notification_permission_granted(&app)?;
+    let sound = crate::commands::preferences::load_saved_preferences(&app)
+        .notifications
+        .sound;
+    let (title, body) = sample_notification(category);
+    let sent = permission_granted
+        && show_notification(&app, category, &sound, title, &body, |builder| builder);
+    Ok(TestNotificationResult {
+        permission_granted,
+        sent,
+    })
+}
+
+fn notification_permission_granted(app: &AppHandle) -> Result<bool, String> {
+    notification_permission(app).map(|status| status == PermissionStatus::Granted)
+}
+
+/// The notification permission as the OS reports it, without asking for it.
+pub(crate) fn notification_permission(app: &AppHandle) -> Result<PermissionStatus, String> {
+    use tauri_plugin_notification::{NotificationExt, PermissionState};
+
+    app.notification()
+        .permission_state()
+        .map(|state| match state {
+            PermissionState::Granted => PermissionStatus::Granted,
+            PermissionState::Denied => PermissionStatus::Denied,
+            _ => PermissionStatus::NotDetermined,
+        })
+        .map_err(|e| format!("Failed to read notification permission: {e}"))
+}
+
+/// Formats an elapsed recording time as "15:00" (or "1:05:00" past an hour).
+fn format_elapsed(elapsed: Duration) -> String {
+    let total = elapsed.as_secs();
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// Called from the recording loop each time another reminder interval passes,
+/// so a forgotten microphone doesn't keep running unnoticed.
+pub fn notify_still_recording(app: &AppHandle, elapsed: Duration) {
+    let body = format!("{} elapsed", format_elapsed(elapsed));
+    notify_with(
+        app,
+        NotificationCategory::RecordingReminder,
+        "Still recording",
+        &body,
+        |builder| builder.action_type_id(RECORDING_REMINDER_ACTION_TYPE),
+    );
+}
+
+/// Announces a finished model download: an event for the in-app display, plus a
+/// notification when the app is in the background.
+pub fn notify_model_download_complete(app: &AppHandle, model_id: &str) {
+    let payload = ModelDownloadComplete {
+        model_id: model_id.to_string(),
+    };
+    if let Err(e) = payload.emit(app) {
+        log::warn!("Failed to emit model download complete event: {e}");
+    }
+    if app.state::<WindowFocusState>().app_focused() {
+        return;
+    }
+    maybe_notify(
+        app,
+        NotificationCategory::ModelDownload,
+        "Speech model ready",
+        "You can now transcribe offline",
+    );
+}
+
+/// Announces a terminal model download failure like `notify_model_download_complete`.
+/// The notification names the error category and offers a Retry action.
+pub fn notify_model_download_failed(app: &AppHandle, failure: ModelDownloadFailed) {
+    let body = match failure.kind {
+        ModelDownloadErrorKind::Offline => "You appear to be offline.",
+        ModelDownloadErrorKind::Checksum => "The downloaded file was corrupt.",
+        ModelDownloadErrorKind::DiskFull => "There isn't enough disk space.",
+        ModelDownloadErrorKind::Other => "Something went wrong.",
+    };
+    let model_id = failure.model_id.clone();
+    let focused = app.state::<WindowFocusState>().app_focused();
+
+    if let Err(e) = failure.emit(app) {
+        log::warn!("Failed to emit model download failed event: {e}");
+    }
+    if focused {
+        return;
+    }
+    notify_with(
+        app,
+        NotificationCategory::ModelDownload,
+        "Speech model download failed",
+        body,
+        |builder| {
+            builder
+                .action_type_id(MODEL_DOWNLOAD_FAILED_ACTION_TYPE)
+                .extra("model_id", model_id)
+        },
+    );
+}
+
+/// Main window route for the updates screen
+const UPDATES_ROUTE: &str = "/settings/updates";
+
+/// Release notes longer than this are cut short in the notification body
+const NOTIFICATION_NOTES_MAX_CHARS: usize = 120;
+
+/// Truncates release notes to their first line, at most `max_chars` characters.
+fn truncate_notes(notes: &str, max_chars: usize) -> String {
+    let first_line = notes
+        .lines()
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or("")
+        .trim();
+    if first_line.chars().count() <= max_chars {
+        return first_line.to_string();
+    }
+    let truncated: String = first_line.chars().take(max_chars).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+/// Announces an available update. The notification carries the route of the
+/// updates screen so clicking it opens the main window there (via `open_main_window`).
+pub fn notify_update_available(app: &AppHandle, version: &str, notes: Option<&str>) {
+    let title = format!("PrivacyScribe {version} is available");
+    let body = notes
+        .map(|notes| truncate_notes(notes, NOTIFICATION_NOTES_MAX_CHARS))
+        .filter(|summary| !summary.is_empty())
+        .unwrap_or_else(|| "Open PrivacyScribe to update.".to_string());
+    notify_with(
+        app,
+        NotificationCategory::UpdateAvailable,
+        &title,
+        &body,
+        |builder| builder.extra("route", UPDATES_ROUTE),
+    );
+}
+
+/// Announces that a deferred update installs and restarts the app in
+/// `seconds`, now that the recording or transcription it waited for is done.
+pub fn notify_update_restart_pending(app: &AppHandle, seconds: u32) {
+    let body =
+        format!("PrivacyScribe restarts to update in {seconds} seconds. Open it to postpone.");
+    notify_with(
+        app,
+        NotificationCategory::UpdateAvailable,
+        "Update ready to install",
+        &body,
+        |builder| builder.extra("route", UPDATES_ROUTE),
+    );
+}
+
+/// Formats seconds as "2m 14s" (or "45s" under a minute).
+pub(crate) fn format_audio_duration(secs: f64) -> String {
+    let total = secs.round() as u64;
+    match (total / 60, total % 60) {
+        (0, s) => format!("{s}s"),
+        (m, s) => format!("{m}m {s}s"),
+    }
+}
+
+/// Called by the transcription pipeline when a transcription finishes. Notifies
+/// only when neither the main window nor the quick pane is focused; the body
+/// describes the audio and word count, never the transcript. The transcript is
+/// held in memory for the notification's Copy/Open actions.
+pub fn notify_transcription_complete(app: &AppHandle, job_id: &str, audio_secs: f64, text: &str) {
+    if app.state::<WindowFocusState>().app_focused() {
+        return;
+    }
+    let word_count = text.split_whitespace().count();
+    let noun = if word_count == 1 { "word" } else { "words" };
+    let body = format!(
+        "{} of audio, {word_count} {noun}",
+        format_audio_duration(audio_secs)
+    );
+    let sent = notify_with(
+        app,
+        NotificationCategory::TranscriptionComplete,
+        "Transcription ready",
+        &body,
+        |builder| {
+            builder
+                .action_type_id(TRANSCRIPTION_ACTION_TYPE)
+                .extra("job_id", job_id)
+        },
+    );
+    if sent {
+        hold_transcript(app, job_id, text.to_string());
+    }
+}
+
+/// Low-disk notifications are sent at most once per app session
+static LOW_DISK_NOTIFIED: AtomicBool = AtomicBool::new(false);
+
+/// Reports a failed disk space check: always emits `low-disk-space` for the
+/// in-app banner, and sends a system notification the first time per session.
+pub fn report_low_disk_space(app: &AppHandle, payload: LowDiskSpace) {
+    log::warn!(
+        "Low disk space for {:?}: {} bytes available, {} wanted (blocked: {})",
+        payload.context,
+        payload.available_bytes,
+        payload.required_bytes,
+        payload.blocked
+    );
+
+    if !LOW_DISK_NOTIFIED.swap(true, Ordering::SeqCst) {
+        let available_mb = (payload.available_bytes / 1_048_576.0).round();
+        let body = match payload.context {
+            DiskSpaceContext::Recording => {
+                format!("Only {available_mb} MB free. Long recordings may not fit.")
+            }
+            DiskSpaceContext::ModelDownload => {
+                format!("Only {available_mb} MB free. The speech model may not fit.")
+            }
+        };
+        maybe_notify(
+            app,
+            NotificationCategory::LowDiskSpace,
+            "Low disk space",
+            &body,
+        );
+    }
+
+    if let Err(e) = payload.emit(app) {
+        log::warn!("Failed to emit low disk space event: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::QuietHours;
+
+    #[test]
+    fn test_format_audio_duration() {
+        assert_eq!(format_audio_duration(45.2), "45s");
+        assert_eq!(format_audio_duration(134.0), "2m 14s");
+        assert_eq!(format_audio_duration(3600.0), "60m 0s");
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_secs(15 * 60)), "15:00");
+        assert_eq!(format_elapsed(Duration::from_secs(3900)), "1:05:00");
+    }
+
+    #[test]
+    fn test_truncate_notes() {
+        assert_eq!(truncate_notes("\n## Fixes\nMore", 20), "## Fixes");
+        assert_eq!(truncate_notes("abcdefgh", 4), "abcd…");
+        assert_eq!(truncate_notes("", 4), "");
+    }
+
+    #[test]
+    fn test_quiet_hours_contains() {
+        let daytime = QuietHours {
+            start_minute: 12 * 60,
+            end_minute: 13 * 60,
+        };
+        assert!(daytime.contains(12 * 60 + 30));
+        assert!(!daytime.contains(13 * 60));
+
+        let overnight = QuietHours {
+            start_minute: 22 * 60,
+            end_minute: 7 * 60,
+        };
+        assert!(overnight.contains(23 * 60));
+        assert!(overnight.contains(3 * 60));
+        assert!(!overnight.contains(12 * 60));
     }
 }
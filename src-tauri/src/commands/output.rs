@@ -0,0 +1,368 @@
+//! Delivering finished transcripts to where the user was working.
+//!
+//! Showing the quick pane remembers which application had focus.
+//! `paste_into_previous_app` (and auto-paste after transcription) copies the
+//! transcript, brings that application back and sends the platform paste
+//! keystroke. If the application can't be identified any more, nothing is
+//! typed anywhere: the transcript stays on the clipboard and `paste-fallback`
+//! says why.
+//!
+//! Delivered text first goes through the formatting profile (see
+//! `utils::formatting`) from the call or the `output_formatting` preference,
+//! and is appended to the daily note when that's on (see `daily_note`).
+//!
+//! `type_out_text` is for fields that block pasting: it types the text into
+//! the same application one character at a time. Long texts need an explicit
+//! confirmation and very long ones are refused, since typing can't be undone
+//! the way a paste can and a 20,000-character run takes minutes.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::commands::transcription::Transcribed;
+use crate::commands::{clipboard, daily_note, preferences};
+use crate::types::{
+    FormattingOptions, PasteFallback, PasteFallbackReason, PasteOutcome, TranscriptContent,
+    TranscriptDelivery, TranscriptSegment, TypeOutError, MAX_TYPE_OUT_CHARS_PER_SECOND,
+    MIN_TYPE_OUT_CHARS_PER_SECOND,
+};
+use crate::utils::foreground::{self, ForegroundApp, Keystroke};
+use crate::utils::formatting;
+use crate::utils::metrics;
+
+/// Time for the re-activated application to take focus before the keystroke
+const ACTIVATION_SETTLE_MS: u64 = 150;
+
+/// Texts longer than this are only typed with `confirmed`
+const TYPE_OUT_CONFIRM_CHARS: usize = 2_000;
+
+/// Texts longer than this are never typed out
+const MAX_TYPE_OUT_CHARS: usize = 20_000;
+
+/// Keystrokes between checks that the target app still has focus
+const FOCUS_CHECK_EVERY: usize = 20;
+
+/// Set while a type-out runs
+static TYPING: AtomicBool = AtomicBool::new(false);
+
+/// Set by `cancel_type_out`; checked before every keystroke
+static CANCEL_TYPING: AtomicBool = AtomicBool::new(false);
+
+/// Application that had focus when the quick pane was last shown
+static PREVIOUS_APP: Mutex<Option<ForegroundApp>> = Mutex::new(None);
+
+/// Records the focused application. Called before the quick pane is shown,
+/// while the other application still has focus.
+pub fn remember_previous_app() {
+    let frontmost = foreground::frontmost_app();
+    if let Ok(mut previous) = PREVIOUS_APP.lock() {
+        *previous = frontmost;
+    }
+}
+
+fn fallback_message(reason: PasteFallbackReason) -> &'static str {
+    match reason {
+        PasteFallbackReason::Unsupported => {
+            "Pasting into other apps isn't supported on this platform"
+        }
+        PasteFallbackReason::NoPreviousApp => "No app was focused when the quick pane opened",
+        PasteFallbackReason::PreviousAppGone => "The app you were using has closed",
+        PasteFallbackReason::AccessibilityDenied => {
+            "Pasting needs Accessibility access (System Settings › Privacy & Security › Accessibility)"
+        }
+        PasteFallbackReason::ActivationFailed => "The app you were using couldn't be brought back",
+        PasteFallbackReason::KeystrokeFailed => "The paste keystroke couldn't be sent",
+    }
+}
+
+fn previous_app() -> Option<ForegroundApp> {
+    PREVIOUS_APP.lock().ok().and_then(|previous| *previous)
+}
+
+/// Brings the remembered application back to the front, ready for keystrokes.
+async fn activate_previous(
+    previous: Option<ForegroundApp>,
+) -> Result<ForegroundApp, PasteFallbackReason> {
+    if !foreground::SUPPORTED {
+        return Err(PasteFallbackReason::Unsupported);
+    }
+    let previous = previous.ok_or(PasteFallbackReason::NoPreviousApp)?;
+    if !foreground::is_running(&previous) {
+        return Err(PasteFallbackReason::PreviousAppGone);
+    }
+    if !crate::utils::permissions::accessibility_trusted(false) {
+        return Err(PasteFallbackReason::AccessibilityDenied);
+    }
+    foreground::activate(&previous).map_err(|e| {
+        log::warn!("Failed to activate previous app: {e}");
+        PasteFallbackReason::ActivationFailed
+    })?;
+    tokio::time::sleep(Duration::from_millis(ACTIVATION_SETTLE_MS)).await;
+    // It may have quit or lost focus again while we waited
+    if foreground::frontmost_app() != Some(previous) {
+        return Err(PasteFallbackReason::ActivationFailed);
+    }
+    Ok(previous)
+}
+
+/// Re-activates the remembered application and pastes into it.
+async fn paste_into(previous: Option<ForegroundApp>) -> Result<(), PasteFallbackReason> {
+    activate_previous(previous).await?;
+    foreground::send_paste().map_err(|e| {
+        log::warn!("Failed to send paste keystroke: {e}");
+        PasteFallbackReason::KeystrokeFailed
+    })
+}
+
+/// Copies `text` and pastes it into the remembered application, or leaves it
+/// on the clipboard and emits `paste-fallback` when that isn't possible.
+async fn paste_transcript(app: &AppHandle, text: &str) -> Result<PasteOutcome, String> {
+    clipboard::copy_transcript(app, text)?;
+    match paste_into(previous_app()).await {
+        Ok(()) => {
+            log::info!("Pasted transcript into the previous app");
+            Ok(PasteOutcome::Pasted)
+        }
+        Err(reason) => {
+            log::info!("Paste fell back to copy-only: {reason:?}");
+            let payload = PasteFallback {
+                reason,
+                message: fallback_message(reason).to_string(),
+            };
+            if let Err(e) = payload.emit(app) {
+                log::warn!("Failed to emit paste-fallback: {e}");
+            }
+            Ok(PasteOutcome::CopiedOnly { reason })
+        }
+    }
+}
+
+/// Formats a finished transcription with `requested`, or the
+/// `output_formatting` preference when that's unset.
+pub(crate) fn format_for_output(
+    app: &AppHandle,
+    segments: &[TranscriptSegment],
+    requested: Option<FormattingOptions>,
+) -> String {
+    let options =
+        requested.unwrap_or_else(|| preferences::load_saved_preferences(app).output_formatting);
+    formatting::format_segments(segments, &options)
+}
+
+/// Delivers a just-finished transcript: formats it, appends it to the daily
+/// note, then pastes it into the previous app or only copies it, as
+/// `delivery` and the preferences say. A failed append always copies, so the
+/// text isn't only in the app. Failures are logged; the transcript is still
+/// returned to the caller.
+pub(crate) async fn deliver(
+    app: &AppHandle,
+    transcribed: &Transcribed,
+    delivery: TranscriptDelivery,
+) {
+    let text = format_for_output(app, &transcribed.segments, delivery.formatting);
+    let copy_to_clipboard =
+        if daily_note::append_transcript(app, &text, delivery.append_to_file).await {
+            delivery.copy_to_clipboard
+        } else {
+            Some(true)
+        };
+    let paste = delivery
+        .paste_into_previous_app
+        .unwrap_or_else(|| preferences::load_saved_preferences(app).auto_paste_into_previous_app);
+    if !paste || text.trim().is_empty() {
+        clipboard::auto_copy(app, &text, copy_to_clipboard);
+        return;
+    }
+    if let Err(e) = paste_transcript(app, &text).await {
+        log::error!("{e}");
+    }
+}
+
+/// Copies `text` and pastes it into the application that had focus when the
+/// quick pane was shown. Falls back to copy-only (with `paste-fallback`) when
+/// that application can't be identified, has quit, or Accessibility access is
+/// missing on macOS.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn paste_into_previous_app(app: AppHandle, text: String) -> Result<PasteOutcome, String> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    paste_transcript(&app, &text).await
+}
+
+/// Formats a transcript with a formatting profile, as auto-copy and
+/// auto-paste would. `paragraphs-on-pause` needs `content` as segments; plain
+/// text is returned unchanged by it.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed(response_size)]
+pub fn format_transcript(content: TranscriptContent, options: FormattingOptions) -> String {
+    formatting::format(&content, &options)
+}
+
+/// Returns whether the OS permission needed to paste into other apps is
+/// granted. When `prompt` is true, macOS shows the Accessibility permission
+/// dialog if needed.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn check_paste_permission(prompt: bool) -> bool {
+    crate::utils::permissions::accessibility_trusted(prompt)
+}
+
+/// The keystrokes that type `text`. Line breaks (`\n`, `\r\n` or a lone `\r`)
+/// become one Return each and tabs a Tab key, since many apps ignore those
+/// characters when they arrive as Unicode input.
+fn type_out_steps(text: &str) -> Vec<Keystroke> {
+    let mut steps = Vec::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        steps.push(match c {
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                Keystroke::Return
+            }
+            '\n' => Keystroke::Return,
+            '\t' => Keystroke::Tab,
+            c => Keystroke::Char(c),
+        });
+    }
+    steps
+}
+
+/// Clears the running flag when a type-out ends, however it ends.
+struct TypingGuard;
+
+impl Drop for TypingGuard {
+    fn drop(&mut self) {
+        TYPING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Types the keystrokes into `target`, stopping on cancel or focus change.
+async fn type_steps(
+    target: ForegroundApp,
+    steps: &[Keystroke],
+    interval: Duration,
+) -> Result<u32, TypeOutError> {
+    for (index, &step) in steps.iter().enumerate() {
+        let typed = index as u32;
+        if CANCEL_TYPING.load(Ordering::SeqCst) {
+            return Err(TypeOutError::Cancelled { typed });
+        }
+        if index % FOCUS_CHECK_EVERY == 0 && foreground::frontmost_app() != Some(target) {
+            return Err(TypeOutError::FocusLost { typed });
+        }
+        foreground::send_keystroke(step).map_err(|message| TypeOutError::Failed { message })?;
+        tokio::time::sleep(interval).await;
+    }
+    Ok(steps.len() as u32)
+}
+
+/// Types `text` into the application that had focus when the quick pane was
+/// shown, as individual keystrokes, for fields that block pasting. Returns the
+/// number of keystrokes sent.
+///
+/// `chars_per_second` defaults to the `type_out_chars_per_second` preference
+/// and is clamped to a range apps keep up with. Texts over 2,000 characters
+/// need `confirmed`; texts over 20,000 are refused. Stops early when
+/// `cancel_type_out` is called or another app takes focus.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn type_out_text(
+    app: AppHandle,
+    text: String,
+    chars_per_second: Option<u32>,
+    confirmed: Option<bool>,
+) -> Result<u32, TypeOutError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    if foreground::SUPPORTED && !crate::utils::permissions::accessibility_trusted(false) {
+        return Err(TypeOutError::PermissionDenied);
+    }
+    let steps = type_out_steps(&text);
+    if steps.len() > MAX_TYPE_OUT_CHARS {
+        return Err(TypeOutError::TooLong {
+            max_chars: MAX_TYPE_OUT_CHARS as u32,
+        });
+    }
+    let rate = chars_per_second
+        .unwrap_or_else(|| preferences::load_saved_preferences(&app).type_out_chars_per_second)
+        .clamp(MIN_TYPE_OUT_CHARS_PER_SECOND, MAX_TYPE_OUT_CHARS_PER_SECOND);
+    if steps.len() > TYPE_OUT_CONFIRM_CHARS && !confirmed.unwrap_or(false) {
+        return Err(TypeOutError::ConfirmationRequired {
+            characters: steps.len() as u32,
+            estimated_secs: (steps.len() as u32).div_ceil(rate),
+        });
+    }
+
+    if TYPING.swap(true, Ordering::SeqCst) {
+        return Err(TypeOutError::AlreadyRunning);
+    }
+    let _guard = TypingGuard;
+    CANCEL_TYPING.store(false, Ordering::SeqCst);
+
+    let target = activate_previous(previous_app())
+        .await
+        .map_err(|reason| match reason {
+            PasteFallbackReason::AccessibilityDenied => TypeOutError::PermissionDenied,
+            reason => TypeOutError::NoTarget { reason },
+        })?;
+    log::info!("Typing out {} characters at {rate}/s", steps.len());
+    let result = type_steps(target, &steps, Duration::from_secs(1) / rate).await;
+    if let Err(e) = &result {
+        log::info!("Type-out stopped: {e}");
+    }
+    result
+}
+
+/// Stops a running `type_out_text` before its next keystroke. Does nothing
+/// when nothing is being typed.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn cancel_type_out() {
+    if TYPING.load(Ordering::SeqCst) {
+        CANCEL_TYPING.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_breaks_and_tabs_become_keys() {
+        assert_eq!(
+            type_out_steps("a\r\nb\nc\rd\te"),
+            vec![
+                Keystroke::Char('a'),
+                Keystroke::Return,
+                Keystroke::Char('b'),
+                Keystroke::Return,
+                Keystroke::Char('c'),
+                Keystroke::Return,
+                Keystroke::Char('d'),
+                Keystroke::Tab,
+                Keystroke::Char('e'),
+            ]
+        );
+    }
+
+    #[test]
+    fn blank_lines_are_kept_and_non_ascii_is_one_step() {
+        assert_eq!(
+            type_out_steps("ø\n\n😀"),
+            vec![
+                Keystroke::Char('ø'),
+                Keystroke::Return,
+                Keystroke::Return,
+                Keystroke::Char('😀'),
+            ]
+        );
+    }
+}
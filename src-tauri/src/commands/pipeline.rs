@@ -0,0 +1,146 @@
+//! Recording/transcription pipeline status broadcaster.
+//!
+//! The audio and transcription modules publish every stage transition here
+//! instead of emitting their own ad-hoc events. Each transition is broadcast
+//! as a single `pipeline-status` event, and the latest snapshot is kept so a
+//! reloaded webview can rehydrate via `get_pipeline_status`. Transitions also
+//! drive the crash sentinel: it is set while a job is active and cleared once
+//! the job finishes.
+
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::types::{PipelineStage, PipelineStatus};
+use crate::utils::metrics;
+
+/// Latest pipeline snapshot (None until the first transition).
+static CURRENT_STATUS: Mutex<Option<PipelineStatus>> = Mutex::new(None);
+
+/// Milliseconds since the Unix epoch, as f64 (JS-friendly; specta forbids u64).
+pub fn now_ms() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as f64)
+        .unwrap_or_default()
+}
+
+fn idle_status() -> PipelineStatus {
+    let now = now_ms();
+    PipelineStatus {
+        job_id: None,
+        stage: PipelineStage::Idle,
+        recovered: false,
+        started_at_ms: now,
+        updated_at_ms: now,
+    }
+}
+
+/// Starts a new pipeline job and returns its id.
+pub fn begin_job(app: &AppHandle, stage: PipelineStage) -> String {
+    start_job(app, stage, false)
+}
+
+/// Starts a new pipeline job for audio salvaged from a recovery file.
+/// Later transitions of the job keep the `recovered` flag.
+pub fn begin_recovered_job(app: &AppHandle, stage: PipelineStage) -> String {
+    start_job(app, stage, true)
+}
+
+fn start_job(app: &AppHandle, stage: PipelineStage, recovered: bool) -> String {
+    let job_id = format!("job_{:x}", now_ms() as u128);
+    let now = now_ms();
+    broadcast(
+        app,
+        PipelineStatus {
+            job_id: Some(job_id.clone()),
+            stage,
+            recovered,
+            started_at_ms: now,
+            updated_at_ms: now,
+        },
+    );
+    job_id
+}
+
+/// Publishes `stage` as a continuation of a just-stopped recording, or as a new job
+/// when nothing is in flight (e.g. transcribing an existing file). Returns the job id.
+pub fn continue_or_begin_job(app: &AppHandle, stage: PipelineStage) -> String {
+    let in_flight = CURRENT_STATUS.lock().ok().and_then(|status| {
+        status.as_ref().and_then(|s| match s.stage {
+            PipelineStage::Stopping | PipelineStage::Resampling => s.job_id.clone(),
+            _ => None,
+        })
+    });
+
+    match in_flight {
+        Some(job_id) => {
+            publish(app, stage);
+            job_id
+        }
+        None => begin_job(app, stage),
+    }
+}
+
+/// Whether a job is between starting and finishing (its audio is in use).
+pub fn is_busy() -> bool {
+    !matches!(
+        get_pipeline_status().stage,
+        PipelineStage::Idle | PipelineStage::Done { .. } | PipelineStage::Error { .. }
+    )
+}
+
+/// Publishes a transition for the current job (keeps its id and start time).
+pub fn publish(app: &AppHandle, stage: PipelineStage) {
+    let previous = CURRENT_STATUS
+        .lock()
+        .ok()
+        .and_then(|status| status.clone())
+        .unwrap_or_else(idle_status);
+
+    broadcast(
+        app,
+        PipelineStatus {
+            stage,
+            updated_at_ms: now_ms(),
+            ..previous
+        },
+    );
+}
+
+fn broadcast(app: &AppHandle, status: PipelineStatus) {
+    log::debug!(
+        "Pipeline status: {:?} (job {:?})",
+        status.stage,
+        status.job_id
+    );
+
+    if let Ok(mut current) = CURRENT_STATUS.lock() {
+        *current = Some(status.clone());
+    }
+
+    match status.stage {
+        PipelineStage::Idle | PipelineStage::Done { .. } | PipelineStage::Error { .. } => {
+            crate::commands::recovery::clear_session_active(app)
+        }
+        _ => crate::commands::recovery::mark_session_active(app),
+    }
+
+    if let Err(e) = status.emit(app) {
+        log::warn!("Failed to emit pipeline status: {e}");
+    }
+}
+
+/// Returns the current pipeline snapshot so a reloaded webview can rehydrate.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn get_pipeline_status() -> PipelineStatus {
+    CURRENT_STATUS
+        .lock()
+        .ok()
+        .and_then(|status| status.clone())
+        .unwrap_or_else(idle_status)
+}
@@ -3,16 +3,22 @@
 //! Handles loading and saving user preferences to disk.
 
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
+use tauri_specta::Event;
 
-use crate::types::{validate_string_input, validate_theme, AppPreferences};
+use crate::commands::{app_lock, audit};
+use crate::types::{
+    clamp_quick_pane_zoom, validate_string_input, validate_theme, AppError, AppPreferences,
+    AppendToFileOptions, AuditEventKind, PreferencesChanged, MAX_TYPE_OUT_CHARS_PER_SECOND,
+    MIN_AUDIT_RETENTION_DAYS, MIN_TYPE_OUT_CHARS_PER_SECOND,
+};
+use crate::utils::metrics;
+use crate::utils::paths;
 
 /// Gets the path to the preferences file.
 fn get_preferences_path(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    let app_data_dir =
+        paths::app_data_dir(app).map_err(|e| format!("Failed to get app data directory: {e}"))?;
 
     // Ensure the directory exists
     std::fs::create_dir_all(&app_data_dir)
@@ -21,9 +27,8 @@ fn get_preferences_path(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(app_data_dir.join("preferences.json"))
 }
 
-/// Load the saved quick pane shortcut from preferences, returning None on any failure.
-/// Used at startup before the full preferences system is available.
-pub fn load_quick_pane_shortcut(app: &AppHandle) -> Option<String> {
+/// Synchronously read preferences from disk, returning None on any failure.
+fn read_saved_preferences(app: &AppHandle) -> Option<AppPreferences> {
     let path = get_preferences_path(app).ok()?;
     if !path.exists() {
         return None;
@@ -31,20 +36,78 @@ pub fn load_quick_pane_shortcut(app: &AppHandle) -> Option<String> {
     let contents = std::fs::read_to_string(&path)
         .inspect_err(|e| log::warn!("Failed to read preferences: {e}"))
         .ok()?;
-    let prefs: AppPreferences = serde_json::from_str(&contents)
+    serde_json::from_str(&contents)
         .inspect_err(|e| log::warn!("Failed to parse preferences: {e}"))
-        .ok()?;
-    prefs.quick_pane_shortcut
+        .ok()
+}
+
+/// Load saved preferences for backend use, falling back to defaults on any failure.
+/// Used by commands that need a preference value without going through the frontend.
+pub fn load_saved_preferences(app: &AppHandle) -> AppPreferences {
+    read_saved_preferences(app).unwrap_or_default()
+}
+
+/// Writes preferences to disk atomically (temp file + rename).
+fn write_preferences(app: &AppHandle, preferences: &AppPreferences) -> Result<(), String> {
+    let prefs_path = get_preferences_path(app)?;
+
+    let json_content = serde_json::to_string_pretty(preferences).map_err(|e| {
+        log::error!("Failed to serialize preferences: {e}");
+        format!("Failed to serialize preferences: {e}")
+    })?;
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = prefs_path.with_extension("tmp");
+
+    std::fs::write(&temp_path, json_content).map_err(|e| {
+        log::error!("Failed to write preferences file: {e}");
+        format!("Failed to write preferences file: {e}")
+    })?;
+
+    if let Err(rename_err) = std::fs::rename(&temp_path, &prefs_path) {
+        log::error!("Failed to finalize preferences file: {rename_err}");
+        // Clean up the temp file to avoid leaving orphaned files on disk
+        if let Err(remove_err) = std::fs::remove_file(&temp_path) {
+            log::warn!("Failed to remove temp file after rename failure: {remove_err}");
+        }
+        return Err(format!("Failed to finalize preferences file: {rename_err}"));
+    }
+
+    log::info!("Successfully saved preferences to {prefs_path:?}");
+    Ok(())
+}
+
+/// Applies `update` to the saved preferences, persists them, and notifies the frontend.
+/// Used when the backend changes a preference itself rather than via `save_preferences`.
+pub fn update_saved_preferences(
+    app: &AppHandle,
+    update: impl FnOnce(&mut AppPreferences),
+) -> Result<AppPreferences, String> {
+    let mut preferences = load_saved_preferences(app);
+    update(&mut preferences);
+    write_preferences(app, &preferences)?;
+
+    if let Err(e) = PreferencesChanged(preferences.clone()).emit(app) {
+        log::warn!("Failed to emit preferences change: {e}");
+    }
+    Ok(preferences)
+}
+
+/// Load the saved quick pane shortcut from preferences, returning None on any failure.
+/// Used at startup before the full preferences system is available.
+pub fn load_quick_pane_shortcut(app: &AppHandle) -> Option<String> {
+    read_saved_preferences(app)?.quick_pane_shortcut
 }
 
 /// Simple greeting command for demonstration purposes.
 #[tauri::command]
 #[specta::specta]
-pub fn greet(name: &str) -> Result<String, String> {
+#[metrics::timed]
+pub fn greet(name: &str) -> Result<String, AppError> {
     // Input validation
     validate_string_input(name, 100, "Name").map_err(|e| {
         log::warn!("Invalid greet input: {e}");
-        e
+        AppError::invalid_input(e)
     })?;
 
     log::info!("Greeting user: {name}");
@@ -52,10 +115,12 @@ pub fn greet(name: &str) -> Result<String, String> {
 }
 
 /// Loads user preferences from disk.
-/// Returns default preferences if the file doesn't exist.
+/// Returns default preferences if the file doesn't exist. While the app is
+/// locked, the de-identification names and patterns come back empty.
 #[tauri::command]
 #[specta::specta]
-pub async fn load_preferences(app: AppHandle) -> Result<AppPreferences, String> {
+#[metrics::timed]
+pub async fn load_preferences(app: AppHandle) -> Result<AppPreferences, AppError> {
     log::debug!("Loading preferences from disk");
     let prefs_path = get_preferences_path(&app)?;
 
@@ -66,15 +131,18 @@ pub async fn load_preferences(app: AppHandle) -> Result<AppPreferences, String>
 
     let contents = std::fs::read_to_string(&prefs_path).map_err(|e| {
         log::error!("Failed to read preferences file: {e}");
-        format!("Failed to read preferences file: {e}")
+        AppError::from(e)
     })?;
 
     let preferences: AppPreferences = serde_json::from_str(&contents).map_err(|e| {
         log::error!("Failed to parse preferences JSON: {e}");
-        format!("Failed to parse preferences: {e}")
+        AppError::internal(format!("Failed to parse preferences: {e}"))
     })?;
 
     log::info!("Successfully loaded preferences");
+    if app_lock::is_locked(&app) {
+        return Ok(without_deidentification_lists(preferences));
+    }
     Ok(preferences)
 }
 
@@ -82,35 +150,224 @@ pub async fn load_preferences(app: AppHandle) -> Result<AppPreferences, String>
 /// Uses atomic write (temp file + rename) to prevent corruption.
 #[tauri::command]
 #[specta::specta]
-pub async fn save_preferences(app: AppHandle, preferences: AppPreferences) -> Result<(), String> {
+#[metrics::timed]
+pub async fn save_preferences(
+    app: AppHandle,
+    mut preferences: AppPreferences,
+) -> Result<(), AppError> {
     // Validate theme value
-    validate_theme(&preferences.theme)?;
+    validate_theme(&preferences.theme).map_err(AppError::invalid_input)?;
+    crate::utils::deidentify::validate_rules(&preferences.deidentification)
+        .map_err(AppError::invalid_input)?;
 
     log::debug!("Saving preferences to disk: {preferences:?}");
-    let prefs_path = get_preferences_path(&app)?;
+    preferences.quick_pane_zoom = clamp_quick_pane_zoom(preferences.quick_pane_zoom);
+    preferences.audit_retention_days = preferences
+        .audit_retention_days
+        .max(MIN_AUDIT_RETENTION_DAYS);
+    preferences.trash_retention_days = preferences.trash_retention_days.max(1);
+    preferences.type_out_chars_per_second = preferences
+        .type_out_chars_per_second
+        .clamp(MIN_TYPE_OUT_CHARS_PER_SECOND, MAX_TYPE_OUT_CHARS_PER_SECOND);
 
-    let json_content = serde_json::to_string_pretty(&preferences).map_err(|e| {
-        log::error!("Failed to serialize preferences: {e}");
-        format!("Failed to serialize preferences: {e}")
-    })?;
+    let previous = load_saved_preferences(&app);
+    if app_lock::is_locked(&app) {
+        // The frontend only saw these emptied (see `load_preferences`); keep the saved ones
+        preferences.deidentification.names = previous.deidentification.names.clone();
+        preferences.deidentification.custom_patterns =
+            previous.deidentification.custom_patterns.clone();
+    }
+    if !only_appearance_changed(&previous, &preferences) {
+        app_lock::ensure_unlocked(&app)?;
+    }
+    write_preferences(&app, &preferences)?;
+    for change in privacy_setting_changes(&previous, &preferences) {
+        audit::record(&app, AuditEventKind::PrivacySettingChanged, None, &change);
+    }
 
-    // Write to a temporary file first, then rename (atomic operation)
-    let temp_path = prefs_path.with_extension("tmp");
+    // Keep the live quick pane in sync with saved placement and zoom
+    crate::commands::quick_pane::apply_quick_pane_position(preferences.quick_pane_position);
+    crate::commands::quick_pane::apply_quick_pane_zoom(
+        &app,
+        preferences.quick_pane_zoom,
+        preferences.quick_pane_scale_window_with_zoom,
+    );
+    if preferences.block_screen_capture != previous.block_screen_capture {
+        crate::commands::screen_capture::apply(&app);
+    }
+    if preferences.exclude_database_from_backups != previous.exclude_database_from_backups {
+        crate::commands::backup_exclusion::apply(&app);
+    }
+    if preferences.command_metrics_enabled != previous.command_metrics_enabled {
+        crate::commands::diagnostics::apply_metrics_preference(&app);
+    }
+    Ok(())
+}
 
-    std::fs::write(&temp_path, json_content).map_err(|e| {
-        log::error!("Failed to write preferences file: {e}");
-        format!("Failed to write preferences file: {e}")
-    })?;
+/// `preferences` without the de-identification names and patterns, which
+/// identify patients and so aren't handed out while the app is locked.
+fn without_deidentification_lists(mut preferences: AppPreferences) -> AppPreferences {
+    preferences.deidentification.names.clear();
+    preferences.deidentification.custom_patterns.clear();
+    preferences
+}
 
-    if let Err(rename_err) = std::fs::rename(&temp_path, &prefs_path) {
-        log::error!("Failed to finalize preferences file: {rename_err}");
-        // Clean up the temp file to avoid leaving orphaned files on disk
-        if let Err(remove_err) = std::fs::remove_file(&temp_path) {
-            log::warn!("Failed to remove temp file after rename failure: {remove_err}");
-        }
-        return Err(format!("Failed to finalize preferences file: {rename_err}"));
+/// Whether `new` differs from `previous` only in appearance settings, which
+/// stay savable while the app is locked. Everything else can weaken the lock,
+/// retention or de-identification, so it needs an unlocked app.
+fn only_appearance_changed(previous: &AppPreferences, new: &AppPreferences) -> bool {
+    let appearance_applied = AppPreferences {
+        theme: new.theme.clone(),
+        language: new.language.clone(),
+        quick_pane_appearance: new.quick_pane_appearance.clone(),
+        quick_pane_zoom: new.quick_pane_zoom,
+        quick_pane_scale_window_with_zoom: new.quick_pane_scale_window_with_zoom,
+        quick_pane_position: new.quick_pane_position,
+        ..previous.clone()
+    };
+    serde_json::to_value(&appearance_applied).ok() == serde_json::to_value(new).ok()
+}
+
+/// `off`, or the daily note path when appending is on.
+fn append_target(options: &AppendToFileOptions) -> String {
+    match (&options.path, options.enabled) {
+        (Some(path), true) => path.clone(),
+        _ => "off".to_string(),
     }
+}
 
-    log::info!("Successfully saved preferences to {prefs_path:?}");
-    Ok(())
+/// Describes changes to settings that affect how long PHI is kept or where it
+/// can surface, as `name=old->new` audit details.
+fn privacy_setting_changes(old: &AppPreferences, new: &AppPreferences) -> Vec<String> {
+    let settings = [
+        (
+            "recovery_retention_hours",
+            old.recovery_retention_hours.to_string(),
+            new.recovery_retention_hours.to_string(),
+        ),
+        (
+            "audit_retention_days",
+            old.audit_retention_days.to_string(),
+            new.audit_retention_days.to_string(),
+        ),
+        (
+            "trash_retention_days",
+            old.trash_retention_days.to_string(),
+            new.trash_retention_days.to_string(),
+        ),
+        (
+            "clipboard_clear_seconds",
+            old.clipboard_clear_seconds.to_string(),
+            new.clipboard_clear_seconds.to_string(),
+        ),
+        (
+            "auto_lock_minutes",
+            old.auto_lock_minutes.to_string(),
+            new.auto_lock_minutes.to_string(),
+        ),
+        (
+            "max_idle_while_recording_minutes",
+            old.max_idle_while_recording_minutes.to_string(),
+            new.max_idle_while_recording_minutes.to_string(),
+        ),
+        (
+            "biometric_unlock",
+            old.biometric_unlock.to_string(),
+            new.biometric_unlock.to_string(),
+        ),
+        (
+            "block_screen_capture",
+            old.block_screen_capture.to_string(),
+            new.block_screen_capture.to_string(),
+        ),
+        (
+            "exclude_database_from_backups",
+            old.exclude_database_from_backups.to_string(),
+            new.exclude_database_from_backups.to_string(),
+        ),
+        (
+            "conceal_clipboard_from_history",
+            old.conceal_clipboard_from_history.to_string(),
+            new.conceal_clipboard_from_history.to_string(),
+        ),
+        (
+            "append_to_file",
+            append_target(&old.append_to_file),
+            append_target(&new.append_to_file),
+        ),
+        (
+            "notifications_enabled",
+            old.notifications.enabled.to_string(),
+            new.notifications.enabled.to_string(),
+        ),
+        (
+            "check_updates_automatically",
+            old.check_updates_automatically.to_string(),
+            new.check_updates_automatically.to_string(),
+        ),
+    ];
+    settings
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(name, old, new)| format!("{name}={old}->{new}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn privacy_changes_list_only_changed_settings() {
+        let old = AppPreferences::default();
+        let mut new = old.clone();
+        assert!(privacy_setting_changes(&old, &new).is_empty());
+
+        new.recovery_retention_hours = 24;
+        new.theme = "dark".to_string();
+        assert_eq!(
+            privacy_setting_changes(&old, &new),
+            vec!["recovery_retention_hours=72->24".to_string()]
+        );
+    }
+
+    #[test]
+    fn logged_preferences_leave_out_deidentification_names() {
+        let mut preferences = AppPreferences::default();
+        preferences.deidentification.names = vec!["Jane Doe".to_string()];
+        preferences.deidentification.custom_patterns = vec![r"\bDoe\b".to_string()];
+        let logged = format!("{preferences:?}");
+        assert!(!logged.contains("Jane") && !logged.contains("Doe"));
+        assert!(logged.contains("<1 redacted>"));
+    }
+
+    #[test]
+    fn only_appearance_changes_skip_the_lock() {
+        let old = AppPreferences::default();
+        let mut new = old.clone();
+        new.theme = "dark".to_string();
+        new.quick_pane_zoom = 1.5;
+        assert!(only_appearance_changed(&old, &new));
+
+        new.auto_lock_minutes = 0;
+        assert!(!only_appearance_changed(&old, &new));
+
+        let mut new = old.clone();
+        new.deidentification.names.push("Jane Doe".to_string());
+        assert!(!only_appearance_changed(&old, &new));
+    }
+
+    #[test]
+    fn locked_preferences_leave_out_deidentification_lists() {
+        let mut saved = AppPreferences::default();
+        saved.deidentification.names = vec!["Jane Doe".to_string()];
+        saved.deidentification.custom_patterns = vec![r"\bDoe\b".to_string()];
+        saved.deidentification.phone_numbers = true;
+
+        let loaded = without_deidentification_lists(saved.clone());
+        assert!(loaded.deidentification.names.is_empty());
+        assert!(loaded.deidentification.custom_patterns.is_empty());
+        assert!(loaded.deidentification.phone_numbers);
+        assert!(!only_appearance_changed(&saved, &loaded));
+    }
 }
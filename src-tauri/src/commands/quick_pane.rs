@@ -3,26 +3,85 @@
 //! The quick pane is a floating panel (NSPanel on macOS, standard window elsewhere)
 //! that provides quick entry functionality accessible via global shortcut.
 
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Mutex;
 use tauri::{AppHandle, Manager, WebviewUrl};
+use tauri_specta::Event;
 
-use crate::types::DEFAULT_QUICK_PANE_SHORTCUT;
+use crate::types::{
+    clamp_quick_pane_zoom, AppError, DirtyHideBehavior, DoubleTapModifier, ModifierKey,
+    QuickPaneAppearance, QuickPaneHideRequested, QuickPanePosition, QuickPaneVisibilityChanged,
+    DEFAULT_QUICK_PANE_SHORTCUT,
+};
+use crate::utils::metrics;
 
 // ============================================================================
 // Constants
 // ============================================================================
 
 /// Window label for the quick pane
-const QUICK_PANE_LABEL: &str = "quick-pane";
+pub const QUICK_PANE_LABEL: &str = "quick-pane";
 
-/// Quick pane window dimensions
+/// Quick pane window dimensions (at zoom 1.0)
 const QUICK_PANE_WIDTH: f64 = 500.0;
 const QUICK_PANE_HEIGHT: f64 = 72.0;
 
+/// Activations closer together than this are treated as key bounce and dropped
+const ACTIVATION_DEBOUNCE_MS: u64 = 250;
+
+/// Upper bound on waiting for an in-flight recording start/stop before toggling anyway
+const RECORDING_SETTLE_TIMEOUT_MS: u64 = 2_000;
+
+/// Gap between the tray icon and the quick pane in menu-bar-anchored mode (logical px)
+const TRAY_ANCHOR_OFFSET: f64 = 6.0;
+
+/// Zoom change per zoom in/out step
+const QUICK_PANE_ZOOM_STEP: f64 = 0.1;
+
 /// Tracks the currently registered quick pane shortcut for selective unregistration.
 /// This allows us to unregister only our shortcut without affecting other shortcuts.
 static CURRENT_QUICK_PANE_SHORTCUT: Mutex<Option<String>> = Mutex::new(None);
 
+/// Current quick pane zoom as a percentage (100 = 1.0), cached for sizing calculations.
+static ZOOM_PERCENT: AtomicU32 = AtomicU32::new(100);
+
+/// Whether the quick pane window size follows its zoom factor.
+static SCALE_WINDOW_WITH_ZOOM: AtomicBool = AtomicBool::new(false);
+
+/// Whether the pane is anchored to the tray icon instead of the cursor's monitor.
+static MENUBAR_ANCHORED: AtomicBool = AtomicBool::new(false);
+
+/// Last known tray icon rect (physical position and size) from a tray event.
+static TRAY_ICON_RECT: Mutex<Option<TrayRect>> = Mutex::new(None);
+
+/// Set by the frontend while the pane holds a result that hasn't been copied/saved.
+static QUICK_PANE_DIRTY: AtomicBool = AtomicBool::new(false);
+
+/// Set when an explicit hide was held back; the next explicit hide always proceeds.
+static EXPLICIT_HIDE_HELD: AtomicBool = AtomicBool::new(false);
+
+/// Show/hide animation duration, cached from preferences so show/hide never hits disk.
+static ANIMATION_MS: AtomicU32 = AtomicU32::new(0);
+
+/// Set while a hide animation runs so repeated dismiss calls don't restart it.
+static HIDE_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// Frame interval for show/hide animations (~60fps)
+const ANIMATION_FRAME_MS: u64 = 16;
+
+/// Starting scale for the macOS show animation (grows to 1.0)
+#[cfg(target_os = "macos")]
+const ANIMATION_START_SCALE: f64 = 0.96;
+
+/// Time of the last accepted activation, for debouncing.
+static LAST_ACTIVATION: Mutex<Option<std::time::Instant>> = Mutex::new(None);
+
+/// Serializes activations so each toggle completes before the next is processed.
+static ACTIVATION_QUEUE: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Active double-tap listener state. None means no platform listener is running.
+static DOUBLE_TAP_LISTENER: Mutex<Option<DoubleTapListener>> = Mutex::new(None);
+
 // ============================================================================
 // macOS-specific: NSPanel support
 // ============================================================================
@@ -53,14 +112,22 @@ tauri_panel! {
 /// The window starts hidden and is shown via show_quick_pane command.
 pub fn init_quick_pane(app: &AppHandle) -> Result<(), String> {
     #[cfg(target_os = "macos")]
-    {
-        init_quick_pane_macos(app)
-    }
+    init_quick_pane_macos(app)?;
 
     #[cfg(not(target_os = "macos"))]
-    {
-        init_quick_pane_standard(app)
-    }
+    init_quick_pane_standard(app)?;
+
+    // Re-apply persisted appearance every time the pane is (re)created
+    let prefs = crate::commands::preferences::load_saved_preferences(app);
+    apply_quick_pane_position(prefs.quick_pane_position);
+    apply_quick_pane_appearance(app, &prefs.quick_pane_appearance);
+    apply_quick_pane_zoom(
+        app,
+        prefs.quick_pane_zoom,
+        prefs.quick_pane_scale_window_with_zoom,
+    );
+    crate::commands::screen_capture::apply_to_window(app, QUICK_PANE_LABEL);
+    Ok(())
 }
 
 /// Creates the quick pane as an NSPanel on macOS (hidden).
@@ -73,7 +140,10 @@ fn init_quick_pane_macos(app: &AppHandle) -> Result<(), String> {
     let panel = PanelBuilder::<_, QuickPanePanel>::new(app, QUICK_PANE_LABEL)
         .url(WebviewUrl::App("quick-pane.html".into()))
         .title("Quick Entry")
-        .size(Size::Logical(LogicalSize::new(500.0, 72.0)))
+        .size(Size::Logical(LogicalSize::new(
+            QUICK_PANE_WIDTH,
+            QUICK_PANE_HEIGHT,
+        )))
         .level(PanelLevel::Status) // Status level to appear above fullscreen apps
         .transparent(true)
         .has_shadow(true)
@@ -97,6 +167,7 @@ fn init_quick_pane_macos(app: &AppHandle) -> Result<(), String> {
 
     // Start hidden - will be shown via show_quick_pane command
     panel.hide();
+    register_outside_click_dismiss(app);
     log::info!("Quick pane NSPanel created (hidden)");
     Ok(())
 }
@@ -114,7 +185,7 @@ fn init_quick_pane_standard(app: &AppHandle) -> Result<(), String> {
         WebviewUrl::App("quick-pane.html".into()),
     )
     .title("Quick Entry")
-    .inner_size(500.0, 72.0)
+    .inner_size(QUICK_PANE_WIDTH, QUICK_PANE_HEIGHT)
     .always_on_top(true)
     .skip_taskbar(true)
     .decorations(false)
@@ -125,6 +196,7 @@ fn init_quick_pane_standard(app: &AppHandle) -> Result<(), String> {
     .build()
     .map_err(|e| format!("Failed to create quick pane window: {e}"))?;
 
+    register_outside_click_dismiss(app);
     log::info!("Quick pane window created (hidden)");
     Ok(())
 }
@@ -185,8 +257,9 @@ fn get_centered_position_on_cursor_monitor(
 
     // Calculate centered position on this monitor
     // Window size needs to be scaled by the monitor's scale factor
-    let scaled_width = (QUICK_PANE_WIDTH * scale_factor) as i32;
-    let scaled_height = (QUICK_PANE_HEIGHT * scale_factor) as i32;
+    let (width, height) = quick_pane_logical_size();
+    let scaled_width = (width * scale_factor) as i32;
+    let scaled_height = (height * scale_factor) as i32;
 
     let x = monitor_pos.x + (monitor_size.width as i32 - scaled_width) / 2;
     let y = monitor_pos.y + (monitor_size.height as i32 - scaled_height) / 2;
@@ -196,9 +269,16 @@ fn get_centered_position_on_cursor_monitor(
     Some(tauri::PhysicalPosition::new(x, y))
 }
 
-/// Positions the quick pane window centered on the monitor containing the cursor.
-fn position_quick_pane_on_cursor_monitor(app: &AppHandle) {
-    if let Some(position) = get_centered_position_on_cursor_monitor(app) {
+/// Positions the quick pane according to the placement mode.
+/// Menu-bar-anchored mode falls back to the cursor monitor when no tray rect is known.
+fn position_quick_pane(app: &AppHandle) {
+    let position = if MENUBAR_ANCHORED.load(Ordering::SeqCst) {
+        get_tray_anchored_position(app).or_else(|| get_centered_position_on_cursor_monitor(app))
+    } else {
+        get_centered_position_on_cursor_monitor(app)
+    };
+
+    if let Some(position) = position {
         if let Some(window) = app.get_webview_window(QUICK_PANE_LABEL) {
             if let Err(e) = window.set_position(position) {
                 log::warn!("Failed to set window position: {e}");
@@ -207,6 +287,105 @@ fn position_quick_pane_on_cursor_monitor(app: &AppHandle) {
     }
 }
 
+/// Screen rect of the tray icon in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrayRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Records the tray icon rect reported by a tray event.
+pub fn set_tray_icon_rect(rect: TrayRect) {
+    if let Ok(mut current) = TRAY_ICON_RECT.lock() {
+        *current = Some(rect);
+    }
+}
+
+/// Computes the pane origin directly beneath the tray icon, horizontally centered on it
+/// and clamped to the monitor, flipping above the icon when there's no room below.
+/// All values are physical pixels; `monitor` is (x, y, width, height).
+fn compute_tray_anchored_position(
+    tray: TrayRect,
+    pane_size: (f64, f64),
+    monitor: (f64, f64, f64, f64),
+    offset: f64,
+) -> (f64, f64) {
+    let (pane_width, pane_height) = pane_size;
+    let (monitor_x, monitor_y, monitor_width, monitor_height) = monitor;
+
+    let centered_x = tray.x + tray.width / 2.0 - pane_width / 2.0;
+    let max_x = (monitor_x + monitor_width - pane_width).max(monitor_x);
+    let x = centered_x.clamp(monitor_x, max_x);
+
+    let below = tray.y + tray.height + offset;
+    let y = if below + pane_height <= monitor_y + monitor_height {
+        below
+    } else {
+        (tray.y - offset - pane_height).max(monitor_y)
+    };
+
+    (x, y)
+}
+
+/// Calculates the pane position beneath the tray icon, if the tray rect is known.
+fn get_tray_anchored_position(app: &AppHandle) -> Option<tauri::PhysicalPosition<i32>> {
+    let tray = (*TRAY_ICON_RECT.lock().ok()?)?;
+    let monitor = get_monitor_for_cursor(app, tauri::PhysicalPosition::new(tray.x, tray.y))?;
+
+    let scale_factor = monitor.scale_factor();
+    let (width, height) = quick_pane_logical_size();
+    let monitor_pos = monitor.position();
+    let monitor_size = monitor.size();
+
+    let (x, y) = compute_tray_anchored_position(
+        tray,
+        (width * scale_factor, height * scale_factor),
+        (
+            f64::from(monitor_pos.x),
+            f64::from(monitor_pos.y),
+            f64::from(monitor_size.width),
+            f64::from(monitor_size.height),
+        ),
+        TRAY_ANCHOR_OFFSET * scale_factor,
+    );
+
+    log::debug!("Tray-anchored position: ({x}, {y})");
+    Some(tauri::PhysicalPosition::new(x as i32, y as i32))
+}
+
+/// Applies the placement mode preference.
+pub fn apply_quick_pane_position(position: QuickPanePosition) {
+    MENUBAR_ANCHORED.store(
+        position == QuickPanePosition::MenubarAnchored,
+        Ordering::SeqCst,
+    );
+}
+
+/// Returns whether the pane is in menu-bar-anchored mode.
+pub fn is_menubar_anchored() -> bool {
+    MENUBAR_ANCHORED.load(Ordering::SeqCst)
+}
+
+/// In menu-bar-anchored mode, hide the pane when it loses key status (outside click),
+/// matching native MenuBarExtra behavior. The nonactivating panel never steals activation.
+fn register_outside_click_dismiss(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(QUICK_PANE_LABEL) else {
+        return;
+    };
+    let app_handle = app.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Focused(false) = event {
+            if is_menubar_anchored() {
+                if let Err(e) = dismiss_quick_pane(app_handle.clone()) {
+                    log::warn!("Failed to auto-hide anchored quick pane: {e}");
+                }
+            }
+        }
+    });
+}
+
 // ============================================================================
 // Window Visibility
 // ============================================================================
@@ -228,20 +407,58 @@ fn is_quick_pane_visible(app: &AppHandle) -> bool {
     }
 }
 
-/// Shows the quick pane window and makes it the key window (for keyboard input).
+/// Notifies all windows of the quick pane's new visibility state.
+fn emit_visibility_changed(app: &AppHandle, visible: bool) {
+    if let Err(e) = (QuickPaneVisibilityChanged { visible }).emit(app) {
+        log::warn!("Failed to emit quick pane visibility change: {e}");
+    }
+}
+
+/// Returns whether the quick pane is currently visible (for menu state on startup).
 #[tauri::command]
 #[specta::specta]
-pub fn show_quick_pane(app: AppHandle) -> Result<(), String> {
-    log::info!("Showing quick pane window");
+#[metrics::timed]
+pub fn get_quick_pane_visible(app: AppHandle) -> bool {
+    is_quick_pane_visible(&app)
+}
 
-    position_quick_pane_on_cursor_monitor(&app);
+/// Shows the quick pane window.
+/// When `focus_keyboard` is true the pane becomes the key window (for keyboard input);
+/// pass None to use the `quick_pane_focus_keyboard` preference.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn show_quick_pane(app: AppHandle, focus_keyboard: Option<bool>) -> Result<(), AppError> {
+    let focus_keyboard = focus_keyboard.unwrap_or_else(|| {
+        crate::commands::preferences::load_saved_preferences(&app).quick_pane_focus_keyboard
+    });
+    show_quick_pane_with_focus(&app, focus_keyboard)
+}
+
+/// Shows the quick pane, optionally making it key so keystrokes register without a click.
+fn show_quick_pane_with_focus(app: &AppHandle, focus_keyboard: bool) -> Result<(), AppError> {
+    log::info!("Showing quick pane window (focus_keyboard: {focus_keyboard})");
+
+    // Remember where the user was typing before the pane can take focus
+    if !is_quick_pane_visible(app) {
+        crate::commands::output::remember_previous_app();
+    }
+
+    position_quick_pane(app);
 
     #[cfg(target_os = "macos")]
     {
         let panel = app
             .get_webview_panel(QUICK_PANE_LABEL)
             .map_err(|e| format!("Quick pane panel not found: {e:?}"))?;
-        panel.show_and_make_key();
+        set_quick_pane_alpha(app, 0.0, 0.0);
+        if focus_keyboard {
+            // The panel uses the nonactivating style mask, so becoming key does not
+            // activate our app - the previous app keeps its menu bar.
+            panel.show_and_make_key();
+        } else {
+            panel.show();
+        }
         log::debug!("Quick pane panel shown (macOS)");
     }
 
@@ -250,23 +467,115 @@ pub fn show_quick_pane(app: AppHandle) -> Result<(), String> {
         let window = app.get_webview_window(QUICK_PANE_LABEL).ok_or_else(|| {
             "Quick pane window not found - was init_quick_pane called at startup?".to_string()
         })?;
+        set_quick_pane_alpha(app, 0.0, 0.0);
         window
             .show()
             .map_err(|e| format!("Failed to show window: {e}"))?;
-        window
-            .set_focus()
-            .map_err(|e| format!("Failed to focus window: {e}"))?;
+        if focus_keyboard {
+            window
+                .set_focus()
+                .map_err(|e| format!("Failed to focus window: {e}"))?;
+        }
         log::debug!("Quick pane window shown");
     }
 
+    HIDE_IN_PROGRESS.store(false, Ordering::SeqCst);
+    EXPLICIT_HIDE_HELD.store(false, Ordering::SeqCst);
+    animate_quick_pane(app, true, |_| {});
+    emit_visibility_changed(app, true);
     Ok(())
 }
 
+/// What initiated a hide, which decides whether unsaved work can hold it back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HideTrigger {
+    /// Blur, Escape, outside click
+    Auto,
+    /// Shortcut, tray or toggle command
+    Explicit,
+    /// The frontend confirmed the hide
+    Forced,
+}
+
+/// Marks whether the pane holds a transcript that hasn't been copied or saved yet.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn set_quick_pane_dirty(dirty: bool) {
+    log::debug!("Quick pane dirty: {dirty}");
+    QUICK_PANE_DIRTY.store(dirty, Ordering::SeqCst);
+}
+
+/// Returns whether a hide may proceed, emitting `quick-pane-hide-requested` when held back.
+/// Explicit hides are held at most once so users are never trapped.
+fn allow_hide(app: &AppHandle, trigger: HideTrigger) -> bool {
+    if trigger == HideTrigger::Forced {
+        return true;
+    }
+
+    let recording =
+        crate::commands::audio::recording_phase() != crate::commands::audio::RecordingPhase::Idle;
+    let unsaved_result = QUICK_PANE_DIRTY.load(Ordering::SeqCst);
+    if !recording && !unsaved_result {
+        return true;
+    }
+
+    let explicit = trigger == HideTrigger::Explicit;
+    if explicit && EXPLICIT_HIDE_HELD.swap(false, Ordering::SeqCst) {
+        log::info!("Second explicit hide - hiding quick pane despite unsaved work");
+        return true;
+    }
+    if explicit {
+        EXPLICIT_HIDE_HELD.store(true, Ordering::SeqCst);
+    }
+
+    let behavior = crate::commands::preferences::load_saved_preferences(app).quick_pane_dirty_hide;
+    if explicit || behavior == DirtyHideBehavior::AskFrontend {
+        let payload = QuickPaneHideRequested {
+            recording,
+            unsaved_result,
+            explicit,
+        };
+        if let Err(e) = payload.emit(app) {
+            log::warn!("Failed to emit quick pane hide request: {e}");
+        }
+    }
+
+    log::info!(
+        "Held back quick pane hide (recording: {recording}, unsaved: {unsaved_result}, explicit: {explicit})"
+    );
+    false
+}
+
 /// Dismisses the quick pane window.
 /// On macOS, resigns key window status before hiding to avoid activating main window.
+/// Blur auto-hide and Escape in the pane both route through here, so an active
+/// recording or unsaved result can hold the hide back.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn dismiss_quick_pane(app: AppHandle) -> Result<(), AppError> {
+    hide_quick_pane(app, HideTrigger::Auto)
+}
+
+/// Dismisses the quick pane even with unsaved work (after the frontend confirmed).
 #[tauri::command]
 #[specta::specta]
-pub fn dismiss_quick_pane(app: AppHandle) -> Result<(), String> {
+#[metrics::timed]
+pub fn force_dismiss_quick_pane(app: AppHandle) -> Result<(), AppError> {
+    hide_quick_pane(app, HideTrigger::Forced)
+}
+
+fn hide_quick_pane(app: AppHandle, trigger: HideTrigger) -> Result<(), AppError> {
+    if HIDE_IN_PROGRESS.load(Ordering::SeqCst) {
+        log::debug!("Quick pane hide already in progress, skipping");
+        return Ok(());
+    }
+
+    if !is_quick_pane_visible(&app) || !allow_hide(&app, trigger) {
+        return Ok(());
+    }
+
     #[cfg(target_os = "macos")]
     {
         if let Ok(panel) = app.get_webview_panel(QUICK_PANE_LABEL) {
@@ -275,11 +584,17 @@ pub fn dismiss_quick_pane(app: AppHandle) -> Result<(), String> {
                 return Ok(());
             }
             log::info!("Dismissing quick pane window");
+            HIDE_IN_PROGRESS.store(true, Ordering::SeqCst);
             // Resign key window BEFORE hiding to prevent macOS from
             // activating our main window (which would cause space switching)
             panel.resign_key_window();
-            panel.hide();
-            log::debug!("Quick pane panel dismissed (macOS)");
+            animate_quick_pane(&app, false, |app| {
+                if let Ok(panel) = app.get_webview_panel(QUICK_PANE_LABEL) {
+                    panel.hide();
+                }
+                finish_hide(app);
+                log::debug!("Quick pane panel dismissed (macOS)");
+            });
         }
     }
 
@@ -292,26 +607,269 @@ pub fn dismiss_quick_pane(app: AppHandle) -> Result<(), String> {
                 return Ok(());
             }
             log::info!("Dismissing quick pane window");
-            window
-                .hide()
-                .map_err(|e| format!("Failed to hide window: {e}"))?;
-            log::debug!("Quick pane window hidden");
+            HIDE_IN_PROGRESS.store(true, Ordering::SeqCst);
+            animate_quick_pane(&app, false, |app| {
+                if let Some(window) = app.get_webview_window(QUICK_PANE_LABEL) {
+                    if let Err(e) = window.hide() {
+                        log::error!("Failed to hide window: {e}");
+                    }
+                }
+                finish_hide(app);
+                log::debug!("Quick pane window hidden");
+            });
         }
     }
 
     Ok(())
 }
 
+/// Completes a hide: restores full opacity for the next show and notifies listeners.
+fn finish_hide(app: &AppHandle) {
+    set_quick_pane_alpha(app, 1.0, 1.0);
+    HIDE_IN_PROGRESS.store(false, Ordering::SeqCst);
+    EXPLICIT_HIDE_HELD.store(false, Ordering::SeqCst);
+    emit_visibility_changed(app, false);
+}
+
 /// Toggles the quick pane window visibility.
 #[tauri::command]
 #[specta::specta]
-pub fn toggle_quick_pane(app: AppHandle) -> Result<(), String> {
+#[metrics::timed]
+pub fn toggle_quick_pane(app: AppHandle) -> Result<(), AppError> {
     log::info!("Toggling quick pane window");
 
     if is_quick_pane_visible(&app) {
-        dismiss_quick_pane(app)
+        hide_quick_pane(app, HideTrigger::Explicit)
     } else {
-        show_quick_pane(app)
+        show_quick_pane(app, None)
+    }
+}
+
+// ============================================================================
+// Zoom
+// ============================================================================
+
+/// Returns the quick pane's logical size, scaled by zoom when that option is enabled.
+fn quick_pane_logical_size() -> (f64, f64) {
+    if !SCALE_WINDOW_WITH_ZOOM.load(Ordering::SeqCst) {
+        return (QUICK_PANE_WIDTH, QUICK_PANE_HEIGHT);
+    }
+    let zoom = f64::from(ZOOM_PERCENT.load(Ordering::SeqCst)) / 100.0;
+    (QUICK_PANE_WIDTH * zoom, QUICK_PANE_HEIGHT * zoom)
+}
+
+fn current_quick_pane_zoom() -> f64 {
+    f64::from(ZOOM_PERCENT.load(Ordering::SeqCst)) / 100.0
+}
+
+/// Applies a zoom factor to the quick pane webview (and optionally its window size).
+/// Called at creation and whenever the zoom preferences change.
+pub fn apply_quick_pane_zoom(app: &AppHandle, zoom: f64, scale_window: bool) {
+    let zoom = clamp_quick_pane_zoom(zoom);
+    ZOOM_PERCENT.store((zoom * 100.0).round() as u32, Ordering::SeqCst);
+    SCALE_WINDOW_WITH_ZOOM.store(scale_window, Ordering::SeqCst);
+
+    let Some(window) = app.get_webview_window(QUICK_PANE_LABEL) else {
+        return;
+    };
+
+    if let Err(e) = window.set_zoom(zoom) {
+        log::warn!("Failed to set quick pane zoom: {e}");
+    }
+
+    let (width, height) = quick_pane_logical_size();
+    if let Err(e) = window.set_size(tauri::LogicalSize::new(width, height)) {
+        log::warn!("Failed to resize quick pane: {e}");
+    }
+    log::debug!("Quick pane zoom set to {zoom} ({width}x{height})");
+}
+
+/// Persists and applies a new quick pane zoom. Returns the clamped value.
+fn persist_quick_pane_zoom(app: &AppHandle, zoom: f64) -> Result<f64, AppError> {
+    let zoom = clamp_quick_pane_zoom(zoom);
+    let prefs = crate::commands::preferences::update_saved_preferences(app, |prefs| {
+        prefs.quick_pane_zoom = zoom;
+    })?;
+    apply_quick_pane_zoom(app, zoom, prefs.quick_pane_scale_window_with_zoom);
+    Ok(zoom)
+}
+
+/// Sets the quick pane zoom factor (clamped to 0.75-2.0) and persists it.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn set_quick_pane_zoom(app: AppHandle, zoom: f64) -> Result<f64, AppError> {
+    persist_quick_pane_zoom(&app, zoom)
+}
+
+/// Zooms the quick pane in by one step (bound to Cmd/Ctrl+= in the pane).
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn zoom_quick_pane_in(app: AppHandle) -> Result<f64, AppError> {
+    persist_quick_pane_zoom(&app, current_quick_pane_zoom() + QUICK_PANE_ZOOM_STEP)
+}
+
+/// Zooms the quick pane out by one step (bound to Cmd/Ctrl+- in the pane).
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn zoom_quick_pane_out(app: AppHandle) -> Result<f64, AppError> {
+    persist_quick_pane_zoom(&app, current_quick_pane_zoom() - QUICK_PANE_ZOOM_STEP)
+}
+
+// ============================================================================
+// Appearance & Animation
+// ============================================================================
+
+/// Applies animation and vibrancy settings to the quick pane.
+/// Called at creation and whenever the appearance preference changes.
+pub fn apply_quick_pane_appearance(app: &AppHandle, appearance: &QuickPaneAppearance) {
+    ANIMATION_MS.store(appearance.animation_ms, Ordering::SeqCst);
+
+    #[cfg(target_os = "macos")]
+    {
+        use crate::types::QuickPaneMaterial;
+        use tauri::window::{Effect, EffectState, EffectsBuilder};
+
+        let Some(window) = app.get_webview_window(QUICK_PANE_LABEL) else {
+            return;
+        };
+        let effect = match appearance.material {
+            QuickPaneMaterial::Hud => Effect::HudWindow,
+            QuickPaneMaterial::Popover => Effect::Popover,
+            QuickPaneMaterial::Sidebar => Effect::Sidebar,
+        };
+        let effects = EffectsBuilder::new()
+            .effect(effect)
+            .state(EffectState::Active)
+            .radius(12.0)
+            .build();
+        if let Err(e) = window.set_effects(effects) {
+            log::warn!("Failed to apply quick pane material: {e}");
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        // Materials are macOS-only; keep the API identical elsewhere
+        let _ = app;
+    }
+}
+
+/// Updates quick pane appearance immediately (the frontend persists the preference).
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn update_quick_pane_appearance(app: AppHandle, appearance: QuickPaneAppearance) {
+    log::info!("Updating quick pane appearance: {appearance:?}");
+    apply_quick_pane_appearance(&app, &appearance);
+}
+
+/// Fades the pane in (`showing`) or out, then runs `on_complete` on the main thread.
+/// With a zero duration the final state is applied and `on_complete` runs immediately.
+fn animate_quick_pane<F>(app: &AppHandle, showing: bool, on_complete: F)
+where
+    F: FnOnce(&AppHandle) + Send + 'static,
+{
+    let duration_ms = ANIMATION_MS.load(Ordering::SeqCst);
+    let (from, to) = if showing { (0.0, 1.0) } else { (1.0, 0.0) };
+
+    if duration_ms == 0 {
+        set_quick_pane_alpha(app, to, 1.0);
+        on_complete(app);
+        return;
+    }
+
+    let frames = (u64::from(duration_ms) / ANIMATION_FRAME_MS).max(1);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        for frame in 1..=frames {
+            std::thread::sleep(std::time::Duration::from_millis(ANIMATION_FRAME_MS));
+            let progress = frame as f64 / frames as f64;
+            let alpha = from + (to - from) * progress;
+            let handle = app.clone();
+            let _ = app.run_on_main_thread(move || set_quick_pane_alpha(&handle, alpha, alpha));
+        }
+        let handle = app.clone();
+        let _ = app.run_on_main_thread(move || on_complete(&handle));
+    });
+}
+
+/// Sets quick pane opacity. `scale_progress` (0..1) drives the macOS scale effect.
+#[cfg(target_os = "macos")]
+fn set_quick_pane_alpha(app: &AppHandle, alpha: f64, scale_progress: f64) {
+    use objc2_app_kit::NSWindow;
+    use objc2_foundation::{NSPoint, NSRect, NSSize};
+
+    let Some(window) = app.get_webview_window(QUICK_PANE_LABEL) else {
+        return;
+    };
+    let Ok(ns_window) = window.ns_window() else {
+        return;
+    };
+
+    // SAFETY: ns_window() returns the live NSWindow backing this webview window,
+    // and this function only runs on the main thread.
+    let ns_window = unsafe { &*(ns_window as *const NSWindow) };
+
+    // Scale around the window center, using the unscaled logical size as the base
+    let scale = ANIMATION_START_SCALE + (1.0 - ANIMATION_START_SCALE) * scale_progress;
+    let frame = ns_window.frame();
+    let (base_width, base_height) = quick_pane_logical_size();
+    let center_x = frame.origin.x + frame.size.width / 2.0;
+    let center_y = frame.origin.y + frame.size.height / 2.0;
+    let width = base_width * scale;
+    let height = base_height * scale;
+    let scaled = NSRect::new(
+        NSPoint::new(center_x - width / 2.0, center_y - height / 2.0),
+        NSSize::new(width, height),
+    );
+
+    ns_window.setAlphaValue(alpha);
+    ns_window.setFrame_display(scaled, true);
+}
+
+/// Sets quick pane opacity via the layered-window alpha on Windows.
+#[cfg(target_os = "windows")]
+fn set_quick_pane_alpha(app: &AppHandle, alpha: f64, _scale_progress: f64) {
+    use windows::Win32::Foundation::{COLORREF, HWND};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongPtrW, SetLayeredWindowAttributes, SetWindowLongPtrW, GWL_EXSTYLE, LWA_ALPHA,
+        WS_EX_LAYERED,
+    };
+
+    let Some(window) = app.get_webview_window(QUICK_PANE_LABEL) else {
+        return;
+    };
+    let Ok(hwnd) = window.hwnd() else {
+        return;
+    };
+    let hwnd = HWND(hwnd.0);
+    let alpha = (alpha.clamp(0.0, 1.0) * 255.0) as u8;
+
+    // SAFETY: hwnd belongs to our live quick pane window
+    unsafe {
+        let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        if ex_style & WS_EX_LAYERED.0 as isize == 0 {
+            SetWindowLongPtrW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED.0 as isize);
+        }
+        if let Err(e) = SetLayeredWindowAttributes(hwnd, COLORREF(0), alpha, LWA_ALPHA) {
+            log::debug!("Failed to set quick pane opacity: {e}");
+        }
+    }
+}
+
+/// Sets quick pane opacity through the GTK window on Linux.
+#[cfg(target_os = "linux")]
+fn set_quick_pane_alpha(app: &AppHandle, alpha: f64, _scale_progress: f64) {
+    use gtk::prelude::WidgetExt;
+
+    let Some(window) = app.get_webview_window(QUICK_PANE_LABEL) else {
+        return;
+    };
+    if let Ok(gtk_window) = window.gtk_window() {
+        gtk_window.set_opacity(alpha.clamp(0.0, 1.0));
     }
 }
 
@@ -319,6 +877,48 @@ pub fn toggle_quick_pane(app: AppHandle) -> Result<(), String> {
 // Shortcut Management
 // ============================================================================
 
+/// Returns true if this activation falls within the debounce window of the previous one.
+fn is_bounced_activation(now: std::time::Instant) -> bool {
+    let Ok(mut last) = LAST_ACTIVATION.lock() else {
+        return false;
+    };
+    let window = std::time::Duration::from_millis(ACTIVATION_DEBOUNCE_MS);
+    if last.is_some_and(|previous| now.duration_since(previous) < window) {
+        return true;
+    }
+    *last = Some(now);
+    false
+}
+
+/// Common entry point for every user activation (global shortcut, double-tap,
+/// command line). Debounces rapid presses and processes accepted activations
+/// one at a time, waiting for any in-flight recording start/stop to settle first.
+pub(crate) fn handle_quick_pane_activation(app: &AppHandle) {
+    if is_bounced_activation(std::time::Instant::now()) {
+        log::debug!(
+            "Dropped quick pane activation within {ACTIVATION_DEBOUNCE_MS}ms debounce window"
+        );
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _serialized = ACTIVATION_QUEUE.lock().await;
+
+        let deadline = std::time::Instant::now()
+            + std::time::Duration::from_millis(RECORDING_SETTLE_TIMEOUT_MS);
+        while crate::commands::audio::is_recording_transitioning()
+            && std::time::Instant::now() < deadline
+        {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        if let Err(e) = toggle_quick_pane(app) {
+            log::error!("Failed to toggle quick pane: {e}");
+        }
+    });
+}
+
 /// Registers the quick pane global shortcut, unregistering any previously registered one.
 /// This helper is used by both setup() and update_quick_pane_shortcut() for consistency.
 #[cfg(desktop)]
@@ -357,9 +957,7 @@ pub fn register_quick_pane_shortcut(app: &AppHandle, shortcut: &str) -> Result<(
             use tauri_plugin_global_shortcut::ShortcutState;
             if event.state == ShortcutState::Pressed {
                 log::info!("Quick pane shortcut triggered");
-                if let Err(e) = toggle_quick_pane(app_handle.clone()) {
-                    log::error!("Failed to toggle quick pane: {e}");
-                }
+                handle_quick_pane_activation(&app_handle);
             }
         })
         .map_err(|e| format!("Failed to register shortcut '{shortcut}': {e}"))?;
@@ -371,9 +969,25 @@ pub fn register_quick_pane_shortcut(app: &AppHandle, shortcut: &str) -> Result<(
     Ok(())
 }
 
+/// The quick pane shortcut, if one was registered and the OS still has it.
+#[cfg(desktop)]
+pub(crate) fn registered_quick_pane_shortcut(app: &AppHandle) -> Option<String> {
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+    let current = CURRENT_QUICK_PANE_SHORTCUT
+        .lock()
+        .ok()
+        .and_then(|current| current.clone())?;
+    let shortcut = current.parse::<Shortcut>().ok()?;
+    app.global_shortcut()
+        .is_registered(shortcut)
+        .then_some(current)
+}
+
 /// Returns the default shortcut constant for frontend use.
 #[tauri::command]
 #[specta::specta]
+#[metrics::timed]
 pub fn get_default_quick_pane_shortcut() -> String {
     DEFAULT_QUICK_PANE_SHORTCUT.to_string()
 }
@@ -382,13 +996,17 @@ pub fn get_default_quick_pane_shortcut() -> String {
 /// Pass None to reset to default.
 #[tauri::command]
 #[specta::specta]
-pub fn update_quick_pane_shortcut(app: AppHandle, shortcut: Option<String>) -> Result<(), String> {
+#[metrics::timed]
+pub fn update_quick_pane_shortcut(
+    app: AppHandle,
+    shortcut: Option<String>,
+) -> Result<(), AppError> {
     #[cfg(desktop)]
     {
         let new_shortcut = shortcut.as_deref().unwrap_or(DEFAULT_QUICK_PANE_SHORTCUT);
         log::info!("Updating quick pane shortcut to: {new_shortcut}");
 
-        register_quick_pane_shortcut(&app, new_shortcut)?;
+        register_quick_pane_shortcut(&app, new_shortcut).map_err(AppError::invalid_input)?;
 
         log::info!("Quick pane shortcut updated successfully");
     }
@@ -401,3 +1019,457 @@ pub fn update_quick_pane_shortcut(app: AppHandle, shortcut: Option<String>) -> R
 
     Ok(())
 }
+
+// ============================================================================
+// Double-tap Modifier Activation
+// ============================================================================
+
+/// Detects a double-tap of a single modifier key.
+/// A tap is a press and release of the modifier with no other key in between,
+/// held no longer than the interval. Two taps within the interval activate.
+/// Only fed by the macOS and Windows listeners.
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+struct DoubleTapDetector {
+    interval: std::time::Duration,
+    pressed_at: Option<std::time::Instant>,
+    interrupted: bool,
+    last_tap: Option<std::time::Instant>,
+}
+
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+impl DoubleTapDetector {
+    fn new(interval_ms: u32) -> Self {
+        Self {
+            interval: std::time::Duration::from_millis(u64::from(interval_ms)),
+            pressed_at: None,
+            interrupted: false,
+            last_tap: None,
+        }
+    }
+
+    /// Records a state change of the watched modifier.
+    /// Returns true when the release completes a double-tap.
+    fn on_modifier(&mut self, pressed: bool, now: std::time::Instant) -> bool {
+        if pressed {
+            self.pressed_at = Some(now);
+            self.interrupted = false;
+            return false;
+        }
+
+        let Some(pressed_at) = self.pressed_at.take() else {
+            return false;
+        };
+
+        // Chords (modifier + other key) and long holds are not taps
+        if self.interrupted || now.duration_since(pressed_at) > self.interval {
+            self.last_tap = None;
+            return false;
+        }
+
+        match self.last_tap.take() {
+            Some(previous) if now.duration_since(previous) <= self.interval => true,
+            _ => {
+                self.last_tap = Some(now);
+                false
+            }
+        }
+    }
+
+    /// Records any other key or modifier activity, which cancels a pending sequence.
+    fn on_other_key(&mut self) {
+        if self.pressed_at.is_some() {
+            self.interrupted = true;
+        }
+        self.last_tap = None;
+    }
+}
+
+/// Running double-tap listener: configuration plus the platform handle to tear it down.
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+struct DoubleTapListener {
+    app: AppHandle,
+    key: ModifierKey,
+    detector: DoubleTapDetector,
+    platform: PlatformListener,
+}
+
+/// Feeds a modifier change into the active detector, activating the pane on a double-tap.
+/// `key` is the modifier that changed (None for a non-modifier key press).
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+fn process_double_tap_input(key: Option<ModifierKey>, pressed: bool) {
+    let Ok(mut guard) = DOUBLE_TAP_LISTENER.lock() else {
+        return;
+    };
+    let Some(listener) = guard.as_mut() else {
+        return;
+    };
+
+    let activated = match key {
+        Some(k) if k == listener.key => listener
+            .detector
+            .on_modifier(pressed, std::time::Instant::now()),
+        _ => {
+            listener.detector.on_other_key();
+            false
+        }
+    };
+
+    if activated {
+        log::info!("Quick pane double-tap triggered");
+        // Activation hands off to the async runtime, so the OS callback returns quickly
+        handle_quick_pane_activation(&listener.app);
+    }
+}
+
+/// Starts (or restarts) the double-tap listener, or stops it when `config` is None.
+/// Coexists with the registered global shortcut; both call the same activation path.
+pub fn apply_double_tap_modifier(
+    app: &AppHandle,
+    config: Option<&DoubleTapModifier>,
+) -> Result<(), AppError> {
+    let mut guard = DOUBLE_TAP_LISTENER
+        .lock()
+        .map_err(|e| AppError::internal(format!("Failed to lock double-tap listener: {e}")))?;
+
+    if let Some(existing) = guard.take() {
+        log::debug!("Stopping double-tap listener");
+        existing.platform.stop(app);
+    }
+
+    let Some(config) = config else {
+        log::info!("Double-tap modifier activation disabled");
+        return Ok(());
+    };
+
+    if !crate::utils::permissions::accessibility_trusted(false) {
+        return Err(AppError::AccessibilityPermissionDenied);
+    }
+
+    let platform = PlatformListener::start(app)?;
+    *guard = Some(DoubleTapListener {
+        app: app.clone(),
+        key: config.key,
+        detector: DoubleTapDetector::new(config.interval_ms),
+        platform,
+    });
+
+    log::info!(
+        "Double-tap listener started for {:?} ({}ms)",
+        config.key,
+        config.interval_ms
+    );
+    Ok(())
+}
+
+/// Updates double-tap modifier activation. Pass None to disable it entirely.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn update_double_tap_modifier(
+    app: AppHandle,
+    config: Option<DoubleTapModifier>,
+) -> Result<(), AppError> {
+    apply_double_tap_modifier(&app, config.as_ref())
+}
+
+/// Returns whether the OS permission needed for double-tap activation is granted.
+/// When `prompt` is true, macOS shows the Accessibility permission dialog if needed.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn check_double_tap_permission(prompt: bool) -> bool {
+    crate::utils::permissions::accessibility_trusted(prompt)
+}
+
+// macOS: NSEvent global + local monitors for flagsChanged/keyDown.
+// The global monitor sees events for other apps; the local one covers our own windows.
+#[cfg(target_os = "macos")]
+struct PlatformListener {
+    monitors: Vec<MonitorToken>,
+}
+
+/// NSEvent monitor token; only created and removed on the main thread.
+#[cfg(target_os = "macos")]
+struct MonitorToken(objc2::rc::Retained<objc2::runtime::AnyObject>);
+
+// SAFETY: tokens are only dereferenced on the main thread (see start/stop).
+#[cfg(target_os = "macos")]
+unsafe impl Send for MonitorToken {}
+
+#[cfg(target_os = "macos")]
+impl PlatformListener {
+    fn start(app: &AppHandle) -> Result<Self, String> {
+        use block2::RcBlock;
+        use objc2_app_kit::{NSEvent, NSEventMask, NSEventType};
+        use std::ptr::NonNull;
+
+        fn handle(event: &NSEvent) {
+            // SAFETY: reading properties of a live event delivered by AppKit
+            let (event_type, key_code, flags) =
+                unsafe { (event.r#type(), event.keyCode(), event.modifierFlags().0) };
+            if event_type == NSEventType::FlagsChanged {
+                if let Some((key, device_mask)) = macos_modifier_for_keycode(key_code) {
+                    process_double_tap_input(Some(key), flags & device_mask != 0);
+                    return;
+                }
+            }
+            process_double_tap_input(None, true);
+        }
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        app.run_on_main_thread(move || {
+            let mask = NSEventMask::FlagsChanged | NSEventMask::KeyDown;
+            let global = RcBlock::new(|event: NonNull<NSEvent>| {
+                // SAFETY: AppKit passes a valid event for the duration of the handler
+                handle(unsafe { event.as_ref() });
+            });
+            let local = RcBlock::new(|event: NonNull<NSEvent>| -> *mut NSEvent {
+                // SAFETY: AppKit passes a valid event for the duration of the handler
+                handle(unsafe { event.as_ref() });
+                event.as_ptr()
+            });
+
+            // SAFETY: called on the main thread with handlers that outlive registration
+            let monitors = unsafe {
+                [
+                    NSEvent::addGlobalMonitorForEventsMatchingMask_handler(mask, &global),
+                    NSEvent::addLocalMonitorForEventsMatchingMask_handler(mask, &local),
+                ]
+            };
+            let _ = tx.send(
+                monitors
+                    .into_iter()
+                    .flatten()
+                    .map(MonitorToken)
+                    .collect::<Vec<_>>(),
+            );
+        })
+        .map_err(|e| format!("Failed to schedule event monitor: {e}"))?;
+
+        let monitors = rx
+            .recv()
+            .map_err(|e| format!("Failed to install event monitor: {e}"))?;
+        if monitors.is_empty() {
+            return Err("Failed to install event monitor".into());
+        }
+        Ok(Self { monitors })
+    }
+
+    fn stop(self, app: &AppHandle) {
+        let monitors = self.monitors;
+        let result = app.run_on_main_thread(move || {
+            for token in monitors {
+                // SAFETY: removing a monitor we installed, on the main thread
+                unsafe { objc2_app_kit::NSEvent::removeMonitor(&token.0) };
+            }
+        });
+        if let Err(e) = result {
+            log::warn!("Failed to remove event monitors: {e}");
+        }
+    }
+}
+
+/// Maps a macOS virtual keycode to a sided modifier and its device-dependent flag bit.
+#[cfg(target_os = "macos")]
+fn macos_modifier_for_keycode(key_code: u16) -> Option<(ModifierKey, usize)> {
+    match key_code {
+        0x37 => Some((ModifierKey::LeftCommand, 0x0008)),
+        0x36 => Some((ModifierKey::RightCommand, 0x0010)),
+        0x3A => Some((ModifierKey::LeftOption, 0x0020)),
+        0x3D => Some((ModifierKey::RightOption, 0x0040)),
+        0x38 => Some((ModifierKey::LeftShift, 0x0002)),
+        0x3C => Some((ModifierKey::RightShift, 0x0004)),
+        0x3B => Some((ModifierKey::LeftControl, 0x0001)),
+        0x3E => Some((ModifierKey::RightControl, 0x2000)),
+        _ => None,
+    }
+}
+
+// Windows: WH_KEYBOARD_LL hook on a dedicated thread with its own message loop.
+#[cfg(target_os = "windows")]
+struct PlatformListener {
+    thread_id: u32,
+}
+
+#[cfg(target_os = "windows")]
+impl PlatformListener {
+    fn start(_app: &AppHandle) -> Result<Self, String> {
+        use windows::Win32::Foundation::{LPARAM, LRESULT, WPARAM};
+        use windows::Win32::System::Threading::GetCurrentThreadId;
+        use windows::Win32::UI::WindowsAndMessaging::{
+            CallNextHookEx, GetMessageW, SetWindowsHookExW, UnhookWindowsHookEx, KBDLLHOOKSTRUCT,
+            MSG, WH_KEYBOARD_LL, WM_KEYDOWN, WM_SYSKEYDOWN,
+        };
+
+        unsafe extern "system" fn hook_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+            if code >= 0 {
+                // SAFETY: for WH_KEYBOARD_LL, lparam points to a KBDLLHOOKSTRUCT
+                let info = unsafe { &*(lparam.0 as *const KBDLLHOOKSTRUCT) };
+                let pressed = matches!(wparam.0 as u32, WM_KEYDOWN | WM_SYSKEYDOWN);
+                match windows_modifier_for_vk(info.vkCode) {
+                    Some(key) => process_double_tap_input(Some(key), pressed),
+                    None if pressed => process_double_tap_input(None, true),
+                    None => {}
+                }
+            }
+            // SAFETY: always pass the event on - we observe, never swallow
+            unsafe { CallNextHookEx(None, code, wparam, lparam) }
+        }
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            // SAFETY: installing a global low-level hook owned by this thread
+            let hook = match unsafe { SetWindowsHookExW(WH_KEYBOARD_LL, Some(hook_proc), None, 0) }
+            {
+                Ok(hook) => hook,
+                Err(e) => {
+                    let _ = tx.send(Err(format!("Failed to install keyboard hook: {e}")));
+                    return;
+                }
+            };
+            // SAFETY: trivially safe Win32 call
+            let _ = tx.send(Ok(unsafe { GetCurrentThreadId() }));
+
+            // Low-level hooks are serviced through this thread's message loop
+            let mut msg = MSG::default();
+            // SAFETY: standard message loop; exits on WM_QUIT posted by stop()
+            while unsafe { GetMessageW(&mut msg, None, 0, 0) }.as_bool() {}
+
+            // SAFETY: unhooking the hook installed above
+            if let Err(e) = unsafe { UnhookWindowsHookEx(hook) } {
+                log::warn!("Failed to remove keyboard hook: {e}");
+            }
+        });
+
+        let thread_id = rx
+            .recv()
+            .map_err(|e| format!("Keyboard hook thread failed: {e}"))??;
+        Ok(Self { thread_id })
+    }
+
+    fn stop(self, _app: &AppHandle) {
+        use windows::Win32::Foundation::{LPARAM, WPARAM};
+        use windows::Win32::UI::WindowsAndMessaging::{PostThreadMessageW, WM_QUIT};
+
+        // SAFETY: posting WM_QUIT to the hook thread's message loop
+        if let Err(e) = unsafe { PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0)) }
+        {
+            log::warn!("Failed to stop keyboard hook thread: {e}");
+        }
+    }
+}
+
+/// Maps a Windows virtual-key code to a sided modifier.
+#[cfg(target_os = "windows")]
+fn windows_modifier_for_vk(vk: u32) -> Option<ModifierKey> {
+    match vk {
+        0x5B => Some(ModifierKey::LeftCommand),  // VK_LWIN
+        0x5C => Some(ModifierKey::RightCommand), // VK_RWIN
+        0xA4 => Some(ModifierKey::LeftOption),   // VK_LMENU
+        0xA5 => Some(ModifierKey::RightOption),  // VK_RMENU
+        0xA0 => Some(ModifierKey::LeftShift),    // VK_LSHIFT
+        0xA1 => Some(ModifierKey::RightShift),   // VK_RSHIFT
+        0xA2 => Some(ModifierKey::LeftControl),  // VK_LCONTROL
+        0xA3 => Some(ModifierKey::RightControl), // VK_RCONTROL
+        _ => None,
+    }
+}
+
+// Other platforms: no global key observation available.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+struct PlatformListener;
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+impl PlatformListener {
+    fn start(_app: &AppHandle) -> Result<Self, String> {
+        Err("Double-tap modifier activation is not supported on this platform".into())
+    }
+
+    fn stop(self, _app: &AppHandle) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    const MONITOR: (f64, f64, f64, f64) = (0.0, 0.0, 1440.0, 900.0);
+
+    #[test]
+    fn test_tray_anchor_places_pane_below_icon() {
+        let tray = TrayRect {
+            x: 1000.0,
+            y: 0.0,
+            width: 24.0,
+            height: 24.0,
+        };
+        let (x, y) = compute_tray_anchored_position(tray, (500.0, 72.0), MONITOR, 6.0);
+        assert_eq!(x, 1012.0 - 250.0);
+        assert_eq!(y, 30.0);
+    }
+
+    #[test]
+    fn test_tray_anchor_clamps_to_monitor_edge() {
+        let tray = TrayRect {
+            x: 1420.0,
+            y: 0.0,
+            width: 20.0,
+            height: 24.0,
+        };
+        let (x, _) = compute_tray_anchored_position(tray, (500.0, 72.0), MONITOR, 6.0);
+        assert_eq!(x, 940.0);
+    }
+
+    #[test]
+    fn test_tray_anchor_flips_above_bottom_taskbar() {
+        let tray = TrayRect {
+            x: 1300.0,
+            y: 860.0,
+            width: 24.0,
+            height: 40.0,
+        };
+        let (_, y) = compute_tray_anchored_position(tray, (500.0, 72.0), MONITOR, 6.0);
+        assert_eq!(y, 860.0 - 6.0 - 72.0);
+    }
+
+    fn tap(detector: &mut DoubleTapDetector, at: Instant) -> bool {
+        detector.on_modifier(true, at);
+        detector.on_modifier(false, at + Duration::from_millis(40))
+    }
+
+    #[test]
+    fn test_double_tap_within_interval_activates() {
+        let mut detector = DoubleTapDetector::new(300);
+        let start = Instant::now();
+        assert!(!tap(&mut detector, start));
+        assert!(tap(&mut detector, start + Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn test_taps_too_far_apart_do_not_activate() {
+        let mut detector = DoubleTapDetector::new(300);
+        let start = Instant::now();
+        assert!(!tap(&mut detector, start));
+        assert!(!tap(&mut detector, start + Duration::from_millis(600)));
+    }
+
+    #[test]
+    fn test_chord_cancels_sequence() {
+        let mut detector = DoubleTapDetector::new(300);
+        let start = Instant::now();
+        assert!(!tap(&mut detector, start));
+        detector.on_modifier(true, start + Duration::from_millis(100));
+        detector.on_other_key();
+        assert!(!detector.on_modifier(false, start + Duration::from_millis(150)));
+        assert!(!tap(&mut detector, start + Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn test_long_hold_is_not_a_tap() {
+        let mut detector = DoubleTapDetector::new(300);
+        let start = Instant::now();
+        assert!(!tap(&mut detector, start));
+        detector.on_modifier(true, start + Duration::from_millis(100));
+        assert!(!detector.on_modifier(false, start + Duration::from_millis(500)));
+    }
+}
@@ -2,20 +2,50 @@
 //!
 //! Provides a simple pattern for saving JSON data to disk for crash recovery
 //! or session persistence.
+//!
+//! Also owns the recording recovery format: while recording, captured samples
+//! are periodically appended to a `.psrec` file so a crash mid-dictation loses
+//! at most a few seconds of audio. Audio is encrypted with ChaCha20-Poly1305
+//! under a key kept in the OS keychain. The file is deleted once the session's
+//! transcription succeeds.
+//!
+//! Transcript drafts being edited in the main window are autosaved to the
+//! `drafts` table so a webview crash doesn't lose them.
 
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
 use serde_json::Value;
-use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Manager};
+use tauri_plugin_fs::FsExt;
+use tauri_specta::Event;
+use zeroize::Zeroizing;
 
-use crate::types::{validate_filename, RecoveryError, MAX_RECOVERY_DATA_BYTES};
+use crate::commands::{audit, output, pipeline, transcription};
+use crate::db::Database;
+use crate::types::{
+    validate_filename, AuditEventKind, DraftsAvailable, FormattingOptions, PipelineErrorKind,
+    PipelineStage, PreviousSessionCrashed, RecordingsRepaired, RecoveryDegraded, RecoveryError,
+    RecoveryEvicted, RecoveryExpired, RecoveryPurgeReason, RecoveryPurgeTarget,
+    RecoverySessionInfo, RecoverySessionStatus, RecoveryUsage, RepairedRecording, TranscriptDraft,
+    MAX_DRAFTS, MAX_DRAFT_BYTES, MAX_RECOVERY_DATA_BYTES,
+};
+use crate::utils::audio::{
+    downmix_to_mono, repair_wav, resample_to_16k, write_wav_16k, TARGET_SAMPLE_RATE,
+};
+use crate::utils::metrics;
+use crate::utils::paths;
+use crate::utils::secrets;
+use crate::utils::secure_delete::{secure_delete, secure_delete_dir};
 
 /// Gets the path to the recovery directory, creating it if necessary.
-fn get_recovery_dir(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+pub(crate) fn get_recovery_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir =
+        paths::app_data_dir(app).map_err(|e| format!("Failed to get app data directory: {e}"))?;
 
     let recovery_dir = app_data_dir.join("recovery");
 
@@ -26,10 +56,18 @@ fn get_recovery_dir(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(recovery_dir)
 }
 
+/// Securely deletes the whole recovery directory: recording recovery files
+/// and emergency data. Used by the emergency wipe.
+pub fn wipe_recovery_dir(app: &AppHandle) -> Result<(), String> {
+    let dir = get_recovery_dir(app)?;
+    secure_delete_dir(&dir).map_err(|e| format!("Failed to wipe the recovery directory: {e}"))
+}
+
 /// Saves emergency data to a JSON file for later recovery.
 /// Validates filename and enforces a 10MB size limit.
 #[tauri::command]
 #[specta::specta]
+#[metrics::timed]
 pub async fn save_emergency_data(
     app: AppHandle,
     filename: String,
@@ -87,7 +125,9 @@ pub async fn save_emergency_data(
 /// Returns FileNotFound if the file doesn't exist.
 #[tauri::command]
 #[specta::specta]
+#[metrics::timed]
 pub async fn load_emergency_data(app: AppHandle, filename: String) -> Result<Value, RecoveryError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
     log::info!("Loading emergency data from file: {filename}");
 
     // Validate filename with proper security checks
@@ -123,6 +163,7 @@ pub async fn load_emergency_data(app: AppHandle, filename: String) -> Result<Val
 /// Returns the count of removed files.
 #[tauri::command]
 #[specta::specta]
+#[metrics::timed]
 pub async fn cleanup_old_recovery_files(app: AppHandle) -> Result<u32, RecoveryError> {
     log::info!("Cleaning up old recovery files");
 
@@ -204,3 +245,1629 @@ pub async fn cleanup_old_recovery_files(app: AppHandle) -> Result<u32, RecoveryE
     log::info!("Cleanup complete. Removed {removed_count} old recovery files");
     Ok(removed_count)
 }
+
+// ============================================================================
+// Recording Sessions
+// ============================================================================
+
+/// File extension for recording recovery files
+pub const SESSION_EXTENSION: &str = "psrec";
+
+/// How often retention is enforced after the startup sweep
+const RETENTION_SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Magic bytes at the start of every recording recovery file
+const SESSION_MAGIC: &[u8; 4] = b"PSRC";
+
+/// Current recording recovery format version
+const SESSION_VERSION: u8 = 2;
+
+/// Keychain entry holding the recovery audio encryption key
+pub(crate) const RECOVERY_KEY_NAME: &str = "recovery-audio-key";
+
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// Chunk framing before the ciphertext: ciphertext_len(4) nonce(12)
+const CHUNK_PREFIX_LEN: usize = 4 + NONCE_LEN;
+
+/// Header flag set once the recording stopped cleanly
+const FLAG_FINALIZED: u8 = 0b1;
+
+/// Header flag set once the session hit its size cap and older audio was dropped
+const FLAG_ROLLED: u8 = 0b10;
+
+/// Header layout: magic(4) version(1) flags(1) channels(2) sample_rate(4)
+/// created_at_ms(8) sample_count(8), all little-endian. The header holds no PHI
+/// and stays in the clear so sessions can be listed without the key.
+///
+/// Encrypted chunks follow, one per flush: ciphertext_len(4) nonce(12) ciphertext.
+/// Each chunk seals interleaved i16 LE samples at the native rate, with its
+/// index as associated data so chunks can't be reordered. A crash mid-append
+/// only loses the torn final chunk.
+pub const SESSION_HEADER_LEN: usize = 28;
+
+/// Fixed-size header of a recording recovery file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionHeader {
+    /// True once the recording was stopped and all samples flushed
+    pub finalized: bool,
+    /// True when only a rolling window of the most recent audio is kept
+    pub rolled: bool,
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub created_at_ms: u64,
+    /// Interleaved sample count, only written on finalize
+    pub sample_count: u64,
+}
+
+impl SessionHeader {
+    fn encode(&self) -> [u8; SESSION_HEADER_LEN] {
+        let mut bytes = [0u8; SESSION_HEADER_LEN];
+        bytes[0..4].copy_from_slice(SESSION_MAGIC);
+        bytes[4] = SESSION_VERSION;
+        bytes[5] = (if self.finalized { FLAG_FINALIZED } else { 0 })
+            | (if self.rolled { FLAG_ROLLED } else { 0 });
+        bytes[6..8].copy_from_slice(&self.channels.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.sample_rate.to_le_bytes());
+        bytes[12..20].copy_from_slice(&self.created_at_ms.to_le_bytes());
+        bytes[20..28].copy_from_slice(&self.sample_count.to_le_bytes());
+        bytes
+    }
+
+    /// Parses a header, returning None for a short, foreign or unsupported file.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < SESSION_HEADER_LEN
+            || &bytes[0..4] != SESSION_MAGIC
+            || bytes[4] != SESSION_VERSION
+        {
+            return None;
+        }
+        let header = Self {
+            finalized: bytes[5] & FLAG_FINALIZED != 0,
+            rolled: bytes[5] & FLAG_ROLLED != 0,
+            channels: u16::from_le_bytes(bytes[6..8].try_into().ok()?),
+            sample_rate: u32::from_le_bytes(bytes[8..12].try_into().ok()?),
+            created_at_ms: u64::from_le_bytes(bytes[12..20].try_into().ok()?),
+            sample_count: u64::from_le_bytes(bytes[20..28].try_into().ok()?),
+        };
+        if header.channels == 0 || header.sample_rate == 0 {
+            return None;
+        }
+        Some(header)
+    }
+}
+
+/// Path of the recovery file for a recording session id.
+pub fn session_path(app: &AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    validate_filename(session_id)?;
+    Ok(get_recovery_dir(app)?.join(format!("{session_id}.{SESSION_EXTENSION}")))
+}
+
+/// Encrypts one chunk and frames it for appending.
+fn seal_chunk(cipher: &ChaCha20Poly1305, index: u64, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad: &index.to_le_bytes(),
+            },
+        )
+        .map_err(|e| format!("Failed to encrypt recovery chunk: {e}"))?;
+
+    let mut framed = Vec::with_capacity(CHUNK_PREFIX_LEN + ciphertext.len());
+    framed.extend_from_slice(&(ciphertext.len() as u32).to_le_bytes());
+    framed.extend_from_slice(&nonce);
+    framed.extend_from_slice(&ciphertext);
+    Ok(framed)
+}
+
+/// Splits the next complete chunk at `pos` into (nonce, ciphertext, end).
+fn next_chunk(data: &[u8], pos: usize) -> Option<(&[u8], &[u8], usize)> {
+    let len_bytes = data.get(pos..pos + 4)?;
+    let ciphertext_len = u32::from_le_bytes(len_bytes.try_into().ok()?) as usize;
+    let nonce_start = pos + 4;
+    let ciphertext_start = nonce_start + NONCE_LEN;
+    let end = ciphertext_start.checked_add(ciphertext_len)?;
+    let ciphertext = data.get(ciphertext_start..end)?;
+    Some((&data[nonce_start..ciphertext_start], ciphertext, end))
+}
+
+/// Decrypts one framed chunk written at `index`.
+fn open_chunk(
+    cipher: &ChaCha20Poly1305,
+    index: u64,
+    framed: &[u8],
+) -> Result<Zeroizing<Vec<u8>>, String> {
+    let (nonce, ciphertext, _) =
+        next_chunk(framed, 0).ok_or_else(|| format!("Recovery chunk {index} is truncated"))?;
+    let payload = Payload {
+        msg: ciphertext,
+        aad: &index.to_le_bytes(),
+    };
+    cipher
+        .decrypt(Nonce::from_slice(nonce), payload)
+        .map(Zeroizing::new)
+        .map_err(|_| format!("Recovery chunk {index} failed authentication"))
+}
+
+/// Decrypts every complete chunk in order. A torn final chunk (short, or failing
+/// authentication after at least one good chunk) is dropped; any other
+/// authentication failure means the wrong key or tampering and is an error.
+fn open_chunks(cipher: &ChaCha20Poly1305, data: &[u8]) -> Result<Zeroizing<Vec<u8>>, String> {
+    // Plaintext is never longer than the ciphertext, so this never reallocates
+    let mut plaintext = Zeroizing::new(Vec::with_capacity(data.len()));
+    let mut pos = 0;
+    let mut index: u64 = 0;
+
+    while let Some((nonce, ciphertext, end)) = next_chunk(data, pos) {
+        let payload = Payload {
+            msg: ciphertext,
+            aad: &index.to_le_bytes(),
+        };
+        match cipher.decrypt(Nonce::from_slice(nonce), payload) {
+            Ok(chunk) => plaintext.extend_from_slice(&Zeroizing::new(chunk)),
+            Err(_) if index > 0 && end == data.len() => {
+                log::warn!("Dropping torn final recovery chunk {index}");
+                break;
+            }
+            Err(_) => return Err(format!("Recovery chunk {index} failed authentication")),
+        }
+        pos = end;
+        index += 1;
+    }
+
+    Ok(plaintext)
+}
+
+/// Walks the chunk framing without decrypting. `reader` must be positioned at the
+/// first chunk. Returns the plaintext bytes in complete chunks and whether the
+/// data ends exactly on a chunk boundary.
+fn scan_chunks<R: Read + Seek>(reader: &mut R, data_len: u64) -> (u64, bool) {
+    let mut pos: u64 = 0;
+    let mut plaintext: u64 = 0;
+
+    while pos < data_len {
+        let mut len_bytes = [0u8; 4];
+        if pos + CHUNK_PREFIX_LEN as u64 > data_len || reader.read_exact(&mut len_bytes).is_err() {
+            return (plaintext, false);
+        }
+        let ciphertext_len = u64::from(u32::from_le_bytes(len_bytes));
+        let end = pos + CHUNK_PREFIX_LEN as u64 + ciphertext_len;
+        if ciphertext_len < TAG_LEN as u64 || end > data_len {
+            return (plaintext, false);
+        }
+        let skip = (NONCE_LEN as u64 + ciphertext_len) as i64;
+        if reader.seek(SeekFrom::Current(skip)).is_err() {
+            return (plaintext, false);
+        }
+        plaintext += ciphertext_len - TAG_LEN as u64;
+        pos = end;
+    }
+
+    (plaintext, true)
+}
+
+/// Returns the recovery key if it exists, without creating one.
+fn recovery_cipher() -> Result<Option<ChaCha20Poly1305>, String> {
+    Ok(secrets::get_key(RECOVERY_KEY_NAME)?.map(|key| ChaCha20Poly1305::new(&key.into())))
+}
+
+/// An open recovery file that in-progress recording samples are appended to.
+/// Owned by the recording thread; never touched from the audio callback.
+///
+/// Once the file exceeds the per-session cap it is rewritten to hold only the
+/// most recent audio window (see `needs_roll` / `roll`).
+pub struct RecoverySession {
+    id: String,
+    path: PathBuf,
+    file: std::fs::File,
+    header: SessionHeader,
+    cipher: ChaCha20Poly1305,
+    /// Where each chunk sits in the file; a chunk's index is its position here
+    chunks: Vec<ChunkSpan>,
+    bytes_written: u64,
+    cap_bytes: u64,
+    window_samples: usize,
+}
+
+/// A chunk already written to a recovery file.
+#[derive(Debug, Clone, Copy)]
+struct ChunkSpan {
+    offset: u64,
+    len: usize,
+    /// Interleaved samples it holds
+    samples: u64,
+}
+
+/// Interleaved samples to keep when rolling: the configured window, but never more
+/// than half the cap so a rolled file has room to grow before rolling again.
+fn rolling_window_samples(
+    window_minutes: u32,
+    sample_rate: u32,
+    channels: u16,
+    cap_bytes: u64,
+) -> usize {
+    let window = u64::from(window_minutes) * 60 * u64::from(sample_rate) * u64::from(channels);
+    let half_cap = cap_bytes / 2 / 2;
+    window.min(half_cap) as usize
+}
+
+/// 16-bit little-endian PCM, the plaintext format of every chunk.
+fn encode_pcm(samples: &[f32]) -> Zeroizing<Vec<u8>> {
+    let mut bytes = Zeroizing::new(Vec::with_capacity(samples.len() * 2));
+    for &sample in samples {
+        let as_i16 = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&as_i16.to_le_bytes());
+    }
+    bytes
+}
+
+impl RecoverySession {
+    /// Creates the recovery file for a new recording and writes its header.
+    pub fn open(
+        app: &AppHandle,
+        session_id: &str,
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<Self, String> {
+        // Never fall back to plaintext: without a key, recording continues memory-only
+        let key = secrets::get_or_create_key(RECOVERY_KEY_NAME)?;
+        let prefs = crate::commands::preferences::load_saved_preferences(app);
+        let cap_bytes = u64::from(prefs.recovery_session_cap_mb) * BYTES_PER_MB;
+
+        // Make room for this session before it starts growing
+        let dir_cap_bytes = u64::from(prefs.recovery_dir_cap_mb) * BYTES_PER_MB;
+        enforce_dir_cap(app, dir_cap_bytes.saturating_sub(cap_bytes));
+
+        let path = session_path(app, session_id)?;
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to create recovery file: {e}"))?;
+
+        let header = SessionHeader {
+            finalized: false,
+            rolled: false,
+            channels,
+            sample_rate,
+            created_at_ms: crate::commands::pipeline::now_ms() as u64,
+            sample_count: 0,
+        };
+        file.write_all(&header.encode())
+            .and_then(|_| file.sync_data())
+            .map_err(|e| format!("Failed to write recovery header: {e}"))?;
+
+        log::info!("Opened recording recovery file: {path:?}");
+        Ok(Self {
+            id: session_id.to_string(),
+            path,
+            file,
+            header,
+            cipher: ChaCha20Poly1305::new(&key.into()),
+            chunks: Vec::new(),
+            bytes_written: SESSION_HEADER_LEN as u64,
+            cap_bytes,
+            window_samples: rolling_window_samples(
+                prefs.recovery_rolling_window_minutes,
+                sample_rate,
+                channels,
+                cap_bytes,
+            ),
+        })
+    }
+
+    /// Whether the file has outgrown the per-session cap and should be rolled.
+    pub fn needs_roll(&self) -> bool {
+        self.bytes_written > self.cap_bytes
+    }
+
+    /// Atomically replaces the file with one holding only the most recent
+    /// window of audio (temp file + rename), then keeps appending to the new
+    /// file. The window is the newest whole chunks that fit it (at least the
+    /// last one), re-encrypted one at a time from this file.
+    pub fn roll(&mut self) -> std::io::Result<()> {
+        let mut first = self.chunks.len();
+        let mut kept_samples = 0;
+        for chunk in self.chunks.iter().rev() {
+            if first < self.chunks.len()
+                && kept_samples + chunk.samples > self.window_samples as u64
+            {
+                break;
+            }
+            first -= 1;
+            kept_samples += chunk.samples;
+        }
+
+        let header = SessionHeader {
+            rolled: true,
+            sample_count: kept_samples,
+            ..self.header
+        };
+        let mut kept = Vec::with_capacity(self.chunks.len() - first);
+        let mut bytes_written = SESSION_HEADER_LEN as u64;
+
+        // Written and closed before the rename, which Windows requires
+        let temp_path = self.path.with_extension("tmp");
+        {
+            let mut file = std::fs::File::create(&temp_path)?;
+            file.write_all(&header.encode())?;
+            for (index, span) in self.chunks.iter().enumerate().skip(first) {
+                let mut framed = vec![0u8; span.len];
+                self.file.seek(SeekFrom::Start(span.offset))?;
+                self.file.read_exact(&mut framed)?;
+                let plaintext = open_chunk(&self.cipher, index as u64, &framed)
+                    .map_err(std::io::Error::other)?;
+                let chunk = seal_chunk(&self.cipher, kept.len() as u64, &plaintext)
+                    .map_err(std::io::Error::other)?;
+                file.write_all(&chunk)?;
+                kept.push(ChunkSpan {
+                    offset: bytes_written,
+                    len: chunk.len(),
+                    samples: span.samples,
+                });
+                bytes_written += chunk.len() as u64;
+            }
+            file.sync_all()?;
+        }
+        std::fs::rename(&temp_path, &self.path)?;
+
+        self.file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&self.path)?;
+        self.file.seek(SeekFrom::End(0))?;
+        self.header = header;
+        self.chunks = kept;
+        self.bytes_written = bytes_written;
+
+        log::info!(
+            "Rolled recovery file {} to the most recent {kept_samples} samples",
+            self.id
+        );
+        Ok(())
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Appends interleaved samples as one encrypted chunk of 16-bit PCM and
+    /// fsyncs so they survive a crash.
+    pub fn append(&mut self, samples: &[f32]) -> std::io::Result<()> {
+        let index = self.chunks.len() as u64;
+        let chunk =
+            seal_chunk(&self.cipher, index, &encode_pcm(samples)).map_err(std::io::Error::other)?;
+        self.file.write_all(&chunk)?;
+        self.file.sync_data()?;
+        self.chunks.push(ChunkSpan {
+            offset: self.bytes_written,
+            len: chunk.len(),
+            samples: samples.len() as u64,
+        });
+        self.bytes_written += chunk.len() as u64;
+        self.header.sample_count += samples.len() as u64;
+        Ok(())
+    }
+
+    /// Marks the file complete by rewriting its header with the final sample count.
+    pub fn finalize(mut self) -> std::io::Result<()> {
+        self.header.finalized = true;
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.write_all(&self.header.encode())?;
+        self.file.sync_all()?;
+        log::info!(
+            "Finalized recording recovery file {} ({} samples)",
+            self.id,
+            self.header.sample_count
+        );
+        Ok(())
+    }
+}
+
+/// Deletes a recording session's recovery file that holds no audio.
+pub fn discard_session(app: &AppHandle, session_id: &str) -> Result<(), String> {
+    let path = session_path(app, session_id)?;
+    secure_delete(&path).map_err(|e| format!("Failed to delete recovery file: {e}"))?;
+    log::info!("Discarded recording recovery file: {path:?}");
+    Ok(())
+}
+
+/// Securely deletes a session's recovery file and records the purge in the audit log.
+/// Returns false when there was no file to purge.
+pub fn purge_session(
+    app: &AppHandle,
+    session_id: &str,
+    reason: RecoveryPurgeReason,
+) -> Result<bool, String> {
+    let path = session_path(app, session_id)?;
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    secure_delete(&path).map_err(|e| format!("Failed to delete recovery file: {e}"))?;
+    audit::record(
+        app,
+        AuditEventKind::RecoveryPurged,
+        Some(session_id),
+        reason.as_str(),
+    );
+    log::info!("Purged recovery session {session_id} ({})", reason.as_str());
+    Ok(true)
+}
+
+/// Purges the recovery file belonging to a recorded WAV once it was transcribed.
+/// Recordings are named after their session id, so other audio files are a no-op.
+pub fn complete_session_for_audio(app: &AppHandle, audio_path: &Path) {
+    let Some(session_id) = audio_path.file_stem().and_then(|stem| stem.to_str()) else {
+        return;
+    };
+    if let Err(e) = purge_session(app, session_id, RecoveryPurgeReason::PostSuccess) {
+        log::warn!("Failed to purge recovery file for {session_id}: {e}");
+    }
+}
+
+/// Number of recording sessions in the recovery directory.
+pub fn session_count(app: &AppHandle) -> Result<u32, String> {
+    Ok(session_ids(&get_recovery_dir(app)?)?.len() as u32)
+}
+
+/// Ids of all recording sessions in the recovery directory.
+fn session_ids(recovery_dir: &Path) -> Result<Vec<String>, String> {
+    let entries = std::fs::read_dir(recovery_dir)
+        .map_err(|e| format!("Failed to read recovery directory: {e}"))?;
+    Ok(entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == SESSION_EXTENSION))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect())
+}
+
+/// Deletes one recording recovery session, or all of them.
+/// The recording currently in progress is never purged. Returns the count removed.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn purge_recovery(
+    app: AppHandle,
+    target: RecoveryPurgeTarget,
+) -> Result<u32, RecoveryError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let ids = match target {
+        RecoveryPurgeTarget::Session { id } => vec![id],
+        RecoveryPurgeTarget::All => {
+            let recovery_dir =
+                get_recovery_dir(&app).map_err(|e| RecoveryError::IoError { message: e })?;
+            session_ids(&recovery_dir).map_err(|e| RecoveryError::IoError { message: e })?
+        }
+    };
+    let active_session = crate::commands::audio::active_session_id();
+
+    let mut purged = 0;
+    for id in ids {
+        if active_session.as_deref() == Some(id.as_str()) {
+            log::info!("Skipping purge of active recording session {id}");
+            continue;
+        }
+        validate_filename(&id).map_err(|e| RecoveryError::ValidationError { message: e })?;
+        if purge_session(&app, &id, RecoveryPurgeReason::UserRequested)
+            .map_err(|e| RecoveryError::IoError { message: e })?
+        {
+            purged += 1;
+        }
+    }
+
+    log::info!("Purged {purged} recovery sessions");
+    Ok(purged)
+}
+
+/// Whether `path` was last modified more than `max_age` ago. False when its
+/// modification time can't be read.
+fn is_older_than(path: &Path, max_age: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age > max_age)
+}
+
+/// Purges sessions older than `max_age` whatever their state, so damaged files
+/// that can never be recovered don't linger either.
+fn purge_expired_sessions(app: &AppHandle, max_age: Duration) -> Result<u32, String> {
+    let recovery_dir = get_recovery_dir(app)?;
+    let mut purged = 0;
+
+    for id in session_ids(&recovery_dir)? {
+        let path = recovery_dir.join(format!("{id}.{SESSION_EXTENSION}"));
+        if !is_older_than(&path, max_age) {
+            continue;
+        }
+        match purge_session(app, &id, RecoveryPurgeReason::Expired) {
+            Ok(true) => purged += 1,
+            Ok(false) => {}
+            Err(e) => log::warn!("Failed to purge expired recovery session {id}: {e}"),
+        }
+    }
+
+    Ok(purged)
+}
+
+/// Size and age of every recording recovery file, oldest first.
+fn session_files(recovery_dir: &Path) -> Result<Vec<(String, u64, SystemTime)>, String> {
+    let mut files: Vec<(String, u64, SystemTime)> = session_ids(recovery_dir)?
+        .into_iter()
+        .filter_map(|id| {
+            let path = recovery_dir.join(format!("{id}.{SESSION_EXTENSION}"));
+            let metadata = std::fs::metadata(path).ok()?;
+            Some((id, metadata.len(), metadata.modified().ok()?))
+        })
+        .collect();
+    files.sort_by_key(|(_, _, modified)| *modified);
+    Ok(files)
+}
+
+/// Evicts the oldest sessions (never the active one) until the directory's session
+/// files fit in `budget_bytes`, warning the frontend about anything evicted.
+fn enforce_dir_cap(app: &AppHandle, budget_bytes: u64) {
+    let files = match get_recovery_dir(app).and_then(|dir| session_files(&dir)) {
+        Ok(files) => files,
+        Err(e) => {
+            log::warn!("Failed to check recovery directory size: {e}");
+            return;
+        }
+    };
+
+    let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+    let active_session = crate::commands::audio::active_session_id();
+    let mut evicted = Vec::new();
+
+    for (id, size, _) in files {
+        if total <= budget_bytes {
+            break;
+        }
+        if active_session.as_deref() == Some(id.as_str()) {
+            continue;
+        }
+        match purge_session(app, &id, RecoveryPurgeReason::Evicted) {
+            Ok(_) => {
+                total = total.saturating_sub(size);
+                evicted.push(id);
+            }
+            Err(e) => log::warn!("Failed to evict recovery session {id}: {e}"),
+        }
+    }
+
+    if evicted.is_empty() {
+        return;
+    }
+    log::warn!(
+        "Evicted {} recovery sessions to stay under {budget_bytes} bytes",
+        evicted.len()
+    );
+    let payload = RecoveryEvicted {
+        session_ids: evicted,
+        budget_bytes: budget_bytes as f64,
+    };
+    if let Err(e) = payload.emit(app) {
+        log::warn!("Failed to emit recovery eviction event: {e}");
+    }
+}
+
+/// Reports how much disk space recovery data uses, for the storage settings screen.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn get_recovery_usage(app: AppHandle) -> Result<RecoveryUsage, RecoveryError> {
+    let recovery_dir = get_recovery_dir(&app).map_err(|e| RecoveryError::IoError { message: e })?;
+    let sessions =
+        session_files(&recovery_dir).map_err(|e| RecoveryError::IoError { message: e })?;
+    let session_bytes: u64 = sessions.iter().map(|(_, size, _)| size).sum();
+
+    let total_bytes: u64 = std::fs::read_dir(&recovery_dir)
+        .map_err(|e| RecoveryError::IoError {
+            message: e.to_string(),
+        })?
+        .filter_map(|entry| entry.ok()?.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    let prefs = crate::commands::preferences::load_saved_preferences(&app);
+    Ok(RecoveryUsage {
+        total_bytes: total_bytes as f64,
+        session_bytes: session_bytes as f64,
+        session_count: sessions.len() as u32,
+        session_cap_bytes: (u64::from(prefs.recovery_session_cap_mb) * BYTES_PER_MB) as f64,
+        dir_cap_bytes: (u64::from(prefs.recovery_dir_cap_mb) * BYTES_PER_MB) as f64,
+    })
+}
+
+/// Securely deletes leftover audio artifacts older than `max_age`: roll temp files
+/// in the recovery directory and stranded `rec_*.wav` recordings in the cache.
+fn purge_expired_files(app: &AppHandle, max_age: Duration) -> Result<u32, String> {
+    let recovery_dir = get_recovery_dir(app)?;
+    let cache_dir = recordings_cache_dir(app)?;
+
+    let candidates = [recovery_dir, cache_dir]
+        .into_iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            name.starts_with("rec_") && (name.ends_with(".tmp") || name.ends_with(".wav"))
+        });
+
+    let active_session = crate::commands::audio::active_session_id();
+    let mut purged = 0;
+    for path in candidates {
+        let in_use = active_session
+            .as_deref()
+            .is_some_and(|id| path.file_stem().and_then(|s| s.to_str()) == Some(id));
+        if in_use || !is_older_than(&path, max_age) {
+            continue;
+        }
+        match secure_delete(&path) {
+            Ok(()) => purged += 1,
+            Err(e) => log::warn!("Failed to delete expired recovery file {path:?}: {e}"),
+        }
+    }
+    Ok(purged)
+}
+
+/// Deletes drafts not updated within `max_age`. The database runs with
+/// `secure_delete` on, so the deleted content is overwritten.
+fn purge_expired_drafts(app: &AppHandle, max_age: Duration) -> Result<u32, String> {
+    let cutoff_ms = crate::commands::pipeline::now_ms() - max_age.as_millis() as f64;
+    let removed = app.state::<Database>().with_conn(|conn| {
+        conn.execute(
+            "DELETE FROM drafts WHERE updated_at < ?1",
+            [cutoff_ms as i64],
+        )
+    })?;
+    Ok(removed as u32)
+}
+
+/// When the last retention sweep ran (ms since epoch) and what it removed
+static LAST_SWEEP: Mutex<Option<(f64, RecoveryExpired)>> = Mutex::new(None);
+
+/// The last retention sweep since the app started, if one ran.
+pub fn last_sweep() -> Option<(f64, RecoveryExpired)> {
+    LAST_SWEEP.lock().ok().and_then(|last| last.clone())
+}
+
+/// Enforces `recovery_retention_hours` on recovery sessions, drafts and stranded
+/// recordings, recording the counts in the audit log and telling the frontend.
+/// Also trims audit entries past `audit_retention_days` and purges transcripts
+/// past `trash_retention_days` in the trash.
+pub fn retention_sweep(app: &AppHandle) {
+    // Audit entries and trashed transcripts have their own retention periods
+    audit::trim_expired(app);
+    if let Err(e) = crate::commands::transcripts::purge_expired_trash(app) {
+        log::warn!("Retention sweep of the trash failed: {e}");
+    }
+
+    let retention_hours =
+        crate::commands::preferences::load_saved_preferences(app).recovery_retention_hours;
+    let max_age = Duration::from_secs(u64::from(retention_hours) * 60 * 60);
+
+    let count = |what: &str, result: Result<u32, String>| {
+        result.unwrap_or_else(|e| {
+            log::warn!("Retention sweep of {what} failed: {e}");
+            0
+        })
+    };
+    let payload = RecoveryExpired {
+        sessions_removed: count("recovery sessions", purge_expired_sessions(app, max_age)),
+        drafts_removed: count("drafts", purge_expired_drafts(app, max_age)),
+        files_removed: count("stranded files", purge_expired_files(app, max_age)),
+    };
+    if let Ok(mut last) = LAST_SWEEP.lock() {
+        *last = Some((pipeline::now_ms(), payload.clone()));
+    }
+
+    let removed = payload.sessions_removed + payload.drafts_removed + payload.files_removed;
+    if removed == 0 {
+        return;
+    }
+
+    log::info!("Retention sweep removed {removed} items older than {retention_hours} hours");
+    audit::record(
+        app,
+        AuditEventKind::RetentionSweep,
+        None,
+        &format!(
+            "retention_hours={retention_hours} sessions={} drafts={} files={}",
+            payload.sessions_removed, payload.drafts_removed, payload.files_removed
+        ),
+    );
+    if let Err(e) = crate::commands::navigation::emit_to_main_when_ready(app, payload) {
+        log::warn!("Failed to announce expired recovery data: {e}");
+    }
+}
+
+/// Runs `retention_sweep` every `RETENTION_SWEEP_INTERVAL` for the life of the app,
+/// so data expires on schedule even when the app is never restarted.
+pub fn spawn_retention_sweep(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(RETENTION_SWEEP_INTERVAL).await;
+            let sweep_app = app.clone();
+            if let Err(e) =
+                tauri::async_runtime::spawn_blocking(move || retention_sweep(&sweep_app)).await
+            {
+                log::error!("Retention sweep panicked: {e}");
+            }
+        }
+    });
+}
+
+/// Startup maintenance of the recovery directory. Called once from setup().
+pub fn startup_sweep(app: &AppHandle) {
+    retention_sweep(app);
+
+    let prefs = crate::commands::preferences::load_saved_preferences(app);
+    enforce_dir_cap(app, u64::from(prefs.recovery_dir_cap_mb) * BYTES_PER_MB);
+
+    repair_stranded_recordings(app);
+    transcription::sweep_partial_downloads(app);
+}
+
+/// Warns the frontend that recovery flushing stopped and the recording is memory-only.
+pub fn emit_degraded(app: &AppHandle, session_id: &str, message: String) {
+    log::warn!("Recording {session_id} continues without crash recovery: {message}");
+    let payload = RecoveryDegraded {
+        session_id: session_id.to_string(),
+        message,
+    };
+    if let Err(e) = payload.emit(app) {
+        log::warn!("Failed to emit recovery degraded event: {e}");
+    }
+}
+
+/// Classifies a recovery file from its header and chunk scan (see `scan_chunks`).
+/// Returns the status and the number of whole interleaved samples on disk.
+fn classify_session(
+    header: Option<&SessionHeader>,
+    (plaintext_bytes, clean_end): (u64, bool),
+    key_available: bool,
+) -> (RecoverySessionStatus, u64) {
+    let Some(header) = header else {
+        return (RecoverySessionStatus::Damaged, 0);
+    };
+    let samples_on_disk = plaintext_bytes / 2;
+    if !key_available {
+        return (RecoverySessionStatus::Unrecoverable, samples_on_disk);
+    }
+    let complete = header.finalized && clean_end && samples_on_disk == header.sample_count;
+    let status = if complete {
+        RecoverySessionStatus::Complete
+    } else {
+        RecoverySessionStatus::Truncated
+    };
+    (status, samples_on_disk)
+}
+
+fn system_time_ms(time: std::io::Result<SystemTime>) -> Option<f64> {
+    time.ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_millis() as f64)
+}
+
+/// Describes a single recovery file. Unreadable headers are reported as damaged.
+fn describe_session(path: &Path, key_available: bool) -> Option<RecoverySessionInfo> {
+    let id = path.file_stem()?.to_str()?.to_string();
+    let metadata = std::fs::metadata(path)
+        .inspect_err(|e| log::warn!("Failed to stat recovery file {path:?}: {e}"))
+        .ok()?;
+    let modified_at_ms = system_time_ms(metadata.modified()).unwrap_or_default();
+
+    let data_len = metadata.len().saturating_sub(SESSION_HEADER_LEN as u64);
+    let mut header_bytes = [0u8; SESSION_HEADER_LEN];
+    let (header, scan) = match std::fs::File::open(path) {
+        Ok(mut file) => {
+            let header = file
+                .read_exact(&mut header_bytes)
+                .ok()
+                .and_then(|_| SessionHeader::decode(&header_bytes));
+            let scan = match header {
+                Some(_) => scan_chunks(&mut file, data_len),
+                None => (0, false),
+            };
+            (header, scan)
+        }
+        Err(_) => (None, (0, false)),
+    };
+
+    let (status, samples_on_disk) = classify_session(header.as_ref(), scan, key_available);
+    let (created_at_ms, duration_secs) = match header {
+        Some(h) => (
+            h.created_at_ms as f64,
+            samples_on_disk as f64 / f64::from(h.channels) / f64::from(h.sample_rate),
+        ),
+        None => (
+            system_time_ms(metadata.created()).unwrap_or(modified_at_ms),
+            0.0,
+        ),
+    };
+
+    Some(RecoverySessionInfo {
+        id,
+        created_at_ms,
+        modified_at_ms,
+        duration_secs,
+        size_bytes: metadata.len() as f64,
+        status,
+        rolled: header.is_some_and(|h| h.rolled),
+    })
+}
+
+/// Lists recoverable recordings in the recovery directory, newest first.
+/// The recording currently in progress is excluded.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn list_recovery_sessions(
+    app: AppHandle,
+) -> Result<Vec<RecoverySessionInfo>, RecoveryError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    collect_recovery_sessions(&app)
+}
+
+fn collect_recovery_sessions(app: &AppHandle) -> Result<Vec<RecoverySessionInfo>, RecoveryError> {
+    let recovery_dir = get_recovery_dir(app).map_err(|e| RecoveryError::IoError { message: e })?;
+    let active_session = crate::commands::audio::active_session_id();
+    let key_available = matches!(recovery_cipher(), Ok(Some(_)));
+
+    let entries = std::fs::read_dir(&recovery_dir).map_err(|e| {
+        log::error!("Failed to read recovery directory: {e}");
+        RecoveryError::IoError {
+            message: e.to_string(),
+        }
+    })?;
+
+    let mut sessions: Vec<RecoverySessionInfo> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == SESSION_EXTENSION))
+        .filter_map(|path| describe_session(&path, key_available))
+        .filter(|session| active_session.as_deref() != Some(session.id.as_str()))
+        .collect();
+
+    sessions.sort_by(|a, b| b.created_at_ms.total_cmp(&a.created_at_ms));
+
+    log::info!("Found {} recoverable recording sessions", sessions.len());
+    Ok(sessions)
+}
+
+/// Decrypts a recovery file's audio and returns its header and every whole frame
+/// as f32 samples. A torn final chunk is dropped.
+fn read_session_samples(
+    path: &Path,
+    cipher: &ChaCha20Poly1305,
+) -> Result<(SessionHeader, Zeroizing<Vec<f32>>), RecoveryError> {
+    let bytes = std::fs::read(path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            RecoveryError::FileNotFound
+        } else {
+            RecoveryError::IoError {
+                message: e.to_string(),
+            }
+        }
+    })?;
+
+    let header = SessionHeader::decode(&bytes).ok_or_else(|| RecoveryError::ParseError {
+        message: "Recovery file header is damaged".to_string(),
+    })?;
+
+    let data = open_chunks(cipher, &bytes[SESSION_HEADER_LEN..])
+        .map_err(|message| RecoveryError::ParseError { message })?;
+    let frame_bytes = usize::from(header.channels) * 2;
+    let whole = data.len() - data.len() % frame_bytes;
+    let mut samples = Zeroizing::new(Vec::with_capacity(whole / 2));
+    samples.extend(
+        data[..whole]
+            .chunks_exact(2)
+            .map(|pair| f32::from(i16::from_le_bytes([pair[0], pair[1]])) / i16::MAX as f32),
+    );
+
+    Ok((header, samples))
+}
+
+/// The recovery cipher, or `KeyUnavailable`. Without the keychain entry the audio
+/// can't be decrypted; the UI offers a purge instead.
+fn require_recovery_cipher() -> Result<ChaCha20Poly1305, RecoveryError> {
+    match recovery_cipher() {
+        Ok(Some(cipher)) => Ok(cipher),
+        Ok(None) => Err(RecoveryError::KeyUnavailable),
+        Err(e) => {
+            log::error!("Failed to read recovery key: {e}");
+            Err(RecoveryError::KeyUnavailable)
+        }
+    }
+}
+
+/// Converts a recovery file into a temporary 16kHz mono WAV (same pipeline as `stop_recording`).
+fn write_recovered_wav(
+    recovery_path: &Path,
+    wav_path: &Path,
+    cipher: &ChaCha20Poly1305,
+) -> Result<(), RecoveryError> {
+    let (header, samples) = read_session_samples(recovery_path, cipher)?;
+    if samples.is_empty() {
+        return Err(RecoveryError::ParseError {
+            message: "Recovery file contains no audio".to_string(),
+        });
+    }
+
+    let mono = downmix_to_mono(&samples, header.channels);
+    drop(samples);
+    let resampled = if header.sample_rate == TARGET_SAMPLE_RATE {
+        mono
+    } else {
+        resample_to_16k(&mono, header.sample_rate)
+            .map_err(|message| RecoveryError::IoError { message })?
+    };
+    write_wav_16k(wav_path, &resampled).map_err(|message| RecoveryError::IoError { message })
+}
+
+/// Transcribes the audio in a recording recovery file.
+/// Publishes the usual `pipeline-status` events with `recovered` set. On success the
+/// temporary WAV and the recovery file are securely deleted; on failure the recovery
+/// file is kept so the user can retry. `auto_copy_to_clipboard` and
+/// `formatting` work as in `transcribe_and_delete`'s delivery options.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn recover_session(
+    app: AppHandle,
+    id: String,
+    language: Option<String>,
+    auto_copy_to_clipboard: Option<bool>,
+    formatting: Option<FormattingOptions>,
+) -> Result<String, RecoveryError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    log::info!("Recovering recording session: {id}");
+
+    let recovery_path =
+        session_path(&app, &id).map_err(|message| RecoveryError::ValidationError { message })?;
+    if !recovery_path.exists() {
+        return Err(RecoveryError::FileNotFound);
+    }
+
+    let cipher = require_recovery_cipher()?;
+
+    let wav_path = recordings_cache_dir(&app)
+        .map_err(|message| RecoveryError::IoError { message })?
+        .join(format!("{id}{RECOVERED_WAV_SUFFIX}"));
+
+    pipeline::begin_recovered_job(&app, PipelineStage::Resampling);
+
+    let conversion = {
+        let wav_path = wav_path.clone();
+        tokio::task::spawn_blocking(move || write_recovered_wav(&recovery_path, &wav_path, &cipher))
+            .await
+            .map_err(|e| RecoveryError::IoError {
+                message: format!("Recovery conversion task panicked: {e}"),
+            })
+            .and_then(|result| result)
+    };
+    if let Err(e) = conversion {
+        log::error!("Failed to convert recovery file {id}: {e}");
+        pipeline::publish(
+            &app,
+            PipelineStage::Error {
+                kind: PipelineErrorKind::Resampling,
+            },
+        );
+        if let Err(e) = secure_delete(&wav_path) {
+            log::warn!("Failed to delete partial recovered WAV: {e}");
+        }
+        return Err(e);
+    }
+
+    let result = transcription::transcribe_file(
+        &app,
+        wav_path.clone(),
+        language,
+        transcription::DEFAULT_MODEL_ID,
+    )
+    .await;
+
+    // HIPAA: the temporary WAV never outlives the attempt
+    if let Err(e) = secure_delete(&wav_path) {
+        log::warn!("Failed to delete recovered WAV {wav_path:?}: {e}");
+    }
+
+    match result {
+        Ok(transcribed) => {
+            let formatted = output::format_for_output(&app, &transcribed.segments, formatting);
+            crate::commands::clipboard::auto_copy(&app, &formatted, auto_copy_to_clipboard);
+            let text = transcribed.text;
+            if let Err(e) = purge_session(&app, &id, RecoveryPurgeReason::PostSuccess) {
+                log::warn!("Failed to delete recovery file after recovery: {e}");
+            }
+            log::info!("Recovered session {id} ({} chars)", text.chars().count());
+            Ok(text)
+        }
+        Err(e) => {
+            log::error!("Recovery transcription failed for {id}, keeping recovery file: {e}");
+            Err(RecoveryError::TranscriptionFailed {
+                message: e.to_string(),
+            })
+        }
+    }
+}
+
+/// Exports a recovery session's audio as a standard 16kHz mono WAV, e.g. for a human
+/// transcriptionist when it won't transcribe. `destination_path` must come from the
+/// save dialog (which grants fs scope) and `acknowledge_phi_export` must be true,
+/// i.e. the user accepted the PHI consent dialog. The export is audited with its
+/// destination; the recovery file itself is kept.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn export_recovery_audio(
+    app: AppHandle,
+    id: String,
+    destination_path: String,
+    acknowledge_phi_export: bool,
+) -> Result<(), RecoveryError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    if !acknowledge_phi_export {
+        return Err(RecoveryError::ConsentRequired);
+    }
+
+    let recovery_path =
+        session_path(&app, &id).map_err(|message| RecoveryError::ValidationError { message })?;
+    if !recovery_path.exists() {
+        return Err(RecoveryError::FileNotFound);
+    }
+
+    let destination = PathBuf::from(&destination_path);
+    let is_wav = destination
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"));
+    if !destination.is_absolute() || !is_wav {
+        return Err(RecoveryError::ValidationError {
+            message: "Destination must be an absolute path to a .wav file".to_string(),
+        });
+    }
+    if !app.fs_scope().is_allowed(&destination) {
+        return Err(RecoveryError::ValidationError {
+            message: "Destination was not chosen through the save dialog".to_string(),
+        });
+    }
+
+    let cipher = require_recovery_cipher()?;
+    let export_path = destination.clone();
+    tokio::task::spawn_blocking(move || {
+        write_recovered_wav(&recovery_path, &export_path, &cipher).inspect_err(|_| {
+            // Don't leave a half-written export behind
+            let _ = std::fs::remove_file(&export_path);
+        })
+    })
+    .await
+    .map_err(|e| RecoveryError::IoError {
+        message: format!("Recovery export task panicked: {e}"),
+    })??;
+
+    audit::record(
+        &app,
+        AuditEventKind::RecoveryExported,
+        Some(&id),
+        &format!("destination={}", destination.display()),
+    );
+    log::info!("Exported recovery session {id}");
+    Ok(())
+}
+
+// ============================================================================
+// Stranded Recordings
+// ============================================================================
+
+/// Suffix of the temporary WAV `recover_session` transcribes from
+const RECOVERED_WAV_SUFFIX: &str = "_recovered.wav";
+
+/// Directory recordings are written to as WAV before transcription.
+fn recordings_cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let cache_dir =
+        paths::app_cache_dir(app).map_err(|e| format!("Failed to get cache dir: {e}"))?;
+    std::fs::create_dir_all(&cache_dir).map_err(|e| format!("Failed to create cache dir: {e}"))?;
+    Ok(cache_dir)
+}
+
+/// Repairs the cached WAV of a recording session and reports its path so it
+/// can be passed to `transcribe_and_delete`.
+fn repair_cached_wav(
+    cache_dir: &Path,
+    session_id: &str,
+) -> Result<RepairedRecording, RecoveryError> {
+    validate_filename(session_id).map_err(|e| RecoveryError::ValidationError { message: e })?;
+    let wav_path = cache_dir.join(format!("{session_id}.wav"));
+    if !wav_path.exists() {
+        return Err(RecoveryError::FileNotFound);
+    }
+    let duration_secs =
+        repair_wav(&wav_path).map_err(|e| RecoveryError::ParseError { message: e })?;
+    Ok(RepairedRecording {
+        session_id: session_id.to_string(),
+        file_path: wav_path.to_string_lossy().into_owned(),
+        duration_secs,
+    })
+}
+
+/// Repairs a recording's WAV left unfinalized by a crash and returns its
+/// recovered duration and path.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn repair_recording(
+    app: AppHandle,
+    session_id: String,
+) -> Result<RepairedRecording, RecoveryError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let cache_dir =
+        recordings_cache_dir(&app).map_err(|message| RecoveryError::IoError { message })?;
+    tokio::task::spawn_blocking(move || repair_cached_wav(&cache_dir, &session_id))
+        .await
+        .map_err(|e| RecoveryError::IoError {
+            message: format!("WAV repair task panicked: {e}"),
+        })?
+}
+
+/// Repairs `rec_*.wav` files stranded in the cache by a crash and announces the
+/// ones that parse. Leftover recovery temp files and unrepairable WAVs are
+/// securely deleted; the encrypted recovery file, if any, remains the fallback.
+fn repair_stranded_recordings(app: &AppHandle) {
+    let cache_dir = match recordings_cache_dir(app) {
+        Ok(dir) => dir,
+        Err(e) => {
+            log::warn!("Stranded recording sweep skipped: {e}");
+            return;
+        }
+    };
+    let Ok(entries) = std::fs::read_dir(&cache_dir) else {
+        return;
+    };
+
+    let mut recordings = Vec::new();
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !name.starts_with("rec_") || !name.ends_with(".wav") {
+            continue;
+        }
+
+        if name.ends_with(RECOVERED_WAV_SUFFIX) {
+            if let Err(e) = secure_delete(&path) {
+                log::warn!("Failed to delete leftover recovered WAV {path:?}: {e}");
+            }
+            continue;
+        }
+
+        let session_id = name.trim_end_matches(".wav");
+        match repair_cached_wav(&cache_dir, session_id) {
+            Ok(recording) => recordings.push(recording),
+            Err(e) => {
+                log::warn!("Stranded recording {session_id} is unrepairable: {e}");
+                if let Err(e) = secure_delete(&path) {
+                    log::warn!("Failed to delete unrepairable WAV {path:?}: {e}");
+                }
+            }
+        }
+    }
+
+    if recordings.is_empty() {
+        return;
+    }
+    log::info!("Repaired {} stranded recordings", recordings.len());
+    if let Err(e) =
+        crate::commands::navigation::emit_to_main_when_ready(app, RecordingsRepaired { recordings })
+    {
+        log::warn!("Failed to announce repaired recordings: {e}");
+    }
+}
+
+// ============================================================================
+// Crash Detection
+// ============================================================================
+
+/// Sentinel present while a recording or transcription is in flight
+const SESSION_ACTIVE_FILENAME: &str = "session_active";
+
+/// Mirrors whether the sentinel exists, so repeated transitions don't touch disk.
+static SESSION_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+fn session_active_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(get_recovery_dir(app)?.join(SESSION_ACTIVE_FILENAME))
+}
+
+/// Writes the crash sentinel when recording or transcription begins.
+pub fn mark_session_active(app: &AppHandle) {
+    if SESSION_ACTIVE.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let result = session_active_path(app).and_then(|path| {
+        std::fs::write(&path, pipeline::now_ms().to_string())
+            .map_err(|e| format!("Failed to write session sentinel: {e}"))
+    });
+    if let Err(e) = result {
+        log::warn!("{e}");
+    }
+}
+
+/// Removes the crash sentinel on clean completion or graceful shutdown.
+pub fn clear_session_active(app: &AppHandle) {
+    if !SESSION_ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    let result = session_active_path(app).and_then(|path| match std::fs::remove_file(&path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(format!("Failed to remove session sentinel: {e}"))
+        }
+        _ => Ok(()),
+    });
+    if let Err(e) = result {
+        log::warn!("{e}");
+    }
+}
+
+/// Checks for a sentinel left by a crashed run and, if found, emits
+/// `previous-session-crashed` with whatever can be recovered. Called from setup();
+/// the event is queued until the main window is ready.
+pub fn check_previous_crash(app: &AppHandle) {
+    let path = match session_active_path(app) {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("Failed to check for previous crash: {e}");
+            return;
+        }
+    };
+    if !path.exists() {
+        return;
+    }
+
+    log::warn!("Previous session did not shut down cleanly");
+    if let Err(e) = std::fs::remove_file(&path) {
+        log::warn!("Failed to remove stale session sentinel: {e}");
+    }
+
+    let recovery_sessions = collect_recovery_sessions(app).unwrap_or_else(|e| {
+        log::warn!("Failed to list recovery sessions after crash: {e}");
+        Vec::new()
+    });
+    let drafts = query_drafts(&app.state::<Database>()).unwrap_or_else(|e| {
+        log::warn!("Failed to load drafts after crash: {e}");
+        Vec::new()
+    });
+
+    if let Err(e) = crate::commands::navigation::emit_to_main_when_ready(
+        app,
+        PreviousSessionCrashed {
+            recovery_sessions,
+            drafts,
+        },
+    ) {
+        log::warn!("Failed to report previous crash: {e}");
+    }
+}
+
+// ============================================================================
+// Drafts
+// ============================================================================
+
+fn database_error(message: String) -> RecoveryError {
+    RecoveryError::DatabaseError { message }
+}
+
+/// Saves (or replaces) a transcript draft. The frontend calls this debounced
+/// every few seconds while editing. Saving beyond `MAX_DRAFTS` evicts the
+/// least recently updated drafts.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn save_draft(
+    app: AppHandle,
+    draft_id: String,
+    content: String,
+    cursor_meta: Option<Value>,
+) -> Result<(), RecoveryError> {
+    validate_filename(&draft_id).map_err(|e| RecoveryError::ValidationError { message: e })?;
+    if content.len() > MAX_DRAFT_BYTES as usize {
+        return Err(RecoveryError::DataTooLarge {
+            max_bytes: MAX_DRAFT_BYTES,
+        });
+    }
+
+    let cursor_meta = cursor_meta
+        .map(|meta| serde_json::to_string(&meta))
+        .transpose()
+        .map_err(|e| RecoveryError::ParseError {
+            message: e.to_string(),
+        })?;
+    let now = pipeline::now_ms() as i64;
+
+    app.state::<Database>()
+        .with_conn(|conn| {
+            let tx = conn.transaction()?;
+            tx.execute(
+                "INSERT INTO drafts (draft_id, content, cursor_meta, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?4)
+                 ON CONFLICT(draft_id) DO UPDATE SET
+                    content = excluded.content,
+                    cursor_meta = excluded.cursor_meta,
+                    updated_at = excluded.updated_at",
+                rusqlite::params![draft_id, content, cursor_meta, now],
+            )?;
+            tx.execute(
+                "DELETE FROM drafts WHERE draft_id NOT IN
+                    (SELECT draft_id FROM drafts ORDER BY updated_at DESC LIMIT ?1)",
+                [MAX_DRAFTS],
+            )?;
+            tx.commit()
+        })
+        .map_err(database_error)?;
+
+    log::debug!("Saved draft {draft_id}");
+    Ok(())
+}
+
+fn query_drafts(database: &Database) -> Result<Vec<TranscriptDraft>, String> {
+    database.with_conn(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT draft_id, content, cursor_meta, created_at, updated_at
+             FROM drafts ORDER BY updated_at DESC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let cursor_meta: Option<String> = row.get(2)?;
+            Ok(TranscriptDraft {
+                draft_id: row.get(0)?,
+                content: row.get(1)?,
+                // Unparseable cursor state only loses the cursor, not the draft
+                cursor_meta: cursor_meta.and_then(|meta| serde_json::from_str(&meta).ok()),
+                created_at_ms: row.get::<_, i64>(3)? as f64,
+                updated_at_ms: row.get::<_, i64>(4)? as f64,
+            })
+        })?;
+        rows.collect()
+    })
+}
+
+/// Loads all saved drafts, most recently updated first.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed(response_size)]
+pub async fn load_drafts(app: AppHandle) -> Result<Vec<TranscriptDraft>, RecoveryError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    query_drafts(&app.state::<Database>()).map_err(database_error)
+}
+
+/// Deletes a draft after the transcript was explicitly saved or discarded.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn delete_draft(app: AppHandle, draft_id: String) -> Result<(), RecoveryError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    app.state::<Database>()
+        .with_conn(|conn| conn.execute("DELETE FROM drafts WHERE draft_id = ?1", [&draft_id]))
+        .map_err(database_error)?;
+    log::debug!("Deleted draft {draft_id}");
+    Ok(())
+}
+
+/// Tells the main window about drafts left from a previous run so it can offer restoring them.
+/// Called from setup(); the event is queued until the main window is ready.
+pub fn announce_drafts(app: &AppHandle) {
+    let draft_ids: Vec<String> = match query_drafts(&app.state::<Database>()) {
+        Ok(drafts) => drafts.into_iter().map(|d| d.draft_id).collect(),
+        Err(e) => {
+            log::warn!("Failed to check for drafts: {e}");
+            return;
+        }
+    };
+    if draft_ids.is_empty() {
+        return;
+    }
+
+    log::info!("Found {} unsaved drafts", draft_ids.len());
+    if let Err(e) =
+        crate::commands::navigation::emit_to_main_when_ready(app, DraftsAvailable { draft_ids })
+    {
+        log::warn!("Failed to announce drafts: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn header() -> SessionHeader {
+        SessionHeader {
+            finalized: true,
+            rolled: true,
+            channels: 2,
+            sample_rate: 48_000,
+            created_at_ms: 1_700_000_000_000,
+            sample_count: 96_000,
+        }
+    }
+
+    fn test_cipher() -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&[7u8; 32].into())
+    }
+
+    fn sealed(chunks: &[&[u8]]) -> Vec<u8> {
+        let cipher = test_cipher();
+        chunks
+            .iter()
+            .enumerate()
+            .flat_map(|(index, chunk)| seal_chunk(&cipher, index as u64, chunk).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_session_header_roundtrip() {
+        let header = header();
+        assert_eq!(SessionHeader::decode(&header.encode()), Some(header));
+    }
+
+    #[test]
+    fn test_session_header_rejects_truncated_and_foreign_files() {
+        let bytes = header().encode();
+        assert_eq!(
+            SessionHeader::decode(&bytes[..SESSION_HEADER_LEN - 1]),
+            None
+        );
+
+        let mut foreign = bytes;
+        foreign[0..4].copy_from_slice(b"RIFF");
+        assert_eq!(SessionHeader::decode(&foreign), None);
+    }
+
+    #[test]
+    fn test_rolling_window_is_bounded_by_half_the_cap() {
+        // 10 minutes of 48kHz stereo fits in a 500MB cap
+        assert_eq!(
+            rolling_window_samples(10, 48_000, 2, 500 * BYTES_PER_MB),
+            10 * 60 * 48_000 * 2
+        );
+        // 60 minutes doesn't; keep half the cap's worth of 16-bit samples
+        assert_eq!(
+            rolling_window_samples(60, 48_000, 2, 500 * BYTES_PER_MB),
+            (500 * BYTES_PER_MB / 4) as usize
+        );
+    }
+
+    #[test]
+    fn test_encrypted_chunks_roundtrip() {
+        let data = sealed(&[b"first", b"second", b"third"]);
+        assert_eq!(
+            *open_chunks(&test_cipher(), &data).unwrap(),
+            b"firstsecondthird"
+        );
+    }
+
+    #[test]
+    fn test_truncated_final_chunk_yields_complete_chunks() {
+        let data = sealed(&[b"first", b"second", b"third"]);
+        let truncated = &data[..data.len() - 3];
+        assert_eq!(
+            *open_chunks(&test_cipher(), truncated).unwrap(),
+            b"firstsecond"
+        );
+    }
+
+    #[test]
+    fn test_roll_keeps_the_newest_chunks_from_the_file() {
+        let path = std::env::temp_dir().join(format!("roll-test-{}.psrec", std::process::id()));
+        let header = SessionHeader {
+            finalized: false,
+            rolled: false,
+            ..header()
+        };
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(&header.encode()).unwrap();
+        let mut session = RecoverySession {
+            id: "roll-test".to_string(),
+            path: path.clone(),
+            file,
+            header,
+            cipher: test_cipher(),
+            chunks: Vec::new(),
+            bytes_written: SESSION_HEADER_LEN as u64,
+            cap_bytes: 0,
+            window_samples: 5,
+        };
+        session.append(&[0.5; 4]).unwrap();
+        session.append(&[0.25; 2]).unwrap();
+        session.append(&[-0.5; 3]).unwrap();
+
+        session.roll().unwrap();
+        session.append(&[1.0]).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let rolled = SessionHeader::decode(&bytes).unwrap();
+        assert!(rolled.rolled);
+        let expected: Vec<u8> = [
+            encode_pcm(&[0.25; 2]),
+            encode_pcm(&[-0.5; 3]),
+            encode_pcm(&[1.0]),
+        ]
+        .iter()
+        .flat_map(|chunk| chunk.iter().copied())
+        .collect();
+        assert_eq!(
+            *open_chunks(&test_cipher(), &bytes[SESSION_HEADER_LEN..]).unwrap(),
+            expected
+        );
+        assert_eq!(session.header.sample_count, 6);
+    }
+
+    #[test]
+    fn test_wrong_key_is_rejected() {
+        let data = sealed(&[b"first"]);
+        let other = ChaCha20Poly1305::new(&[8u8; 32].into());
+        assert!(open_chunks(&other, &data).is_err());
+    }
+
+    #[test]
+    fn test_scan_chunks_counts_plaintext_without_key() {
+        let data = sealed(&[&[0u8; 40], &[0u8; 20]]);
+        let len = data.len() as u64;
+        assert_eq!(scan_chunks(&mut Cursor::new(&data), len), (60, true));
+        assert_eq!(
+            scan_chunks(&mut Cursor::new(&data[..data.len() - 1]), len - 1),
+            (40, false)
+        );
+    }
+
+    #[test]
+    fn test_classify_finalized_session_as_complete() {
+        let header = header();
+        let plaintext_bytes = header.sample_count * 2;
+        assert_eq!(
+            classify_session(Some(&header), (plaintext_bytes, true), true),
+            (RecoverySessionStatus::Complete, header.sample_count)
+        );
+    }
+
+    #[test]
+    fn test_classify_interrupted_session_as_truncated() {
+        let header = SessionHeader {
+            finalized: false,
+            ..header()
+        };
+        assert_eq!(
+            classify_session(Some(&header), (1000, true), true),
+            (RecoverySessionStatus::Truncated, 500)
+        );
+        assert_eq!(
+            classify_session(Some(&header), (1000, true), false).0,
+            RecoverySessionStatus::Unrecoverable
+        );
+        assert_eq!(
+            classify_session(None, (0, false), true).0,
+            RecoverySessionStatus::Damaged
+        );
+    }
+}
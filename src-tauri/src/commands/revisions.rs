@@ -0,0 +1,156 @@
+//! Transcript edit history.
+//!
+//! Whenever an edit changes a transcript's text, the version being replaced
+//! is kept as a revision. Only the newest `MAX_REVISIONS_PER_TRANSCRIPT` are
+//! kept per transcript, and revisions are deleted with their transcript.
+//! Restoring a revision is itself an edit, so the version it replaces becomes
+//! a revision in turn.
+
+use rusqlite::{Connection, OptionalExtension};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::{audit, pipeline, transcripts};
+use crate::db::Database;
+use crate::types::{
+    count_words, AuditEventKind, Transcript, TranscriptError, TranscriptRevision,
+    MAX_REVISIONS_PER_TRANSCRIPT,
+};
+use crate::utils::duplicates;
+use crate::utils::metrics;
+
+fn database_error(message: String) -> TranscriptError {
+    TranscriptError::DatabaseError { message }
+}
+
+/// Stores the transcript's current title and text as a revision, unless its
+/// text already equals `new_text`, then prunes the oldest revisions beyond
+/// the cap. Runs inside the caller's transaction, before the update.
+pub(crate) fn snapshot_before_edit(
+    conn: &Connection,
+    transcript_id: &str,
+    new_text: &str,
+) -> rusqlite::Result<()> {
+    let inserted = conn.execute(
+        "INSERT INTO transcript_revisions (transcript_id, title, text, created_at)
+         SELECT id, title, text, ?3 FROM transcripts WHERE id = ?1 AND text != ?2",
+        rusqlite::params![transcript_id, new_text, pipeline::now_ms() as i64],
+    )?;
+    if inserted > 0 {
+        conn.execute(
+            "DELETE FROM transcript_revisions WHERE transcript_id = ?1 AND id NOT IN (
+                SELECT id FROM transcript_revisions WHERE transcript_id = ?1
+                ORDER BY id DESC LIMIT ?2)",
+            rusqlite::params![transcript_id, MAX_REVISIONS_PER_TRANSCRIPT],
+        )?;
+    }
+    Ok(())
+}
+
+/// Lists a transcript's earlier versions, newest first.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed(response_size)]
+pub async fn list_revisions(
+    app: AppHandle,
+    id: String,
+) -> Result<Vec<TranscriptRevision>, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    transcripts::validate_id(&id)?;
+    let database = app.state::<Database>();
+    if transcripts::query_transcript(&database, &id)
+        .map_err(database_error)?
+        .is_none()
+    {
+        return Err(TranscriptError::NotFound { id });
+    }
+
+    let revisions = database
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, transcript_id, title, text, created_at FROM transcript_revisions
+                 WHERE transcript_id = ?1 ORDER BY id DESC",
+            )?;
+            let revisions = stmt.query_map([&id], |row| {
+                let text: String = row.get(3)?;
+                Ok(TranscriptRevision {
+                    revision_id: row.get(0)?,
+                    transcript_id: row.get(1)?,
+                    title: row.get(2)?,
+                    word_count: count_words(&text),
+                    text,
+                    created_at_ms: row.get::<_, i64>(4)? as f64,
+                })
+            })?;
+            revisions.collect::<rusqlite::Result<Vec<_>>>()
+        })
+        .map_err(database_error)?;
+
+    audit::record(
+        &app,
+        AuditEventKind::TranscriptViewed,
+        Some(&id),
+        "revisions",
+    );
+    Ok(revisions)
+}
+
+/// Restores a transcript's title and text from one of its revisions and
+/// returns the updated transcript. The replaced version is kept as a revision.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed(response_size)]
+pub async fn restore_revision(
+    app: AppHandle,
+    id: String,
+    revision_id: u32,
+) -> Result<Transcript, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    transcripts::validate_id(&id)?;
+    let database = app.state::<Database>();
+    let restored = database
+        .with_conn(|conn| {
+            let tx = conn.transaction()?;
+            let revision: Option<(String, String)> = tx
+                .query_row(
+                    "SELECT title, text FROM transcript_revisions
+                     WHERE id = ?1 AND transcript_id = ?2",
+                    rusqlite::params![revision_id, id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            let Some((title, text)) = revision else {
+                return Ok(false);
+            };
+
+            snapshot_before_edit(&tx, &id, &text)?;
+            tx.execute(
+                "UPDATE transcripts SET title = ?2, text = ?3, word_count = ?4, updated_at = ?5,
+                    content_hash = ?6
+                 WHERE id = ?1",
+                rusqlite::params![
+                    id,
+                    title,
+                    text,
+                    count_words(&text),
+                    pipeline::now_ms() as i64,
+                    duplicates::content_hash(&text)
+                ],
+            )?;
+            tx.commit().map(|_| true)
+        })
+        .map_err(database_error)?;
+    if !restored {
+        return Err(TranscriptError::RevisionNotFound { id: revision_id });
+    }
+
+    audit::record(
+        &app,
+        AuditEventKind::TranscriptEdited,
+        Some(&id),
+        &format!("fields=title,text restored_revision={revision_id}"),
+    );
+    log::info!("Restored transcript {id} to revision {revision_id}");
+    transcripts::query_transcript(&database, &id)
+        .map_err(database_error)?
+        .ok_or(TranscriptError::NotFound { id })
+}
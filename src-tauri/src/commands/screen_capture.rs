@@ -0,0 +1,88 @@
+//! Keeping transcripts out of screen shares.
+//!
+//! With `block_screen_capture` on, the main window and the quick pane are
+//! excluded from screenshots, recordings and screen sharing (see
+//! `utils::capture`), so sharing a telehealth call can't show another
+//! patient's transcript. It's applied when each window is created and when
+//! the preference changes. `get_screen_capture_state` reads back what the OS
+//! reports for each window, so the UI can say when the platform doesn't
+//! support it instead of implying protection.
+
+use tauri::{AppHandle, Manager};
+
+use crate::commands::navigation::MAIN_WINDOW_LABEL;
+use crate::commands::preferences;
+use crate::commands::quick_pane::QUICK_PANE_LABEL;
+use crate::types::{ScreenCaptureState, WindowScreenCapture};
+use crate::utils::capture;
+use crate::utils::metrics;
+
+/// Windows holding transcripts
+const PROTECTED_WINDOWS: &[&str] = &[MAIN_WINDOW_LABEL, QUICK_PANE_LABEL];
+
+/// Applies the `block_screen_capture` preference to one window. Must run on
+/// the main thread.
+pub fn apply_to_window(app: &AppHandle, label: &str) {
+    let block = preferences::load_saved_preferences(app).block_screen_capture;
+    let Some(window) = app.get_webview_window(label) else {
+        return;
+    };
+    match capture::set_excluded(&window, block) {
+        Ok(()) => log::debug!("Screen capture of {label} blocked: {block}"),
+        Err(e) => log::warn!("Failed to set screen capture exclusion for {label}: {e}"),
+    }
+}
+
+/// Applies the `block_screen_capture` preference to every protected window.
+pub fn apply(app: &AppHandle) {
+    let handle = app.clone();
+    let result = app.run_on_main_thread(move || {
+        for label in PROTECTED_WINDOWS {
+            apply_to_window(&handle, label);
+        }
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to apply screen capture exclusion: {e}");
+    }
+}
+
+/// What the OS reports for each protected window, for callers off the main
+/// thread.
+pub async fn current_state(app: &AppHandle) -> Result<Vec<WindowScreenCapture>, String> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let handle = app.clone();
+    app.run_on_main_thread(move || {
+        let _ = sender.send(get_screen_capture_state(handle));
+    })
+    .map_err(|e| format!("Failed to read screen capture state: {e}"))?;
+    receiver
+        .await
+        .map_err(|e| format!("Failed to read screen capture state: {e}"))
+}
+
+/// Reports whether each protected window is actually excluded from capture.
+/// Windows not created yet are left out. Sync, so it runs on the main thread.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn get_screen_capture_state(app: AppHandle) -> Vec<WindowScreenCapture> {
+    let block = preferences::load_saved_preferences(&app).block_screen_capture;
+    PROTECTED_WINDOWS
+        .iter()
+        .filter_map(|label| {
+            let window = app.get_webview_window(label)?;
+            let state = match capture::is_excluded(&window) {
+                Ok(true) => ScreenCaptureState::Blocked,
+                Ok(false) if !block => ScreenCaptureState::Allowed,
+                Ok(false) => ScreenCaptureState::Unsupported {
+                    reason: capture::UNSUPPORTED_REASON.to_string(),
+                },
+                Err(reason) => ScreenCaptureState::Unsupported { reason },
+            };
+            Some(WindowScreenCapture {
+                window: label.to_string(),
+                state,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,82 @@
+//! Stopping a recording left running on an unattended workstation.
+//!
+//! The app lock's idle timer calls `stop_and_lock` once a recording has run
+//! `max_idle_while_recording_minutes` without user input or gated commands.
+//! The app locks first so nothing more is shown, then the recording is
+//! stopped and transcribed with the default model. The text is kept as a
+//! draft rather than delivered, so it waits behind the lock like any other
+//! unsaved work; the audio is deleted as usual.
+
+use std::path::Path;
+
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::commands::{app_lock, audio, pipeline, recovery, transcription};
+use crate::types::{AppLockReason, AutoStoppedAndLocked};
+
+/// Prefix of drafts holding an auto-stopped recording
+const DRAFT_PREFIX: &str = "auto-stopped-";
+
+/// Transcribes the WAV at `file_path` into a new draft and deletes the audio.
+/// Returns the draft id.
+async fn save_as_draft(app: &AppHandle, file_path: &str) -> Result<String, String> {
+    let result = transcription::transcribe_file(
+        app,
+        file_path.into(),
+        None,
+        transcription::DEFAULT_MODEL_ID,
+    )
+    .await;
+
+    let saved = match result {
+        Ok(transcribed) => {
+            let draft_id = format!("{DRAFT_PREFIX}{}", pipeline::now_ms() as u64);
+            recovery::save_draft(app.clone(), draft_id.clone(), transcribed.text, None)
+                .await
+                .map(|()| draft_id)
+                .map_err(|e| e.to_string())
+        }
+        Err(e) => Err(e.to_string()),
+    };
+    if saved.is_ok() {
+        recovery::complete_session_for_audio(app, Path::new(file_path));
+    }
+
+    // HIPAA: delete audio file regardless of transcription outcome
+    if let Err(e) = std::fs::remove_file(file_path) {
+        log::warn!("Failed to delete audio file {file_path}: {e}");
+    }
+    saved
+}
+
+/// Locks the app, stops the recording and saves its transcription as a
+/// draft, then emits `auto-stopped-and-locked`. A failed transcription
+/// leaves the encrypted recovery file to be offered at the next start.
+pub async fn stop_and_lock(app: &AppHandle, idle_minutes: u32) {
+    log::warn!("Recording unattended for {idle_minutes} minutes; stopping it");
+    let locked = app_lock::lock(app, AppLockReason::IdleWhileRecording);
+
+    let draft_id = match audio::stop_recording(app.clone()).await {
+        Ok(file_path) => match save_as_draft(app, &file_path).await {
+            Ok(draft_id) => Some(draft_id),
+            Err(e) => {
+                log::error!("Failed to save the auto-stopped recording: {e}");
+                None
+            }
+        },
+        Err(e) => {
+            log::warn!("Failed to stop the unattended recording: {e}");
+            None
+        }
+    };
+
+    let payload = AutoStoppedAndLocked {
+        draft_id,
+        idle_minutes,
+        locked,
+    };
+    if let Err(e) = payload.emit(app) {
+        log::warn!("Failed to emit auto-stopped-and-locked: {e}");
+    }
+}
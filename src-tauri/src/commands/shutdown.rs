@@ -0,0 +1,111 @@
+//! Quitting without losing in-flight work.
+//!
+//! An exit request while recording or transcribing is held back. A recording
+//! is stopped with its encrypted recovery file finalized; a transcription gets
+//! a grace period to finish. `shutdown-blocked` tells the UI what the quit is
+//! waiting for, and the app quits after `SHUTDOWN_HARD_CAP` whatever happens.
+//!
+//! Work that didn't finish stays in the recovery directory, and the crash
+//! sentinel is left in place so the next launch offers it.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::commands::audio::{self, RecordingPhase};
+use crate::commands::{pipeline, recovery};
+use crate::types::{ShutdownBlockReason, ShutdownBlocked};
+
+/// Longest a quit waits for in-flight work
+const SHUTDOWN_HARD_CAP: Duration = Duration::from_secs(10);
+
+/// How often the wait checks whether the pipeline went idle
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Set while in-flight work is wrapped up, so repeated quits don't start over
+static WRAPPING_UP: AtomicBool = AtomicBool::new(false);
+
+/// Set once wrapped up, so the exit requested afterwards goes through
+static WRAPPED_UP: AtomicBool = AtomicBool::new(false);
+
+/// Whether a recording or transcription was left for the next launch to recover
+static WORK_PRESERVED: AtomicBool = AtomicBool::new(false);
+
+fn announce(app: &AppHandle, reason: ShutdownBlockReason, deadline_ms: f64) {
+    log::info!("Quit waits for {reason:?}");
+    let payload = ShutdownBlocked {
+        reason,
+        deadline_ms,
+    };
+    if let Err(e) = payload.emit(app) {
+        log::warn!("Failed to emit shutdown-blocked: {e}");
+    }
+}
+
+/// Stops the recording in progress (keeping its recovery file), then waits
+/// for the pipeline to go idle.
+async fn wrap_up(app: &AppHandle, deadline_ms: f64) {
+    // A start settles first, so there's a stream to stop
+    while audio::recording_phase() == RecordingPhase::Starting {
+        tokio::time::sleep(IDLE_POLL_INTERVAL).await;
+    }
+    if audio::recording_phase() == RecordingPhase::Recording {
+        announce(app, ShutdownBlockReason::Recording, deadline_ms);
+        if audio::abort_recording(app).await {
+            log::warn!("Quit while recording; the recording is kept for recovery");
+            WORK_PRESERVED.store(true, Ordering::SeqCst);
+        }
+    }
+    if pipeline::is_busy() {
+        announce(app, ShutdownBlockReason::Transcribing, deadline_ms);
+        while pipeline::is_busy() {
+            tokio::time::sleep(IDLE_POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Called on `ExitRequested`. Returns whether to prevent the exit while
+/// in-flight work is wrapped up; the exit (or restart) with `code` is
+/// requested again once it is, or once `SHUTDOWN_HARD_CAP` has passed.
+pub fn hold_exit(app: &AppHandle, code: Option<i32>) -> bool {
+    if WRAPPED_UP.load(Ordering::SeqCst) {
+        return false;
+    }
+    if audio::recording_phase() == RecordingPhase::Idle && !pipeline::is_busy() {
+        return false;
+    }
+    if WRAPPING_UP.swap(true, Ordering::SeqCst) {
+        return true;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let deadline_ms = pipeline::now_ms() + SHUTDOWN_HARD_CAP.as_millis() as f64;
+        let finished = tokio::time::timeout(SHUTDOWN_HARD_CAP, wrap_up(&app, deadline_ms)).await;
+        if finished.is_err() {
+            log::warn!(
+                "In-flight work didn't finish within {}s; quitting and keeping it for recovery",
+                SHUTDOWN_HARD_CAP.as_secs()
+            );
+            WORK_PRESERVED.store(true, Ordering::SeqCst);
+        }
+        WRAPPED_UP.store(true, Ordering::SeqCst);
+        if code == Some(tauri::RESTART_EXIT_CODE) {
+            app.restart();
+        }
+        app.exit(code.unwrap_or(0));
+    });
+    true
+}
+
+/// Called on `Exit`. Leaves the crash sentinel when work was kept for
+/// recovery, so the next launch offers it; a clean exit clears it.
+pub fn on_exit(app: &AppHandle) {
+    if WORK_PRESERVED.load(Ordering::SeqCst) {
+        recovery::mark_session_active(app);
+    } else {
+        recovery::clear_session_active(app);
+    }
+}
@@ -0,0 +1,162 @@
+//! Usage statistics for the dashboard.
+//!
+//! Aggregates run in SQL over the transcripts' metadata columns, so transcript
+//! text is never loaded. Transcription speed comes from `transcription_stats`,
+//! which records the timing of every transcription, saved or not.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{Local, NaiveDate};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::{audit, pipeline};
+use crate::db::Database;
+use crate::types::{DailyUsage, ModelUsage, TranscriptionPerformance, UsagePeriod, UsageStats};
+use crate::utils::metrics;
+
+/// Timing rows older than the longest period are pruned as new ones arrive
+const STATS_RETENTION_DAYS: u32 = 366;
+
+/// Records how long a transcription took. Failures are logged, never surfaced.
+pub fn record_transcription(app: &AppHandle, model_id: &str, audio_secs: f64, elapsed: Duration) {
+    let now = pipeline::now_ms();
+    let cutoff = now - f64::from(STATS_RETENTION_DAYS) * audit::MS_PER_DAY;
+    let result = app.state::<Database>().with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO transcription_stats (model_id, audio_secs, processing_ms, completed_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![model_id, audio_secs, elapsed.as_millis() as i64, now as i64],
+        )?;
+        conn.execute(
+            "DELETE FROM transcription_stats WHERE completed_at < ?1",
+            [cutoff as i64],
+        )
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to record transcription stats: {e}");
+    }
+}
+
+/// One entry per day from `first`, filling days without transcripts with zeros.
+fn fill_days(
+    first: NaiveDate,
+    days: u32,
+    mut by_date: HashMap<String, DailyUsage>,
+) -> Vec<DailyUsage> {
+    first
+        .iter_days()
+        .take(days as usize)
+        .map(|day| {
+            let date = day.format("%Y-%m-%d").to_string();
+            by_date.remove(&date).unwrap_or(DailyUsage {
+                date,
+                transcripts: 0,
+                audio_secs: 0.0,
+                words: 0,
+            })
+        })
+        .collect()
+}
+
+/// Dictation volume and transcription speed over `period` (default: the last
+/// week), with a per-day histogram. Transcripts in the trash aren't counted.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn get_usage_stats(
+    app: AppHandle,
+    period: Option<UsagePeriod>,
+) -> Result<UsageStats, String> {
+    let period = period.unwrap_or_default();
+    let first_day = Local::now().date_naive() - chrono::Days::new(u64::from(period.days() - 1));
+    let from_ms = first_day
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+        .map(|start| start.timestamp_millis() as f64)
+        .ok_or("Could not determine the start of the period")?;
+
+    let (by_date, performance) = app.state::<Database>().with_conn(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT date(created_at / 1000, 'unixepoch', 'localtime') AS day,
+                    COUNT(*), TOTAL(duration_secs), COALESCE(SUM(word_count), 0)
+             FROM transcripts
+             WHERE deleted_at IS NULL AND created_at >= ?1
+             GROUP BY day",
+        )?;
+        let by_date = stmt
+            .query_map([from_ms as i64], |row| {
+                Ok(DailyUsage {
+                    date: row.get(0)?,
+                    transcripts: row.get(1)?,
+                    audio_secs: row.get(2)?,
+                    words: row.get(3)?,
+                })
+            })?
+            .map(|usage| usage.map(|usage| (usage.date.clone(), usage)))
+            .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+
+        let (transcriptions, average_processing_secs, average_rtf) = conn.query_row(
+            "SELECT COUNT(*), AVG(processing_ms) / 1000.0,
+                    AVG(CASE WHEN audio_secs > 0 THEN processing_ms / 1000.0 / audio_secs END)
+             FROM transcription_stats WHERE completed_at >= ?1",
+            [from_ms as i64],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        let mut stmt = conn.prepare(
+            "SELECT model_id, COUNT(*) FROM transcription_stats WHERE completed_at >= ?1
+             GROUP BY model_id ORDER BY COUNT(*) DESC, model_id",
+        )?;
+        let models = stmt
+            .query_map([from_ms as i64], |row| {
+                Ok(ModelUsage {
+                    model_id: row.get(0)?,
+                    transcriptions: row.get(1)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok((
+            by_date,
+            TranscriptionPerformance {
+                transcriptions,
+                average_processing_secs,
+                average_rtf,
+                models,
+            },
+        ))
+    })?;
+
+    let daily = fill_days(first_day, period.days(), by_date);
+    Ok(UsageStats {
+        period,
+        from_ms,
+        transcripts: daily.iter().map(|day| day.transcripts).sum(),
+        total_audio_secs: daily.iter().map(|day| day.audio_secs).sum(),
+        total_words: daily.iter().map(|day| day.words).sum(),
+        daily,
+        performance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_days_are_filled_with_zeros() {
+        let first = NaiveDate::from_ymd_opt(2024, 2, 28).unwrap();
+        let busy = DailyUsage {
+            date: "2024-02-29".into(),
+            transcripts: 2,
+            audio_secs: 300.0,
+            words: 700,
+        };
+        let daily = fill_days(first, 3, HashMap::from([(busy.date.clone(), busy.clone())]));
+
+        let dates: Vec<&str> = daily.iter().map(|day| day.date.as_str()).collect();
+        assert_eq!(dates, ["2024-02-28", "2024-02-29", "2024-03-01"]);
+        assert_eq!(daily[1], busy);
+        assert_eq!(daily[0].transcripts + daily[2].transcripts, 0);
+    }
+}
@@ -0,0 +1,261 @@
+//! Where the app's data lives, for the frontend.
+//!
+//! The backend resolves its paths through `utils::paths`; the frontend's SQL
+//! plugin database is opened by URL, so it asks here for one that follows
+//! portable mode.
+//!
+//! A portable data folder that can't be written stops the app at startup with
+//! an error dialog rather than scattering data elsewhere.
+//!
+//! `get_storage_usage` answers "why is this app using 3 GB?" with bytes per
+//! category. Its walks are bounded (see `utils::diagnostics::dir_size`) and
+//! the result is reused for `USAGE_CACHE_TTL`, so the settings screen can ask
+//! each time it opens. `clear_category` empties the categories that hold
+//! nothing the user keeps, securely deleting their files.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+
+use crate::commands::navigation::MAIN_WINDOW_LABEL;
+use crate::commands::{cache, pipeline, recovery};
+use crate::db::{self, Database};
+use crate::types::{
+    AppError, ClearableStorage, ModelStorage, StorageCleared, StorageLocations, StorageUsage,
+    TableStorage,
+};
+use crate::utils::diagnostics::{dir_size, probe};
+use crate::utils::secure_delete::secure_delete;
+use crate::utils::{metrics, paths};
+
+/// Database opened by the frontend through the SQL plugin
+const FRONTEND_DATABASE_FILENAME: &str = "privacyscribe.db";
+
+/// Folder in the app data dir holding whisper and LLM models
+const MODELS_DIRNAME: &str = "models";
+
+/// How long a measured `StorageUsage` is reused
+const USAGE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// The last measured usage and when it was measured
+static USAGE_CACHE: Mutex<Option<(Instant, StorageUsage)>> = Mutex::new(None);
+
+/// Connection URL for the frontend's SQL plugin database. The plugin joins
+/// the path onto the platform config directory, so in portable mode the URL
+/// carries an absolute path, which replaces it.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn get_frontend_database_url(app: AppHandle) -> Result<String, String> {
+    if !paths::is_portable() {
+        return Ok(format!("sqlite:{FRONTEND_DATABASE_FILENAME}"));
+    }
+    let dir = paths::app_config_dir(&app)
+        .map_err(|e| format!("Failed to get app config directory: {e}"))?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create app config directory: {e}"))?;
+    Ok(format!(
+        "sqlite:{}",
+        dir.join(FRONTEND_DATABASE_FILENAME).to_string_lossy()
+    ))
+}
+
+/// Tells the user the portable data folder can't be used, then quits once the
+/// dialog is dismissed. Called from setup in place of the rest of startup.
+pub fn report_unusable_portable_root(app: &AppHandle, message: &str) {
+    log::error!("Portable data folder unusable: {message}");
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+        let _ = window.hide();
+    }
+    let exit_app = app.clone();
+    app.dialog()
+        .message(format!(
+            "{message}\n\nMove PrivacyScribe to a folder you can write to, or remove {} \
+             beside it to keep data in the standard locations.",
+            paths::PORTABLE_FLAG_FILENAME
+        ))
+        .title("PrivacyScribe can't start in portable mode")
+        .kind(MessageDialogKind::Error)
+        .show(move |_| exit_app.exit(1));
+}
+
+/// Files in the models folder, partial downloads included, largest first.
+fn model_storage(app: &AppHandle) -> Result<Vec<ModelStorage>, String> {
+    let dir = paths::app_data_dir(app)
+        .map_err(|e| format!("Failed to get app data dir: {e}"))?
+        .join(MODELS_DIRNAME);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to list models: {e}")),
+    };
+    let mut models: Vec<ModelStorage> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let metadata = entry.path().symlink_metadata().ok()?;
+            metadata.is_file().then(|| ModelStorage {
+                file_name: entry.file_name().to_string_lossy().into_owned(),
+                bytes: metadata.len() as f64,
+            })
+        })
+        .collect();
+    models.sort_by(|a, b| b.bytes.total_cmp(&a.bytes));
+    Ok(models)
+}
+
+/// Bytes per table from the pages its b-trees occupy, largest first.
+fn table_storage(app: &AppHandle) -> Result<Vec<TableStorage>, String> {
+    app.state::<Database>().with_conn(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT s.tbl_name, SUM(d.pgsize) FROM dbstat d \
+             JOIN sqlite_schema s ON s.name = d.name \
+             GROUP BY s.tbl_name ORDER BY 2 DESC",
+        )?;
+        let tables = stmt
+            .query_map([], |row| {
+                Ok(TableStorage {
+                    table: row.get(0)?,
+                    bytes: row.get::<_, i64>(1)? as f64,
+                })
+            })?
+            .collect();
+        tables
+    })
+}
+
+fn dir_bytes(dir: tauri::Result<PathBuf>) -> Result<f64, String> {
+    let dir = dir.map_err(|e| e.to_string())?;
+    dir_size(&dir).map(|bytes| bytes as f64)
+}
+
+fn storage_locations(app: &AppHandle) -> Result<StorageLocations, AppError> {
+    let location = |dir: tauri::Result<PathBuf>| {
+        dir.map(|dir| dir.to_string_lossy().into_owned())
+            .map_err(|e| AppError::internal(format!("Failed to resolve a data folder: {e}")))
+    };
+    Ok(StorageLocations {
+        portable: paths::is_portable(),
+        data_dir: location(paths::app_data_dir(app))?,
+        config_dir: location(paths::app_config_dir(app))?,
+        cache_dir: location(paths::app_cache_dir(app))?,
+        log_dir: location(paths::app_log_dir(app))?,
+    })
+}
+
+/// Measures every category. Blocks on the walks.
+fn measure(app: &AppHandle) -> Result<StorageUsage, AppError> {
+    let recovery_bytes = recovery::get_recovery_dir(app)
+        .and_then(|dir| dir_size(&dir))
+        .map(|bytes| bytes as f64);
+    Ok(StorageUsage {
+        measured_at_ms: pipeline::now_ms(),
+        models: probe("model sizes", model_storage(app)),
+        database_bytes: probe("the database size", db::file_size(app).map(|b| b as f64)),
+        database_tables: probe("database table sizes", table_storage(app)),
+        recovery_bytes: probe("recovery usage", recovery_bytes),
+        cache_bytes: probe("cache usage", dir_bytes(paths::app_cache_dir(app))),
+        log_bytes: probe("log usage", dir_bytes(paths::app_log_dir(app))),
+        locations: storage_locations(app)?,
+    })
+}
+
+/// Bytes the app uses per category, and the folders in effect. Measured at
+/// most once per `USAGE_CACHE_TTL`; clearing a category measures again.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn get_storage_usage(app: AppHandle) -> Result<StorageUsage, AppError> {
+    {
+        let cached = USAGE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((measured, usage)) = cached.as_ref() {
+            if measured.elapsed() < USAGE_CACHE_TTL {
+                return Ok(usage.clone());
+            }
+        }
+    }
+    let usage = tokio::task::spawn_blocking(move || measure(&app))
+        .await
+        .map_err(|e| AppError::internal(format!("Storage usage task panicked: {e}")))??;
+    *USAGE_CACHE.lock().unwrap_or_else(|e| e.into_inner()) = Some((Instant::now(), usage.clone()));
+    Ok(usage)
+}
+
+/// Log files in `dir` with their sizes, newest first.
+fn log_files(dir: &Path) -> Result<Vec<(PathBuf, u64)>, String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to list log files: {e}")),
+    };
+    let mut files: Vec<(SystemTime, PathBuf, u64)> =
+        entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let metadata = path.symlink_metadata().ok()?;
+                let modified = metadata.modified().ok()?;
+                (metadata.is_file() && path.extension().is_some_and(|ext| ext == "log"))
+                    .then_some((modified, path, metadata.len()))
+            })
+            .collect();
+    files.sort_by_key(|(modified, _, _)| std::cmp::Reverse(*modified));
+    Ok(files
+        .into_iter()
+        .map(|(_, path, len)| (path, len))
+        .collect())
+}
+
+/// Securely deletes the log files but the newest, which the log plugin is
+/// still writing to.
+fn clear_logs(app: &AppHandle) -> Result<StorageCleared, String> {
+    let dir = paths::app_log_dir(app).map_err(|e| format!("Failed to get log dir: {e}"))?;
+    let mut cleared = StorageCleared::default();
+    for (index, (path, len)) in log_files(&dir)?.into_iter().enumerate() {
+        if index == 0 {
+            cleared.files_skipped += 1;
+            continue;
+        }
+        match secure_delete(&path) {
+            Ok(()) => {
+                cleared.files_deleted += 1;
+                cleared.bytes_freed += len as f64;
+            }
+            Err(e) => {
+                log::warn!("Failed to delete log file {path:?}: {e}");
+                cleared.files_skipped += 1;
+            }
+        }
+    }
+    log::info!(
+        "Cleared {} log files ({} bytes)",
+        cleared.files_deleted,
+        cleared.bytes_freed
+    );
+    Ok(cleared)
+}
+
+/// Empties a category that holds nothing the user keeps, securely deleting
+/// its files. Files in use (a running recording's audio, the current log
+/// file) are skipped.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn clear_category(
+    app: AppHandle,
+    kind: ClearableStorage,
+) -> Result<StorageCleared, AppError> {
+    let cleared = tokio::task::spawn_blocking(move || match kind {
+        ClearableStorage::Cache => cache::wipe_now(&app).map(|report| StorageCleared {
+            files_deleted: report.files_wiped,
+            bytes_freed: report.bytes_wiped,
+            files_skipped: report.files_skipped,
+        }),
+        ClearableStorage::Logs => clear_logs(&app),
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Clear task panicked: {e}")))??;
+    *USAGE_CACHE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    Ok(cleared)
+}
@@ -0,0 +1,304 @@
+//! Transcript tag commands.
+//!
+//! Tags are free-form labels ("follow-up", "dictated letter") normalized on
+//! write: whitespace is trimmed and collapsed and names are lowercased, so
+//! "Follow-up" and "follow-up " are the same tag. Tags survive having no
+//! transcripts; `delete_tag` removes them explicitly.
+
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{Connection, OptionalExtension};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::{audit, pipeline, transcripts};
+use crate::db::Database;
+use crate::types::{
+    AuditEventKind, TagMatch, TagUsage, TranscriptError, MAX_TAGS_PER_TRANSCRIPT, MAX_TAG_CHARS,
+};
+use crate::utils::metrics;
+
+fn database_error(message: String) -> TranscriptError {
+    TranscriptError::DatabaseError { message }
+}
+
+/// Trims, collapses inner whitespace, drops control characters and lowercases.
+/// Returns None for names that are empty afterwards.
+pub(crate) fn normalize_tag(name: &str) -> Option<String> {
+    let normalized = name
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| !c.is_control())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+/// Normalizes and de-duplicates a tag list, enforcing length and count limits.
+pub(crate) fn normalize_tags(tags: &[String]) -> Result<Vec<String>, TranscriptError> {
+    let mut normalized: Vec<String> = tags.iter().filter_map(|t| normalize_tag(t)).collect();
+    normalized.sort();
+    normalized.dedup();
+
+    if let Some(long) = normalized
+        .iter()
+        .find(|t| t.chars().count() > MAX_TAG_CHARS)
+    {
+        return Err(TranscriptError::ValidationError {
+            message: format!("Tag \"{long}\" too long (max {MAX_TAG_CHARS} characters)"),
+        });
+    }
+    if normalized.len() > MAX_TAGS_PER_TRANSCRIPT {
+        return Err(TranscriptError::ValidationError {
+            message: format!("Too many tags (max {MAX_TAGS_PER_TRANSCRIPT})"),
+        });
+    }
+    Ok(normalized)
+}
+
+/// Replaces a transcript's tags, creating tags that don't exist yet.
+/// Tags must already be normalized. Runs inside the caller's transaction.
+pub(crate) fn replace_tags(
+    conn: &Connection,
+    transcript_id: &str,
+    tags: &[String],
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM transcript_tags WHERE transcript_id = ?1",
+        [transcript_id],
+    )?;
+    let now = pipeline::now_ms() as i64;
+    for tag in tags {
+        conn.execute(
+            "INSERT OR IGNORE INTO tags (name, created_at) VALUES (?1, ?2)",
+            rusqlite::params![tag, now],
+        )?;
+        conn.execute(
+            "INSERT OR IGNORE INTO transcript_tags (transcript_id, tag_id)
+             SELECT ?1, id FROM tags WHERE name = ?2",
+            rusqlite::params![transcript_id, tag],
+        )?;
+    }
+    Ok(())
+}
+
+/// SQL condition on `transcripts.id` matching a tag filter, with its parameters.
+/// None when there is nothing to filter on.
+pub(crate) fn tag_condition(
+    tags: &[String],
+    tag_match: TagMatch,
+) -> Option<(String, Vec<SqlValue>)> {
+    let mut tags: Vec<String> = tags.iter().filter_map(|t| normalize_tag(t)).collect();
+    tags.sort();
+    tags.dedup();
+    if tags.is_empty() {
+        return None;
+    }
+
+    let placeholders = vec!["?"; tags.len()].join(", ");
+    let having = match tag_match {
+        TagMatch::Any => String::new(),
+        TagMatch::All => format!(
+            " GROUP BY tt.transcript_id HAVING COUNT(*) = {}",
+            tags.len()
+        ),
+    };
+    let condition = format!(
+        "id IN (SELECT tt.transcript_id FROM transcript_tags tt JOIN tags tg ON tg.id = tt.tag_id
+                WHERE tg.name IN ({placeholders}){having})"
+    );
+    Some((condition, tags.into_iter().map(SqlValue::Text).collect()))
+}
+
+/// Replaces a transcript's tags. Returns the normalized tags now attached.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn set_transcript_tags(
+    app: AppHandle,
+    id: String,
+    tags: Vec<String>,
+) -> Result<Vec<String>, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    transcripts::validate_id(&id)?;
+    let tags = normalize_tags(&tags)?;
+
+    let found = app
+        .state::<Database>()
+        .with_conn(|conn| {
+            let tx = conn.transaction()?;
+            let updated = tx.execute(
+                "UPDATE transcripts SET updated_at = ?2 WHERE id = ?1",
+                rusqlite::params![id, pipeline::now_ms() as i64],
+            )?;
+            if updated == 0 {
+                return Ok(false);
+            }
+            replace_tags(&tx, &id, &tags)?;
+            tx.commit().map(|_| true)
+        })
+        .map_err(database_error)?;
+    if !found {
+        return Err(TranscriptError::NotFound { id });
+    }
+
+    audit::record(
+        &app,
+        AuditEventKind::TranscriptEdited,
+        Some(&id),
+        "fields=tags",
+    );
+    Ok(tags)
+}
+
+/// Lists all tags alphabetically with how many transcripts (outside the trash) use each.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn list_tags(app: AppHandle) -> Result<Vec<TagUsage>, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    app.state::<Database>()
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT tg.name, COUNT(t.id) FROM tags tg
+                 LEFT JOIN transcript_tags tt ON tt.tag_id = tg.id
+                 LEFT JOIN transcripts t ON t.id = tt.transcript_id AND t.deleted_at IS NULL
+                 GROUP BY tg.id ORDER BY tg.name",
+            )?;
+            let tags = stmt.query_map([], |row| {
+                Ok(TagUsage {
+                    name: row.get(0)?,
+                    transcript_count: row.get(1)?,
+                })
+            })?;
+            tags.collect()
+        })
+        .map_err(database_error)
+}
+
+fn tag_id(conn: &Connection, name: &str) -> rusqlite::Result<Option<i64>> {
+    conn.query_row("SELECT id FROM tags WHERE name = ?1", [name], |row| {
+        row.get(0)
+    })
+    .optional()
+}
+
+fn normalized_name(name: &str) -> Result<String, TranscriptError> {
+    normalize_tags(&[name.to_string()])?
+        .pop()
+        .ok_or_else(|| TranscriptError::ValidationError {
+            message: "Tag name cannot be empty".to_string(),
+        })
+}
+
+/// Renames a tag on every transcript. Renaming onto an existing tag merges the two.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn rename_tag(
+    app: AppHandle,
+    name: String,
+    new_name: String,
+) -> Result<(), TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let name = normalized_name(&name)?;
+    let new_name = normalized_name(&new_name)?;
+    if name == new_name {
+        return Ok(());
+    }
+
+    let renamed = app
+        .state::<Database>()
+        .with_conn(|conn| {
+            let tx = conn.transaction()?;
+            let Some(old_id) = tag_id(&tx, &name)? else {
+                return Ok(false);
+            };
+            match tag_id(&tx, &new_name)? {
+                Some(new_id) => {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO transcript_tags (transcript_id, tag_id)
+                         SELECT transcript_id, ?2 FROM transcript_tags WHERE tag_id = ?1",
+                        [old_id, new_id],
+                    )?;
+                    tx.execute("DELETE FROM transcript_tags WHERE tag_id = ?1", [old_id])?;
+                    tx.execute("DELETE FROM tags WHERE id = ?1", [old_id])?;
+                }
+                None => {
+                    tx.execute(
+                        "UPDATE tags SET name = ?2 WHERE id = ?1",
+                        rusqlite::params![old_id, new_name],
+                    )?;
+                }
+            }
+            tx.commit().map(|_| true)
+        })
+        .map_err(database_error)?;
+    if !renamed {
+        return Err(TranscriptError::TagNotFound { name });
+    }
+
+    log::info!("Renamed tag");
+    Ok(())
+}
+
+/// Deletes a tag and removes it from every transcript.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn delete_tag(app: AppHandle, name: String) -> Result<(), TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let name = normalized_name(&name)?;
+    let deleted = app
+        .state::<Database>()
+        .with_conn(|conn| {
+            let tx = conn.transaction()?;
+            let Some(id) = tag_id(&tx, &name)? else {
+                return Ok(false);
+            };
+            tx.execute("DELETE FROM transcript_tags WHERE tag_id = ?1", [id])?;
+            tx.execute("DELETE FROM tags WHERE id = ?1", [id])?;
+            tx.commit().map(|_| true)
+        })
+        .map_err(database_error)?;
+    if !deleted {
+        return Err(TranscriptError::TagNotFound { name });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_trimmed_collapsed_and_lowercased() {
+        assert_eq!(normalize_tag("  Follow-Up "), Some("follow-up".into()));
+        assert_eq!(
+            normalize_tag("Dictated\t  LETTER"),
+            Some("dictated letter".into())
+        );
+        assert_eq!(normalize_tag("ØYE"), Some("øye".into()));
+        assert_eq!(normalize_tag(" \n "), None);
+    }
+
+    #[test]
+    fn tag_lists_are_deduplicated_after_normalizing() {
+        let tags = vec!["Research".to_string(), "research ".into(), "".into()];
+        assert_eq!(normalize_tags(&tags).unwrap(), vec!["research".to_string()]);
+    }
+
+    #[test]
+    fn all_match_requires_every_tag() {
+        let (condition, params) = tag_condition(&["b".into(), "A".into()], TagMatch::All).unwrap();
+        assert!(condition.contains("HAVING COUNT(*) = 2"));
+        assert_eq!(
+            params,
+            vec![SqlValue::Text("a".into()), SqlValue::Text("b".into())]
+        );
+        assert!(tag_condition(&[" ".into()], TagMatch::Any).is_none());
+    }
+}
@@ -6,10 +6,28 @@
 //! better accuracy. Audio is **deleted immediately** after transcription
 //! for HIPAA compliance.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::AppHandle;
+use tauri_specta::Event;
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use zeroize::Zeroizing;
+
+use crate::commands::jobs::{self, JobHandle};
+use crate::commands::{app_lock, notifications, output, pipeline, recovery, stats, transcripts};
+use crate::types::{
+    AppError, DiskSpaceContext, JobKind, JobOutput, LowDiskSpace, ModelDiagnostics,
+    ModelDownloadErrorKind, ModelDownloadFailed, ModelDownloadResumable, ModelIntegrity,
+    ModelVerification, PipelineErrorKind, PipelineStage, Probe, TranscriptDelivery, TranscriptNew,
+    TranscriptSaved, TranscriptSegment, WhisperModelDownloadProgress,
+};
+use crate::utils::audio::TARGET_SAMPLE_RATE;
+use crate::utils::diagnostics::probe;
+use crate::utils::disk::{self, DiskHeadroom};
+use crate::utils::formatting;
+use crate::utils::metrics;
+use crate::utils::model_integrity::{self, Verdict};
+use crate::utils::paths;
 
 // ---------------------------------------------------------------------------
 // Model catalogue
@@ -30,6 +48,9 @@ pub struct WhisperModelInfo {
     pub downloaded: bool,
 }
 
+/// Model used when none is specified or the requested one isn't downloaded
+pub const DEFAULT_MODEL_ID: &str = "whisper-large-v3-turbo";
+
 fn model_catalogue() -> Vec<WhisperModelInfo> {
     vec![
         WhisperModelInfo {
@@ -53,17 +74,15 @@ fn model_catalogue() -> Vec<WhisperModelInfo> {
     ]
 }
 
-fn models_dir(app: &AppHandle) -> Result<PathBuf, String> {
-    let dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {e}"))?
+fn models_dir(app: &AppHandle) -> Result<PathBuf, AppError> {
+    let dir = paths::app_data_dir(app)
+        .map_err(|e| AppError::internal(format!("Failed to get app data dir: {e}")))?
         .join("models");
-    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create models dir: {e}"))?;
+    std::fs::create_dir_all(&dir)?;
     Ok(dir)
 }
 
-fn model_path(app: &AppHandle, filename: &str) -> Result<PathBuf, String> {
+fn model_path(app: &AppHandle, filename: &str) -> Result<PathBuf, AppError> {
     Ok(models_dir(app)?.join(filename))
 }
 
@@ -81,7 +100,8 @@ fn is_downloaded(path: &PathBuf) -> bool {
 /// Return the model catalogue with `downloaded` status filled in.
 #[tauri::command]
 #[specta::specta]
-pub async fn list_whisper_models(app: AppHandle) -> Result<Vec<WhisperModelInfo>, String> {
+#[metrics::timed]
+pub async fn list_whisper_models(app: AppHandle) -> Result<Vec<WhisperModelInfo>, AppError> {
     let mut models = model_catalogue();
     for m in &mut models {
         let path = model_path(&app, &m.filename)?;
@@ -90,110 +110,533 @@ pub async fn list_whisper_models(app: AppHandle) -> Result<Vec<WhisperModelInfo>
     Ok(models)
 }
 
-/// Download a model by id. Emits `whisper-model-download-progress` events:
+/// Re-hashes every downloaded model against the checksum recorded at
+/// download and reports each one. Corrupt models are deleted so they can be
+/// downloaded again. Slow: every model file is read in full.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn verify_all_models(app: AppHandle) -> Result<Vec<ModelVerification>, AppError> {
+    let mut report = Vec::new();
+    for info in model_catalogue() {
+        let path = model_path(&app, &info.filename)?;
+        let status = if !is_downloaded(&path) {
+            ModelIntegrity::NotDownloaded
+        } else {
+            let check_path = path.clone();
+            match tokio::task::spawn_blocking(move || model_integrity::recheck(&check_path)).await {
+                Ok(Ok(Verdict::Intact)) => ModelIntegrity::Verified,
+                Ok(Ok(Verdict::Corrupt)) => {
+                    discard_corrupt_model(&info.id, &path);
+                    ModelIntegrity::Corrupt
+                }
+                Ok(Err(message)) => ModelIntegrity::Error { message },
+                Err(e) => ModelIntegrity::Error {
+                    message: format!("Model check panicked: {e}"),
+                },
+            }
+        };
+        report.push(ModelVerification {
+            model_id: info.id,
+            status,
+        });
+    }
+    Ok(report)
+}
+
+/// Diagnostics for the default model, which the quick pane, tray and command
+/// line transcribe with. Doesn't hash the model.
+pub(crate) fn default_model_diagnostics(app: &AppHandle) -> ModelDiagnostics {
+    let info = find_model(DEFAULT_MODEL_ID).expect("default model must be in catalogue");
+    let path = model_path(app, &info.filename);
+    let downloaded = path.as_ref().is_ok_and(is_downloaded);
+    let (verified, size_bytes) = match &path {
+        Ok(path) if downloaded => (
+            probe("the model checksum", model_integrity::is_recorded(path)),
+            probe(
+                "the model size",
+                std::fs::metadata(path).map(|metadata| metadata.len() as f64),
+            ),
+        ),
+        Ok(_) => (Probe::Known { value: false }, Probe::Known { value: 0.0 }),
+        Err(e) => (
+            probe("the model checksum", Err(e)),
+            probe("the model size", Err(e)),
+        ),
+    };
+    ModelDiagnostics {
+        model_id: info.id,
+        name: info.name,
+        downloaded,
+        verified,
+        size_bytes,
+    }
+}
+
+/// Path of the default model, or None when it isn't downloaded.
+pub(crate) fn default_model_path(app: &AppHandle) -> Result<Option<PathBuf>, AppError> {
+    let info = find_model(DEFAULT_MODEL_ID)?;
+    let path = model_path(app, &info.filename)?;
+    Ok(is_downloaded(&path).then_some(path))
+}
+
+/// Loads the model at `path` into whisper and releases it again, to prove it
+/// loads. Blocks for as long as loading takes.
+pub(crate) fn try_load_model(path: &Path) -> Result<(), AppError> {
+    let model_str = path
+        .to_str()
+        .ok_or_else(|| AppError::internal("Model path is not valid UTF-8"))?;
+    WhisperContext::new_with_params(model_str, WhisperContextParameters::default())?;
+    Ok(())
+}
+
+/// Download a model by id, as a cancellable `model_download` job. Also emits
+/// the older `whisper-model-download-progress` events:
 /// `{ "model_id": "...", "percent": 0..100 }`.
 #[tauri::command]
 #[specta::specta]
-pub async fn download_whisper_model(app: AppHandle, model_id: String) -> Result<(), String> {
-    let catalogue = model_catalogue();
-    let info = catalogue
-        .iter()
+#[metrics::timed]
+pub async fn download_whisper_model(app: AppHandle, model_id: String) -> Result<(), AppError> {
+    let info = find_model(&model_id)?;
+    fetch_model(&app, &info, false).await
+}
+
+/// Continue an interrupted download from its `.tmp` partial using a Range request.
+/// Falls back to a full download if the server ignores the range.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn resume_model_download(app: AppHandle, model_id: String) -> Result<(), AppError> {
+    let info = find_model(&model_id)?;
+    fetch_model(&app, &info, true).await
+}
+
+/// Attempts per download before a network failure is reported as terminal
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// Base delay between download attempts (multiplied by the attempt number)
+const DOWNLOAD_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+fn find_model(model_id: &str) -> Result<WhisperModelInfo, AppError> {
+    model_catalogue()
+        .into_iter()
         .find(|m| m.id == model_id)
-        .ok_or_else(|| format!("Unknown model id: {model_id}"))?;
+        .ok_or_else(|| AppError::invalid_input(format!("Unknown model id: {model_id}")))
+}
+
+/// Downloads a model as a job, retrying transient network failures (resuming
+/// the partial each time). Announces the outcome with `model-download-complete`
+/// / `model-download-failed` and, when the app is in the background, a
+/// notification. A cancelled download keeps its partial and isn't announced.
+async fn fetch_model(
+    app: &AppHandle,
+    info: &WhisperModelInfo,
+    resume: bool,
+) -> Result<(), AppError> {
+    let job = jobs::start(app, JobKind::ModelDownload);
+    let result = fetch_model_with_retries(app, &job, info, resume).await;
+    job.finish(&result, |()| JobOutput::ModelDownload {
+        model_id: info.id.clone(),
+    });
+    result
+}
+
+async fn fetch_model_with_retries(
+    app: &AppHandle,
+    job: &JobHandle,
+    info: &WhisperModelInfo,
+    resume: bool,
+) -> Result<(), AppError> {
+    let mut attempt = 1;
+    let result = loop {
+        match download_attempt(app, job, info, resume || attempt > 1).await {
+            Err(failure)
+                if failure.error == AppError::Offline && attempt < MAX_DOWNLOAD_ATTEMPTS =>
+            {
+                log::warn!(
+                    "Download of {} failed (attempt {attempt}), retrying: {}",
+                    info.id,
+                    failure.message
+                );
+                tokio::time::sleep(DOWNLOAD_RETRY_DELAY * attempt).await;
+                attempt += 1;
+            }
+            result => break result,
+        }
+    };
+
+    match result {
+        Ok(()) => {
+            notifications::notify_model_download_complete(app, &info.id);
+            Ok(())
+        }
+        Err(failure) if failure.error == AppError::Cancelled => {
+            log::info!("Download of {} cancelled", info.id);
+            Err(failure.error)
+        }
+        Err(failure) => {
+            log::error!("Download of {} failed: {}", info.id, failure.message);
+            notifications::notify_model_download_failed(
+                app,
+                ModelDownloadFailed {
+                    model_id: info.id.clone(),
+                    kind: failure.kind(),
+                    message: failure.message,
+                },
+            );
+            Err(failure.error)
+        }
+    }
+}
+
+/// Why a download attempt failed, for retry decisions and the failure notice.
+struct DownloadFailure {
+    error: AppError,
+    /// What went wrong, with the underlying error, for the log and the notice
+    message: String,
+}
+
+impl DownloadFailure {
+    fn new(error: AppError) -> Self {
+        let message = error.to_string();
+        Self { error, message }
+    }
+
+    fn network(context: &str, e: reqwest::Error) -> Self {
+        let message = format!("{context}: {e}");
+        Self {
+            error: e.into(),
+            message,
+        }
+    }
+
+    fn io(context: &str, e: std::io::Error) -> Self {
+        let message = format!("{context}: {e}");
+        Self {
+            error: e.into(),
+            message,
+        }
+    }
 
-    let path = model_path(&app, &info.filename)?;
+    fn kind(&self) -> ModelDownloadErrorKind {
+        match self.error {
+            AppError::Offline => ModelDownloadErrorKind::Offline,
+            AppError::ChecksumMismatch { .. } => ModelDownloadErrorKind::Checksum,
+            AppError::DiskFull { .. } => ModelDownloadErrorKind::DiskFull,
+            _ => ModelDownloadErrorKind::Other,
+        }
+    }
+}
+
+/// Reports download progress to the job and with the older
+/// `whisper-model-download-progress` event.
+fn report_download_progress(app: &AppHandle, job: &JobHandle, model_id: &str, percent: u32) {
+    job.progress(percent);
+    let payload = WhisperModelDownloadProgress {
+        model_id: model_id.to_string(),
+        percent,
+    };
+    let _ = payload.emit(app);
+}
+
+async fn download_attempt(
+    app: &AppHandle,
+    job: &JobHandle,
+    info: &WhisperModelInfo,
+    resume: bool,
+) -> Result<(), DownloadFailure> {
+    let model_id = &info.id;
+    let path = model_path(app, &info.filename).map_err(DownloadFailure::new)?;
 
     if is_downloaded(&path) {
         log::info!("Model {model_id} already downloaded");
         return Ok(());
     }
 
-    log::info!("Downloading Whisper model {model_id} from {}", info.url);
-    let _ = app.emit(
-        "whisper-model-download-progress",
-        serde_json::json!({ "model_id": model_id, "percent": 0 }),
+    if job.is_cancelled() {
+        return Err(DownloadFailure::new(AppError::Cancelled));
+    }
+
+    let tmp_path = path.with_extension("tmp");
+    let mut offset = if resume {
+        std::fs::metadata(&tmp_path).map(|m| m.len()).unwrap_or(0)
+    } else {
+        0
+    };
+
+    log::info!(
+        "Downloading Whisper model {model_id} from {} (offset {offset})",
+        info.url
     );
+    report_download_progress(app, job, model_id, 0);
 
-    let response = reqwest::get(&info.url)
+    let mut request = reqwest::Client::new().get(&info.url);
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={offset}-"));
+    }
+    let response = request
+        .send()
         .await
-        .map_err(|e| format!("Failed to download model: {e}"))?;
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| DownloadFailure::network("Failed to download model", e))?;
 
-    let total_size = response.content_length().unwrap_or(0);
-    let mut downloaded: u64 = 0;
+    if offset > 0 && response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+        log::warn!("Server ignored range request for {model_id}, restarting download");
+        offset = 0;
+    }
 
-    let tmp_path = path.with_extension("tmp");
-    let mut file = tokio::fs::File::create(&tmp_path)
-        .await
-        .map_err(|e| format!("Failed to create temp model file: {e}"))?;
+    let total_size = response.content_length().map_or(0, |len| len + offset);
+    check_download_space(app, &path, total_size.saturating_sub(offset))
+        .map_err(DownloadFailure::new)?;
+    let mut downloaded: u64 = offset;
+
+    let mut file = if offset > 0 {
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&tmp_path)
+            .await
+            .map_err(|e| DownloadFailure::io("Failed to open partial model file", e))?
+    } else {
+        tokio::fs::File::create(&tmp_path)
+            .await
+            .map_err(|e| DownloadFailure::io("Failed to create temp model file", e))?
+    };
 
     use futures_util::StreamExt;
     use tokio::io::AsyncWriteExt;
 
     let mut stream = response.bytes_stream();
     while let Some(chunk) = stream.next().await {
-        let chunk = chunk.map_err(|e| format!("Download stream error: {e}"))?;
+        // The partial is kept, so the download can be resumed later
+        if job.is_cancelled() {
+            return Err(DownloadFailure::new(AppError::Cancelled));
+        }
+        let chunk = chunk.map_err(|e| DownloadFailure::network("Download stream error", e))?;
         file.write_all(&chunk)
             .await
-            .map_err(|e| format!("Failed to write model chunk: {e}"))?;
+            .map_err(|e| DownloadFailure::io("Failed to write model chunk", e))?;
 
         downloaded += chunk.len() as u64;
         if total_size > 0 {
             let percent = (downloaded as f64 / total_size as f64 * 100.0) as u32;
-            let _ = app.emit(
-                "whisper-model-download-progress",
-                serde_json::json!({ "model_id": model_id, "percent": percent }),
-            );
+            report_download_progress(app, job, model_id, percent);
         }
     }
 
     file.flush()
         .await
-        .map_err(|e| format!("Failed to flush model file: {e}"))?;
+        .map_err(|e| DownloadFailure::io("Failed to flush model file", e))?;
+    drop(file);
+
+    // The server's length is the only integrity reference the catalogue has
+    if total_size > 0 && downloaded != total_size {
+        if downloaded > total_size {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(DownloadFailure {
+                error: AppError::ChecksumMismatch {
+                    model_id: model_id.clone(),
+                },
+                message: format!("Downloaded {downloaded} bytes, expected {total_size}"),
+            });
+        }
+        // Connection closed early; the partial is kept for the next attempt
+        return Err(DownloadFailure {
+            error: AppError::Offline,
+            message: format!("Download ended after {downloaded} of {total_size} bytes"),
+        });
+    }
 
     // Atomic rename prevents corrupt files on crash
-    std::fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to finalize model file: {e}"))?;
+    std::fs::rename(&tmp_path, &path)
+        .map_err(|e| DownloadFailure::io("Failed to finalize model file", e))?;
+    let record_path = path.clone();
+    match tokio::task::spawn_blocking(move || model_integrity::record(&record_path)).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => log::warn!("Failed to record checksum of {model_id}: {e}"),
+        Err(e) => log::warn!("Recording checksum of {model_id} panicked: {e}"),
+    }
 
-    let _ = app.emit(
-        "whisper-model-download-progress",
-        serde_json::json!({ "model_id": model_id, "percent": 100 }),
-    );
+    report_download_progress(app, job, model_id, 100);
     log::info!("Model {model_id} downloaded: {}", path.display());
 
     Ok(())
 }
 
+/// Refuses a download that can't fit and warns when it would leave less than
+/// `low_disk_warning_minutes` of 16kHz recording headroom afterwards.
+fn check_download_space(
+    app: &AppHandle,
+    model_path: &Path,
+    remaining: u64,
+) -> Result<(), AppError> {
+    let dir = model_path.parent().unwrap_or(model_path);
+    let available = match disk::available_bytes(dir) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            log::warn!("Could not check free disk space: {e}");
+            return Ok(());
+        }
+    };
+
+    let prefs = crate::commands::preferences::load_saved_preferences(app);
+    let headroom = disk::recording_bytes_per_minute(TARGET_SAMPLE_RATE, 1)
+        * u64::from(prefs.low_disk_warning_minutes);
+    let warn_below = remaining + headroom;
+
+    let level = disk::classify_headroom(available, warn_below, remaining);
+    if level == DiskHeadroom::Sufficient {
+        return Ok(());
+    }
+    let blocked = level == DiskHeadroom::Insufficient;
+    notifications::report_low_disk_space(
+        app,
+        LowDiskSpace {
+            context: DiskSpaceContext::ModelDownload,
+            available_bytes: available as f64,
+            required_bytes: warn_below as f64,
+            blocked,
+        },
+    );
+    if blocked {
+        return Err(AppError::DiskFull {
+            required: Some(remaining as f64),
+            available: Some(available as f64),
+        });
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Interrupted downloads
+// ---------------------------------------------------------------------------
+
+/// Partial downloads untouched for this long are deleted rather than offered
+const PARTIAL_DOWNLOAD_MAX_AGE_DAYS: u64 = 30;
+
+/// Whether a partial of `len` bytes can belong to a model of `expected` bytes.
+/// Catalogue sizes are approximate, so allow 10% slack above them.
+fn partial_is_plausible(len: u64, expected: u64) -> bool {
+    len > 0 && len <= expected + expected / 10
+}
+
+/// Finds `.tmp` partials left by an interrupted download. Stale or implausible
+/// partials are deleted; the rest are announced with `model-download-resumable`.
+/// Called from the startup recovery sweep.
+pub fn sweep_partial_downloads(app: &AppHandle) {
+    let dir = match models_dir(app) {
+        Ok(dir) => dir,
+        Err(e) => {
+            log::warn!("Partial download sweep skipped: {e}");
+            return;
+        }
+    };
+    let max_age = std::time::Duration::from_secs(PARTIAL_DOWNLOAD_MAX_AGE_DAYS * 24 * 60 * 60);
+
+    for info in model_catalogue() {
+        let tmp_path = dir.join(&info.filename).with_extension("tmp");
+        let Ok(metadata) = std::fs::metadata(&tmp_path) else {
+            continue;
+        };
+        let stale = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > max_age);
+
+        if stale || !partial_is_plausible(metadata.len(), u64::from(info.size_bytes)) {
+            log::info!("Deleting unusable partial download for {}", info.id);
+            if let Err(e) = std::fs::remove_file(&tmp_path) {
+                log::warn!("Failed to delete partial download {tmp_path:?}: {e}");
+            }
+            continue;
+        }
+
+        log::info!(
+            "Found resumable download for {} ({} bytes)",
+            info.id,
+            metadata.len()
+        );
+        if let Err(e) = crate::commands::navigation::emit_to_main_when_ready(
+            app,
+            ModelDownloadResumable {
+                model_id: info.id,
+                bytes_done: metadata.len() as f64,
+                total_bytes: f64::from(info.size_bytes),
+            },
+        ) {
+            log::warn!("Failed to announce resumable download: {e}");
+        }
+    }
+}
+
 /// Transcribe a 16kHz mono WAV file and delete it immediately after.
 ///
 /// `language` is an optional ISO 639-1 code (e.g. "en", "no").
 /// Pass `None` to auto-detect. `model_id` selects which model to use;
 /// if the model is not downloaded it falls back to `whisper-large-v3-turbo`.
+/// With `auto_save`, the transcript is also saved to the `transcripts` table
+/// before returning (announced via `transcript-saved`), so it survives a
+/// webview crash between this call returning and the frontend saving it.
+/// The saved transcript records the audio duration, language, the model that
+/// actually ran and `session_label`, and is de-identified first when the
+/// `apply_to_auto_saved` de-identification preference is on. The returned
+/// text is never scrubbed.
+/// While the app is locked the text is saved (whatever `auto_save` says) but
+/// neither delivered nor returned; the call fails with `Locked`.
+/// `delivery` decides whether the text is put on the clipboard as soon as
+/// it's ready (announced via `transcript-copied`), pasted into the app that
+/// had focus when the quick pane opened, and which formatting profile it gets
+/// on the way (see `output`). The returned and saved text is never formatted.
 #[tauri::command]
 #[specta::specta]
+#[metrics::timed(response_size)]
 pub async fn transcribe_and_delete(
     app: AppHandle,
     file_path: String,
     language: Option<String>,
     model_id: String,
-) -> Result<String, String> {
+    auto_save: Option<bool>,
+    session_label: Option<String>,
+    delivery: Option<TranscriptDelivery>,
+) -> Result<String, AppError> {
     let wav_path = PathBuf::from(&file_path);
 
     if !wav_path.exists() {
-        return Err(format!("Audio file not found: {file_path}"));
+        return Err(AppError::invalid_input(format!(
+            "Audio file not found: {file_path}"
+        )));
     }
 
-    // Resolve model path — fall back to default if requested model not downloaded
-    let resolved_model_path = resolve_model(&app, &model_id)?;
+    let duration_secs = crate::utils::audio::wav_duration_secs(&wav_path).unwrap_or(0.0);
+    let result = transcribe_file(&app, wav_path, language.clone(), &model_id).await;
 
-    log::info!(
-        "Transcription request — language: {}, model: {model_id}",
-        language.as_deref().unwrap_or("auto")
-    );
-
-    let result = tokio::task::spawn_blocking(move || {
-        run_transcription(&resolved_model_path, &wav_path, language)
-    })
-    .await
-    .map_err(|e| format!("Transcription task panicked: {e}"))?;
+    // A lock that came on while transcribing withholds the text; it's saved
+    // instead so nothing is lost, and waits behind the lock
+    let unlocked = app_lock::ensure_unlocked(&app).is_ok();
+    if let Ok(transcribed) = &result {
+        if unlocked {
+            output::deliver(&app, transcribed, delivery.unwrap_or_default()).await;
+        }
+        if auto_save.unwrap_or(false) || !unlocked {
+            let new = TranscriptNew {
+                title: String::new(),
+                text: transcribed.text.clone(),
+                language,
+                model_id: Some(transcribed.model_id.clone()),
+                duration_secs,
+                note_id: None,
+                session_label,
+                tags: Vec::new(),
+                deidentify: crate::commands::preferences::load_saved_preferences(&app)
+                    .deidentification
+                    .apply_to_auto_saved,
+                reject_duplicates: false,
+            };
+            auto_save_transcript(&app, new);
+        }
+        // The transcript exists now, so the crash-recovery copy is no longer needed
+        recovery::complete_session_for_audio(&app, Path::new(&file_path));
+    }
 
     // HIPAA: delete audio file regardless of transcription outcome
     if let Err(e) = std::fs::remove_file(&file_path) {
@@ -202,23 +645,169 @@ pub async fn transcribe_and_delete(
         log::info!("Deleted ephemeral audio: {file_path}");
     }
 
-    result
+    let text = result?.text;
+    app_lock::ensure_unlocked(&app)?;
+    Ok(text)
 }
 
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
-/// Resolve a model id to a local path, falling back to the default model
-/// (auto-downloading it if needed) when the requested model is not present.
-fn resolve_model(app: &AppHandle, model_id: &str) -> Result<PathBuf, String> {
+/// Saves a finished transcript on the caller's behalf. A failed save is
+/// logged; the text is still returned so the frontend can save it itself.
+fn auto_save_transcript(app: &AppHandle, new: TranscriptNew) {
+    match transcripts::insert_transcript(app, new) {
+        Ok(saved) => {
+            let payload = TranscriptSaved {
+                transcript_id: saved.transcript.id,
+                duplicate_of: saved.duplicate_of,
+            };
+            if let Err(e) = payload.emit(app) {
+                log::warn!("Failed to emit transcript-saved: {e}");
+            }
+        }
+        Err(e) => log::error!("Failed to auto-save transcript: {e}"),
+    }
+}
+
+/// Text produced by `transcribe_file`, and the model that produced it.
+pub(crate) struct Transcribed {
+    pub text: String,
+    /// `text` in whisper's timed pieces, for formatting that needs timing
+    pub segments: Vec<TranscriptSegment>,
+    /// Differs from the requested model when that one wasn't downloaded
+    pub model_id: String,
+}
+
+/// Transcribes a 16kHz mono WAV as a `transcription` job, publishing pipeline
+/// status along the way. Continues the current pipeline job when one is in
+/// flight (a just-stopped recording or a recovery), otherwise starts a new
+/// one. Does not delete the file.
+pub(crate) async fn transcribe_file(
+    app: &AppHandle,
+    wav_path: PathBuf,
+    language: Option<String>,
+    model_id: &str,
+) -> Result<Transcribed, AppError> {
+    let job = jobs::start(app, JobKind::Transcription);
+    let result = transcribe_with_progress(app, wav_path, language, model_id, job.reporter()).await;
+    job.finish(&result, |transcribed| JobOutput::Transcription {
+        model_id: transcribed.model_id.clone(),
+        text_length: transcribed.text.chars().count() as u32,
+    });
+    result
+}
+
+async fn transcribe_with_progress(
+    app: &AppHandle,
+    wav_path: PathBuf,
+    language: Option<String>,
+    model_id: &str,
+    on_progress: impl Fn(u32) + Send + 'static,
+) -> Result<Transcribed, AppError> {
+    // Resolve model path — fall back to default if requested model not downloaded
+    let (resolved_model_id, resolved_model_path) = resolve_model(app, model_id)?;
+
+    log::info!(
+        "Transcription request — language: {}, model: {model_id}",
+        language.as_deref().unwrap_or("auto")
+    );
+
+    let job_id = pipeline::continue_or_begin_job(app, PipelineStage::Transcribing { percent: 0 });
+    if let Err(e) = check_model_integrity(&resolved_model_id, resolved_model_path.clone()).await {
+        pipeline::publish(
+            app,
+            PipelineStage::Error {
+                kind: PipelineErrorKind::ModelCorrupt,
+            },
+        );
+        return Err(e);
+    }
+    let audio_secs = crate::utils::audio::wav_duration_secs(&wav_path).unwrap_or(0.0);
+
+    let progress_app = app.clone();
+    let started = std::time::Instant::now();
+    let result = tokio::task::spawn_blocking(move || {
+        run_transcription(&resolved_model_path, &wav_path, language, move |percent| {
+            pipeline::publish(&progress_app, PipelineStage::Transcribing { percent });
+            on_progress(percent);
+        })
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Transcription task panicked: {e}")))?;
+
+    let result = result.map(|segments| (formatting::join_segments(&segments), segments));
+    match &result {
+        Ok((text, _)) => {
+            stats::record_transcription(app, &resolved_model_id, audio_secs, started.elapsed());
+            pipeline::publish(
+                app,
+                PipelineStage::Done {
+                    text_length: text.chars().count() as u32,
+                },
+            );
+            notifications::notify_transcription_complete(app, &job_id, audio_secs, text);
+        }
+        Err(_) => pipeline::publish(
+            app,
+            PipelineStage::Error {
+                kind: PipelineErrorKind::Transcription,
+            },
+        ),
+    }
+
+    result.map(|(text, segments)| Transcribed {
+        text,
+        segments,
+        model_id: resolved_model_id,
+    })
+}
+
+/// Refuses a model whose file changed since it was verified and no longer
+/// matches its checksum. The file is deleted so the model shows as not
+/// downloaded and can be fetched again.
+async fn check_model_integrity(model_id: &str, path: PathBuf) -> Result<(), AppError> {
+    let check_path = path.clone();
+    let verdict =
+        tokio::task::spawn_blocking(move || model_integrity::check_before_load(&check_path))
+            .await
+            .map_err(|e| AppError::internal(format!("Model check panicked: {e}")))?;
+    match verdict {
+        Ok(Verdict::Intact) => Ok(()),
+        Ok(Verdict::Corrupt) => {
+            discard_corrupt_model(model_id, &path);
+            Err(AppError::ChecksumMismatch {
+                model_id: model_id.to_string(),
+            })
+        }
+        Err(e) => Err(AppError::internal(format!(
+            "Model {model_id} could not be verified: {e}"
+        ))),
+    }
+}
+
+fn discard_corrupt_model(model_id: &str, path: &Path) {
+    log::error!("Model {model_id} failed its integrity check; deleting it");
+    if let Err(e) = std::fs::remove_file(path) {
+        log::warn!("Failed to delete corrupt model {model_id}: {e}");
+    }
+    if let Err(e) = model_integrity::forget(path) {
+        log::warn!("Failed to forget checksum of {model_id}: {e}");
+    }
+}
+
+/// Resolve a model id to the id and local path of the model to run, falling
+/// back to the default model (auto-downloading it if needed) when the
+/// requested model is not present.
+fn resolve_model(app: &AppHandle, model_id: &str) -> Result<(String, PathBuf), AppError> {
     let catalogue = model_catalogue();
 
     // Try the requested model first
     if let Some(info) = catalogue.iter().find(|m| m.id == model_id) {
         let path = model_path(app, &info.filename)?;
         if is_downloaded(&path) {
-            return Ok(path);
+            return Ok((info.id.clone(), path));
         }
         log::warn!("Model {model_id} not downloaded — falling back to whisper-large-v3-turbo");
     } else {
@@ -228,7 +817,7 @@ fn resolve_model(app: &AppHandle, model_id: &str) -> Result<PathBuf, String> {
     // Fall back to default model (blocking download if needed)
     let default = catalogue
         .iter()
-        .find(|m| m.id == "whisper-large-v3-turbo")
+        .find(|m| m.id == DEFAULT_MODEL_ID)
         .expect("default model must be in catalogue");
     let default_path = model_path(app, &default.filename)?;
 
@@ -237,22 +826,23 @@ fn resolve_model(app: &AppHandle, model_id: &str) -> Result<PathBuf, String> {
         // but resolve_model itself is sync — use a blocking reqwest call)
         log::info!("Default model not found, triggering download");
         // We can't await here; return an error so the frontend can trigger a proper download
-        return Err(
-            "Default model not downloaded. Please transcribe once to trigger download.".into(),
-        );
+        return Err(AppError::ModelNotDownloaded {
+            model_id: default.id.clone(),
+        });
     }
 
-    Ok(default_path)
+    Ok((default.id.clone(), default_path))
 }
 
-/// Run whisper.cpp transcription on a 16kHz mono WAV file.
+/// Run whisper.cpp transcription on a 16kHz mono WAV file, returning the
+/// timed segments. `on_progress` receives whisper's progress percentage (0-100).
 fn run_transcription(
     model_path: &std::path::Path,
     wav_path: &std::path::Path,
     language: Option<String>,
-) -> Result<String, String> {
-    let mut reader =
-        hound::WavReader::open(wav_path).map_err(|e| format!("Failed to open WAV: {e}"))?;
+    on_progress: impl FnMut(u32) + 'static,
+) -> Result<Vec<TranscriptSegment>, AppError> {
+    let mut reader = hound::WavReader::open(wav_path)?;
 
     let spec = reader.spec();
     log::info!(
@@ -263,29 +853,32 @@ fn run_transcription(
         spec.sample_format
     );
 
-    let samples: Vec<f32> = match spec.sample_format {
-        hound::SampleFormat::Int => reader
-            .samples::<i16>()
-            .filter_map(|s| s.ok())
-            .map(|s| s as f32 / i16::MAX as f32)
-            .collect(),
-        hound::SampleFormat::Float => reader.samples::<f32>().filter_map(|s| s.ok()).collect(),
-    };
+    // Sized from the header so collecting never reallocates; zeroed on drop
+    let mut samples = Zeroizing::new(Vec::with_capacity(reader.len() as usize));
+    match spec.sample_format {
+        hound::SampleFormat::Int => samples.extend(
+            reader
+                .samples::<i16>()
+                .filter_map(|s| s.ok())
+                .map(|s| s as f32 / i16::MAX as f32),
+        ),
+        hound::SampleFormat::Float => {
+            samples.extend(reader.samples::<f32>().filter_map(|s| s.ok()))
+        }
+    }
 
     if samples.is_empty() {
-        return Err("WAV file contains no audio data".into());
+        return Err(AppError::NoAudio);
     }
 
     log::info!("Loaded {} audio samples for transcription", samples.len());
 
-    let model_str = model_path.to_str().ok_or("Model path is not valid UTF-8")?;
+    let model_str = model_path
+        .to_str()
+        .ok_or_else(|| AppError::internal("Model path is not valid UTF-8"))?;
 
-    let ctx = WhisperContext::new_with_params(model_str, WhisperContextParameters::default())
-        .map_err(|e| format!("Failed to load Whisper model: {e}"))?;
-
-    let mut state = ctx
-        .create_state()
-        .map_err(|e| format!("Failed to create Whisper state: {e}"))?;
+    let ctx = WhisperContext::new_with_params(model_str, WhisperContextParameters::default())?;
+    let mut state = ctx.create_state()?;
 
     let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
     params.set_language(language.as_deref());
@@ -293,31 +886,50 @@ fn run_transcription(
     params.set_print_realtime(false);
     params.set_print_timestamps(false);
     params.set_single_segment(false);
-    params.set_no_timestamps(true);
+    // Segment timestamps give paragraphs-on-pause formatting its breaks
+    params.set_no_timestamps(false);
+
+    let mut on_progress = on_progress;
+    params
+        .set_progress_callback_safe(move |percent: i32| on_progress(percent.clamp(0, 100) as u32));
 
-    state
-        .full(params, &samples)
-        .map_err(|e| format!("Transcription failed: {e}"))?;
+    state.full(params, &samples)?;
 
     let num_segments = state.full_n_segments();
 
-    let mut text = String::new();
-    for segment in state.as_iter() {
-        if let Ok(segment_text) = segment.to_str_lossy() {
-            let trimmed = segment_text.trim();
-            if !trimmed.is_empty() {
-                if !text.is_empty() {
-                    text.push(' ');
-                }
-                text.push_str(trimmed);
-            }
-        }
-    }
+    // Whisper timestamps are in centiseconds
+    let segments: Vec<TranscriptSegment> = state
+        .as_iter()
+        .filter_map(|segment| {
+            let text = segment.to_str_lossy().ok()?.trim().to_string();
+            (!text.is_empty()).then(|| TranscriptSegment {
+                start_secs: segment.start_timestamp() as f64 / 100.0,
+                end_secs: segment.end_timestamp() as f64 / 100.0,
+                text,
+            })
+        })
+        .collect();
 
     log::info!(
         "Transcribed {} segments, {} chars",
         num_segments,
-        text.len()
+        segments
+            .iter()
+            .map(|segment| segment.text.len())
+            .sum::<usize>()
     );
-    Ok(text)
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_is_plausible() {
+        assert!(partial_is_plausible(1, 574_000_000));
+        assert!(partial_is_plausible(574_041_195, 574_000_000));
+        assert!(!partial_is_plausible(0, 574_000_000));
+        assert!(!partial_is_plausible(700_000_000, 574_000_000));
+    }
 }
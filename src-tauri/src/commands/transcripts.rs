@@ -0,0 +1,1001 @@
+//! Saved transcript commands.
+//!
+//! Transcripts live in the backend-owned `transcripts` table so validation,
+//! retention and audit logging happen in one place instead of in frontend SQL.
+//! Views, edits, creation and deletion are audited by id; content never
+//! reaches the log.
+//!
+//! Saves are checked against today's transcripts for near-duplicates (see
+//! `utils::duplicates`), reported as `duplicate_of` or refused with
+//! `reject_duplicates`.
+//!
+//! Saving with `deidentify` scrubs identifiers from the title and text first
+//! (see `utils::deidentify`); only the scrubbed text is ever stored.
+//!
+//! Deleting a transcript moves it to the trash; it's purged for good by
+//! `purge_transcript` or once it has been there `trash_retention_days`.
+//!
+//! The table is in the encrypted backend database (see `db`). When that
+//! database couldn't be opened, every command fails and `get_database_status`
+//! says why.
+
+use rusqlite::{params_from_iter, types::Value as SqlValue, Connection, OptionalExtension, Row};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::{audit, pipeline, preferences, revisions, tags};
+use crate::db::Database;
+use crate::types::{
+    count_words, validate_filename, validate_string_input, AuditEventKind, DatabaseStatus,
+    DatabaseUnavailable, DeidentificationReport, DeidentifiedText, Pagination, SavedTranscript,
+    SnippetSegment, Transcript, TranscriptError, TranscriptFilter, TranscriptNew, TranscriptPage,
+    TranscriptPatch, TranscriptSearchHit, TranscriptSummary, TranscriptSummaryPage,
+    DEFAULT_TRANSCRIPT_PAGE_SIZE, MAX_SESSION_LABEL_CHARS, MAX_TRANSCRIPT_BYTES,
+    MAX_TRANSCRIPT_PAGE_SIZE, MAX_TRANSCRIPT_TITLE_CHARS,
+};
+use crate::utils::{deidentify, duplicates, metrics};
+
+const TRANSCRIPT_COLUMNS: &str = "id, title, text, language, model_id, duration_secs, note_id, \
+     created_at, updated_at, word_count, session_label, deleted_at, deidentified_with";
+
+/// Everything in `TRANSCRIPT_COLUMNS` except the text, for list results
+const SUMMARY_COLUMNS: &str = "id, title, language, model_id, duration_secs, note_id, \
+     created_at, updated_at, word_count, session_label, deleted_at, deidentified_with";
+
+/// The row's tags, joined with the unit separator
+const TAGS_COLUMN: &str = "(SELECT group_concat(tags.name, char(31)) FROM transcript_tags
+      JOIN tags ON tags.id = transcript_tags.tag_id
+      WHERE transcript_tags.transcript_id = transcripts.id)";
+
+fn database_error(message: String) -> TranscriptError {
+    TranscriptError::DatabaseError { message }
+}
+
+fn validation_error(message: String) -> TranscriptError {
+    TranscriptError::ValidationError { message }
+}
+
+fn validate_text(text: &str) -> Result<(), TranscriptError> {
+    if text.len() > MAX_TRANSCRIPT_BYTES as usize {
+        return Err(TranscriptError::DataTooLarge {
+            max_bytes: MAX_TRANSCRIPT_BYTES,
+        });
+    }
+    Ok(())
+}
+
+fn validate_title(title: &str) -> Result<(), TranscriptError> {
+    validate_string_input(title, MAX_TRANSCRIPT_TITLE_CHARS, "Title").map_err(validation_error)
+}
+
+/// Trims a session label; an empty label means none.
+fn normalize_session_label(label: Option<String>) -> Result<Option<String>, TranscriptError> {
+    let label = label
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty());
+    if let Some(label) = &label {
+        validate_string_input(label, MAX_SESSION_LABEL_CHARS, "Session label")
+            .map_err(validation_error)?;
+    }
+    Ok(label)
+}
+
+/// Ids are generated here, but commands also accept ids from the frontend;
+/// hold them to the same character set as other stored identifiers.
+pub(crate) fn validate_id(id: &str) -> Result<(), TranscriptError> {
+    validate_filename(id).map_err(validation_error)
+}
+
+fn new_transcript_id() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    format!("tr_{nanos:x}")
+}
+
+fn split_tags(joined: Option<String>) -> Vec<String> {
+    let mut tags: Vec<String> = joined
+        .map(|joined| joined.split('\u{1f}').map(str::to_string).collect())
+        .unwrap_or_default();
+    tags.sort();
+    tags
+}
+
+/// Maps a `SELECT {TRANSCRIPT_COLUMNS}, {TAGS_COLUMN}` row.
+fn row_to_transcript(row: &Row) -> rusqlite::Result<Transcript> {
+    Ok(Transcript {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        text: row.get(2)?,
+        language: row.get(3)?,
+        model_id: row.get(4)?,
+        duration_secs: row.get(5)?,
+        note_id: row.get(6)?,
+        created_at_ms: row.get::<_, i64>(7)? as f64,
+        updated_at_ms: row.get::<_, i64>(8)? as f64,
+        word_count: row.get(9)?,
+        session_label: row.get(10)?,
+        deleted_at_ms: row.get::<_, Option<i64>>(11)?.map(|ms| ms as f64),
+        deidentified_with: row.get(12)?,
+        tags: split_tags(row.get(13)?),
+    })
+}
+
+/// Maps a `SELECT {SUMMARY_COLUMNS}, {TAGS_COLUMN}` row.
+fn row_to_summary(row: &Row) -> rusqlite::Result<TranscriptSummary> {
+    Ok(TranscriptSummary {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        language: row.get(2)?,
+        model_id: row.get(3)?,
+        duration_secs: row.get(4)?,
+        note_id: row.get(5)?,
+        created_at_ms: row.get::<_, i64>(6)? as f64,
+        updated_at_ms: row.get::<_, i64>(7)? as f64,
+        word_count: row.get(8)?,
+        session_label: row.get(9)?,
+        deleted_at_ms: row.get::<_, Option<i64>>(10)?.map(|ms| ms as f64),
+        deidentified_with: row.get(11)?,
+        tags: split_tags(row.get(12)?),
+    })
+}
+
+pub(crate) fn query_transcript(
+    database: &Database,
+    id: &str,
+) -> Result<Option<Transcript>, String> {
+    database.with_conn(|conn| {
+        conn.query_row(
+            &format!("SELECT {TRANSCRIPT_COLUMNS}, {TAGS_COLUMN} FROM transcripts WHERE id = ?1"),
+            [id],
+            row_to_transcript,
+        )
+        .optional()
+    })
+}
+
+/// Builds the WHERE clause and its parameters for a list filter.
+fn filter_clause(filter: &TranscriptFilter) -> (String, Vec<SqlValue>) {
+    let mut conditions = Vec::new();
+    let mut params = Vec::new();
+    if let Some(note_id) = &filter.note_id {
+        conditions.push("note_id = ?");
+        params.push(SqlValue::Text(note_id.clone()));
+    }
+    if let Some(after) = filter.created_after_ms {
+        conditions.push("created_at >= ?");
+        params.push(SqlValue::Integer(after as i64));
+    }
+    if let Some(before) = filter.created_before_ms {
+        conditions.push("created_at < ?");
+        params.push(SqlValue::Integer(before as i64));
+    }
+    let tag_filter = tags::tag_condition(&filter.tags, filter.tag_match);
+    if let Some((condition, tag_params)) = &tag_filter {
+        conditions.push(condition.as_str());
+        params.extend(tag_params.iter().cloned());
+    }
+    if !filter.include_deleted {
+        conditions.push("deleted_at IS NULL");
+    }
+
+    if conditions.is_empty() {
+        (String::new(), params)
+    } else {
+        (format!(" WHERE {}", conditions.join(" AND ")), params)
+    }
+}
+
+/// Most recent transcripts compared word by word when looking for a
+/// near-duplicate. Exact matches are found through the hash index regardless.
+const MAX_DUPLICATE_CANDIDATES: u32 = 100;
+
+/// Start of the current local day, falling back to 24 hours ago when local
+/// midnight doesn't exist (DST transitions).
+fn start_of_today_ms() -> i64 {
+    chrono::Local::now()
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| midnight.and_local_timezone(chrono::Local).earliest())
+        .map(|start| start.timestamp_millis())
+        .unwrap_or_else(|| (pipeline::now_ms() - audit::MS_PER_DAY) as i64)
+}
+
+/// The most recent transcript created since `since_ms` that looks like the
+/// same dictation as `text`: the same content hash, or token overlap above
+/// `FUZZY_DUPLICATE_THRESHOLD`, and a similar duration either way. Trashed
+/// transcripts don't count.
+fn find_duplicate(
+    conn: &Connection,
+    text: &str,
+    duration_secs: f64,
+    since_ms: i64,
+) -> rusqlite::Result<Option<String>> {
+    let tokens = duplicates::tokens(text);
+    if tokens.is_empty() {
+        return Ok(None);
+    }
+
+    let mut exact = conn.prepare(
+        "SELECT id, duration_secs FROM transcripts
+         WHERE content_hash = ?1 AND created_at >= ?2 AND deleted_at IS NULL
+         ORDER BY created_at DESC",
+    )?;
+    let mut rows = exact.query(rusqlite::params![duplicates::content_hash(text), since_ms])?;
+    while let Some(row) = rows.next()? {
+        if duplicates::similar_duration(duration_secs, row.get(1)?) {
+            return Ok(Some(row.get(0)?));
+        }
+    }
+
+    let mut recent = conn.prepare(
+        "SELECT id, text, duration_secs FROM transcripts
+         WHERE created_at >= ?1 AND deleted_at IS NULL
+         ORDER BY created_at DESC LIMIT ?2",
+    )?;
+    let mut rows = recent.query(rusqlite::params![since_ms, MAX_DUPLICATE_CANDIDATES])?;
+    while let Some(row) = rows.next()? {
+        if !duplicates::similar_duration(duration_secs, row.get(2)?) {
+            continue;
+        }
+        let candidate = duplicates::tokens(&row.get::<_, String>(1)?);
+        if duplicates::token_overlap(&tokens, &candidate) > duplicates::FUZZY_DUPLICATE_THRESHOLD {
+            return Ok(Some(row.get(0)?));
+        }
+    }
+    Ok(None)
+}
+
+/// Inserts a transcript and returns it. Shared by `save_transcript` and
+/// `transcribe_and_delete`'s auto-save.
+pub(crate) fn insert_transcript(
+    app: &AppHandle,
+    mut new: TranscriptNew,
+) -> Result<SavedTranscript, TranscriptError> {
+    validate_title(&new.title)?;
+    validate_text(&new.text)?;
+    if let Some(note_id) = &new.note_id {
+        validate_id(note_id)?;
+    }
+    let tags = tags::normalize_tags(&new.tags)?;
+    let session_label = normalize_session_label(new.session_label)?;
+
+    let mut deidentification = None;
+    let mut deidentified_with = None;
+    if new.deidentify {
+        let rules = preferences::load_saved_preferences(app).deidentification;
+        let mut report = DeidentificationReport::default();
+        new.title =
+            deidentify::deidentify(&new.title, &rules, &mut report).map_err(validation_error)?;
+        new.text =
+            deidentify::deidentify(&new.text, &rules, &mut report).map_err(validation_error)?;
+        // Placeholders can be longer than what they replaced
+        validate_title(&new.title)?;
+        validate_text(&new.text)?;
+        deidentification = Some(report);
+        deidentified_with = Some(deidentify::rule_set_version(&rules));
+    }
+
+    let now = pipeline::now_ms();
+    let transcript = Transcript {
+        id: new_transcript_id(),
+        title: new.title,
+        word_count: count_words(&new.text),
+        session_label,
+        text: new.text,
+        language: new.language,
+        model_id: new.model_id,
+        duration_secs: new.duration_secs.max(0.0),
+        note_id: new.note_id,
+        tags,
+        deidentified_with,
+        created_at_ms: now,
+        updated_at_ms: now,
+        deleted_at_ms: None,
+    };
+
+    let reject_duplicates = new.reject_duplicates;
+    let duplicate_of = app
+        .state::<Database>()
+        .with_conn(|conn| {
+            let tx = conn.transaction()?;
+            let duplicate_of = find_duplicate(
+                &tx,
+                &transcript.text,
+                transcript.duration_secs,
+                start_of_today_ms(),
+            )?;
+            if duplicate_of.is_some() && reject_duplicates {
+                return Ok(duplicate_of);
+            }
+            tx.execute(
+                &format!(
+                    "INSERT INTO transcripts ({TRANSCRIPT_COLUMNS}, content_hash)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8, ?9, ?10, NULL, ?11, ?12)"
+                ),
+                rusqlite::params![
+                    transcript.id,
+                    transcript.title,
+                    transcript.text,
+                    transcript.language,
+                    transcript.model_id,
+                    transcript.duration_secs,
+                    transcript.note_id,
+                    now as i64,
+                    transcript.word_count,
+                    transcript.session_label,
+                    transcript.deidentified_with,
+                    duplicates::content_hash(&transcript.text),
+                ],
+            )?;
+            tags::replace_tags(&tx, &transcript.id, &transcript.tags)?;
+            tx.commit().map(|_| duplicate_of)
+        })
+        .map_err(database_error)?;
+    if let (Some(id), true) = (&duplicate_of, reject_duplicates) {
+        log::info!("Rejected duplicate of transcript {id}");
+        return Err(TranscriptError::Duplicate { id: id.clone() });
+    }
+
+    let detail = transcript
+        .deidentified_with
+        .as_ref()
+        .map(|rule_set| format!("deidentified rule_set={rule_set}"))
+        .unwrap_or_default();
+    audit::record(
+        app,
+        AuditEventKind::TranscriptSaved,
+        Some(&transcript.id),
+        &detail,
+    );
+    log::info!("Saved transcript {}", transcript.id);
+    Ok(SavedTranscript {
+        transcript,
+        deidentification,
+        duplicate_of,
+    })
+}
+
+/// Reports whether the transcripts database opened, and if not, why.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn get_database_status(app: AppHandle) -> DatabaseStatus {
+    app.state::<Database>().status()
+}
+
+/// Tells the main window the database is unusable so it can explain why
+/// rather than show an empty library, or that it was rebuilt after corruption
+/// (with what was salvaged). Called from setup().
+pub fn announce_database_status(app: &AppHandle) {
+    if let Some(recovered) = app.state::<Database>().take_recovery() {
+        if let Err(e) = crate::commands::navigation::emit_to_main_when_ready(app, recovered) {
+            log::warn!("Failed to announce database recovery: {e}");
+        }
+    }
+
+    let status = app.state::<Database>().status();
+    if matches!(status, DatabaseStatus::Ready | DatabaseStatus::Locked) {
+        return;
+    }
+    if let Err(e) =
+        crate::commands::navigation::emit_to_main_when_ready(app, DatabaseUnavailable(status))
+    {
+        log::warn!("Failed to announce database status: {e}");
+    }
+}
+
+/// Saves a new transcript and returns it with its assigned id, plus the
+/// de-identification report when `deidentify` was set and the id of an
+/// earlier duplicate from today, if there is one.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed(response_size)]
+pub async fn save_transcript(
+    app: AppHandle,
+    transcript: TranscriptNew,
+) -> Result<SavedTranscript, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    insert_transcript(&app, transcript)
+}
+
+/// Applies the saved de-identification rules to `text` without storing
+/// anything, so the result can be previewed before saving.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed(response_size)]
+pub async fn deidentify_text(
+    app: AppHandle,
+    text: String,
+) -> Result<DeidentifiedText, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    validate_text(&text)?;
+    let rules = preferences::load_saved_preferences(&app).deidentification;
+    let mut report = DeidentificationReport::default();
+    let text = deidentify::deidentify(&text, &rules, &mut report).map_err(validation_error)?;
+    Ok(DeidentifiedText {
+        text,
+        report,
+        rule_set: deidentify::rule_set_version(&rules),
+    })
+}
+
+/// Loads a single transcript.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed(response_size)]
+pub async fn get_transcript(app: AppHandle, id: String) -> Result<Transcript, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    validate_id(&id)?;
+    let transcript = query_transcript(&app.state::<Database>(), &id)
+        .map_err(database_error)?
+        .ok_or(TranscriptError::NotFound { id })?;
+    audit::record(
+        &app,
+        AuditEventKind::TranscriptViewed,
+        Some(&transcript.id),
+        "",
+    );
+    Ok(transcript)
+}
+
+/// One page of transcripts matching `filter`, most recently created first.
+pub(crate) fn query_transcript_page(
+    database: &Database,
+    filter: &TranscriptFilter,
+    limit: u32,
+    offset: u32,
+) -> Result<TranscriptPage, String> {
+    let (transcripts, total) = query_page(
+        database,
+        filter,
+        TRANSCRIPT_COLUMNS,
+        row_to_transcript,
+        limit,
+        offset,
+    )?;
+    Ok(TranscriptPage { transcripts, total })
+}
+
+/// Selects `columns` (plus tags) for one page of filtered transcripts, with
+/// the total number of matches.
+fn query_page<T>(
+    database: &Database,
+    filter: &TranscriptFilter,
+    columns: &str,
+    map_row: fn(&Row) -> rusqlite::Result<T>,
+    limit: u32,
+    offset: u32,
+) -> Result<(Vec<T>, u32), String> {
+    let (clause, mut params) = filter_clause(filter);
+
+    database.with_conn(|conn| {
+        let total: u32 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM transcripts{clause}"),
+            params_from_iter(params.iter()),
+            |row| row.get(0),
+        )?;
+
+        params.push(SqlValue::Integer(i64::from(limit)));
+        params.push(SqlValue::Integer(i64::from(offset)));
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {columns}, {TAGS_COLUMN} FROM transcripts{clause}
+             ORDER BY created_at DESC, id DESC LIMIT ? OFFSET ?"
+        ))?;
+        let rows = stmt
+            .query_map(params_from_iter(params.iter()), map_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok((rows, total))
+    })
+}
+
+/// Lists transcripts matching `filter`, most recently created first. Results
+/// carry the session metadata but not the text; `get_transcript` loads that.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn list_transcripts(
+    app: AppHandle,
+    filter: Option<TranscriptFilter>,
+    pagination: Option<Pagination>,
+) -> Result<TranscriptSummaryPage, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let pagination = pagination.unwrap_or_default();
+    let (transcripts, total) = query_page(
+        &app.state::<Database>(),
+        &filter.unwrap_or_default(),
+        SUMMARY_COLUMNS,
+        row_to_summary,
+        pagination.limit.clamp(1, MAX_TRANSCRIPT_PAGE_SIZE),
+        pagination.offset,
+    )
+    .map_err(database_error)?;
+    Ok(TranscriptSummaryPage { transcripts, total })
+}
+
+/// Applies a partial update and returns the updated transcript.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed(response_size)]
+pub async fn update_transcript(
+    app: AppHandle,
+    id: String,
+    patch: TranscriptPatch,
+) -> Result<Transcript, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    validate_id(&id)?;
+    if let Some(title) = &patch.title {
+        validate_title(title)?;
+    }
+    if let Some(text) = &patch.text {
+        validate_text(text)?;
+    }
+    // An empty note id detaches the transcript from its note
+    let note_id = match patch.note_id.as_deref() {
+        Some("") => Some(None),
+        Some(note_id) => {
+            validate_id(note_id)?;
+            Some(Some(note_id.to_string()))
+        }
+        None => None,
+    };
+
+    let database = app.state::<Database>();
+    let updated = database
+        .with_conn(|conn| {
+            let tx = conn.transaction()?;
+            // Keep the version being replaced when the body changes
+            if let Some(text) = &patch.text {
+                revisions::snapshot_before_edit(&tx, &id, text)?;
+            }
+            let updated = tx.execute(
+                "UPDATE transcripts SET
+                    title = COALESCE(?2, title),
+                    text = COALESCE(?3, text),
+                    note_id = CASE WHEN ?4 THEN ?5 ELSE note_id END,
+                    updated_at = ?6,
+                    word_count = COALESCE(?7, word_count),
+                    content_hash = COALESCE(?8, content_hash)
+                 WHERE id = ?1",
+                rusqlite::params![
+                    id,
+                    patch.title,
+                    patch.text,
+                    note_id.is_some(),
+                    note_id.clone().flatten(),
+                    pipeline::now_ms() as i64,
+                    patch.text.as_deref().map(count_words),
+                    patch.text.as_deref().map(duplicates::content_hash),
+                ],
+            )?;
+            tx.commit().map(|_| updated)
+        })
+        .map_err(database_error)?;
+    if updated == 0 {
+        return Err(TranscriptError::NotFound { id });
+    }
+
+    let changed: Vec<&str> = [
+        ("title", patch.title.is_some()),
+        ("text", patch.text.is_some()),
+        ("note_id", note_id.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(field, set)| set.then_some(field))
+    .collect();
+    audit::record(
+        &app,
+        AuditEventKind::TranscriptEdited,
+        Some(&id),
+        &format!("fields={}", changed.join(",")),
+    );
+    log::debug!("Updated transcript {id}");
+    query_transcript(&database, &id)
+        .map_err(database_error)?
+        .ok_or(TranscriptError::NotFound { id })
+}
+
+/// Sets or clears (with None or an empty string) a transcript's session label.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed(response_size)]
+pub async fn update_session_label(
+    app: AppHandle,
+    id: String,
+    label: Option<String>,
+) -> Result<Transcript, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    validate_id(&id)?;
+    let label = normalize_session_label(label)?;
+
+    let database = app.state::<Database>();
+    let updated = database
+        .with_conn(|conn| {
+            conn.execute(
+                "UPDATE transcripts SET session_label = ?2, updated_at = ?3 WHERE id = ?1",
+                rusqlite::params![id, label, pipeline::now_ms() as i64],
+            )
+        })
+        .map_err(database_error)?;
+    if updated == 0 {
+        return Err(TranscriptError::NotFound { id });
+    }
+
+    audit::record(
+        &app,
+        AuditEventKind::TranscriptEdited,
+        Some(&id),
+        "fields=session_label",
+    );
+    query_transcript(&database, &id)
+        .map_err(database_error)?
+        .ok_or(TranscriptError::NotFound { id })
+}
+
+// ============================================================================
+// Search
+// ============================================================================
+
+/// Private-use characters marking highlights in `snippet()` output; split out
+/// by `snippet_segments` before anything reaches the frontend
+const HIGHLIGHT_START: char = '\u{E000}';
+const HIGHLIGHT_END: char = '\u{E001}';
+
+/// Approximate number of tokens in a search snippet
+const SNIPPET_TOKENS: u32 = 16;
+
+/// Turns free text into an FTS5 query that can't be a syntax error: every word
+/// is quoted, quoted phrases stay phrases, and a trailing `*` keeps prefix matching.
+/// Returns None when the input has no searchable words.
+fn sanitize_fts_query(query: &str) -> Option<String> {
+    let mut terms = Vec::new();
+    for (index, part) in query.split('"').enumerate() {
+        let words: Vec<&str> = part
+            .split(|c: char| !c.is_alphanumeric() && c != '*')
+            .filter(|word| word.chars().any(char::is_alphanumeric))
+            .collect();
+        // Odd-numbered parts sit between quotes
+        if index % 2 == 1 && !words.is_empty() {
+            let phrase: Vec<&str> = words.iter().map(|w| w.trim_matches('*')).collect();
+            terms.push(format!("\"{}\"", phrase.join(" ")));
+            continue;
+        }
+        for word in words {
+            let prefix = word.ends_with('*');
+            let word: String = word.chars().filter(|c| c.is_alphanumeric()).collect();
+            terms.push(format!("\"{word}\"{}", if prefix { "*" } else { "" }));
+        }
+    }
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Splits marked `snippet()` output into plain and highlighted runs.
+fn snippet_segments(snippet: &str) -> Vec<SnippetSegment> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut highlighted = false;
+    for c in snippet.chars() {
+        if c == HIGHLIGHT_START || c == HIGHLIGHT_END {
+            if !current.is_empty() {
+                segments.push(SnippetSegment {
+                    text: std::mem::take(&mut current),
+                    highlighted,
+                });
+            }
+            highlighted = c == HIGHLIGHT_START;
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        segments.push(SnippetSegment {
+            text: current,
+            highlighted,
+        });
+    }
+    segments
+}
+
+fn is_fts_syntax_error(e: &rusqlite::Error) -> bool {
+    matches!(e, rusqlite::Error::SqliteFailure(_, Some(message))
+        if message.contains("fts5") || message.contains("syntax error"))
+}
+
+fn run_search(
+    conn: &rusqlite::Connection,
+    fts_query: &str,
+    filter: &TranscriptFilter,
+    limit: u32,
+    offset: u32,
+) -> rusqlite::Result<Vec<TranscriptSearchHit>> {
+    let (clause, filter_params) = filter_clause(filter);
+    let filter_condition = if clause.is_empty() {
+        String::new()
+    } else {
+        format!(" AND t.id IN (SELECT id FROM transcripts{clause})")
+    };
+
+    // Parameters are positional, in the order they appear in the statement
+    let mut params = vec![
+        SqlValue::Text(HIGHLIGHT_START.to_string()),
+        SqlValue::Text(HIGHLIGHT_END.to_string()),
+        SqlValue::Text(fts_query.to_string()),
+    ];
+    params.extend(filter_params);
+    params.push(SqlValue::Integer(i64::from(limit)));
+    params.push(SqlValue::Integer(i64::from(offset)));
+
+    // Title matches weigh more than body matches; the id column is unindexed
+    let mut stmt = conn.prepare(&format!(
+        "SELECT t.id, t.title, t.created_at,
+                snippet(transcripts_fts, 2, ?, ?, '…', {SNIPPET_TOKENS})
+         FROM transcripts_fts JOIN transcripts t ON t.id = transcripts_fts.id
+         WHERE transcripts_fts MATCH ?{filter_condition}
+         ORDER BY bm25(transcripts_fts, 0.0, 5.0, 1.0)
+         LIMIT ? OFFSET ?"
+    ))?;
+    let hits = stmt.query_map(params_from_iter(params.iter()), |row| {
+        let snippet: String = row.get(3)?;
+        Ok(TranscriptSearchHit {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            snippet: snippet_segments(&snippet),
+            created_at_ms: row.get::<_, i64>(2)? as f64,
+        })
+    })?;
+    hits.collect()
+}
+
+/// Full-text search over transcript titles and text, best match first.
+/// Accepts FTS5 syntax (`"exact phrase"`, `prefix*`, `AND`/`OR`/`NOT`); a query
+/// that isn't valid FTS5 is retried as plain words. `filter` narrows the
+/// results like `list_transcripts`.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn search_transcripts(
+    app: AppHandle,
+    query: String,
+    filter: Option<TranscriptFilter>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<TranscriptSearchHit>, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let limit = limit
+        .unwrap_or(DEFAULT_TRANSCRIPT_PAGE_SIZE)
+        .clamp(1, MAX_TRANSCRIPT_PAGE_SIZE);
+    let offset = offset.unwrap_or(0);
+    let filter = filter.unwrap_or_default();
+
+    app.state::<Database>()
+        .with_conn(
+            |conn| match run_search(conn, query, &filter, limit, offset) {
+                Err(e) if is_fts_syntax_error(&e) => match sanitize_fts_query(query) {
+                    Some(sanitized) => run_search(conn, &sanitized, &filter, limit, offset),
+                    None => Ok(Vec::new()),
+                },
+                result => result,
+            },
+        )
+        .map_err(database_error)
+}
+
+// ============================================================================
+// Trash
+// ============================================================================
+
+/// Moves a transcript to the trash. It disappears from lists and search but
+/// can be restored until it's purged.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn delete_transcript(app: AppHandle, id: String) -> Result<(), TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    validate_id(&id)?;
+    let deleted = app
+        .state::<Database>()
+        .with_conn(|conn| {
+            conn.execute(
+                "UPDATE transcripts SET deleted_at = ?2 WHERE id = ?1 AND deleted_at IS NULL",
+                rusqlite::params![id, pipeline::now_ms() as i64],
+            )
+        })
+        .map_err(database_error)?;
+    if deleted == 0 {
+        return Err(TranscriptError::NotFound { id });
+    }
+
+    audit::record(&app, AuditEventKind::TranscriptDeleted, Some(&id), "");
+    log::info!("Moved transcript {id} to the trash");
+    Ok(())
+}
+
+/// Lists transcripts in the trash, most recently deleted first.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn list_trash(app: AppHandle) -> Result<Vec<TranscriptSummary>, TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    app.state::<Database>()
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {SUMMARY_COLUMNS}, {TAGS_COLUMN} FROM transcripts
+                 WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC, id DESC"
+            ))?;
+            let rows = stmt.query_map([], row_to_summary)?;
+            rows.collect()
+        })
+        .map_err(database_error)
+}
+
+/// Takes a transcript back out of the trash.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn restore_transcript(app: AppHandle, id: String) -> Result<(), TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    validate_id(&id)?;
+    let restored = app
+        .state::<Database>()
+        .with_conn(|conn| {
+            conn.execute(
+                "UPDATE transcripts SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+                [&id],
+            )
+        })
+        .map_err(database_error)?;
+    if restored == 0 {
+        return Err(TranscriptError::NotFound { id });
+    }
+
+    audit::record(&app, AuditEventKind::TranscriptRestored, Some(&id), "");
+    log::info!("Restored transcript {id} from the trash");
+    Ok(())
+}
+
+/// Permanently deletes a transcript, with its tags and revisions. The database
+/// runs with `secure_delete`, so the text is overwritten rather than left in
+/// free pages.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn purge_transcript(app: AppHandle, id: String) -> Result<(), TranscriptError> {
+    crate::commands::app_lock::ensure_unlocked(&app)?;
+    validate_id(&id)?;
+    let purged = app
+        .state::<Database>()
+        .with_conn(|conn| conn.execute("DELETE FROM transcripts WHERE id = ?1", [&id]))
+        .map_err(database_error)?;
+    if purged == 0 {
+        return Err(TranscriptError::NotFound { id });
+    }
+
+    audit::record(&app, AuditEventKind::TranscriptPurged, Some(&id), "");
+    log::info!("Purged transcript {id}");
+    Ok(())
+}
+
+/// Purges transcripts that have been in the trash longer than
+/// `trash_retention_days`. Run by the retention sweep; returns how many went.
+pub fn purge_expired_trash(app: &AppHandle) -> Result<u32, String> {
+    let retention_days = crate::commands::preferences::load_saved_preferences(app)
+        .trash_retention_days
+        .max(1);
+    let cutoff_ms = pipeline::now_ms() - f64::from(retention_days) * audit::MS_PER_DAY;
+
+    let purged = app.state::<Database>().with_conn(|conn| {
+        let tx = conn.transaction()?;
+        let ids = tx
+            .prepare("SELECT id FROM transcripts WHERE deleted_at < ?1")?
+            .query_map([cutoff_ms as i64], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        tx.execute(
+            "DELETE FROM transcripts WHERE deleted_at < ?1",
+            [cutoff_ms as i64],
+        )?;
+        tx.commit().map(|_| ids)
+    })?;
+
+    let detail = format!("scheduled retention_days={retention_days}");
+    for id in &purged {
+        audit::record(app, AuditEventKind::TranscriptPurged, Some(id), &detail);
+    }
+    if !purged.is_empty() {
+        log::info!(
+            "Purged {} transcripts from the trash after {retention_days} days",
+            purged.len()
+        );
+    }
+    Ok(purged.len() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_quotes_words_and_keeps_phrases_and_prefixes() {
+        assert_eq!(
+            sanitize_fts_query("metoprolol titr* \"chest pain\""),
+            Some("\"metoprolol\" \"titr\"* \"chest pain\"".into())
+        );
+    }
+
+    #[test]
+    fn sanitize_neutralizes_fts_syntax() {
+        assert_eq!(
+            sanitize_fts_query("bp: (140/90 AND"),
+            Some("\"bp\" \"140\" \"90\" \"AND\"".into())
+        );
+        // Unbalanced quote: the tail is treated as a phrase
+        assert_eq!(
+            sanitize_fts_query("\"left knee"),
+            Some("\"left knee\"".into())
+        );
+        assert_eq!(sanitize_fts_query("*** ()"), None);
+    }
+
+    #[test]
+    fn snippet_segments_split_on_markers() {
+        let snippet = format!("start {HIGHLIGHT_START}dose{HIGHLIGHT_END} increased");
+        assert_eq!(
+            snippet_segments(&snippet),
+            vec![
+                SnippetSegment {
+                    text: "start ".into(),
+                    highlighted: false
+                },
+                SnippetSegment {
+                    text: "dose".into(),
+                    highlighted: true
+                },
+                SnippetSegment {
+                    text: " increased".into(),
+                    highlighted: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn default_filter_only_hides_the_trash() {
+        let (clause, params) = filter_clause(&TranscriptFilter::default());
+        assert_eq!(clause, " WHERE deleted_at IS NULL");
+        assert!(params.is_empty());
+
+        let with_trash = TranscriptFilter {
+            include_deleted: true,
+            ..Default::default()
+        };
+        assert!(filter_clause(&with_trash).0.is_empty());
+    }
+
+    #[test]
+    fn filter_conditions_are_combined_in_order() {
+        let filter = TranscriptFilter {
+            note_id: Some("note-1".into()),
+            created_after_ms: Some(1_000.0),
+            created_before_ms: Some(2_000.0),
+            ..Default::default()
+        };
+        let (clause, params) = filter_clause(&filter);
+        assert_eq!(
+            clause,
+            " WHERE note_id = ? AND created_at >= ? AND created_at < ? AND deleted_at IS NULL"
+        );
+        assert_eq!(
+            params,
+            vec![
+                SqlValue::Text("note-1".into()),
+                SqlValue::Integer(1_000),
+                SqlValue::Integer(2_000),
+            ]
+        );
+    }
+}
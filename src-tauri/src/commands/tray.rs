@@ -0,0 +1,68 @@
+//! Tray / menu-bar icon.
+//!
+//! Left-clicking the icon toggles the quick pane dropped down beneath it when
+//! the pane is in menu-bar-anchored mode, and opens the main window otherwise.
+
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::AppHandle;
+
+use crate::commands::quick_pane::{self, TrayRect};
+
+/// Identifier for the app's tray icon
+pub const TRAY_ID: &str = "main";
+
+/// Creates the tray icon. Must be called from setup().
+pub fn init_tray(app: &AppHandle) -> Result<(), String> {
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("PrivacyScribe")
+        .show_menu_on_left_click(false)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                rect,
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                handle_tray_click(tray.app_handle(), rect);
+            }
+        });
+
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+
+    // Template icons adapt to light/dark menu bars
+    #[cfg(target_os = "macos")]
+    {
+        builder = builder.icon_as_template(true);
+    }
+
+    builder
+        .build(app)
+        .map_err(|e| format!("Failed to create tray icon: {e}"))?;
+
+    log::info!("Tray icon created");
+    Ok(())
+}
+
+fn handle_tray_click(app: &AppHandle, rect: tauri::Rect) {
+    // Tray rects are reported in physical pixels; the scale factor is unused for those
+    let position = rect.position.to_physical::<f64>(1.0);
+    let size = rect.size.to_physical::<f64>(1.0);
+    quick_pane::set_tray_icon_rect(TrayRect {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    });
+
+    if quick_pane::is_menubar_anchored() {
+        log::debug!("Tray clicked, toggling anchored quick pane");
+        if let Err(e) = quick_pane::toggle_quick_pane(app.clone()) {
+            log::error!("Failed to toggle quick pane from tray: {e}");
+        }
+    } else if let Err(e) = crate::commands::navigation::focus_main_window(app) {
+        log::error!("Failed to open main window from tray: {e}");
+    }
+}
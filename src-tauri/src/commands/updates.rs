@@ -0,0 +1,743 @@
+//! Update checks and installs.
+//!
+//! When `check_updates_automatically` is on, the updater is queried at most
+//! once a day. An available update is announced with a notification and an
+//! `update-available` event for the in-app banner. Installing always stays an
+//! explicit user action: `check_for_updates`, then
+//! `download_and_install_update` (progress on `update-download-progress`),
+//! then `restart_to_update` when the user is ready. On Windows the installer
+//! takes over and exits the app as soon as it's installed.
+//!
+//! Neither runs during a recording or transcription, whose audio a restart
+//! would lose: the call returns `Busy` and the install is deferred. The
+//! pipeline is checked again right before installing, since one can start
+//! during the download; the downloaded update is then kept for the deferred
+//! install. Once the pipeline is idle, a notification and
+//! `update-install-countdown` events give the user 30 seconds to cancel
+//! before it installs and restarts.
+//!
+//! Each `update_channel` has its own manifest: stable uses the endpoints in
+//! `tauri.conf.json`, beta the same URLs with `-beta` added to the file name
+//! (`latest.json` → `latest-beta.json`); releases publish both. Only newer
+//! versions are offered, so leaving beta never downgrades.
+//!
+//! The updater plugin only exists on desktop; elsewhere the commands return
+//! `Unsupported`.
+
+#[cfg(desktop)]
+use std::path::PathBuf;
+#[cfg(desktop)]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(desktop)]
+use std::sync::Mutex;
+#[cfg(desktop)]
+use std::time::Duration;
+
+use tauri::AppHandle;
+#[cfg(desktop)]
+use tauri_plugin_updater::{Update, Updater, UpdaterExt};
+#[cfg(desktop)]
+use tauri_specta::Event;
+
+use crate::commands::preferences;
+#[cfg(desktop)]
+use crate::commands::{audio, notifications, pipeline};
+#[cfg(desktop)]
+use crate::types::{UpdateAvailable, UpdateDownloadProgress, UpdateInstallCountdown};
+use crate::types::{UpdateChannel, UpdateError, UpdateInfo};
+#[cfg(desktop)]
+use crate::utils::metrics;
+use crate::utils::paths;
+
+/// Seconds between the pipeline going idle and a deferred install
+#[cfg(desktop)]
+const INSTALL_COUNTDOWN_SECS: u32 = 30;
+
+/// Without a total size, progress is reported once per this many bytes
+#[cfg(desktop)]
+const PROGRESS_STEP_BYTES: usize = 1024 * 1024;
+
+/// Minimum time between background checks, across restarts
+#[cfg(desktop)]
+const UPDATE_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How often the background task wakes to see whether a check is due
+#[cfg(desktop)]
+const UPDATE_POLL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// File holding the time of the last background check (ms since epoch)
+#[cfg(desktop)]
+const LAST_CHECK_FILENAME: &str = "last_update_check";
+
+/// The update found by the last check and the channel it came from, for
+/// `download_and_install_update`
+#[cfg(desktop)]
+static LATEST_UPDATE: Mutex<Option<(UpdateChannel, Update)>> = Mutex::new(None);
+
+/// An update downloaded but not installed because the pipeline got busy,
+/// with the channel it came from
+#[cfg(desktop)]
+static DOWNLOADED_UPDATE: Mutex<Option<(UpdateChannel, Update, Vec<u8>)>> = Mutex::new(None);
+
+/// Version installed and waiting for a restart
+#[cfg(desktop)]
+static INSTALLED_VERSION: Mutex<Option<String>> = Mutex::new(None);
+
+#[cfg(desktop)]
+fn last_check_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir =
+        paths::app_data_dir(app).map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+    Ok(dir.join(LAST_CHECK_FILENAME))
+}
+
+/// Whether a day has passed since the last check. A missing or unreadable
+/// record counts as due.
+#[cfg(desktop)]
+fn check_due(app: &AppHandle) -> bool {
+    let last_ms = last_check_path(app)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| contents.trim().parse::<f64>().ok());
+    match last_ms {
+        Some(last_ms) => {
+            crate::commands::pipeline::now_ms() - last_ms
+                >= UPDATE_CHECK_INTERVAL.as_millis() as f64
+        }
+        None => true,
+    }
+}
+
+#[cfg(desktop)]
+fn record_check(app: &AppHandle) {
+    let result = last_check_path(app).and_then(|path| {
+        std::fs::write(path, crate::commands::pipeline::now_ms().to_string())
+            .map_err(|e| format!("Failed to record update check: {e}"))
+    });
+    if let Err(e) = result {
+        log::warn!("{e}");
+    }
+}
+
+/// Sorts updater failures into what the UI tells apart.
+#[cfg(desktop)]
+fn update_error(e: tauri_plugin_updater::Error) -> UpdateError {
+    use tauri_plugin_updater::Error;
+
+    match e {
+        Error::Reqwest(e) => UpdateError::Network {
+            message: e.to_string(),
+        },
+        Error::Network(message) => UpdateError::Network { message },
+        Error::Minisign(_) | Error::Base64(_) | Error::SignatureUtf8(_) => {
+            UpdateError::InvalidSignature
+        }
+        e => UpdateError::Failed {
+            message: e.to_string(),
+        },
+    }
+}
+
+/// Size of the update for this platform, from an optional `size` next to its
+/// `url` in the manifest.
+#[cfg(desktop)]
+fn manifest_download_size(update: &Update) -> Option<f64> {
+    update
+        .raw_json
+        .get("platforms")?
+        .get(&update.target)?
+        .get("size")?
+        .as_f64()
+}
+
+#[cfg(desktop)]
+fn update_info(app: &AppHandle, update: Option<&Update>) -> UpdateInfo {
+    match update {
+        Some(update) => UpdateInfo {
+            available: true,
+            current_version: update.current_version.clone(),
+            latest_version: Some(update.version.clone()),
+            notes: update.body.clone(),
+            pub_date: update.date.and_then(|date| {
+                chrono::DateTime::from_timestamp(date.unix_timestamp(), 0)
+                    .map(|date| date.to_rfc3339())
+            }),
+            download_size: manifest_download_size(update),
+        },
+        None => UpdateInfo {
+            available: false,
+            current_version: app.package_info().version.to_string(),
+            latest_version: None,
+            notes: None,
+            pub_date: None,
+            download_size: None,
+        },
+    }
+}
+
+/// The beta manifest URL for a stable one: `-beta` before the extension of
+/// the last path segment.
+#[cfg(desktop)]
+fn beta_endpoint(stable: &str) -> String {
+    let (path, query) = stable.split_once('?').unwrap_or((stable, ""));
+    let file_start = path.rfind('/').map_or(0, |slash| slash + 1);
+    let beta_path = match path[file_start..].rfind('.') {
+        Some(dot) => {
+            let dot = file_start + dot;
+            format!("{}-beta{}", &path[..dot], &path[dot..])
+        }
+        None => format!("{path}-beta"),
+    };
+    if query.is_empty() {
+        beta_path
+    } else {
+        format!("{beta_path}?{query}")
+    }
+}
+
+/// An updater for `channel`, offering only versions newer than this one.
+#[cfg(desktop)]
+fn channel_updater(app: &AppHandle, channel: UpdateChannel) -> Result<Updater, UpdateError> {
+    let mut builder = app
+        .updater_builder()
+        .version_comparator(|current, release| release.version > current);
+    if channel == UpdateChannel::Beta {
+        let configured = app
+            .config()
+            .plugins
+            .0
+            .get("updater")
+            .and_then(|updater| updater.get("endpoints"))
+            .and_then(|endpoints| endpoints.as_array())
+            .cloned()
+            .unwrap_or_default();
+        let endpoints = configured
+            .iter()
+            .filter_map(|endpoint| endpoint.as_str())
+            .map(|endpoint| {
+                beta_endpoint(endpoint)
+                    .parse()
+                    .map_err(|e| UpdateError::Failed {
+                        message: format!("Invalid beta update endpoint: {e}"),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        builder = builder.endpoints(endpoints).map_err(update_error)?;
+    }
+    builder.build().map_err(update_error)
+}
+
+/// Queries the update server for the current channel and remembers the
+/// result for installing.
+#[cfg(desktop)]
+async fn check(app: &AppHandle) -> Result<UpdateInfo, UpdateError> {
+    let channel = preferences::load_saved_preferences(app).update_channel;
+    let update = channel_updater(app, channel)?
+        .check()
+        .await
+        .map_err(update_error)?;
+    record_check(app);
+
+    let info = update_info(app, update.as_ref());
+    match &update {
+        Some(update) => log::info!(
+            "Update available on {}: {}",
+            channel.as_str(),
+            update.version
+        ),
+        None => log::info!("No update available on {}", channel.as_str()),
+    }
+    *LATEST_UPDATE.lock().unwrap_or_else(|e| e.into_inner()) =
+        update.map(|update| (channel, update));
+    Ok(info)
+}
+
+/// Checks for an update once and announces it. Never installs.
+#[cfg(desktop)]
+async fn check_in_background(app: &AppHandle) -> Result<(), UpdateError> {
+    let info = check(app).await?;
+    let Some(version) = info.latest_version else {
+        return Ok(());
+    };
+
+    let payload = UpdateAvailable {
+        version,
+        current_version: info.current_version,
+        notes: info.notes,
+    };
+    notifications::notify_update_available(app, &payload.version, payload.notes.as_deref());
+    if let Err(e) = crate::commands::navigation::emit_to_main_when_ready(app, payload) {
+        log::warn!("Failed to announce update: {e}");
+    }
+    Ok(())
+}
+
+/// Starts the background update check loop. Called once from setup().
+/// The preference is re-read on every wake so toggling it takes effect without a restart.
+#[cfg(desktop)]
+pub fn spawn_update_checks(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let enabled = preferences::load_saved_preferences(&app).check_updates_automatically;
+            if enabled && check_due(&app) {
+                if let Err(e) = check_in_background(&app).await {
+                    log::warn!("Update check failed: {e}");
+                }
+            }
+            tokio::time::sleep(UPDATE_POLL_INTERVAL).await;
+        }
+    });
+}
+
+/// Returns a download callback that emits progress, once per percent when
+/// the total is known and once per megabyte otherwise.
+#[cfg(desktop)]
+fn progress_reporter(app: &AppHandle) -> impl FnMut(usize, Option<u64>) {
+    let app = app.clone();
+    let mut downloaded = 0usize;
+    let mut last_step = None;
+    move |chunk, total| {
+        downloaded += chunk;
+        let step = match total {
+            Some(total) if total > 0 => downloaded as u64 * 100 / total,
+            _ => (downloaded / PROGRESS_STEP_BYTES) as u64,
+        };
+        if last_step == Some(step) {
+            return;
+        }
+        last_step = Some(step);
+        let payload = UpdateDownloadProgress {
+            downloaded_bytes: downloaded as f64,
+            total_bytes: total.map(|total| total as f64),
+        };
+        if let Err(e) = payload.emit(&app) {
+            log::warn!("Failed to emit update progress: {e}");
+        }
+    }
+}
+
+/// Downloads the update for the current channel (or reuses one downloaded
+/// earlier) and installs it. If the pipeline got busy in the meantime, the
+/// download is kept and the install deferred, unless `force` is set.
+#[cfg(desktop)]
+async fn download_and_install(app: &AppHandle, force: bool) -> Result<String, UpdateError> {
+    // Only what the current channel offers; the channel may have changed since
+    let channel = preferences::load_saved_preferences(app).update_channel;
+    let latest = || {
+        LATEST_UPDATE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .filter(|(checked, _)| *checked == channel)
+            .map(|(_, update)| update)
+    };
+    let downloaded = DOWNLOADED_UPDATE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+        .filter(|(downloaded_from, _, _)| *downloaded_from == channel);
+    let (update, bytes) = match downloaded {
+        Some((_, update, bytes)) => (update, bytes),
+        None => {
+            let update = match latest() {
+                Some(update) => update,
+                None => {
+                    check(app).await?;
+                    latest().ok_or(UpdateError::NoUpdate)?
+                }
+            };
+            log::info!("Downloading update {}", update.version);
+            let bytes = update
+                .download(progress_reporter(app), || {})
+                .await
+                .map_err(update_error)?;
+            (update, bytes)
+        }
+    };
+
+    // The download takes a while; a recording may have started since
+    if pipeline::is_busy() {
+        if !force {
+            *DOWNLOADED_UPDATE.lock().unwrap_or_else(|e| e.into_inner()) =
+                Some((channel, update, bytes));
+            defer_install(app);
+            return Err(UpdateError::Busy);
+        }
+        prepare_forced(app).await;
+    }
+    update.install(bytes).map_err(update_error)?;
+    log::info!("Installed update {}; waiting for restart", update.version);
+
+    *INSTALLED_VERSION.lock().unwrap_or_else(|e| e.into_inner()) = Some(update.version.clone());
+    Ok(update.version)
+}
+
+#[cfg(desktop)]
+fn installed_version() -> Option<String> {
+    INSTALLED_VERSION
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+#[cfg(desktop)]
+fn restart(app: &AppHandle) -> Result<(), UpdateError> {
+    let version = installed_version().ok_or(UpdateError::NotInstalled)?;
+    log::info!("Restarting into update {version}");
+    app.restart()
+}
+
+/// An install-and-restart put off until the pipeline is idle.
+#[cfg(desktop)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeferredInstall {
+    /// A recording or transcription is in progress
+    WaitingForIdle,
+    /// Idle; installs when the countdown runs out
+    CountingDown { seconds_left: u32 },
+}
+
+/// What one second of a deferred install calls for.
+#[cfg(desktop)]
+#[derive(Debug, PartialEq, Eq)]
+enum DeferredStep {
+    /// Nothing changed
+    Wait,
+    /// The pipeline got busy during the countdown; it starts over once idle
+    Paused,
+    /// Seconds left before installing
+    Countdown(u32),
+    Install,
+}
+
+#[cfg(desktop)]
+impl DeferredInstall {
+    /// Advances one second. Returns the next state (None once it's time to
+    /// install) and what to announce.
+    fn tick(self, busy: bool) -> (Option<Self>, DeferredStep) {
+        match (self, busy) {
+            (DeferredInstall::WaitingForIdle, true) => {
+                (Some(DeferredInstall::WaitingForIdle), DeferredStep::Wait)
+            }
+            (DeferredInstall::CountingDown { .. }, true) => {
+                (Some(DeferredInstall::WaitingForIdle), DeferredStep::Paused)
+            }
+            (DeferredInstall::WaitingForIdle, false) => (
+                Some(DeferredInstall::CountingDown {
+                    seconds_left: INSTALL_COUNTDOWN_SECS,
+                }),
+                DeferredStep::Countdown(INSTALL_COUNTDOWN_SECS),
+            ),
+            (DeferredInstall::CountingDown { seconds_left }, false) if seconds_left <= 1 => {
+                (None, DeferredStep::Install)
+            }
+            (DeferredInstall::CountingDown { seconds_left }, false) => (
+                Some(DeferredInstall::CountingDown {
+                    seconds_left: seconds_left - 1,
+                }),
+                DeferredStep::Countdown(seconds_left - 1),
+            ),
+        }
+    }
+}
+
+/// Install waiting for the pipeline; None when there's none or it was cancelled
+#[cfg(desktop)]
+static DEFERRED_INSTALL: Mutex<Option<DeferredInstall>> = Mutex::new(None);
+
+/// Whether the task advancing `DEFERRED_INSTALL` is running
+#[cfg(desktop)]
+static DEFERRED_TICKER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[cfg(desktop)]
+fn emit_countdown(app: &AppHandle, seconds_remaining: Option<u32>) {
+    let payload = UpdateInstallCountdown { seconds_remaining };
+    if let Err(e) = payload.emit(app) {
+        log::warn!("Failed to emit update install countdown: {e}");
+    }
+}
+
+/// Installs (if not yet) and restarts, for a deferred install whose
+/// countdown ran out.
+#[cfg(desktop)]
+async fn run_deferred_install(app: &AppHandle) -> Result<(), UpdateError> {
+    if installed_version().is_none() {
+        download_and_install(app, false).await?;
+    }
+    // Don't restart into a recording started during the install
+    if pipeline::is_busy() {
+        defer_install(app);
+        return Err(UpdateError::Busy);
+    }
+    restart(app)
+}
+
+/// Registers an install-and-restart for when the pipeline is idle, and
+/// starts the task that counts down to it.
+#[cfg(desktop)]
+fn defer_install(app: &AppHandle) {
+    log::info!("Update install deferred until the pipeline is idle");
+    emit_countdown(app, None);
+    // Under the lock, so a ticker that's about to stop can't miss this
+    let ticker_running = {
+        let mut deferred = DEFERRED_INSTALL.lock().unwrap_or_else(|e| e.into_inner());
+        *deferred = Some(DeferredInstall::WaitingForIdle);
+        DEFERRED_TICKER_RUNNING.swap(true, Ordering::SeqCst)
+    };
+    if ticker_running {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            let step = {
+                let mut deferred = DEFERRED_INSTALL.lock().unwrap_or_else(|e| e.into_inner());
+                let Some(state) = *deferred else {
+                    DEFERRED_TICKER_RUNNING.store(false, Ordering::SeqCst);
+                    return;
+                };
+                let (next, step) = state.tick(pipeline::is_busy());
+                if next.is_none() {
+                    DEFERRED_TICKER_RUNNING.store(false, Ordering::SeqCst);
+                }
+                *deferred = next;
+                step
+            };
+            match step {
+                DeferredStep::Wait => {}
+                DeferredStep::Paused => emit_countdown(&app, None),
+                DeferredStep::Countdown(seconds) => {
+                    if seconds == INSTALL_COUNTDOWN_SECS {
+                        notifications::notify_update_restart_pending(&app, seconds);
+                    }
+                    emit_countdown(&app, Some(seconds));
+                }
+                DeferredStep::Install => {
+                    if let Err(e) = run_deferred_install(&app).await {
+                        log::warn!("Deferred update install failed: {e}");
+                    }
+                    return;
+                }
+            }
+        }
+    });
+}
+
+/// Makes way for a forced install: a recording is aborted, which finalizes
+/// its encrypted recovery file so it can be recovered after the restart. A
+/// transcription can't be interrupted; its recovery file is kept until a
+/// transcription succeeds, so it's recoverable too.
+#[cfg(desktop)]
+async fn prepare_forced(app: &AppHandle) {
+    if audio::abort_recording(app).await {
+        log::warn!("Recording aborted for a forced update; its recovery file is kept");
+    } else if pipeline::is_busy() {
+        log::warn!("Installing a forced update during a transcription");
+    }
+}
+
+/// Lets an install or restart go ahead when the pipeline is idle (or
+/// `force` is set); otherwise defers it and returns `Busy`.
+#[cfg(desktop)]
+async fn gate_on_pipeline(app: &AppHandle, force: bool) -> Result<(), UpdateError> {
+    if !pipeline::is_busy() {
+        return Ok(());
+    }
+    if force {
+        prepare_forced(app).await;
+        return Ok(());
+    }
+    defer_install(app);
+    Err(UpdateError::Busy)
+}
+
+/// Checks for an update. The result is kept so
+/// `download_and_install_update` installs exactly what was shown.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn check_for_updates(app: AppHandle) -> Result<UpdateInfo, UpdateError> {
+    #[cfg(desktop)]
+    return check(&app).await;
+
+    #[cfg(not(desktop))]
+    {
+        let _ = app;
+        Err(UpdateError::Unsupported)
+    }
+}
+
+/// Downloads and installs the update from the last check (checking first if
+/// there was none), emitting `update-download-progress`. Doesn't restart;
+/// call `restart_to_update`. Returns the installed version.
+///
+/// While a recording or transcription is in progress this returns `Busy`
+/// and the install (and restart) runs by itself once it's done, unless
+/// `force` is set: then a recording is stopped first (see `prepare_forced`).
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn download_and_install_update(
+    app: AppHandle,
+    force: bool,
+) -> Result<String, UpdateError> {
+    #[cfg(desktop)]
+    {
+        gate_on_pipeline(&app, force).await?;
+        download_and_install(&app, force).await
+    }
+
+    #[cfg(not(desktop))]
+    {
+        let _ = (app, force);
+        Err(UpdateError::Unsupported)
+    }
+}
+
+/// Switches `update_channel` and checks the new channel right away. Going
+/// from beta to stable reports no update until stable passes the installed
+/// version.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn set_update_channel(
+    app: AppHandle,
+    channel: UpdateChannel,
+) -> Result<UpdateInfo, UpdateError> {
+    preferences::update_saved_preferences(&app, |prefs| prefs.update_channel = channel)
+        .map_err(|message| UpdateError::Failed { message })?;
+    log::info!("Update channel set to {}", channel.as_str());
+
+    #[cfg(desktop)]
+    return check(&app).await;
+
+    #[cfg(not(desktop))]
+    Err(UpdateError::Unsupported)
+}
+
+/// Restarts into the installed update. Deferred like
+/// `download_and_install_update` while the pipeline is busy, unless `force`.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub async fn restart_to_update(app: AppHandle, force: bool) -> Result<(), UpdateError> {
+    #[cfg(desktop)]
+    {
+        if installed_version().is_none() {
+            return Err(UpdateError::NotInstalled);
+        }
+        gate_on_pipeline(&app, force).await?;
+        restart(&app)
+    }
+
+    #[cfg(not(desktop))]
+    {
+        let _ = (app, force);
+        Err(UpdateError::Unsupported)
+    }
+}
+
+/// Cancels an install waiting for the pipeline or counting down. Returns
+/// whether one was pending.
+#[tauri::command]
+#[specta::specta]
+#[metrics::timed]
+pub fn cancel_pending_update_install() -> bool {
+    #[cfg(desktop)]
+    {
+        let cancelled = DEFERRED_INSTALL
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .is_some();
+        if cancelled {
+            log::info!("Deferred update install cancelled");
+        }
+        cancelled
+    }
+
+    #[cfg(not(desktop))]
+    false
+}
+
+#[cfg(all(test, desktop))]
+mod tests {
+    use super::*;
+
+    fn run(
+        mut state: DeferredInstall,
+        busy: &[bool],
+    ) -> (Option<DeferredInstall>, Vec<DeferredStep>) {
+        let mut steps = Vec::new();
+        for &busy in busy {
+            let (next, step) = state.tick(busy);
+            steps.push(step);
+            match next {
+                Some(next) => state = next,
+                None => return (None, steps),
+            }
+        }
+        (Some(state), steps)
+    }
+
+    #[test]
+    fn deferred_install_waits_for_idle_then_counts_down() {
+        let mut busy = vec![true, true, false];
+        busy.extend(std::iter::repeat_n(false, INSTALL_COUNTDOWN_SECS as usize));
+        let (state, steps) = run(DeferredInstall::WaitingForIdle, &busy);
+        assert_eq!(state, None);
+        assert_eq!(
+            steps[..3],
+            [
+                DeferredStep::Wait,
+                DeferredStep::Wait,
+                DeferredStep::Countdown(INSTALL_COUNTDOWN_SECS),
+            ]
+        );
+        assert_eq!(steps[steps.len() - 2], DeferredStep::Countdown(1));
+        assert_eq!(steps.last(), Some(&DeferredStep::Install));
+    }
+
+    #[test]
+    fn a_new_recording_pauses_the_countdown() {
+        let (state, steps) = run(
+            DeferredInstall::WaitingForIdle,
+            &[false, false, true, false],
+        );
+        assert_eq!(
+            steps,
+            [
+                DeferredStep::Countdown(INSTALL_COUNTDOWN_SECS),
+                DeferredStep::Countdown(INSTALL_COUNTDOWN_SECS - 1),
+                DeferredStep::Paused,
+                DeferredStep::Countdown(INSTALL_COUNTDOWN_SECS),
+            ]
+        );
+        assert_eq!(
+            state,
+            Some(DeferredInstall::CountingDown {
+                seconds_left: INSTALL_COUNTDOWN_SECS
+            })
+        );
+    }
+
+    #[test]
+    fn beta_manifest_sits_next_to_the_stable_one() {
+        assert_eq!(
+            beta_endpoint("https://example.com/releases/latest/download/latest.json"),
+            "https://example.com/releases/latest/download/latest-beta.json"
+        );
+        assert_eq!(
+            beta_endpoint("https://updates.example.com/{{target}}/{{arch}}/manifest.json?v=2"),
+            "https://updates.example.com/{{target}}/{{arch}}/manifest-beta.json?v=2"
+        );
+        assert_eq!(
+            beta_endpoint("https://updates.example.com/v1.2/latest"),
+            "https://updates.example.com/v1.2/latest-beta"
+        );
+    }
+}
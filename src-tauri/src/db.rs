@@ -0,0 +1,878 @@
+//! Rust-side access to the app's SQLite databases.
+//!
+//! Backend-owned tables (drafts, transcripts, the audit log) live in their own database,
+//! encrypted with SQLCipher under a key kept in the OS keychain. The
+//! frontend's `privacyscribe.db` (opened through tauri-plugin-sql) stays
+//! plaintext; earlier versions kept the backend tables there, and they are
+//! migrated out on first start. The connection is held in managed state.
+//!
+//! The database never falls back to plaintext. If the key is unavailable or
+//! doesn't open the file, the connection stays closed and the reason is
+//! reported through `status()` so the UI can explain it instead of showing
+//! an empty library.
+//!
+//! Backups are made with SQLite's online backup API (safe while the app runs)
+//! and stay encrypted under the same key; restoring swaps the file in and
+//! keeps the replaced database as `.pre-restore`.
+//!
+//! The schema is built by the versioned migrations in `migrations`, applied at
+//! startup before the connection is managed, so no command sees an old schema.
+//!
+//! Every start runs `PRAGMA quick_check`. A damaged database is rebuilt from
+//! whatever rows can still be read, and the original is kept as `.corrupt-*`.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rusqlite::backup::{Backup, StepResult};
+use rusqlite::{Connection, ErrorCode, OptionalExtension};
+use tauri::AppHandle;
+
+use crate::types::{count_words, DatabaseRecovered, DatabaseStatus};
+use crate::utils::secure_delete::secure_delete;
+use crate::utils::{duplicates, paths, secrets};
+
+mod migrations;
+
+/// Frontend database filename (matches `sqlite:privacyscribe.db` in src/lib/db.ts).
+/// Backend tables were kept here before encryption.
+const LEGACY_DATABASE_FILENAME: &str = "privacyscribe.db";
+
+/// Encrypted backend database filename
+const DATABASE_FILENAME: &str = "privacyscribe-vault.db";
+
+/// Keychain entry holding the SQLCipher key
+pub(crate) const DATABASE_KEY_NAME: &str = "database-key";
+
+/// First bytes of every unencrypted SQLite database file
+const PLAINTEXT_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// How long a statement waits on a lock held by another connection
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Version of the backend schema (the newest migration), mirrored in
+/// `PRAGMA user_version`. Restores refuse backups from a newer schema.
+pub const SCHEMA_VERSION: i64 = migrations::MIGRATIONS.len() as i64;
+
+/// Suffix of the database kept aside when a backup is restored over it
+const PRE_RESTORE_SUFFIX: &str = "pre-restore";
+
+/// Pages copied per online-backup step (progress is reported between steps)
+const BACKUP_PAGES_PER_STEP: std::os::raw::c_int = 256;
+
+/// Integrity problems included in reports (integrity_check lists up to 100)
+pub const MAX_REPORTED_PROBLEMS: usize = 10;
+
+/// Bytes copied between progress reports when restoring
+const RESTORE_CHUNK_BYTES: usize = 1024 * 1024;
+
+/// Backend tables that may exist in the legacy plaintext database:
+/// (table, primary key, columns copied).
+const LEGACY_TABLES: &[(&str, &str, &str)] = &[
+    (
+        "drafts",
+        "draft_id",
+        "draft_id, content, cursor_meta, created_at, updated_at",
+    ),
+    (
+        "transcripts",
+        "id",
+        "id, title, text, language, model_id, duration_secs, note_id, created_at, updated_at",
+    ),
+];
+
+/// A successfully opened database, and what was salvaged if it had to be
+/// rebuilt from a corrupted file.
+struct Opened {
+    conn: Connection,
+    recovered: Option<DatabaseRecovered>,
+}
+
+/// The connection (`None` when the database couldn't be opened) and why.
+struct DatabaseState {
+    conn: Option<Connection>,
+    status: DatabaseStatus,
+    /// Set when startup rebuilt a corrupted database, until announced
+    recovered: Option<DatabaseRecovered>,
+}
+
+impl DatabaseState {
+    fn opened(result: Result<Opened, DatabaseStatus>) -> Self {
+        match result {
+            Ok(Opened { conn, recovered }) => {
+                log::info!("Database opened");
+                Self {
+                    conn: Some(conn),
+                    status: DatabaseStatus::Ready,
+                    recovered,
+                }
+            }
+            Err(status) => {
+                log::error!("Database unavailable: {status}");
+                Self {
+                    conn: None,
+                    status,
+                    recovered: None,
+                }
+            }
+        }
+    }
+}
+
+/// Managed database connection.
+pub struct Database {
+    state: Mutex<DatabaseState>,
+}
+
+impl Database {
+    /// Opens the encrypted database (in the app config dir, next to the frontend's),
+    /// creates backend-owned tables and migrates any left in the legacy plaintext file.
+    /// Never fails: an unusable database is managed in a closed state.
+    pub fn open(app: &AppHandle) -> Self {
+        Self {
+            state: Mutex::new(DatabaseState::opened(open_encrypted(app))),
+        }
+    }
+
+    /// A database left closed until `open_deferred`, for an app that starts
+    /// locked: its key stays in the keychain until the user unlocks.
+    pub fn deferred() -> Self {
+        Self {
+            state: Mutex::new(DatabaseState {
+                conn: None,
+                status: DatabaseStatus::Locked,
+                recovered: None,
+            }),
+        }
+    }
+
+    /// Opens a database created with `deferred`. Returns false if it was
+    /// already opened (or tried).
+    pub fn open_deferred(&self, app: &AppHandle) -> bool {
+        let Ok(mut state) = self.lock() else {
+            return false;
+        };
+        if state.status != DatabaseStatus::Locked {
+            return false;
+        }
+        *state = DatabaseState::opened(open_encrypted(app));
+        true
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, DatabaseState>, String> {
+        self.state
+            .lock()
+            .map_err(|e| format!("Database lock poisoned: {e}"))
+    }
+
+    /// Takes the summary of a startup rebuild of a corrupted database, if one happened.
+    pub fn take_recovery(&self) -> Option<DatabaseRecovered> {
+        self.lock()
+            .ok()
+            .and_then(|mut state| state.recovered.take())
+    }
+
+    /// The SQLCipher version serving the live connection, or None when the
+    /// library is plain SQLite.
+    pub fn cipher_version(&self) -> Result<Option<String>, String> {
+        self.with_conn(|conn| {
+            conn.query_row("PRAGMA cipher_version", [], |row| row.get(0))
+                .optional()
+        })
+    }
+
+    /// Runs a full `PRAGMA integrity_check` on the live database. Returns the
+    /// problems found; empty means the database is intact.
+    pub fn integrity_problems(&self) -> Result<Vec<String>, String> {
+        self.with_conn(|conn| integrity_problems(conn, "integrity_check"))
+    }
+
+    /// Runs `PRAGMA quick_check`, which skips the index cross-checks of
+    /// `integrity_problems` and is fast enough to run on demand.
+    pub fn quick_check_problems(&self) -> Result<Vec<String>, String> {
+        self.with_conn(|conn| integrity_problems(conn, "quick_check"))
+    }
+
+    /// Whether the database opened, and if not, why.
+    pub fn status(&self) -> DatabaseStatus {
+        match self.lock() {
+            Ok(state) => state.status.clone(),
+            Err(message) => DatabaseStatus::Failed { message },
+        }
+    }
+
+    /// Runs `f` with the connection locked.
+    pub fn with_conn<T>(
+        &self,
+        f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>,
+    ) -> Result<T, String> {
+        let mut state = self.lock()?;
+        let DatabaseState { conn, status, .. } = &mut *state;
+        let conn = conn
+            .as_mut()
+            .ok_or_else(|| format!("Database unavailable: {status}"))?;
+        f(conn).map_err(|e| format!("Database error: {e}"))
+    }
+
+    /// Closes the connection and securely deletes the database with its
+    /// journal and kept copies (`.pre-restore`, `.corrupt-*`), and the legacy
+    /// plaintext database. Used by the emergency wipe, which restarts the app.
+    pub fn destroy(&self, app: &AppHandle) -> Result<(), String> {
+        let dir = database_dir(app).map_err(|status| status.to_string())?;
+        let mut state = self.lock()?;
+        state.conn = None;
+        state.status = DatabaseStatus::Failed {
+            message: "The database was wiped".to_string(),
+        };
+
+        let entries =
+            std::fs::read_dir(&dir).map_err(|e| format!("Failed to list database files: {e}"))?;
+        let mut failures = Vec::new();
+        for entry in entries.filter_map(Result::ok) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.starts_with(DATABASE_FILENAME) && !name.starts_with(LEGACY_DATABASE_FILENAME) {
+                continue;
+            }
+            if let Err(e) = secure_delete(&entry.path()) {
+                failures.push(format!("{name}: {e}"));
+            }
+        }
+        if failures.is_empty() {
+            log::info!("Database wiped");
+            Ok(())
+        } else {
+            Err(format!("Failed to delete {}", failures.join("; ")))
+        }
+    }
+
+    /// Copies the live database to a new file at `destination` with SQLite's
+    /// online backup API, encrypted under the same key. Calls `progress` with
+    /// the percentage copied between steps.
+    pub fn backup_to(
+        &self,
+        destination: &Path,
+        mut progress: impl FnMut(u32),
+    ) -> Result<(), String> {
+        let key = existing_key().map_err(|status| status.to_string())?;
+        let state = self.lock()?;
+        let source = state
+            .conn
+            .as_ref()
+            .ok_or_else(|| format!("Database unavailable: {}", state.status))?;
+
+        let mut target = Connection::open(destination)
+            .map_err(|e| format!("Failed to create backup file: {e}"))?;
+        apply_key(&target, &key).map_err(|e| format!("Failed to key backup file: {e}"))?;
+
+        let backup =
+            Backup::new(source, &mut target).map_err(|e| format!("Failed to start backup: {e}"))?;
+        loop {
+            match backup
+                .step(BACKUP_PAGES_PER_STEP)
+                .map_err(|e| format!("Backup failed: {e}"))?
+            {
+                StepResult::Done => break,
+                StepResult::More => {
+                    let p = backup.progress();
+                    let done = p.pagecount - p.remaining;
+                    progress((done * 100 / p.pagecount.max(1)) as u32);
+                }
+                // Busy or Locked: another connection holds a lock; try again shortly
+                _ => std::thread::sleep(Duration::from_millis(50)),
+            }
+        }
+        drop(backup);
+        progress(100);
+
+        let check: String = target
+            .query_row("PRAGMA quick_check", [], |row| row.get(0))
+            .map_err(|e| format!("Failed to verify backup: {e}"))?;
+        if check != "ok" {
+            return Err(format!("Backup failed verification: {check}"));
+        }
+        Ok(())
+    }
+
+    /// Replaces the database with the backup at `source`. The backup is
+    /// validated first (same key, intact, not from a newer schema); the current
+    /// connection is then closed, the current file kept as `.pre-restore`, and
+    /// the backup copied into place and opened. Any failure after the swap puts
+    /// the previous database back. Calls `progress` with the percentage copied.
+    pub fn restore_from(
+        &self,
+        app: &AppHandle,
+        source: &Path,
+        mut progress: impl FnMut(u32),
+    ) -> Result<PathBuf, String> {
+        validate_backup(source)?;
+        let path = database_dir(app)
+            .map_err(|status| status.to_string())?
+            .join(DATABASE_FILENAME);
+        let kept = path.with_extension(format!("db.{PRE_RESTORE_SUFFIX}"));
+
+        let mut state = self.lock()?;
+        // Dropping the connection closes the file before it's moved
+        state.conn = None;
+
+        let mut swap = || -> Result<(), String> {
+            if kept.exists() {
+                std::fs::remove_file(&kept)
+                    .map_err(|e| format!("Failed to remove previous pre-restore copy: {e}"))?;
+            }
+            if path.exists() {
+                std::fs::rename(&path, &kept)
+                    .map_err(|e| format!("Failed to set aside current database: {e}"))?;
+            }
+            copy_with_progress(source, &path, &mut progress)
+        };
+        let result = swap().and_then(|_| {
+            open_encrypted(app).map_err(|status| format!("Restored database won't open: {status}"))
+        });
+
+        match result {
+            Ok(opened) => {
+                *state = DatabaseState::opened(Ok(opened));
+                log::info!("Database restored from backup");
+                Ok(kept)
+            }
+            Err(message) => {
+                log::error!("Restore failed, putting the previous database back: {message}");
+                if kept.exists() {
+                    if let Err(e) = std::fs::rename(&kept, &path) {
+                        log::error!("Failed to put the previous database back: {e}");
+                    }
+                }
+                *state = DatabaseState::opened(open_encrypted(app));
+                Err(message)
+            }
+        }
+    }
+}
+
+/// The database key, which must already exist (backups use the live key).
+fn existing_key() -> Result<[u8; secrets::KEY_LEN], DatabaseStatus> {
+    match secrets::get_key(DATABASE_KEY_NAME) {
+        Ok(Some(key)) => Ok(key),
+        Ok(None) => Err(DatabaseStatus::KeyMissing),
+        Err(message) => Err(DatabaseStatus::KeychainUnavailable { message }),
+    }
+}
+
+fn apply_key(conn: &Connection, key: &[u8; secrets::KEY_LEN]) -> rusqlite::Result<()> {
+    let hex_key: String = key.iter().map(|b| format!("{b:02x}")).collect();
+    conn.execute_batch(&format!("PRAGMA key = \"x'{hex_key}'\";"))
+}
+
+/// SQLCipher only checks the key on first read; a wrong key reads as "not a database".
+fn verify_key(conn: &Connection) -> Result<(), DatabaseStatus> {
+    match conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+        row.get::<_, i64>(0)
+    }) {
+        Ok(_) => Ok(()),
+        Err(rusqlite::Error::SqliteFailure(e, _)) if e.code == ErrorCode::NotADatabase => {
+            Err(DatabaseStatus::KeyMismatch)
+        }
+        Err(e) => Err(failed("Failed to read database", e)),
+    }
+}
+
+/// Checks a backup opens with the current key, passes an integrity check and
+/// isn't from a newer schema than this build understands.
+fn validate_backup(source: &Path) -> Result<(), String> {
+    let key = existing_key().map_err(|status| status.to_string())?;
+    let conn = Connection::open_with_flags(source, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open backup: {e}"))?;
+    apply_key(&conn, &key).map_err(|e| format!("Failed to key backup: {e}"))?;
+    verify_key(&conn).map_err(|status| match status {
+        DatabaseStatus::KeyMismatch => {
+            "Backup is not a PrivacyScribe database or was made with a different key".to_string()
+        }
+        other => other.to_string(),
+    })?;
+
+    let check: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to check backup integrity: {e}"))?;
+    if check != "ok" {
+        return Err(format!("Backup is damaged: {check}"));
+    }
+    let version: i64 = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to read backup schema version: {e}"))?;
+    if version > SCHEMA_VERSION {
+        return Err(format!(
+            "Backup is from a newer version of the app (schema {version}, this version supports {SCHEMA_VERSION})"
+        ));
+    }
+    let has_transcripts: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'transcripts')",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to inspect backup: {e}"))?;
+    if !has_transcripts {
+        return Err("Backup has no transcripts table".to_string());
+    }
+    Ok(())
+}
+
+fn copy_with_progress(
+    source: &Path,
+    destination: &Path,
+    progress: &mut impl FnMut(u32),
+) -> Result<(), String> {
+    let mut reader =
+        std::fs::File::open(source).map_err(|e| format!("Failed to open backup: {e}"))?;
+    let total = reader
+        .metadata()
+        .map_err(|e| format!("Failed to read backup: {e}"))?
+        .len();
+    let mut writer = std::fs::File::create(destination)
+        .map_err(|e| format!("Failed to create database file: {e}"))?;
+
+    let mut buffer = vec![0u8; RESTORE_CHUNK_BYTES];
+    let mut copied: u64 = 0;
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read backup: {e}"))?;
+        if read == 0 {
+            break;
+        }
+        writer
+            .write_all(&buffer[..read])
+            .map_err(|e| format!("Failed to write database file: {e}"))?;
+        copied += read as u64;
+        progress((copied * 100 / total.max(1)) as u32);
+    }
+    writer
+        .sync_all()
+        .map_err(|e| format!("Failed to write database file: {e}"))
+}
+
+fn failed(context: &str, e: impl std::fmt::Display) -> DatabaseStatus {
+    DatabaseStatus::Failed {
+        message: format!("{context}: {e}"),
+    }
+}
+
+pub(crate) fn database_dir(app: &AppHandle) -> Result<PathBuf, DatabaseStatus> {
+    let dir =
+        paths::app_config_dir(app).map_err(|e| failed("Failed to get app config directory", e))?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| failed("Failed to create app config directory", e))?;
+    Ok(dir)
+}
+
+/// Whether the database file starts with the plain SQLite header, i.e. could
+/// be read without the key. None when there's no database file yet.
+pub fn file_is_plaintext(app: &AppHandle) -> Result<Option<bool>, String> {
+    let path = database_dir(app)
+        .map_err(|status| status.to_string())?
+        .join(DATABASE_FILENAME);
+    let mut file = match std::fs::File::open(&path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to open database file: {e}")),
+    };
+    let mut header = [0u8; 16];
+    file.read_exact(&mut header)
+        .map_err(|e| format!("Failed to read database header: {e}"))?;
+    Ok(Some(&header == PLAINTEXT_HEADER))
+}
+
+/// Bytes the database takes on disk, its journals included (not the kept
+/// `.pre-restore` and `.corrupt-*` copies).
+pub fn file_size(app: &AppHandle) -> Result<u64, String> {
+    let dir = database_dir(app).map_err(|status| status.to_string())?;
+    let mut total = 0;
+    for suffix in ["", "-wal", "-journal"] {
+        match std::fs::metadata(dir.join(format!("{DATABASE_FILENAME}{suffix}"))) {
+            Ok(metadata) => total += metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to read database size: {e}")),
+        }
+    }
+    Ok(total)
+}
+
+fn open_encrypted(app: &AppHandle) -> Result<Opened, DatabaseStatus> {
+    let dir = database_dir(app)?;
+    let path = dir.join(DATABASE_FILENAME);
+
+    // An existing database needs its existing key; only a new one may get a new key
+    let key = if path.exists() {
+        existing_key()?
+    } else {
+        secrets::get_or_create_key(DATABASE_KEY_NAME)
+            .map_err(|message| DatabaseStatus::KeychainUnavailable { message })?
+    };
+
+    let mut conn = Connection::open(&path).map_err(|e| failed("Failed to open database", e))?;
+    apply_key(&conn, &key).map_err(|e| failed("Failed to key database", e))?;
+    verify_key(&conn)?;
+
+    // quick_check keeps startup fast on healthy databases; the full check and
+    // salvage only run when it finds damage
+    let mut recovered = None;
+    let quick_problems =
+        integrity_problems(&conn, "quick_check").unwrap_or_else(|e| vec![e.to_string()]);
+    if !quick_problems.is_empty() {
+        log::error!("Database failed quick_check: {}", quick_problems.join("; "));
+        let problems =
+            integrity_problems(&conn, "integrity_check").unwrap_or_else(|e| vec![e.to_string()]);
+        if !problems.is_empty() {
+            drop(conn);
+            let summary = rebuild_corrupt_database(&path, &key, problems)
+                .map_err(|message| DatabaseStatus::Failed { message })?;
+            recovered = Some(summary);
+            conn = Connection::open(&path).map_err(|e| failed("Failed to open database", e))?;
+            apply_key(&conn, &key).map_err(|e| failed("Failed to key database", e))?;
+            verify_key(&conn)?;
+        }
+    }
+
+    // Overwrite deleted rows (drafts and transcripts hold PHI) instead of leaving them in free pages
+    conn.busy_timeout(BUSY_TIMEOUT)
+        .and_then(|_| conn.pragma_update(None, "secure_delete", true))
+        .and_then(|_| conn.pragma_update(None, "foreign_keys", true))
+        .map_err(|e| failed("Failed to initialize database", e))?;
+    migrations::run(&mut conn).map_err(|e| failed("Database migration failed", e))?;
+    backfill_search_index(&conn).map_err(|e| failed("Failed to build search index", e))?;
+
+    let legacy_path = dir.join(LEGACY_DATABASE_FILENAME);
+    if legacy_path.exists() {
+        // A failed migration leaves the legacy rows in place to retry next start
+        if let Err(e) = migrate_legacy(&mut conn, &legacy_path) {
+            log::error!("Failed to migrate legacy database tables: {e}");
+        }
+    }
+
+    Ok(Opened { conn, recovered })
+}
+
+/// Runs `PRAGMA quick_check` or `PRAGMA integrity_check`, returning the
+/// reported problems (none when the check says "ok").
+fn integrity_problems(conn: &Connection, pragma: &str) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA {pragma}"))?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(rows.into_iter().filter(|row| row != "ok").collect())
+}
+
+/// Backend tables salvaged from a corrupted database: (table, columns).
+/// The search index isn't listed; the insert triggers rebuild it.
+const SALVAGE_TABLES: &[(&str, &str)] = &[
+    (
+        "transcripts",
+        "id, title, text, language, model_id, duration_secs, note_id, created_at, updated_at, \
+         word_count, session_label, deleted_at, deidentified_with, content_hash",
+    ),
+    (
+        "drafts",
+        "draft_id, content, cursor_meta, created_at, updated_at",
+    ),
+    (
+        "audit_log",
+        "id, timestamp_ms, actor, kind, subject_id, detail",
+    ),
+    ("tags", "id, name, created_at"),
+    ("transcript_tags", "transcript_id, tag_id"),
+    (
+        "transcript_revisions",
+        "id, transcript_id, title, text, created_at",
+    ),
+];
+
+/// Copies every readable row of `table` from the attached `damaged` database.
+/// Reading stops at the first corrupted page; rows before it are kept.
+fn salvage_table(conn: &Connection, table: &str, columns: &str) -> rusqlite::Result<u32> {
+    // A database from an older schema lacks newer columns; those keep their defaults
+    let existing = conn
+        .prepare("SELECT name FROM pragma_table_info(?1, 'damaged')")?
+        .query_map([table], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let columns = columns
+        .split(',')
+        .map(str::trim)
+        .filter(|column| existing.iter().any(|name| name == column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if columns.is_empty() {
+        return Ok(0);
+    }
+    let column_count = columns.split(',').count();
+    let placeholders = vec!["?"; column_count].join(", ");
+    let insert_sql =
+        format!("INSERT OR IGNORE INTO main.{table} ({columns}) VALUES ({placeholders})");
+
+    let mut select = conn.prepare(&format!("SELECT {columns} FROM damaged.{table}"))?;
+    let mut rows = select.query([])?;
+    let mut salvaged = 0;
+    loop {
+        match rows.next() {
+            Ok(Some(row)) => {
+                let values = (0..column_count)
+                    .map(|i| row.get::<_, rusqlite::types::Value>(i))
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                conn.prepare_cached(&insert_sql)?
+                    .execute(rusqlite::params_from_iter(values))?;
+                salvaged += 1;
+            }
+            Ok(None) => break,
+            Err(e) => {
+                log::warn!("Stopped salvaging {table} after {salvaged} rows: {e}");
+                break;
+            }
+        }
+    }
+    Ok(salvaged)
+}
+
+/// Dump-and-reload recovery: copies what can still be read from the corrupted
+/// database at `path` into a fresh file, keeps the damaged original as
+/// `<name>.corrupt-<timestamp>`, and moves the fresh file into place.
+fn rebuild_corrupt_database(
+    path: &Path,
+    key: &[u8; secrets::KEY_LEN],
+    problems: Vec<String>,
+) -> Result<DatabaseRecovered, String> {
+    let fresh_path = path.with_extension("db.rebuild");
+    if fresh_path.exists() {
+        std::fs::remove_file(&fresh_path)
+            .map_err(|e| format!("Failed to remove stale rebuild file: {e}"))?;
+    }
+
+    let mut fresh =
+        Connection::open(&fresh_path).map_err(|e| format!("Failed to create rebuild file: {e}"))?;
+    apply_key(&fresh, key).map_err(|e| format!("Failed to key rebuild file: {e}"))?;
+    migrations::run(&mut fresh).map_err(|e| format!("Failed to initialize rebuild file: {e}"))?;
+
+    let path_str = path.to_str().ok_or("Database path is not valid UTF-8")?;
+    let hex_key: String = key.iter().map(|b| format!("{b:02x}")).collect();
+    fresh
+        .execute(
+            &format!("ATTACH DATABASE ?1 AS damaged KEY \"x'{hex_key}'\""),
+            [path_str],
+        )
+        .map_err(|e| format!("Failed to attach damaged database: {e}"))?;
+
+    let mut counts = Vec::with_capacity(SALVAGE_TABLES.len());
+    for &(table, columns) in SALVAGE_TABLES {
+        let salvaged = fresh
+            .execute_batch("BEGIN")
+            .and_then(|_| salvage_table(&fresh, table, columns))
+            .and_then(|count| fresh.execute_batch("COMMIT").map(|_| count))
+            .unwrap_or_else(|e| {
+                log::warn!("Could not salvage {table}: {e}");
+                let _ = fresh.execute_batch("ROLLBACK");
+                0
+            });
+        counts.push(salvaged);
+    }
+    if let Err(e) = fill_word_counts(&fresh) {
+        log::warn!("Failed to count words in salvaged transcripts: {e}");
+    }
+    if let Err(e) = fill_content_hashes(&fresh) {
+        log::warn!("Failed to hash salvaged transcripts: {e}");
+    }
+    if let Err(e) = fresh.execute_batch("DETACH DATABASE damaged") {
+        log::warn!("Failed to detach damaged database: {e}");
+    }
+    drop(fresh);
+
+    let corrupt_path = PathBuf::from(format!(
+        "{}.corrupt-{}",
+        path.display(),
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::rename(path, &corrupt_path)
+        .map_err(|e| format!("Failed to set aside damaged database: {e}"))?;
+    std::fs::rename(&fresh_path, path)
+        .map_err(|e| format!("Failed to move rebuilt database into place: {e}"))?;
+
+    log::warn!(
+        "Rebuilt corrupted database: {} transcripts, {} drafts, {} audit entries salvaged",
+        counts[0],
+        counts[1],
+        counts[2]
+    );
+    Ok(DatabaseRecovered {
+        transcripts_salvaged: counts[0],
+        drafts_salvaged: counts[1],
+        audit_entries_salvaged: counts[2],
+        corrupt_copy_path: corrupt_path.display().to_string(),
+        problems: problems.into_iter().take(MAX_REPORTED_PROBLEMS).collect(),
+    })
+}
+
+/// Counts words for transcripts stored without a count (added by an upgrade
+/// or copied from the legacy database).
+fn fill_word_counts(conn: &Connection) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare("SELECT id, text FROM transcripts WHERE word_count = 0")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (id, text) in rows {
+        conn.execute(
+            "UPDATE transcripts SET word_count = ?2 WHERE id = ?1",
+            rusqlite::params![id, count_words(&text)],
+        )?;
+    }
+    Ok(())
+}
+
+/// Hashes transcripts stored without a content hash (added by an upgrade or
+/// copied from the legacy database), for duplicate detection.
+fn fill_content_hashes(conn: &Connection) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare("SELECT id, text FROM transcripts WHERE content_hash IS NULL")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (id, text) in rows {
+        conn.execute(
+            "UPDATE transcripts SET content_hash = ?2 WHERE id = ?1",
+            rusqlite::params![id, duplicates::content_hash(&text)],
+        )?;
+    }
+    Ok(())
+}
+
+/// Indexes transcripts saved before the search index existed. The index is
+/// rebuilt only when it's out of step with the table, so this runs once after
+/// the upgrade and is a pair of counts afterwards.
+fn backfill_search_index(conn: &Connection) -> rusqlite::Result<()> {
+    let count = |table: &str| -> rusqlite::Result<i64> {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+            row.get(0)
+        })
+    };
+    let transcripts = count("transcripts")?;
+    if count("transcripts_fts")? == transcripts {
+        return Ok(());
+    }
+
+    conn.execute_batch(
+        "BEGIN;
+         DELETE FROM transcripts_fts;
+         INSERT INTO transcripts_fts (id, title, text) SELECT id, title, text FROM transcripts;
+         COMMIT;",
+    )?;
+    log::info!("Indexed {transcripts} transcripts for search");
+    Ok(())
+}
+
+/// Copies backend tables out of the legacy plaintext database, verifies every
+/// row arrived, then drops them from the legacy file and vacuums it so the
+/// plaintext pages are overwritten. The legacy file itself stays: the
+/// frontend still keeps notes and templates there.
+fn migrate_legacy(conn: &mut Connection, legacy_path: &Path) -> Result<(), String> {
+    let legacy_path_str = legacy_path
+        .to_str()
+        .ok_or("Legacy database path is not valid UTF-8")?;
+
+    // An empty key attaches the file as plain SQLite
+    conn.execute("ATTACH DATABASE ?1 AS legacy KEY ''", [legacy_path_str])
+        .map_err(|e| format!("Failed to attach legacy database: {e}"))?;
+
+    let result = copy_legacy_tables(conn);
+    if let Err(e) = conn.execute_batch("DETACH DATABASE legacy") {
+        log::warn!("Failed to detach legacy database: {e}");
+    }
+    let migrated = result?;
+    if migrated.is_empty() {
+        return Ok(());
+    }
+
+    let legacy = Connection::open(legacy_path)
+        .map_err(|e| format!("Failed to reopen legacy database: {e}"))?;
+    legacy
+        .busy_timeout(BUSY_TIMEOUT)
+        .and_then(|_| legacy.pragma_update(None, "secure_delete", true))
+        .map_err(|e| format!("Failed to configure legacy database: {e}"))?;
+    for table in &migrated {
+        legacy
+            .execute_batch(&format!("DROP TABLE {table}"))
+            .map_err(|e| format!("Failed to drop legacy {table}: {e}"))?;
+    }
+    // VACUUM rewrites the file without the dropped pages; truncating the WAL
+    // (tauri-plugin-sql runs in WAL mode) removes the copies logged there
+    legacy
+        .execute_batch("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")
+        .map_err(|e| format!("Failed to vacuum legacy database: {e}"))?;
+
+    log::info!(
+        "Migrated {} tables out of the plaintext database",
+        migrated.len()
+    );
+    Ok(())
+}
+
+/// Copies each legacy table present into the encrypted database in one
+/// transaction. Returns the tables copied; nothing is committed unless every
+/// legacy row is found in the encrypted copy.
+fn copy_legacy_tables(conn: &mut Connection) -> Result<Vec<&'static str>, String> {
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start migration: {e}"))?;
+    let mut migrated = Vec::new();
+
+    for &(table, key, columns) in LEGACY_TABLES {
+        let present: bool = tx
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM legacy.sqlite_master WHERE type = 'table' AND name = ?1)",
+                [table],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to inspect legacy database: {e}"))?;
+        if !present {
+            continue;
+        }
+
+        let legacy_rows: i64 = tx
+            .query_row(&format!("SELECT COUNT(*) FROM legacy.{table}"), [], |row| {
+                row.get(0)
+            })
+            .map_err(|e| format!("Failed to count legacy {table}: {e}"))?;
+        tx.execute(
+            &format!(
+                "INSERT OR IGNORE INTO main.{table} ({columns}) SELECT {columns} FROM legacy.{table}"
+            ),
+            [],
+        )
+        .map_err(|e| format!("Failed to copy legacy {table}: {e}"))?;
+        let copied_rows: i64 = tx
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM main.{table} WHERE {key} IN (SELECT {key} FROM legacy.{table})"
+                ),
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to verify {table}: {e}"))?;
+        if copied_rows != legacy_rows {
+            return Err(format!(
+                "Row count mismatch migrating {table}: {legacy_rows} legacy, {copied_rows} copied"
+            ));
+        }
+
+        log::info!("Migrated {legacy_rows} rows from legacy {table}");
+        migrated.push(table);
+    }
+    if migrated.contains(&"transcripts") {
+        fill_word_counts(&tx).map_err(|e| format!("Failed to count words: {e}"))?;
+        fill_content_hashes(&tx).map_err(|e| format!("Failed to hash transcripts: {e}"))?;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit migration: {e}"))?;
+    Ok(migrated)
+}
@@ -0,0 +1,337 @@
+//! Versioned schema migrations for the backend database.
+//!
+//! Migrations are SQL files under `src-tauri/migrations/`, embedded at build
+//! time and applied in order, each in its own transaction. Applied versions
+//! are recorded in `schema_migrations` with a SHA-256 of their SQL; a
+//! migration whose SQL no longer matches what was applied stops startup
+//! instead of leaving databases on diverging schemas. Shipped migrations are
+//! never edited — schema changes go in a new file.
+//!
+//! `PRAGMA user_version` still mirrors the newest applied version so backups
+//! can be checked before a restore. Databases from before this runner only
+//! have `user_version`; their versions are recorded as applied on first start.
+
+use std::collections::HashMap;
+
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+
+use crate::commands::pipeline;
+
+/// One schema change.
+pub(super) struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+    /// Data fix-up run after `sql`, in the same transaction
+    pub after: Option<fn(&Connection) -> rusqlite::Result<()>>,
+}
+
+/// Every migration, oldest first. Versions are consecutive from 1.
+pub(super) const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial",
+        sql: include_str!("../../migrations/0001_initial.sql"),
+        after: None,
+    },
+    Migration {
+        version: 2,
+        name: "transcript_session_metadata",
+        sql: include_str!("../../migrations/0002_transcript_session_metadata.sql"),
+        after: Some(super::fill_word_counts),
+    },
+    Migration {
+        version: 3,
+        name: "transcript_trash",
+        sql: include_str!("../../migrations/0003_transcript_trash.sql"),
+        after: None,
+    },
+    Migration {
+        version: 4,
+        name: "tags",
+        sql: include_str!("../../migrations/0004_tags.sql"),
+        after: None,
+    },
+    Migration {
+        version: 5,
+        name: "transcript_revisions",
+        sql: include_str!("../../migrations/0005_transcript_revisions.sql"),
+        after: None,
+    },
+    Migration {
+        version: 6,
+        name: "transcription_stats",
+        sql: include_str!("../../migrations/0006_transcription_stats.sql"),
+        after: None,
+    },
+    Migration {
+        version: 7,
+        name: "transcript_deidentification",
+        sql: include_str!("../../migrations/0007_transcript_deidentification.sql"),
+        after: None,
+    },
+    Migration {
+        version: 8,
+        name: "transcript_content_hash",
+        sql: include_str!("../../migrations/0008_transcript_content_hash.sql"),
+        after: Some(super::fill_content_hashes),
+    },
+];
+
+/// Why migrations couldn't be applied.
+#[derive(Debug)]
+pub(super) enum MigrationError {
+    /// A shipped migration was edited after this database applied it
+    ChecksumMismatch {
+        version: i64,
+        name: &'static str,
+    },
+    /// The database has migrations this version of the app doesn't know
+    NewerSchema {
+        version: i64,
+    },
+    Sqlite(rusqlite::Error),
+}
+
+impl std::fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationError::ChecksumMismatch { version, name } => write!(
+                f,
+                "Migration {version} ({name}) differs from the one applied to this database. \
+                 Shipped migrations must not be edited; restore the original SQL and add a new \
+                 migration instead"
+            ),
+            MigrationError::NewerSchema { version } => write!(
+                f,
+                "Database has schema version {version}, newer than this app supports. \
+                 Update the app or restore a backup made with this version"
+            ),
+            MigrationError::Sqlite(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<rusqlite::Error> for MigrationError {
+    fn from(e: rusqlite::Error) -> Self {
+        MigrationError::Sqlite(e)
+    }
+}
+
+fn checksum(sql: &str) -> String {
+    Sha256::digest(sql.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Applies pending migrations. Returns the versions applied by this call.
+pub(super) fn run(conn: &mut Connection) -> Result<Vec<i64>, MigrationError> {
+    run_migrations(conn, MIGRATIONS)
+}
+
+fn run_migrations(
+    conn: &mut Connection,
+    migrations: &[Migration],
+) -> Result<Vec<i64>, MigrationError> {
+    let tracked: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !tracked {
+        let tx = conn.transaction()?;
+        tx.execute_batch(
+            "CREATE TABLE schema_migrations (
+                version    INTEGER PRIMARY KEY,
+                name       TEXT NOT NULL,
+                checksum   TEXT NOT NULL,
+                applied_at INTEGER NOT NULL
+            );",
+        )?;
+        // Databases from before this runner recorded their version only in user_version
+        let legacy_version: i64 = tx.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        for migration in migrations.iter().filter(|m| m.version <= legacy_version) {
+            record(&tx, migration)?;
+        }
+        tx.commit()?;
+        if legacy_version > 0 {
+            log::info!("Started tracking migrations at schema version {legacy_version}");
+        }
+    }
+
+    let applied: HashMap<i64, String> = conn
+        .prepare("SELECT version, checksum FROM schema_migrations")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    if let Some(&version) = applied
+        .keys()
+        .filter(|version| !migrations.iter().any(|m| m.version == **version))
+        .max()
+    {
+        return Err(MigrationError::NewerSchema { version });
+    }
+    for migration in migrations {
+        if let Some(applied_checksum) = applied.get(&migration.version) {
+            if *applied_checksum != checksum(migration.sql) {
+                return Err(MigrationError::ChecksumMismatch {
+                    version: migration.version,
+                    name: migration.name,
+                });
+            }
+        }
+    }
+
+    let mut newly_applied = Vec::new();
+    for migration in migrations
+        .iter()
+        .filter(|m| !applied.contains_key(&m.version))
+    {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration.sql)?;
+        if let Some(after) = migration.after {
+            after(&tx)?;
+        }
+        record(&tx, migration)?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+        tx.commit()?;
+        log::info!(
+            "Applied migration {} ({})",
+            migration.version,
+            migration.name
+        );
+        newly_applied.push(migration.version);
+    }
+    Ok(newly_applied)
+}
+
+fn record(conn: &Connection, migration: &Migration) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO schema_migrations (version, name, checksum, applied_at)
+         VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![
+            migration.version,
+            migration.name,
+            checksum(migration.sql),
+            pipeline::now_ms() as i64
+        ],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::duplicates::content_hash;
+
+    /// Column names and declared types of every table, for comparing schemas
+    /// reached by different paths.
+    fn columns(conn: &Connection) -> Vec<(String, String, String)> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT m.name, c.name, c.type FROM sqlite_master m, pragma_table_info(m.name) c
+                 WHERE m.type = 'table' AND m.name NOT LIKE 'transcripts_fts%'
+                   AND m.name NOT LIKE 'sqlite_%'
+                 ORDER BY m.name, c.name",
+            )
+            .unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+
+    fn head() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run(&mut conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn versions_are_consecutive_from_one() {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, index as i64 + 1, "{}", migration.name);
+        }
+    }
+
+    #[test]
+    fn every_version_migrates_to_head() {
+        let expected = columns(&head());
+        for version in 0..=MIGRATIONS.len() {
+            let mut conn = Connection::open_in_memory().unwrap();
+            run_migrations(&mut conn, &MIGRATIONS[..version]).unwrap();
+            if version >= 1 {
+                conn.execute(
+                    "INSERT INTO transcripts (id, text, created_at, updated_at)
+                     VALUES ('tr_1', 'three short words', 0, 0)",
+                    [],
+                )
+                .unwrap();
+            }
+
+            let applied = run(&mut conn).unwrap();
+            assert_eq!(applied.len(), MIGRATIONS.len() - version);
+            assert_eq!(columns(&conn), expected, "from version {version}");
+            let user_version: i64 = conn
+                .query_row("PRAGMA user_version", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(user_version, MIGRATIONS.len() as i64);
+            if version == 1 {
+                let words: u32 = conn
+                    .query_row("SELECT word_count FROM transcripts", [], |row| row.get(0))
+                    .unwrap();
+                assert_eq!(words, 3);
+            }
+            // Rows from before migration 8 are hashed by it
+            if (1..8).contains(&version) {
+                let hash: String = conn
+                    .query_row("SELECT content_hash FROM transcripts", [], |row| row.get(0))
+                    .unwrap();
+                assert_eq!(hash, content_hash("three short words"));
+            }
+        }
+    }
+
+    #[test]
+    fn untracked_databases_start_from_their_user_version() {
+        let expected = columns(&head());
+        for version in 1..=3 {
+            let mut conn = Connection::open_in_memory().unwrap();
+            run_migrations(&mut conn, &MIGRATIONS[..version]).unwrap();
+            conn.execute_batch("DROP TABLE schema_migrations").unwrap();
+
+            // Would fail on duplicate columns if earlier migrations re-ran
+            run(&mut conn).unwrap();
+            assert_eq!(columns(&conn), expected, "from user_version {version}");
+        }
+    }
+
+    #[test]
+    fn edited_migrations_are_refused() {
+        let mut conn = head();
+        conn.execute(
+            "UPDATE schema_migrations SET checksum = 'edited' WHERE version = 2",
+            [],
+        )
+        .unwrap();
+        assert!(matches!(
+            run(&mut conn),
+            Err(MigrationError::ChecksumMismatch { version: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn unknown_newer_migrations_are_refused() {
+        let mut conn = head();
+        conn.execute(
+            "INSERT INTO schema_migrations VALUES (99, 'future', '', 0)",
+            [],
+        )
+        .unwrap();
+        assert!(matches!(
+            run(&mut conn),
+            Err(MigrationError::NewerSchema { version: 99 })
+        ));
+    }
+}
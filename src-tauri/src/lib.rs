@@ -6,6 +6,7 @@
 
 mod bindings;
 mod commands;
+mod db;
 mod types;
 mod utils;
 
@@ -17,24 +18,42 @@ pub use types::DEFAULT_QUICK_PANE_SHORTCUT;
 /// Application entry point. Sets up all plugins and initializes the app.
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Portable mode redirects every path, so it's decided before any plugin
+    // resolves one; an unwritable portable folder is reported once setup runs
+    let portable_error = utils::paths::init().err();
+    #[cfg(target_os = "windows")]
+    if portable_error.is_none() {
+        if let Some(dir) = utils::paths::portable_webview_dir() {
+            // Read by WebView2 when the first webview is created
+            std::env::set_var("WEBVIEW2_USER_DATA_FOLDER", dir);
+        }
+    }
+
     let builder = bindings::generate_bindings();
+    let invoke_handler = builder.invoke_handler();
 
     // Export TypeScript bindings in debug builds
     #[cfg(debug_assertions)]
     bindings::export_ts_bindings();
 
     // Build with common plugins
-    let mut app_builder =
-        tauri::Builder::default().plugin(tauri_plugin_store::Builder::new().build());
+    let mut app_builder = tauri::Builder::default();
 
     // Single instance plugin must be registered FIRST
     // When user tries to open a second instance, focus the existing window instead
     #[cfg(desktop)]
     {
-        app_builder = app_builder.plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.set_focus();
-                let _ = window.unminimize();
+        app_builder = app_builder.plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            // Arguments like --start-recording act in the background; only a
+            // plain launch brings the window forward
+            let args = args.get(1..).unwrap_or_default();
+            if commands::cli::handle_args(app, args, std::path::Path::new(&cwd)) {
+                return;
+            }
+            // Shows (or recreates) the window, which may never have been shown
+            // after a hidden start
+            if let Err(e) = commands::navigation::focus_main_window(app) {
+                log::error!("Failed to focus main window for second instance: {e}");
             }
         }));
     }
@@ -43,11 +62,15 @@ pub fn run() {
     // Note: Only applies to windows listed in capabilities (main window only, not quick-pane)
     #[cfg(desktop)]
     {
-        app_builder = app_builder.plugin(
-            tauri_plugin_window_state::Builder::new()
-                .with_state_flags(tauri_plugin_window_state::StateFlags::all())
-                .build(),
-        );
+        // Visibility isn't restored: setup shows the main window itself, unless
+        // starting hidden
+        use tauri_plugin_window_state::StateFlags;
+        let mut window_state = tauri_plugin_window_state::Builder::new()
+            .with_state_flags(StateFlags::all() & !StateFlags::VISIBLE);
+        if let Some(file) = utils::paths::portable_window_state_file() {
+            window_state = window_state.with_filename(file);
+        }
+        app_builder = app_builder.plugin(window_state.build());
     }
 
     // Updater plugin for in-app updates
@@ -56,8 +79,19 @@ pub fn run() {
         app_builder = app_builder.plugin(tauri_plugin_updater::Builder::new().build());
     }
 
+    // Portable mode keeps the log file beside the executable too
+    #[cfg(target_os = "macos")]
+    let log_file = match utils::paths::portable_log_dir().filter(|_| portable_error.is_none()) {
+        Some(path) => tauri_plugin_log::TargetKind::Folder {
+            path,
+            file_name: None,
+        },
+        None => tauri_plugin_log::TargetKind::LogDir { file_name: None },
+    };
+
     app_builder = app_builder
         .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_notification::init())
         .plugin(
             tauri_plugin_log::Builder::new()
@@ -74,9 +108,7 @@ pub fn run() {
                     tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Webview),
                     // Log to system logs on macOS (appears in Console.app)
                     #[cfg(target_os = "macos")]
-                    tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::LogDir {
-                        file_name: None,
-                    }),
+                    tauri_plugin_log::Target::new(log_file),
                 ])
                 .build(),
         );
@@ -95,13 +127,57 @@ pub fn run() {
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_os::init())
-        .setup(|app| {
+        .manage(commands::navigation::MainWindowState::default())
+        .manage(commands::notifications::WindowFocusState::default())
+        .manage(commands::notifications::HeldTranscripts::default())
+        .manage(commands::app_lock::AppLockState::default())
+        .manage(commands::jobs::JobManager::default())
+        // Track focus so background notifications know whether the user is looking
+        .on_window_event(|window, event| match event {
+            tauri::WindowEvent::Focused(focused) => window
+                .state::<commands::notifications::WindowFocusState>()
+                .set_focused(window.label(), *focused),
+            tauri::WindowEvent::Destroyed => window
+                .state::<commands::notifications::WindowFocusState>()
+                .set_focused(window.label(), false),
+            _ => {}
+        })
+        // A reloading main window can't receive events until it reports ready again
+        .on_page_load(|webview, payload| {
+            if webview.label() == commands::navigation::MAIN_WINDOW_LABEL
+                && payload.event() == tauri::webview::PageLoadEvent::Started
+            {
+                webview
+                    .state::<commands::navigation::MainWindowState>()
+                    .mark_loading();
+            }
+        })
+        .setup(move |app| {
+            // Typed events can only be emitted once registered
+            builder.mount_events(app);
+
             log::info!("Application starting up");
             log::debug!(
                 "App handle initialized for package: {}",
                 app.package_info().name
             );
 
+            if let Some(message) = portable_error {
+                // Left closed: commands reaching it before the app exits get
+                // an error rather than a missing-state panic
+                app.manage(db::Database::deferred());
+                commands::storage::report_unusable_portable_root(app.handle(), &message);
+                return Ok(());
+            }
+            if let Some(root) = utils::paths::portable_root() {
+                log::info!("Portable mode: data kept in {}", root.display());
+            }
+            commands::diagnostics::apply_metrics_preference(app.handle());
+
+            // Before anything can serve PHI: start locked when a passcode is set
+            commands::app_lock::init(app.handle());
+            commands::app_lock::spawn_idle_timer(app.handle());
+
             // Set up global shortcut plugin (without any shortcuts - we register them separately)
             #[cfg(desktop)]
             {
@@ -123,20 +199,102 @@ pub fn run() {
                     app.handle(),
                     shortcut_to_register,
                 )?;
+
+                // Double-tap activation only runs an event listener when enabled
+                let prefs = commands::preferences::load_saved_preferences(app.handle());
+                if let Some(double_tap) = prefs.double_tap_modifier.as_ref() {
+                    if let Err(e) = commands::quick_pane::apply_double_tap_modifier(
+                        app.handle(),
+                        Some(double_tap),
+                    ) {
+                        log::warn!("Failed to start double-tap listener: {e}");
+                    }
+                }
             }
 
+            commands::notifications::register_notification_actions(app.handle());
+
+            // Tray icon - entry point for menu-bar-anchored quick pane and hidden starts
+            #[cfg(desktop)]
+            if let Err(e) = commands::tray::init_tray(app.handle()) {
+                log::error!("Failed to create tray icon: {e}");
+            }
+
+            // The main window starts invisible; with start_hidden or --hidden it
+            // stays that way until opened from the tray or by a second launch
+            let start_hidden = commands::preferences::load_saved_preferences(app.handle())
+                .start_hidden
+                || std::env::args().any(|arg| arg == commands::cli::HIDDEN_ARG);
+            if start_hidden {
+                log::info!("Starting hidden; main window opens from the tray or shortcut");
+            } else if let Err(e) = commands::navigation::focus_main_window(app.handle()) {
+                log::error!("Failed to show main window: {e}");
+            }
+
+            // Encrypted backend database (drafts, transcripts); stays closed rather
+            // than falling back to plaintext when its key is unavailable. With the
+            // app locked, it isn't opened (nor its key read) until the first unlock
+            if commands::app_lock::is_locked(app.handle()) {
+                app.manage(db::Database::deferred());
+            } else {
+                app.manage(db::Database::open(app.handle()));
+                commands::app_lock::database_opened(app.handle());
+            }
+            commands::recovery::check_previous_crash(app.handle());
+
+            // Off the main thread: purge expired recovery data, finish an interrupted cache
+            // wipe and keep PHI directories out of backups
+            let sweep_app = app.handle().clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                commands::cache::finish_pending_wipe(&sweep_app);
+                commands::recovery::startup_sweep(&sweep_app);
+                commands::backup_exclusion::apply(&sweep_app);
+                commands::backup_exclusion::warn_if_cloud_synced(&sweep_app);
+            });
+            commands::recovery::spawn_retention_sweep(app.handle());
+
+            #[cfg(desktop)]
+            commands::updates::spawn_update_checks(app.handle());
+
+            // The main window exists by now; the quick pane applies it on creation
+            commands::screen_capture::apply_to_window(
+                app.handle(),
+                commands::navigation::MAIN_WINDOW_LABEL,
+            );
+
             // Create the quick pane window (hidden) - must be done on main thread
             if let Err(e) = commands::quick_pane::init_quick_pane(app.handle()) {
                 log::error!("Failed to create quick pane: {e}");
                 // Non-fatal: app can still run without quick pane
             }
 
+            // Last, so everything links and arguments may use is set up
+            commands::deep_link::init(app.handle());
+            let args: Vec<String> = std::env::args().skip(1).collect();
+            let cwd = std::env::current_dir().unwrap_or_default();
+            commands::cli::handle_args(app.handle(), &args, &cwd);
+
             // NOTE: Application menu is built from JavaScript for i18n support
             // See src/lib/menu.ts for the menu implementation
 
             Ok(())
         })
-        .invoke_handler(builder.invoke_handler())
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(invoke_handler)
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| match event {
+            // Quitting mid-recording or mid-transcription first wraps that up
+            tauri::RunEvent::ExitRequested { api, code, .. }
+                if commands::shutdown::hold_exit(app, code) =>
+            {
+                api.prevent_exit();
+            }
+            // A graceful exit isn't a crash, so don't prompt for recovery next
+            // launch unless work was kept for it
+            tauri::RunEvent::Exit => {
+                commands::shutdown::on_exit(app);
+                commands::cache::wipe_on_quit(app);
+            }
+            _ => {}
+        });
 }
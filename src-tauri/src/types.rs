@@ -4,43 +4,2601 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::sync::LazyLock;
+use tauri_specta::Event;
 
 /// Default shortcut for the quick pane
 pub const DEFAULT_QUICK_PANE_SHORTCUT: &str = "CommandOrControl+Shift+.";
 
+/// Default age after which recovery audio and drafts are securely deleted.
+/// Deliberately much shorter than transcript retention.
+pub const DEFAULT_RECOVERY_RETENTION_HOURS: u32 = 72;
+
+/// Default per-session recovery file cap before rolling to the most recent audio
+pub const DEFAULT_RECOVERY_SESSION_CAP_MB: u32 = 500;
+
+/// Default minutes of audio a rolled recovery file keeps
+pub const DEFAULT_RECOVERY_ROLLING_WINDOW_MINUTES: u32 = 30;
+
+/// Default cap on all recording recovery files combined
+pub const DEFAULT_RECOVERY_DIR_CAP_MB: u32 = 2048;
+
+/// Default minutes of recording headroom below which a low-disk warning is shown
+pub const DEFAULT_LOW_DISK_WARNING_MINUTES: u32 = 15;
+
+/// Default minutes of recording headroom below which recording is refused
+pub const DEFAULT_LOW_DISK_BLOCK_MINUTES: u32 = 1;
+
+/// Default minutes of continuous recording between "still recording" reminders
+pub const DEFAULT_RECORDING_REMINDER_MINUTES: u32 = 15;
+
+/// Default days a deleted transcript stays in the trash before it's purged
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+/// Default seconds before a copied transcript is cleared from the clipboard
+pub const DEFAULT_CLIPBOARD_CLEAR_SECONDS: u32 = 60;
+
+/// Default characters per second for type-out mode
+pub const DEFAULT_TYPE_OUT_CHARS_PER_SECOND: u32 = 60;
+
+/// Allowed range for the type-out rate. Some apps drop keystrokes sent faster
+/// than a few hundred per second.
+pub const MIN_TYPE_OUT_CHARS_PER_SECOND: u32 = 5;
+pub const MAX_TYPE_OUT_CHARS_PER_SECOND: u32 = 500;
+
+/// Default minutes without activity before the app locks itself
+pub const DEFAULT_AUTO_LOCK_MINUTES: u32 = 10;
+
+/// Default minutes a recording may run without user activity before it's
+/// stopped, saved as a draft and the app locked
+pub const DEFAULT_MAX_IDLE_WHILE_RECORDING_MINUTES: u32 = 30;
+
+/// Default days audit log entries are kept (six years, the HIPAA documentation period)
+pub const DEFAULT_AUDIT_RETENTION_DAYS: u32 = 2190;
+
+/// Audit entries younger than this can't be deleted, whatever the preference says.
+/// Also enforced by the `audit_log_retention_only` trigger in db.rs.
+pub const MIN_AUDIT_RETENTION_DAYS: u32 = 365;
+
+/// Default maximum gap between two modifier taps to count as a double-tap
+pub const DEFAULT_DOUBLE_TAP_INTERVAL_MS: u32 = 300;
+
+/// Allowed range for the quick pane zoom factor
+pub const MIN_QUICK_PANE_ZOOM: f64 = 0.75;
+pub const MAX_QUICK_PANE_ZOOM: f64 = 2.0;
+
 /// Maximum size for recovery data files (10MB)
 pub const MAX_RECOVERY_DATA_BYTES: u32 = 10_485_760;
 
-/// Pre-compiled regex pattern for filename validation.
-/// Only allows alphanumeric characters, dashes, underscores, and a single extension.
-pub static FILENAME_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"^[a-zA-Z0-9_-]+(\.[a-zA-Z0-9]+)?$")
-        .expect("Failed to compile filename regex pattern")
-});
+/// Maximum size of a single transcript draft (1MB)
+pub const MAX_DRAFT_BYTES: u32 = 1_048_576;
+
+/// Maximum number of drafts kept; saving beyond this evicts the oldest
+pub const MAX_DRAFTS: u32 = 50;
+
+/// Maximum size of a saved transcript's text (4MB)
+pub const MAX_TRANSCRIPT_BYTES: u32 = 4_194_304;
+
+/// Maximum characters in a transcript title
+pub const MAX_TRANSCRIPT_TITLE_CHARS: usize = 200;
+
+/// Maximum characters in a transcript's session label
+pub const MAX_SESSION_LABEL_CHARS: usize = 100;
+
+/// Revisions kept per transcript; older ones are pruned first
+pub const MAX_REVISIONS_PER_TRANSCRIPT: u32 = 50;
+
+/// Maximum characters in a tag name
+pub const MAX_TAG_CHARS: usize = 50;
+
+/// Maximum tags on one transcript
+pub const MAX_TAGS_PER_TRANSCRIPT: usize = 20;
+
+/// Default and maximum page size for `list_transcripts`
+pub const DEFAULT_TRANSCRIPT_PAGE_SIZE: u32 = 50;
+pub const MAX_TRANSCRIPT_PAGE_SIZE: u32 = 500;
+
+/// Pre-compiled regex pattern for filename validation.
+/// Only allows alphanumeric characters, dashes, underscores, and a single extension.
+pub static FILENAME_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[a-zA-Z0-9_-]+(\.[a-zA-Z0-9]+)?$")
+        .expect("Failed to compile filename regex pattern")
+});
+
+// ============================================================================
+// Preferences
+// ============================================================================
+
+/// Application preferences that persist to disk.
+/// Only contains settings that should be saved between sessions.
+/// Missing fields fall back to their defaults so older preference files keep loading.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct AppPreferences {
+    pub theme: String,
+    /// Global shortcut for quick pane (e.g., "CommandOrControl+Shift+.")
+    /// If None, uses the default shortcut
+    pub quick_pane_shortcut: Option<String>,
+    /// User's preferred language (e.g., "en", "es", "de")
+    /// If None, uses system locale detection
+    pub language: Option<String>,
+    /// Whether showing the quick pane makes it the key window so typing works immediately.
+    /// When false the pane is ordered front without taking keyboard focus.
+    pub quick_pane_focus_keyboard: bool,
+    /// Double-tap modifier that toggles the quick pane, in addition to the shortcut.
+    /// If None, double-tap activation is disabled and no event listener runs.
+    pub double_tap_modifier: Option<DoubleTapModifier>,
+    /// Show/hide animation and background material for the quick pane
+    pub quick_pane_appearance: QuickPaneAppearance,
+    /// Webview zoom factor for the quick pane only (0.75-2.0)
+    pub quick_pane_zoom: f64,
+    /// Whether the quick pane window grows/shrinks with its zoom so content doesn't overflow
+    pub quick_pane_scale_window_with_zoom: bool,
+    /// Where the quick pane appears when shown
+    pub quick_pane_position: QuickPanePosition,
+    /// What happens when the pane would auto-hide with an active recording or unsaved result
+    pub quick_pane_dirty_hide: DirtyHideBehavior,
+    /// Hours before recovery sessions, drafts and stranded recordings are securely deleted
+    pub recovery_retention_hours: u32,
+    /// Size (MB) at which a recording's recovery file keeps only recent audio
+    pub recovery_session_cap_mb: u32,
+    /// Minutes of recent audio kept once a recovery file hits its cap
+    pub recovery_rolling_window_minutes: u32,
+    /// Size (MB) of all recovery files combined before the oldest are evicted
+    pub recovery_dir_cap_mb: u32,
+    /// Which system notifications are sent, and when
+    pub notifications: NotificationPreferences,
+    /// Minutes of recording the free disk space must cover before a low-disk warning
+    pub low_disk_warning_minutes: u32,
+    /// Minutes of recording the free disk space must cover for recording to start at all
+    pub low_disk_block_minutes: u32,
+    /// Minutes between "still recording" reminders. 0 disables them.
+    pub recording_reminder_minutes: u32,
+    /// Check for app updates in the background (at most once a day)
+    pub check_updates_automatically: bool,
+    /// Which releases update checks offer
+    pub update_channel: UpdateChannel,
+    /// Start with only the tray icon and shortcuts, leaving the main window closed
+    pub start_hidden: bool,
+    /// Time commands in memory for the diagnostics screen (never their arguments or results)
+    pub command_metrics_enabled: bool,
+    /// Days audit log entries are kept (never less than `MIN_AUDIT_RETENTION_DAYS`)
+    pub audit_retention_days: u32,
+    /// Days deleted transcripts stay in the trash before they're purged (at least 1)
+    pub trash_retention_days: u32,
+    /// Rules for the optional de-identification pass on save
+    pub deidentification: DeidentificationRules,
+    /// Copy transcription results to the clipboard as soon as they're ready
+    pub auto_copy_to_clipboard: bool,
+    /// Paste transcription results into the app that had focus when the quick pane opened
+    pub auto_paste_into_previous_app: bool,
+    /// Seconds before a transcript the app copied is cleared from the clipboard. 0 disables.
+    pub clipboard_clear_seconds: u32,
+    /// Typing speed for `type_out_text` when the call doesn't give one
+    pub type_out_chars_per_second: u32,
+    /// How transcripts are shaped before they're copied or pasted
+    pub output_formatting: FormattingOptions,
+    /// Appending finished transcriptions to a plain-text daily note
+    pub append_to_file: AppendToFileOptions,
+    /// Mark copied transcripts so clipboard history tools skip them
+    pub conceal_clipboard_from_history: bool,
+    /// Flavors written when the app copies a transcript
+    pub clipboard_format: ClipboardFormat,
+    /// Minutes without activity before the app locks, when a passcode is set. 0 disables.
+    pub auto_lock_minutes: u32,
+    /// Offer Touch ID / Windows Hello on the lock screen. The passcode always works too.
+    pub biometric_unlock: bool,
+    /// Hide the main window and quick pane from screenshots, recording and screen sharing
+    pub block_screen_capture: bool,
+    /// Minutes a recording may run without user input or commands before it's
+    /// stopped, saved as a draft and the app locked. 0 disables.
+    pub max_idle_while_recording_minutes: u32,
+    /// Also keep the database directory out of Time Machine and iCloud
+    /// backups (macOS). The cache and recovery directories always are.
+    pub exclude_database_from_backups: bool,
+}
+
+impl Default for AppPreferences {
+    fn default() -> Self {
+        Self {
+            theme: "system".to_string(),
+            quick_pane_shortcut: None, // None means use default
+            language: None,            // None means use system locale
+            quick_pane_focus_keyboard: true,
+            double_tap_modifier: None,
+            quick_pane_appearance: QuickPaneAppearance::default(),
+            quick_pane_zoom: 1.0,
+            quick_pane_scale_window_with_zoom: false,
+            quick_pane_position: QuickPanePosition::default(),
+            quick_pane_dirty_hide: DirtyHideBehavior::default(),
+            recovery_retention_hours: DEFAULT_RECOVERY_RETENTION_HOURS,
+            recovery_session_cap_mb: DEFAULT_RECOVERY_SESSION_CAP_MB,
+            recovery_rolling_window_minutes: DEFAULT_RECOVERY_ROLLING_WINDOW_MINUTES,
+            recovery_dir_cap_mb: DEFAULT_RECOVERY_DIR_CAP_MB,
+            notifications: NotificationPreferences::default(),
+            low_disk_warning_minutes: DEFAULT_LOW_DISK_WARNING_MINUTES,
+            low_disk_block_minutes: DEFAULT_LOW_DISK_BLOCK_MINUTES,
+            recording_reminder_minutes: DEFAULT_RECORDING_REMINDER_MINUTES,
+            check_updates_automatically: true,
+            update_channel: UpdateChannel::default(),
+            start_hidden: false,
+            command_metrics_enabled: true,
+            audit_retention_days: DEFAULT_AUDIT_RETENTION_DAYS,
+            trash_retention_days: DEFAULT_TRASH_RETENTION_DAYS,
+            deidentification: DeidentificationRules::default(),
+            auto_copy_to_clipboard: false,
+            auto_paste_into_previous_app: false,
+            clipboard_clear_seconds: DEFAULT_CLIPBOARD_CLEAR_SECONDS,
+            type_out_chars_per_second: DEFAULT_TYPE_OUT_CHARS_PER_SECOND,
+            output_formatting: FormattingOptions::default(),
+            append_to_file: AppendToFileOptions::default(),
+            conceal_clipboard_from_history: true,
+            clipboard_format: ClipboardFormat::default(),
+            auto_lock_minutes: DEFAULT_AUTO_LOCK_MINUTES,
+            biometric_unlock: false,
+            block_screen_capture: false,
+            max_idle_while_recording_minutes: DEFAULT_MAX_IDLE_WHILE_RECORDING_MINUTES,
+            exclude_database_from_backups: false,
+        }
+    }
+}
+
+/// Which releases the updater offers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdateChannel {
+    /// Stable releases only
+    #[default]
+    Stable,
+    /// Pre-releases as well as stable releases
+    Beta,
+}
+
+impl UpdateChannel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "stable",
+            UpdateChannel::Beta => "beta",
+        }
+    }
+}
+
+/// What the app puts on the clipboard when it copies a transcript.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClipboardFormat {
+    /// Plain text only
+    #[default]
+    Plain,
+    /// Plain text plus minimal HTML (paragraphs, line breaks, bold section
+    /// headers), so the destination picks
+    Rich,
+}
+
+/// Quick pane placement mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuickPanePosition {
+    /// Centered on the monitor containing the cursor
+    #[default]
+    CursorMonitor,
+    /// Dropped down from the tray/menu-bar icon like a MenuBarExtra
+    MenubarAnchored,
+}
+
+/// Auto-hide behavior while the quick pane has an active recording or unsaved result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DirtyHideBehavior {
+    /// Keep the pane open; blur and Escape are ignored
+    #[default]
+    Block,
+    /// Emit `quick-pane-hide-requested` and let the frontend decide
+    AskFrontend,
+}
+
+/// Background material for the quick pane's vibrancy effect (macOS only).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickPaneMaterial {
+    Hud,
+    Popover,
+    Sidebar,
+}
+
+/// Quick pane appearance options.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct QuickPaneAppearance {
+    /// Fade (and scale on macOS) duration for show/hide in milliseconds. 0 disables it.
+    pub animation_ms: u32,
+    /// Vibrancy material. Ignored on platforms without window materials.
+    pub material: QuickPaneMaterial,
+}
+
+impl Default for QuickPaneAppearance {
+    fn default() -> Self {
+        Self {
+            animation_ms: 120,
+            material: QuickPaneMaterial::Hud,
+        }
+    }
+}
+
+/// A specific (sided) modifier key. Command maps to the Windows key and
+/// Option to Alt on non-macOS platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ModifierKey {
+    LeftCommand,
+    RightCommand,
+    LeftOption,
+    RightOption,
+    LeftShift,
+    RightShift,
+    LeftControl,
+    RightControl,
+}
+
+/// Double-tap modifier activation settings for the quick pane.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct DoubleTapModifier {
+    pub key: ModifierKey,
+    /// Maximum time between the two taps in milliseconds
+    pub interval_ms: u32,
+}
+
+impl Default for DoubleTapModifier {
+    fn default() -> Self {
+        Self {
+            key: ModifierKey::RightCommand,
+            interval_ms: DEFAULT_DOUBLE_TAP_INTERVAL_MS,
+        }
+    }
+}
+
+/// Kind of system notification, so users can mute each independently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    /// A transcription finished while the app was in the background
+    TranscriptionComplete,
+    /// Free disk space is too low for a recording or model download
+    LowDiskSpace,
+    /// The microphone has been recording for a long time
+    RecordingReminder,
+    /// A speech model download finished or failed
+    ModelDownload,
+    /// A new app version is available
+    UpdateAvailable,
+    /// A command-line or link request was queued or couldn't run
+    Automation,
+}
+
+impl NotificationCategory {
+    /// Critical categories are still sent while the OS is in Do Not Disturb.
+    /// Only the recording reminder qualifies: it guards against a forgotten open mic.
+    pub fn is_critical(self) -> bool {
+        matches!(self, NotificationCategory::RecordingReminder)
+    }
+}
+
+/// Daily window in local time during which notifications are held back.
+/// Minutes since midnight; `start_minute > end_minute` spans midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct QuietHours {
+    pub start_minute: u32,
+    pub end_minute: u32,
+}
+
+impl QuietHours {
+    /// Whether `minute_of_day` (0-1439) falls inside the window.
+    pub fn contains(&self, minute_of_day: u32) -> bool {
+        if self.start_minute <= self.end_minute {
+            (self.start_minute..self.end_minute).contains(&minute_of_day)
+        } else {
+            minute_of_day >= self.start_minute || minute_of_day < self.end_minute
+        }
+    }
+}
+
+/// System notification settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct NotificationPreferences {
+    /// Master switch for all system notifications
+    pub enabled: bool,
+    /// Categories the user turned off
+    pub muted_categories: Vec<NotificationCategory>,
+    /// If set, notifications are suppressed during this window
+    pub quiet_hours: Option<QuietHours>,
+    /// Categories still sent during quiet hours. Defaults to the recording
+    /// reminder, since a forgotten open microphone is a privacy problem.
+    pub quiet_hours_exempt: Vec<NotificationCategory>,
+    /// Sound played with notifications
+    pub sound: NotificationSound,
+}
+
+/// Notification sound. Named system sounds (e.g. "Glass") are macOS-only and
+/// fall back to the default sound elsewhere.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotificationSound {
+    #[default]
+    Default,
+    /// Silent notifications (e.g. clinic exam rooms)
+    None,
+    Named {
+        name: String,
+    },
+}
+
+/// Result of `send_test_notification`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TestNotificationResult {
+    /// Whether the OS allows this app to show notifications
+    pub permission_granted: bool,
+    /// Whether the sample notification was shown
+    pub sent: bool,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            muted_categories: Vec::new(),
+            quiet_hours: None,
+            quiet_hours_exempt: vec![NotificationCategory::RecordingReminder],
+            sound: NotificationSound::default(),
+        }
+    }
+}
+
+// ============================================================================
+// Event Payloads
+// ============================================================================
+
+/// Payload for the `quick-pane-visibility-changed` event.
+/// Emitted from every show/hide path so menu checkmarks reflect the real state.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct QuickPaneVisibilityChanged {
+    pub visible: bool,
+}
+
+/// Payload for the `quick-pane-hide-requested` event, sent when a hide was held back.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct QuickPaneHideRequested {
+    /// A recording is active or starting/stopping
+    pub recording: bool,
+    /// The frontend flagged a result that hasn't been copied/saved
+    pub unsaved_result: bool,
+    /// The hide came from the shortcut/toggle; pressing again will hide anyway
+    pub explicit: bool,
+}
+
+/// Payload for the `recovery-degraded` event.
+/// Sent when a recording's recovery file can't be written (e.g. disk full);
+/// the recording continues in memory only.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct RecoveryDegraded {
+    pub session_id: String,
+    pub message: String,
+}
+
+/// Payload for the `drafts-available` event, emitted at startup when unsaved drafts exist.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct DraftsAvailable {
+    pub draft_ids: Vec<String>,
+}
+
+/// Payload for the `previous-session-crashed` event, emitted at startup when the
+/// last run ended with a recording or transcription in flight.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct PreviousSessionCrashed {
+    pub recovery_sessions: Vec<RecoverySessionInfo>,
+    pub drafts: Vec<TranscriptDraft>,
+}
+
+/// Payload for the `recovery-evicted` event, sent when the oldest recovery
+/// sessions were deleted to keep the recovery directory under its cap.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct RecoveryEvicted {
+    pub session_ids: Vec<String>,
+    pub budget_bytes: f64,
+}
+
+/// Payload for the `model-download-resumable` event, sent at startup for each
+/// interrupted model download that `resume_model_download` can continue.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct ModelDownloadResumable {
+    pub model_id: String,
+    pub bytes_done: f64,
+    pub total_bytes: f64,
+}
+
+/// Payload for the `recordings-repaired` event, sent at startup when WAVs left
+/// unfinalized by a crash were repaired.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct RecordingsRepaired {
+    pub recordings: Vec<RepairedRecording>,
+}
+
+/// Payload for the `recovery-expired` event, sent when the retention sweep
+/// deleted recovery data older than `recovery_retention_hours`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct RecoveryExpired {
+    /// Recording recovery sessions, including damaged ones
+    pub sessions_removed: u32,
+    pub drafts_removed: u32,
+    /// Stranded recording WAVs and interrupted temp files
+    pub files_removed: u32,
+}
+
+/// What was about to write to disk when a low-disk-space check failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DiskSpaceContext {
+    Recording,
+    ModelDownload,
+}
+
+/// Payload for the `low-disk-space` event.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct LowDiskSpace {
+    pub context: DiskSpaceContext,
+    pub available_bytes: f64,
+    /// Bytes needed to be comfortable (the warning threshold)
+    pub required_bytes: f64,
+    /// True when the operation was refused rather than just warned about
+    pub blocked: bool,
+}
+
+/// Why a model download ultimately failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelDownloadErrorKind {
+    /// Network unreachable or the connection kept dropping
+    Offline,
+    /// The downloaded file didn't match the expected length
+    Checksum,
+    /// Not enough disk space for the model
+    DiskFull,
+    Other,
+}
+
+/// Payload for the `model-download-complete` event.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct ModelDownloadComplete {
+    pub model_id: String,
+}
+
+/// Payload for the `model-download-failed` event, sent once retries are exhausted.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct ModelDownloadFailed {
+    pub model_id: String,
+    pub kind: ModelDownloadErrorKind,
+    pub message: String,
+}
+
+/// Integrity of a model file, from `verify_all_models`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModelIntegrity {
+    /// Matches the checksum recorded when it was downloaded
+    Verified,
+    NotDownloaded,
+    /// Changed since download; it was deleted and has to be downloaded again
+    Corrupt,
+    /// The file couldn't be read
+    Error {
+        message: String,
+    },
+}
+
+/// One model's entry in the `verify_all_models` report.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelVerification {
+    pub model_id: String,
+    pub status: ModelIntegrity,
+}
+
+/// Payload for the `update-available` event from the background update check.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct UpdateAvailable {
+    pub version: String,
+    pub current_version: String,
+    /// Full release notes, if the update manifest has any
+    pub notes: Option<String>,
+}
+
+/// Result of `check_for_updates`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UpdateInfo {
+    pub available: bool,
+    pub current_version: String,
+    /// Version on offer; None when there's no update
+    pub latest_version: Option<String>,
+    /// Release notes, if the update manifest has any
+    pub notes: Option<String>,
+    /// Release date (RFC 3339), if the manifest has one
+    pub pub_date: Option<String>,
+    /// Size of the download in bytes, if the manifest lists it
+    pub download_size: Option<f64>,
+}
+
+/// Payload for the `update-download-progress` event.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct UpdateDownloadProgress {
+    pub downloaded_bytes: f64,
+    /// None when the server doesn't send a length
+    pub total_bytes: Option<f64>,
+}
+
+/// Payload for the `update-install-countdown` event, sent while an install
+/// deferred by a recording or transcription waits to run.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct UpdateInstallCountdown {
+    /// Seconds until the app installs and restarts; None while it waits for
+    /// the recording or transcription to finish
+    pub seconds_remaining: Option<u32>,
+}
+
+/// Error types for update operations (typed for frontend matching)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "type")]
+pub enum UpdateError {
+    /// The update server couldn't be reached or returned an error
+    Network { message: String },
+    /// The download doesn't match the app's update signing key
+    InvalidSignature,
+    /// There's no update to download
+    NoUpdate,
+    /// No update has been installed, so there's nothing to restart into
+    NotInstalled,
+    /// A recording or transcription is in progress; the update installs once
+    /// it finishes, after a countdown on `update-install-countdown`
+    Busy,
+    /// In-app updates aren't available on this platform
+    Unsupported,
+    /// Any other updater failure
+    Failed { message: String },
+}
+
+impl std::fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateError::Network { message } => write!(f, "Network error: {message}"),
+            UpdateError::InvalidSignature => write!(f, "Update signature is invalid"),
+            UpdateError::NoUpdate => write!(f, "No update available"),
+            UpdateError::NotInstalled => write!(f, "No update has been installed"),
+            UpdateError::Busy => write!(f, "A recording or transcription is in progress"),
+            UpdateError::Unsupported => write!(f, "Updates aren't supported on this platform"),
+            UpdateError::Failed { message } => write!(f, "Update failed: {message}"),
+        }
+    }
+}
+
+/// Payload for the `notification-suppressed` event, sent instead of a system
+/// notification while the OS is in Do Not Disturb so the app can show it in-app.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct NotificationSuppressed {
+    pub category: NotificationCategory,
+    pub title: String,
+    pub body: String,
+}
+
+/// Payload for the `transcript-saved` event, sent when `transcribe_and_delete`
+/// auto-saved its result.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct TranscriptSaved {
+    pub transcript_id: String,
+    /// Earlier transcript from today with the same content, if any
+    pub duplicate_of: Option<String>,
+}
+
+/// Payload for the `transcript-copied` event, sent when the backend put a
+/// transcript on the clipboard. Carries no content.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct TranscriptCopied {
+    pub characters: u32,
+}
+
+/// How a finished transcription is handed over. Unset fields fall back to
+/// the preferences of the same name.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct TranscriptDelivery {
+    /// Default: `auto_copy_to_clipboard`
+    pub copy_to_clipboard: Option<bool>,
+    /// Default: `auto_paste_into_previous_app`. Pasting always copies too.
+    pub paste_into_previous_app: Option<bool>,
+    /// Default: `output_formatting`
+    pub formatting: Option<FormattingOptions>,
+    /// Default: `append_to_file.enabled`
+    pub append_to_file: Option<bool>,
+}
+
+/// Why a paste into the previous app fell back to copy-only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PasteFallbackReason {
+    /// The OS has no supported way to paste into other apps (Linux)
+    Unsupported,
+    /// No other app had focus when the quick pane was shown
+    NoPreviousApp,
+    /// That app has quit or its window closed
+    PreviousAppGone,
+    /// macOS Accessibility access isn't granted
+    AccessibilityDenied,
+    /// The app couldn't be brought to the front
+    ActivationFailed,
+    /// The keystroke couldn't be synthesized
+    KeystrokeFailed,
+}
+
+/// Result of `paste_into_previous_app`. The transcript is on the clipboard
+/// either way.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PasteOutcome {
+    Pasted,
+    CopiedOnly { reason: PasteFallbackReason },
+}
+
+/// Whether a window is kept out of screen capture.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScreenCaptureState {
+    /// Excluded from screenshots, recording and screen sharing
+    Blocked,
+    /// Capturable; `block_screen_capture` is off
+    Allowed,
+    /// `block_screen_capture` is on but the platform can't honour it
+    Unsupported { reason: String },
+}
+
+/// One entry of `get_screen_capture_state`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WindowScreenCapture {
+    /// Window label (`main` or `quick-pane`)
+    pub window: String,
+    pub state: ScreenCaptureState,
+}
+
+/// A directory holding PHI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DataDirectory {
+    /// Downloads and other cached files
+    Cache,
+    /// Encrypted in-progress recordings and drafts
+    Recovery,
+    /// The transcript database, alongside preferences
+    Database,
+}
+
+/// Whether a directory is kept out of backups.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BackupExclusionState {
+    /// Excluded from Time Machine and iCloud backups
+    Excluded,
+    /// Backed up; `exclude_database_from_backups` is off, or the flag was cleared
+    Included,
+    /// The platform has no exclusion the app can set
+    Unsupported {
+        reason: String,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// One directory in `get_backup_exclusion_status`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DirectoryBackupExclusion {
+    pub directory: DataDirectory,
+    pub path: String,
+    pub state: BackupExclusionState,
+}
+
+/// A data directory inside a cloud-synced folder such as OneDrive.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CloudSyncedDirectory {
+    pub directory: DataDirectory,
+    pub path: String,
+    /// Name of the sync service, e.g. `OneDrive`
+    pub provider: String,
+}
+
+/// Result of `get_backup_exclusion_status`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BackupExclusionStatus {
+    pub directories: Vec<DirectoryBackupExclusion>,
+    /// Directories that leave the device through a sync service
+    pub cloud_synced: Vec<CloudSyncedDirectory>,
+}
+
+/// Payload for the `data-dir-cloud-synced` event, sent at startup when data
+/// directories are inside a cloud-synced folder.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct DataDirCloudSynced {
+    pub directories: Vec<CloudSyncedDirectory>,
+}
+
+/// Payload for the `file-append-failed` event, sent when a transcription
+/// couldn't be appended to the daily note file. The transcript was copied to
+/// the clipboard instead.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct FileAppendFailed {
+    pub path: String,
+    pub message: String,
+}
+
+/// Payload for the `paste-fallback` event.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct PasteFallback {
+    pub reason: PasteFallbackReason,
+    /// User-facing explanation
+    pub message: String,
+}
+
+/// Why `type_out_text` didn't type (all of) the text.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "type")]
+pub enum TypeOutError {
+    /// macOS Accessibility access isn't granted
+    PermissionDenied,
+    /// Long text; call again with `confirmed` to type it anyway
+    ConfirmationRequired {
+        characters: u32,
+        estimated_secs: u32,
+    },
+    /// Longer than type-out ever sends; paste it instead
+    TooLong { max_chars: u32 },
+    /// There's no app to type into
+    NoTarget { reason: PasteFallbackReason },
+    /// Another type-out is still running
+    AlreadyRunning,
+    /// Stopped by `cancel_type_out` after `typed` characters
+    Cancelled { typed: u32 },
+    /// Another app took focus after `typed` characters, so typing stopped
+    FocusLost { typed: u32 },
+    /// A keystroke couldn't be sent
+    Failed { message: String },
+    /// The app is locked; unlock it first
+    Locked,
+}
+
+impl std::fmt::Display for TypeOutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeOutError::PermissionDenied => write!(f, "Typing needs Accessibility access"),
+            TypeOutError::ConfirmationRequired {
+                characters,
+                estimated_secs,
+            } => write!(
+                f,
+                "Typing {characters} characters takes about {estimated_secs} seconds; confirm to continue"
+            ),
+            TypeOutError::TooLong { max_chars } => {
+                write!(f, "Text too long to type out (max {max_chars} characters)")
+            }
+            TypeOutError::NoTarget { reason } => write!(f, "Nothing to type into: {reason:?}"),
+            TypeOutError::AlreadyRunning => write!(f, "Already typing"),
+            TypeOutError::Cancelled { typed } => write!(f, "Cancelled after {typed} characters"),
+            TypeOutError::FocusLost { typed } => {
+                write!(f, "Focus changed after {typed} characters; typing stopped")
+            }
+            TypeOutError::Failed { message } => write!(f, "Typing failed: {message}"),
+            TypeOutError::Locked => write!(f, "The app is locked"),
+        }
+    }
+}
+
+/// Payload for the `clipboard-cleared` event, sent when a transcript the app
+/// copied was cleared from the clipboard.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct ClipboardCleared {
+    /// Delay the clear ran after
+    pub after_secs: u32,
+}
+
+/// Payload for the `transcript-export-progress` event, sent after each
+/// transcript is written to a bulk export archive.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct TranscriptExportProgress {
+    pub done: u32,
+    pub total: u32,
+}
+
+/// Database operation reported by `database-transfer-progress`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabaseOperation {
+    Backup,
+    Restore,
+}
+
+/// Payload for the `database-transfer-progress` event, sent while a backup
+/// or restore copies the database.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct DatabaseTransferProgress {
+    pub operation: DatabaseOperation,
+    pub percent: u32,
+}
+
+/// Payload for the `database-recovered` event, sent at startup when the
+/// database failed its integrity check and was rebuilt from readable rows.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct DatabaseRecovered {
+    pub transcripts_salvaged: u32,
+    pub drafts_salvaged: u32,
+    pub audit_entries_salvaged: u32,
+    /// Where the damaged original was kept
+    pub corrupt_copy_path: String,
+    /// First problems reported by `PRAGMA integrity_check`
+    pub problems: Vec<String>,
+}
+
+/// Result of `run_integrity_check`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct IntegrityReport {
+    pub ok: bool,
+    /// First problems reported by `PRAGMA integrity_check` (empty when ok)
+    pub problems: Vec<String>,
+}
+
+/// Payload for the `navigate` event sent to the main window.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NavigatePayload {
+    /// Frontend route to open (e.g. "/notes/123")
+    pub route: String,
+    /// Optional data for the target view, passed through untouched
+    pub payload: Option<serde_json::Value>,
+}
+
+/// The `navigate` event, carrying a route to the main window.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct Navigate(pub NavigatePayload);
+
+/// The `preferences-changed` event, carrying the saved preferences.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct PreferencesChanged(pub AppPreferences);
+
+/// The `database-unavailable` event, sent at startup when the encrypted
+/// database couldn't be opened.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct DatabaseUnavailable(pub DatabaseStatus);
+
+/// Payload for the `whisper-model-download-progress` event.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct WhisperModelDownloadProgress {
+    pub model_id: String,
+    /// 0–100
+    pub percent: u32,
+}
+
+/// Payload for the `llm-model-download-progress` event.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct LlmModelDownloadProgress {
+    /// 0–100
+    pub percent: u32,
+}
+
+/// The `llm-chunk` event, carrying one generated token.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct LlmChunk(pub String);
+
+/// The `llm-done` event, sent once generation finished.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct LlmDone;
+
+/// The `llm-error` event, carrying why generation failed.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct LlmError(pub String);
+
+// ============================================================================
+// Pipeline Status
+// ============================================================================
+
+/// Category of a pipeline failure, for frontend matching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineErrorKind {
+    Recording,
+    Resampling,
+    Transcription,
+    /// The model file failed its integrity check and was deleted; download it again
+    ModelCorrupt,
+}
+
+/// A stage of the record → transcribe pipeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum PipelineStage {
+    Idle,
+    Recording,
+    Stopping,
+    Resampling,
+    Transcribing { percent: u32 },
+    Done { text_length: u32 },
+    Error { kind: PipelineErrorKind },
+}
+
+/// Payload for the `pipeline-status` event and `get_pipeline_status` snapshot.
+/// Timestamps are milliseconds since the Unix epoch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type, Event)]
+pub struct PipelineStatus {
+    /// Id shared by every transition of one recording/transcription job
+    pub job_id: Option<String>,
+    pub stage: PipelineStage,
+    /// True when the job transcribes audio salvaged from a recovery file
+    pub recovered: bool,
+    pub started_at_ms: f64,
+    pub updated_at_ms: f64,
+}
+
+// ============================================================================
+// Jobs
+// ============================================================================
+
+/// What a long-running job does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    Transcription,
+    ModelDownload,
+}
+
+/// What a finished job produced. Never carries transcript text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobOutput {
+    Transcription { model_id: String, text_length: u32 },
+    ModelDownload { model_id: String },
+}
+
+/// Where a job is.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum JobStatus {
+    /// `percent` is 0–100, None until the job reports any
+    Running {
+        percent: Option<u32>,
+    },
+    Succeeded {
+        output: JobOutput,
+    },
+    Failed {
+        error: AppError,
+    },
+    Cancelled,
+}
+
+/// A long-running job, from `get_job` and `list_jobs`. Timestamps are
+/// milliseconds since the Unix epoch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct Job {
+    pub id: String,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    /// Whether `cancel_job` can stop it
+    pub cancellable: bool,
+    pub created_at_ms: f64,
+    pub updated_at_ms: f64,
+}
+
+/// The `job-progress` event, sent when a job starts, progresses and finishes.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct JobProgress(pub Job);
+
+// ============================================================================
+// Recovery Sessions
+// ============================================================================
+
+/// Integrity of a recording recovery file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoverySessionStatus {
+    /// Recording stopped cleanly and every sample is on disk
+    Complete,
+    /// Recording was interrupted; audio up to the last flush is recoverable
+    Truncated,
+    /// Header is missing or corrupt; the audio can't be interpreted
+    Damaged,
+    /// The encryption key is missing from the keychain; the file can only be purged
+    Unrecoverable,
+}
+
+/// Which recovery sessions `purge_recovery` deletes.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecoveryPurgeTarget {
+    Session { id: String },
+    All,
+}
+
+/// Why a recovery file was purged (recorded in the audit log).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryPurgeReason {
+    /// The user deleted it explicitly
+    UserRequested,
+    /// Its transcription completed, so the audio is no longer needed
+    PostSuccess,
+    /// It outlived the recovery retention period
+    Expired,
+    /// It was the oldest session when the recovery directory exceeded its cap
+    Evicted,
+}
+
+impl RecoveryPurgeReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RecoveryPurgeReason::UserRequested => "user_requested",
+            RecoveryPurgeReason::PostSuccess => "post_success",
+            RecoveryPurgeReason::Expired => "expired",
+            RecoveryPurgeReason::Evicted => "evicted",
+        }
+    }
+}
+
+/// A recoverable recording found in the recovery directory.
+/// Timestamps are milliseconds since the Unix epoch; sizes are f64 because specta forbids u64.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RecoverySessionInfo {
+    pub id: String,
+    pub created_at_ms: f64,
+    pub modified_at_ms: f64,
+    /// Approximate audio duration from sample count and recorded rate (0 when damaged)
+    pub duration_secs: f64,
+    pub size_bytes: f64,
+    pub status: RecoverySessionStatus,
+    /// The session hit its size cap, so only its most recent audio was kept
+    pub rolled: bool,
+}
+
+/// A cached recording WAV repaired after a crash, ready for `transcribe_and_delete`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RepairedRecording {
+    pub session_id: String,
+    pub file_path: String,
+    pub duration_secs: f64,
+}
+
+/// Disk usage of the recovery directory, for the storage settings screen.
+/// Sizes are f64 because specta forbids u64.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RecoveryUsage {
+    /// Everything in the recovery directory (recordings plus emergency data)
+    pub total_bytes: f64,
+    /// Recording recovery files only
+    pub session_bytes: f64,
+    pub session_count: u32,
+    pub session_cap_bytes: f64,
+    pub dir_cap_bytes: f64,
+}
+
+// ============================================================================
+// Drafts
+// ============================================================================
+
+/// An autosaved transcript draft.
+/// Timestamps are milliseconds since the Unix epoch.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptDraft {
+    pub draft_id: String,
+    pub content: String,
+    /// Editor cursor/selection state, passed through untouched
+    pub cursor_meta: Option<serde_json::Value>,
+    pub created_at_ms: f64,
+    pub updated_at_ms: f64,
+}
+
+// ============================================================================
+// Transcripts
+// ============================================================================
+
+/// A saved transcript.
+/// Timestamps are milliseconds since the Unix epoch.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Transcript {
+    pub id: String,
+    pub title: String,
+    pub text: String,
+    /// Language the transcript was produced in (None for auto-detect)
+    pub language: Option<String>,
+    /// Whisper model that produced the transcript
+    pub model_id: Option<String>,
+    /// Length of the source audio in seconds (0 when unknown)
+    pub duration_secs: f64,
+    /// Whitespace-separated words in `text`, kept current on edit
+    pub word_count: u32,
+    /// Free-text label for the session ("Ward round", "Dr. Berg consult")
+    pub session_label: Option<String>,
+    /// Note this transcript belongs to, if any
+    pub note_id: Option<String>,
+    /// Normalized (lowercase) tag names, sorted
+    pub tags: Vec<String>,
+    /// Rule set the transcript was de-identified with when saved (None if it wasn't)
+    pub deidentified_with: Option<String>,
+    pub created_at_ms: f64,
+    pub updated_at_ms: f64,
+    /// When the transcript was moved to the trash (None when it isn't there)
+    pub deleted_at_ms: Option<f64>,
+}
+
+/// A transcript without its text, for library lists.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptSummary {
+    pub id: String,
+    pub title: String,
+    pub language: Option<String>,
+    pub model_id: Option<String>,
+    pub duration_secs: f64,
+    pub word_count: u32,
+    pub session_label: Option<String>,
+    pub note_id: Option<String>,
+    pub tags: Vec<String>,
+    pub deidentified_with: Option<String>,
+    pub created_at_ms: f64,
+    pub updated_at_ms: f64,
+    pub deleted_at_ms: Option<f64>,
+}
+
+/// Fields for a new transcript; the id and timestamps are assigned on save.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptNew {
+    #[serde(default)]
+    pub title: String,
+    pub text: String,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub model_id: Option<String>,
+    #[serde(default)]
+    pub duration_secs: f64,
+    #[serde(default)]
+    pub note_id: Option<String>,
+    #[serde(default)]
+    pub session_label: Option<String>,
+    /// Tags to attach; normalized like `set_transcript_tags`
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Scrub identifiers from the title and text with the saved
+    /// de-identification rules before anything is stored
+    #[serde(default)]
+    pub deidentify: bool,
+    /// Fail with `Duplicate` instead of saving when an equivalent transcript
+    /// was saved earlier today
+    #[serde(default)]
+    pub reject_duplicates: bool,
+}
+
+/// Result of `save_transcript`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SavedTranscript {
+    pub transcript: Transcript,
+    /// What the de-identification pass replaced, when it was requested
+    pub deidentification: Option<DeidentificationReport>,
+    /// Earlier transcript from today that this one appears to duplicate
+    /// (same or nearly the same text, similar duration)
+    pub duplicate_of: Option<String>,
+}
+
+/// Partial update for a transcript. Omitted fields are left unchanged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct TranscriptPatch {
+    pub title: Option<String>,
+    pub text: Option<String>,
+    /// New owning note; an empty string detaches the transcript from its note
+    pub note_id: Option<String>,
+}
+
+/// How a tag filter combines several tags.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TagMatch {
+    /// Transcripts with at least one of the tags
+    #[default]
+    Any,
+    /// Transcripts with every one of the tags
+    All,
+}
+
+/// Filter for `list_transcripts` and `search_transcripts`. All fields are
+/// optional and combined with AND.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct TranscriptFilter {
+    pub note_id: Option<String>,
+    /// Only transcripts created at or after this time (ms since epoch)
+    pub created_after_ms: Option<f64>,
+    /// Only transcripts created before this time (ms since epoch)
+    pub created_before_ms: Option<f64>,
+    /// Only transcripts with these tags (empty means no tag filter)
+    pub tags: Vec<String>,
+    /// Whether `tags` must all match or any one is enough
+    pub tag_match: TagMatch,
+    /// Also match transcripts in the trash
+    pub include_deleted: bool,
+}
+
+/// A tag and how many transcripts carry it.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TagUsage {
+    pub name: String,
+    pub transcript_count: u32,
+}
+
+/// A transcript's title and text as they were before an edit.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptRevision {
+    pub revision_id: u32,
+    pub transcript_id: String,
+    pub title: String,
+    pub text: String,
+    pub word_count: u32,
+    /// When this version was replaced (ms since epoch)
+    pub created_at_ms: f64,
+}
+
+/// Page window for list queries.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct Pagination {
+    pub limit: u32,
+    pub offset: u32,
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Self {
+            limit: DEFAULT_TRANSCRIPT_PAGE_SIZE,
+            offset: 0,
+        }
+    }
+}
+
+/// One page of transcripts, most recently created first.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptPage {
+    pub transcripts: Vec<Transcript>,
+    /// Number of transcripts matching the filter across all pages
+    pub total: u32,
+}
+
+/// One page of `list_transcripts` results, most recently created first.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptSummaryPage {
+    pub transcripts: Vec<TranscriptSummary>,
+    /// Number of transcripts matching the filter across all pages
+    pub total: u32,
+}
+
+/// File format for transcript exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptExportFormat {
+    Txt,
+    Md,
+    Pdf,
+}
+
+impl TranscriptExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            TranscriptExportFormat::Txt => "txt",
+            TranscriptExportFormat::Md => "md",
+            TranscriptExportFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// Passphrase encryption for an export, which is then written as a `.psenc`
+/// envelope (see `utils::envelope`) that `decrypt_export` opens.
+#[derive(Clone, Serialize, Deserialize, Type)]
+pub struct ExportEncryption {
+    pub passphrase: String,
+}
+
+impl std::fmt::Debug for ExportEncryption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExportEncryption")
+            .field("passphrase", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Creation-time window for bulk exports. Either bound may be omitted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct DateRange {
+    /// Inclusive start (ms since epoch)
+    pub from_ms: Option<f64>,
+    /// Exclusive end (ms since epoch)
+    pub to_ms: Option<f64>,
+}
+
+/// Outcome of `export_all_transcripts`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BulkExportSummary {
+    pub exported: u32,
+    pub total: u32,
+    /// The export was cancelled; the partial archive was deleted
+    pub cancelled: bool,
+}
+
+/// A run of snippet text, highlighted when it matched the search query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct SnippetSegment {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+/// A transcript matching a search, best match first.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptSearchHit {
+    pub id: String,
+    pub title: String,
+    /// Excerpt around the match, split into plain and highlighted runs
+    /// (structured rather than HTML so transcript text is never rendered as markup)
+    pub snippet: Vec<SnippetSegment>,
+    pub created_at_ms: f64,
+}
+
+/// State of the encrypted backend database, checked once at startup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "type")]
+pub enum DatabaseStatus {
+    /// Opened and decrypted
+    Ready,
+    /// The OS keychain couldn't be read (locked, or no Secret Service on Linux)
+    KeychainUnavailable { message: String },
+    /// The database exists but its key is gone from the keychain
+    KeyMissing,
+    /// The keychain key doesn't decrypt the database
+    KeyMismatch,
+    /// Any other failure opening the database
+    Failed { message: String },
+    /// Not opened yet: the app started locked, and the key is read on unlock
+    Locked,
+}
+
+impl std::fmt::Display for DatabaseStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DatabaseStatus::Ready => write!(f, "Ready"),
+            DatabaseStatus::KeychainUnavailable { message } => {
+                write!(f, "Keychain unavailable: {message}")
+            }
+            DatabaseStatus::KeyMissing => write!(f, "Database key missing from keychain"),
+            DatabaseStatus::KeyMismatch => write!(f, "Database key does not match database"),
+            DatabaseStatus::Failed { message } => write!(f, "{message}"),
+            DatabaseStatus::Locked => write!(f, "Database opens when the app is unlocked"),
+        }
+    }
+}
+
+/// Error types for transcript operations (typed for frontend matching)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "type")]
+pub enum TranscriptError {
+    /// No transcript with this id
+    NotFound { id: String },
+    /// No tag with this name
+    TagNotFound { name: String },
+    /// No revision with this id on the transcript
+    RevisionNotFound { id: u32 },
+    /// Not saved: transcript `id` has the same content
+    Duplicate { id: String },
+    /// The app is locked; unlock it first
+    Locked,
+    /// Input validation failed
+    ValidationError { message: String },
+    /// Transcript text exceeds size limit
+    DataTooLarge { max_bytes: u32 },
+    /// Database read/write error
+    DatabaseError { message: String },
+    /// Export file could not be written
+    IoError { message: String },
+    /// An encrypted export didn't open: wrong passphrase, or the file was altered
+    WrongPassphrase,
+}
+
+impl std::fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranscriptError::NotFound { id } => write!(f, "Transcript not found: {id}"),
+            TranscriptError::TagNotFound { name } => write!(f, "Tag not found: {name}"),
+            TranscriptError::RevisionNotFound { id } => write!(f, "Revision not found: {id}"),
+            TranscriptError::Duplicate { id } => write!(f, "Duplicate of transcript {id}"),
+            TranscriptError::Locked => write!(f, "The app is locked"),
+            TranscriptError::ValidationError { message } => {
+                write!(f, "Validation error: {message}")
+            }
+            TranscriptError::DataTooLarge { max_bytes } => {
+                write!(f, "Transcript too large (max {max_bytes} bytes)")
+            }
+            TranscriptError::DatabaseError { message } => write!(f, "Database error: {message}"),
+            TranscriptError::IoError { message } => write!(f, "IO error: {message}"),
+            TranscriptError::WrongPassphrase => write!(f, "Wrong passphrase"),
+        }
+    }
+}
+
+// ============================================================================
+// De-identification
+// ============================================================================
+
+/// Version of the built-in de-identification patterns. Bump it whenever a
+/// pattern changes so transcripts record which rules actually ran.
+pub const DEIDENTIFICATION_RULES_VERSION: u32 = 1;
+
+/// Which identifiers the de-identification pass replaces.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct DeidentificationRules {
+    /// Dates following "DOB", "date of birth", "born" and the like
+    pub dates_of_birth: bool,
+    pub phone_numbers: bool,
+    /// SSN-style (123-45-6789) and Nordic personal numbers
+    pub national_ids: bool,
+    pub email_addresses: bool,
+    /// Names to replace wherever they appear (case-insensitive, whole words)
+    pub names: Vec<String>,
+    /// Extra regular expressions; matches become `[REDACTED]`
+    pub custom_patterns: Vec<String>,
+    /// Also de-identify transcripts auto-saved after transcription
+    pub apply_to_auto_saved: bool,
+}
+
+impl Default for DeidentificationRules {
+    fn default() -> Self {
+        Self {
+            dates_of_birth: true,
+            phone_numbers: true,
+            national_ids: true,
+            email_addresses: true,
+            names: Vec::new(),
+            custom_patterns: Vec::new(),
+            apply_to_auto_saved: false,
+        }
+    }
+}
+
+/// Names and custom patterns are PHI, so only their counts are shown.
+impl std::fmt::Debug for DeidentificationRules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeidentificationRules")
+            .field("dates_of_birth", &self.dates_of_birth)
+            .field("phone_numbers", &self.phone_numbers)
+            .field("national_ids", &self.national_ids)
+            .field("email_addresses", &self.email_addresses)
+            .field("names", &format!("<{} redacted>", self.names.len()))
+            .field(
+                "custom_patterns",
+                &format!("<{} redacted>", self.custom_patterns.len()),
+            )
+            .field("apply_to_auto_saved", &self.apply_to_auto_saved)
+            .finish()
+    }
+}
+
+/// How many matches of each category were replaced. The removed values are
+/// never returned.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct DeidentificationReport {
+    pub names: u32,
+    pub dates_of_birth: u32,
+    pub phone_numbers: u32,
+    pub national_ids: u32,
+    pub email_addresses: u32,
+    pub custom: u32,
+}
+
+/// Result of `deidentify_text`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DeidentifiedText {
+    pub text: String,
+    pub report: DeidentificationReport,
+    /// Identifies the rules applied, as stored on de-identified transcripts
+    pub rule_set: String,
+}
+
+// ============================================================================
+// Output Formatting
+// ============================================================================
+
+/// Default silence (seconds) between segments that starts a new paragraph
+pub const DEFAULT_PARAGRAPH_PAUSE_SECS: f64 = 1.5;
+
+/// Shape given to a transcript before it's copied or pasted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum FormattingProfile {
+    /// Exactly as transcribed
+    #[default]
+    Verbatim,
+    /// All whitespace and line breaks collapsed to single spaces
+    SingleParagraph,
+    /// A new paragraph wherever the speaker paused; needs segment timestamps
+    ParagraphsOnPause,
+    /// One "- " bullet per sentence
+    BulletedSentences,
+}
+
+/// A formatting profile and its settings.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct FormattingOptions {
+    pub profile: FormattingProfile,
+    /// Gap between segments (seconds) that `paragraphs-on-pause` splits on
+    pub paragraph_pause_secs: f64,
+}
+
+impl Default for FormattingOptions {
+    fn default() -> Self {
+        Self {
+            profile: FormattingProfile::default(),
+            paragraph_pause_secs: DEFAULT_PARAGRAPH_PAUSE_SECS,
+        }
+    }
+}
+
+/// One timed piece of a transcription, as whisper splits it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct TranscriptSegment {
+    pub start_secs: f64,
+    pub end_secs: f64,
+    pub text: String,
+}
+
+/// Default entry written to the daily note: a timestamp header, then the text
+pub const DEFAULT_APPEND_TEMPLATE: &str = "--- {date} {time} ---\n{text}\n\n";
+
+/// Where and how transcriptions are appended to a plain-text file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct AppendToFileOptions {
+    pub enabled: bool,
+    /// Target file, chosen through a file dialog. A `{date}` in the path is
+    /// replaced with today's date (YYYY-MM-DD), starting a new file daily; its
+    /// folder must then have been chosen through the folder picker.
+    pub path: Option<String>,
+    /// Entry written per transcription. `{date}`, `{time}` and `{text}` are
+    /// replaced; `{text}` is added at the end if missing.
+    pub template: String,
+}
+
+impl Default for AppendToFileOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            template: DEFAULT_APPEND_TEMPLATE.to_string(),
+        }
+    }
+}
+
+/// Input to `format_transcript`: plain text, or segments when timestamps are
+/// available.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptContent {
+    Text(String),
+    Segments(Vec<TranscriptSegment>),
+}
+
+// ============================================================================
+// Usage Statistics
+// ============================================================================
+
+/// Range covered by `get_usage_stats`, ending today (local time).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum UsagePeriod {
+    /// The last 7 days
+    #[default]
+    Week,
+    /// The last 30 days
+    Month,
+    /// The last 365 days
+    Year,
+}
+
+impl UsagePeriod {
+    pub fn days(self) -> u32 {
+        match self {
+            UsagePeriod::Week => 7,
+            UsagePeriod::Month => 30,
+            UsagePeriod::Year => 365,
+        }
+    }
+}
+
+/// Saved transcripts on one local calendar day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct DailyUsage {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub transcripts: u32,
+    pub audio_secs: f64,
+    pub words: u32,
+}
+
+/// How many transcriptions a model ran in the period.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelUsage {
+    pub model_id: String,
+    pub transcriptions: u32,
+}
+
+/// Transcription speed over the period, from every transcription run
+/// (saved or not).
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptionPerformance {
+    pub transcriptions: u32,
+    /// Mean processing time per transcription (None without any)
+    pub average_processing_secs: Option<f64>,
+    /// Mean real-time factor, processing time over audio length (below 1 is
+    /// faster than real time)
+    pub average_rtf: Option<f64>,
+    /// Most used first
+    pub models: Vec<ModelUsage>,
+}
+
+/// Result of `get_usage_stats`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UsageStats {
+    pub period: UsagePeriod,
+    /// Start of the first day in the period (ms since epoch)
+    pub from_ms: f64,
+    pub transcripts: u32,
+    pub total_audio_secs: f64,
+    pub total_words: u32,
+    /// One entry per day in the period, oldest first, including empty days
+    pub daily: Vec<DailyUsage>,
+    pub performance: TranscriptionPerformance,
+}
 
 // ============================================================================
-// Preferences
+// Audit Log
 // ============================================================================
 
-/// Application preferences that persist to disk.
-/// Only contains settings that should be saved between sessions.
+/// Kind of PHI-touching operation recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventKind {
+    RecoveryPurged,
+    /// Recovery audio was exported outside the app (detail holds the destination)
+    RecoveryExported,
+    /// Summary of one retention sweep (counts only)
+    RetentionSweep,
+    TranscriptSaved,
+    TranscriptViewed,
+    /// A transcript was edited (detail lists the changed fields)
+    TranscriptEdited,
+    /// A transcript was moved to the trash
+    TranscriptDeleted,
+    /// A transcript was taken back out of the trash
+    TranscriptRestored,
+    /// A transcript was permanently deleted (detail says whether by schedule)
+    TranscriptPurged,
+    /// A transcript was exported to a file (detail holds the format, whether it
+    /// was encrypted, and the destination)
+    TranscriptExported,
+    /// An encrypted export was decrypted (detail holds the source and destination)
+    ExportDecrypted,
+    /// A compliance report was written (detail holds the format and destination)
+    ComplianceReportGenerated,
+    /// A database backup was written (detail holds the destination)
+    DatabaseBackedUp,
+    /// The database was replaced from a backup (detail holds the source)
+    DatabaseRestored,
+    /// The audit log was exported (detail holds the destination)
+    AuditLogExported,
+    /// Audit entries past their retention were removed (detail holds the count)
+    AuditLogTrimmed,
+    /// A setting affecting data retention or exposure changed (detail holds old and new values)
+    PrivacySettingChanged,
+    /// A support log bundle was written (detail holds the destination and file count)
+    LogBundleExported,
+}
+
+impl AuditEventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditEventKind::RecoveryPurged => "recovery_purged",
+            AuditEventKind::RecoveryExported => "recovery_exported",
+            AuditEventKind::RetentionSweep => "retention_sweep",
+            AuditEventKind::TranscriptSaved => "transcript_saved",
+            AuditEventKind::TranscriptViewed => "transcript_viewed",
+            AuditEventKind::TranscriptEdited => "transcript_edited",
+            AuditEventKind::TranscriptDeleted => "transcript_deleted",
+            AuditEventKind::TranscriptRestored => "transcript_restored",
+            AuditEventKind::TranscriptPurged => "transcript_purged",
+            AuditEventKind::TranscriptExported => "transcript_exported",
+            AuditEventKind::ExportDecrypted => "export_decrypted",
+            AuditEventKind::ComplianceReportGenerated => "compliance_report_generated",
+            AuditEventKind::DatabaseBackedUp => "database_backed_up",
+            AuditEventKind::DatabaseRestored => "database_restored",
+            AuditEventKind::AuditLogExported => "audit_log_exported",
+            AuditEventKind::AuditLogTrimmed => "audit_log_trimmed",
+            AuditEventKind::PrivacySettingChanged => "privacy_setting_changed",
+            AuditEventKind::LogBundleExported => "log_bundle_exported",
+        }
+    }
+}
+
+/// An audit log entry. `actor` is the OS account the app ran under.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
-pub struct AppPreferences {
-    pub theme: String,
-    /// Global shortcut for quick pane (e.g., "CommandOrControl+Shift+.")
-    /// If None, uses the default shortcut
-    pub quick_pane_shortcut: Option<String>,
-    /// User's preferred language (e.g., "en", "es", "de")
-    /// If None, uses system locale detection
-    pub language: Option<String>,
+pub struct AuditEvent {
+    pub timestamp_ms: f64,
+    pub actor: String,
+    pub kind: AuditEventKind,
+    pub subject_id: Option<String>,
+    pub detail: String,
 }
 
-impl Default for AppPreferences {
-    fn default() -> Self {
-        Self {
-            theme: "system".to_string(),
-            quick_pane_shortcut: None, // None means use default
-            language: None,            // None means use system locale
+/// Filter for `list_audit_events`. All fields are optional and combined with AND.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct AuditFilter {
+    pub kind: Option<AuditEventKind>,
+    pub subject_id: Option<String>,
+    /// Only entries at or after this time (ms since epoch)
+    pub from_ms: Option<f64>,
+    /// Only entries before this time (ms since epoch)
+    pub to_ms: Option<f64>,
+}
+
+/// One page of audit entries, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AuditEventPage {
+    pub events: Vec<AuditEvent>,
+    /// Number of entries matching the filter across all pages
+    pub total: u32,
+}
+
+/// File format for `export_audit_log`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditExportFormat {
+    Csv,
+    Json,
+}
+
+impl AuditExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            AuditExportFormat::Csv => "csv",
+            AuditExportFormat::Json => "json",
+        }
+    }
+}
+
+// ============================================================================
+// Cache Wipe
+// ============================================================================
+
+/// Result of a secure wipe of the cache directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct CacheWipeReport {
+    pub files_wiped: u32,
+    pub bytes_wiped: f64,
+    /// Files left in place: audio of a running recording or transcription,
+    /// webview files while the app runs, and files that couldn't be deleted
+    pub files_skipped: u32,
+    pub duration_ms: u32,
+    /// False when the time budget ran out; the rest is wiped next start
+    pub complete: bool,
+}
+
+// ============================================================================
+// Emergency Wipe
+// ============================================================================
+
+/// Phrase `emergency_wipe` requires, typed exactly
+pub const EMERGENCY_WIPE_PHRASE: &str = "DELETE ALL PATIENT DATA";
+
+/// One kind of data the emergency wipe removes, in the order it's wiped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum WipeCategory {
+    /// The recording in progress, stopped without saving
+    Recording,
+    Drafts,
+    /// Transcripts, revisions, tags and the audit log
+    Database,
+    /// Recording recovery files and emergency data
+    Recovery,
+    /// Cached WAVs and temp files
+    Cache,
+    /// A transcript the app copied, if still on the clipboard
+    Clipboard,
+    /// Encryption keys and the app lock passcode
+    Keychain,
+    /// Preferences and other app state
+    Settings,
+}
+
+/// Outcome of wiping one category.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WipeCategoryResult {
+    pub category: WipeCategory,
+    pub success: bool,
+    /// Why it failed
+    pub message: Option<String>,
+}
+
+/// Result of `emergency_wipe`. The app restarts shortly after returning it.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct EmergencyWipeReport {
+    pub results: Vec<WipeCategoryResult>,
+}
+
+/// Error types for the emergency wipe (typed for frontend matching)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "type")]
+pub enum EmergencyWipeError {
+    /// The confirmation phrase didn't match `expected`; nothing was wiped
+    ConfirmationMismatch { expected: String },
+    /// A wipe has already run; the app is restarting
+    AlreadyRunning,
+    /// The app is locked; unlock it first
+    Locked,
+}
+
+impl std::fmt::Display for EmergencyWipeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmergencyWipeError::ConfirmationMismatch { expected } => {
+                write!(f, "Type \"{expected}\" to confirm the wipe")
+            }
+            EmergencyWipeError::AlreadyRunning => write!(f, "An emergency wipe already ran"),
+            EmergencyWipeError::Locked => write!(f, "The app is locked"),
+        }
+    }
+}
+
+// ============================================================================
+// App Lock
+// ============================================================================
+
+/// Shortest passcode `set_app_lock_passcode` accepts
+pub const MIN_APP_LOCK_PASSCODE_LEN: usize = 4;
+
+/// Result of `get_app_lock_status`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AppLockStatus {
+    /// A passcode is set
+    pub enabled: bool,
+    pub locked: bool,
+    /// Seconds before another unlock attempt is accepted (0 when allowed now)
+    pub retry_after_secs: u32,
+}
+
+/// Why the app locked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AppLockReason {
+    /// `lock_app` was called
+    Manual,
+    /// No activity for `auto_lock_minutes`
+    Idle,
+    /// A recording ran `max_idle_while_recording_minutes` without activity
+    IdleWhileRecording,
+}
+
+/// Payload for the `app-locked` event.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct AppLocked {
+    pub reason: AppLockReason,
+}
+
+/// Payload for the `auto-stopped-and-locked` event, sent after an unattended
+/// recording was stopped.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct AutoStoppedAndLocked {
+    /// Draft holding the transcription; None when nothing could be saved
+    pub draft_id: Option<String>,
+    /// Minutes without activity that triggered it
+    pub idle_minutes: u32,
+    /// False when no passcode is set, so the app couldn't lock
+    pub locked: bool,
+}
+
+/// What a quit is waiting for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ShutdownBlockReason {
+    /// The recording in progress is being stopped and kept for recovery
+    Recording,
+    /// A transcription gets a grace period to finish
+    Transcribing,
+}
+
+/// Payload for the `shutdown-blocked` event, sent while a quit waits for
+/// in-flight work.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct ShutdownBlocked {
+    pub reason: ShutdownBlockReason,
+    /// When the app quits regardless (ms since the Unix epoch)
+    pub deadline_ms: f64,
+}
+
+/// The OS biometric prompt behind `unlock_with_biometrics`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BiometricKind {
+    TouchId,
+    FaceId,
+    OpticId,
+    WindowsHello,
+}
+
+/// Why biometric unlock isn't offered or didn't unlock. The passcode still works.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BiometricFallbackReason {
+    /// The OS has no supported biometric API (Linux)
+    Unsupported,
+    /// The `biometric_unlock` preference is off
+    Disabled,
+    /// No biometric hardware, or it's turned off by policy
+    Unavailable,
+    /// No fingerprint or face is enrolled
+    NotEnrolled,
+    /// Too many failed matches; the OS requires its password first
+    LockedOut,
+    /// The prompt was cancelled by the user or the system
+    Cancelled,
+    /// The biometric didn't match
+    Failed,
+}
+
+/// Result of `get_biometric_availability`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BiometricAvailability {
+    Available { kind: BiometricKind },
+    Unavailable { reason: BiometricFallbackReason },
+}
+
+/// Error types for app lock operations (typed for frontend matching)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "type")]
+pub enum AppLockError {
+    /// No passcode is set
+    NotEnabled,
+    /// The passcode didn't match. Further attempts wait `retry_after_secs`.
+    WrongPasscode {
+        failed_attempts: u32,
+        retry_after_secs: u32,
+    },
+    /// Too many failed attempts; try again after `retry_after_secs`
+    RateLimited { retry_after_secs: u32 },
+    /// The new passcode is too short
+    PasscodeTooShort { min_length: u32 },
+    /// The keychain couldn't be read or written
+    StorageError { message: String },
+    /// Biometric unlock isn't available or didn't succeed; use the passcode
+    BiometricsUnavailable { reason: BiometricFallbackReason },
+}
+
+impl std::fmt::Display for AppLockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppLockError::NotEnabled => write!(f, "No app lock passcode is set"),
+            AppLockError::WrongPasscode {
+                failed_attempts, ..
+            } => write!(f, "Wrong passcode ({failed_attempts} failed attempts)"),
+            AppLockError::RateLimited { retry_after_secs } => {
+                write!(
+                    f,
+                    "Too many attempts; try again in {retry_after_secs} seconds"
+                )
+            }
+            AppLockError::PasscodeTooShort { min_length } => {
+                write!(f, "Passcode must be at least {min_length} characters")
+            }
+            AppLockError::StorageError { message } => write!(f, "Keychain error: {message}"),
+            AppLockError::BiometricsUnavailable { reason } => {
+                write!(
+                    f,
+                    "Biometric unlock unavailable ({reason:?}); use the passcode"
+                )
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Command Metrics
+// ============================================================================
+
+/// Coarse size of a command's response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadSize {
+    /// Under 1 KB
+    Tiny,
+    /// Under 64 KB
+    Small,
+    /// Under 1 MB
+    Medium,
+    /// 1 MB or more
+    Large,
+}
+
+/// How one command performed over the recent calls kept in memory.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CommandMetrics {
+    pub command: String,
+    /// Calls recorded
+    pub count: u32,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    /// Share of calls that failed, 0–1
+    pub error_rate: f64,
+    /// Error variant of the most recent failure
+    pub last_error: Option<String>,
+    /// Largest response seen (None for commands whose responses aren't sized)
+    pub largest_response: Option<PayloadSize>,
+}
+
+// ============================================================================
+// Diagnostics
+// ============================================================================
+
+/// One diagnostics reading. A probe that fails reports `unknown` with why,
+/// rather than failing the whole report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Probe<T> {
+    Known { value: T },
+    Unknown { reason: String },
+}
+
+/// Whether the OS lets the app use something.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionStatus {
+    Granted,
+    Denied,
+    /// The user hasn't been asked yet
+    NotDetermined,
+    /// Blocked by policy (parental controls, MDM)
+    Restricted,
+}
+
+/// The app build and how it runs.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AppDiagnostics {
+    pub version: String,
+    pub update_channel: UpdateChannel,
+    pub portable: bool,
+}
+
+/// The machine the app runs on. Sizes are in bytes.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SystemDiagnostics {
+    /// "macos", "windows" or "linux"
+    pub os: String,
+    pub os_version: String,
+    pub arch: String,
+    pub cpu_model: Probe<String>,
+    /// Logical cores
+    pub cpu_cores: Probe<u32>,
+    pub total_memory_bytes: Probe<f64>,
+    pub available_memory_bytes: Probe<f64>,
+    /// Whether whisper and llama can run on the GPU through Metal (macOS)
+    pub metal_available: Probe<bool>,
+}
+
+/// The model transcriptions use unless the caller picks another.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelDiagnostics {
+    pub model_id: String,
+    pub name: String,
+    pub downloaded: bool,
+    /// Whether the file still matches the checksum recorded at download
+    /// (by size and modification time; nothing is re-hashed)
+    pub verified: Probe<bool>,
+    pub size_bytes: Probe<f64>,
+}
+
+/// Rows in one database table.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TableRowCount {
+    pub table: String,
+    pub rows: u32,
+}
+
+/// Disk use of the app's data. Sizes are in bytes.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StorageDiagnostics {
+    pub database_bytes: Probe<f64>,
+    pub row_counts: Probe<Vec<TableRowCount>>,
+    pub recovery_bytes: Probe<f64>,
+    pub cache_bytes: Probe<f64>,
+    /// Free space on the volume holding the app data
+    pub free_disk_bytes: Probe<f64>,
+}
+
+/// OS permissions the app depends on.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PermissionDiagnostics {
+    pub microphone: Probe<PermissionStatus>,
+    pub notifications: Probe<PermissionStatus>,
+}
+
+/// Preferences that decide where PHI can end up and how long it stays.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PrivacyDiagnostics {
+    pub app_lock_enabled: bool,
+    pub auto_lock_minutes: u32,
+    pub biometric_unlock: bool,
+    pub block_screen_capture: bool,
+    pub auto_copy_to_clipboard: bool,
+    pub auto_paste_into_previous_app: bool,
+    pub clipboard_clear_seconds: u32,
+    pub conceal_clipboard_from_history: bool,
+    pub append_to_file: bool,
+    pub deidentify_auto_saved: bool,
+    pub exclude_database_from_backups: bool,
+    pub recovery_retention_hours: u32,
+    pub trash_retention_days: u32,
+    pub audit_retention_days: u32,
+    pub max_idle_while_recording_minutes: u32,
+    pub check_updates_automatically: bool,
+    pub command_metrics_enabled: bool,
+}
+
+/// Result of `get_diagnostics`: what support asks first. Carries no
+/// transcript content.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DiagnosticsReport {
+    /// Milliseconds since the Unix epoch
+    pub generated_at_ms: f64,
+    pub app: AppDiagnostics,
+    pub system: SystemDiagnostics,
+    pub model: ModelDiagnostics,
+    pub storage: StorageDiagnostics,
+    pub permissions: PermissionDiagnostics,
+    pub privacy: PrivacyDiagnostics,
+}
+
+/// Result of `export_log_bundle`: what went into the ZIP.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct LogBundleSummary {
+    /// Log files included, by name, newest first
+    pub files: Vec<String>,
+    /// Size of the written ZIP in bytes
+    pub bytes: f64,
+    /// Account names replaced in paths and text
+    pub account_names_scrubbed: u32,
+    /// Lines marked as possibly holding PHI, replaced whole
+    pub lines_redacted: u32,
+    /// Webview console lines left out
+    pub lines_dropped: u32,
+    /// Whether older log content was left out to stay under the size cap
+    pub truncated: bool,
+}
+
+// ============================================================================
+// Storage Usage
+// ============================================================================
+
+/// A file in the models folder.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelStorage {
+    pub file_name: String,
+    pub bytes: f64,
+}
+
+/// Space a database table takes, indexes included, estimated from the pages
+/// it occupies.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TableStorage {
+    pub table: String,
+    pub bytes: f64,
+}
+
+/// The folders in effect, which portable mode moves beside the executable.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StorageLocations {
+    pub portable: bool,
+    /// Preferences, models and recovery files
+    pub data_dir: String,
+    /// The databases
+    pub config_dir: String,
+    /// Temporary recordings
+    pub cache_dir: String,
+    pub log_dir: String,
+}
+
+/// Result of `get_storage_usage`: bytes used per category. Drafts live in
+/// the database and show as its `drafts` table.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StorageUsage {
+    /// Milliseconds since the Unix epoch
+    pub measured_at_ms: f64,
+    /// Largest first
+    pub models: Probe<Vec<ModelStorage>>,
+    /// The encrypted database with its journal files
+    pub database_bytes: Probe<f64>,
+    /// Largest first
+    pub database_tables: Probe<Vec<TableStorage>>,
+    pub recovery_bytes: Probe<f64>,
+    pub cache_bytes: Probe<f64>,
+    pub log_bytes: Probe<f64>,
+    pub locations: StorageLocations,
+}
+
+/// A category `clear_category` can empty without losing anything the user
+/// keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ClearableStorage {
+    Cache,
+    Logs,
+}
+
+/// Result of `clear_category`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct StorageCleared {
+    pub files_deleted: u32,
+    pub bytes_freed: f64,
+    /// Files in use or that couldn't be deleted
+    pub files_skipped: u32,
+}
+
+// ============================================================================
+// Health Check
+// ============================================================================
+
+/// A check run by `run_health_check`, in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthCheckKind {
+    /// Microphone permission and an input device that delivers audio
+    Microphone,
+    /// The default model is downloaded and matches its checksum
+    Model,
+    /// whisper loads the default model
+    Whisper,
+    /// The database is open and passes `quick_check`
+    Database,
+    /// Crash recovery can write its files
+    RecoveryDirectory,
+    /// Free space against the low-disk thresholds
+    DiskSpace,
+    /// The quick pane shortcut is registered with the OS
+    Shortcut,
+    Notifications,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Pass,
+    /// Works, but something needs the user's attention
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct HealthCheckResult {
+    pub check: HealthCheckKind,
+    pub status: HealthStatus,
+    /// What was found, for display
+    pub detail: String,
+}
+
+/// The `health-check-progress` event, sent as each check of
+/// `run_health_check` finishes.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct HealthCheckProgress {
+    pub result: HealthCheckResult,
+    /// Checks finished so far, this one included
+    pub done: u32,
+    pub total: u32,
+}
+
+// ============================================================================
+// App Errors
+// ============================================================================
+
+/// Error type for recording, transcription, preferences, quick pane and
+/// notification commands (typed for frontend matching)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "type")]
+pub enum AppError {
+    /// There's no microphone to record from
+    NoInputDevice,
+    /// A recording is already in progress
+    AlreadyRecording,
+    /// There's no recording in progress to stop
+    NotRecording,
+    /// The recording or file holds no audio
+    NoAudio,
+    /// The OS denied access to the microphone
+    MicrophonePermissionDenied,
+    /// macOS Accessibility access isn't granted
+    AccessibilityPermissionDenied,
+    /// The model isn't downloaded; download it first
+    ModelNotDownloaded { model_id: String },
+    /// A model file doesn't match its expected size or checksum. A corrupt
+    /// model is deleted so it can be downloaded again.
+    ChecksumMismatch { model_id: String },
+    /// The network is unreachable or the connection kept dropping
+    Offline,
+    /// Not enough free disk space. Sizes are in bytes, None when the OS only
+    /// reported the disk as full.
+    DiskFull {
+        required: Option<f64>,
+        available: Option<f64>,
+    },
+    /// The app is locked; unlock it first
+    Locked,
+    /// The operation was cancelled before it finished
+    Cancelled,
+    /// Not available on this platform
+    Unsupported,
+    /// Input validation failed
+    InvalidInput { message: String },
+    /// Any other failure
+    Internal { message: String },
+}
+
+impl AppError {
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        AppError::InvalidInput {
+            message: message.into(),
+        }
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        AppError::Internal {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::NoInputDevice => write!(f, "No input device available"),
+            AppError::AlreadyRecording => write!(f, "Already recording"),
+            AppError::NotRecording => write!(f, "Not recording"),
+            AppError::NoAudio => write!(f, "No audio data captured"),
+            AppError::MicrophonePermissionDenied => write!(f, "Microphone access was denied"),
+            AppError::AccessibilityPermissionDenied => {
+                write!(f, "Accessibility permission is required")
+            }
+            AppError::ModelNotDownloaded { model_id } => {
+                write!(f, "Model {model_id} is not downloaded")
+            }
+            AppError::ChecksumMismatch { model_id } => {
+                write!(f, "Model {model_id} failed its integrity check")
+            }
+            AppError::Offline => write!(f, "The network is unreachable"),
+            AppError::DiskFull {
+                required: Some(required),
+                available: Some(available),
+            } => write!(
+                f,
+                "Not enough free disk space ({required} bytes needed, {available} available)"
+            ),
+            AppError::DiskFull { .. } => write!(f, "Not enough free disk space"),
+            AppError::Locked => write!(f, "The app is locked"),
+            AppError::Cancelled => write!(f, "Cancelled"),
+            AppError::Unsupported => write!(f, "Not supported on this platform"),
+            AppError::InvalidInput { message } => write!(f, "Invalid input: {message}"),
+            AppError::Internal { message } => write!(f, "{message}"),
+        }
+    }
+}
+
+/// For helpers that still report errors as text
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Internal { message }
+    }
+}
+
+/// For callers that still report errors as text
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        error.to_string()
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        if crate::utils::disk::is_disk_full(&e) {
+            AppError::DiskFull {
+                required: None,
+                available: None,
+            }
+        } else {
+            AppError::internal(format!("IO error: {e}"))
+        }
+    }
+}
+
+impl From<hound::Error> for AppError {
+    fn from(e: hound::Error) -> Self {
+        match e {
+            hound::Error::IoError(e) => e.into(),
+            e => AppError::internal(format!("Invalid WAV: {e}")),
+        }
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_connect() || e.is_timeout() || e.is_body() || e.is_request() {
+            AppError::Offline
+        } else {
+            AppError::internal(format!("Network error: {e}"))
+        }
+    }
+}
+
+impl From<whisper_rs::WhisperError> for AppError {
+    fn from(e: whisper_rs::WhisperError) -> Self {
+        AppError::internal(format!("Whisper error: {e}"))
+    }
+}
+
+/// Whether a backend-specific audio error is the OS refusing microphone access
+/// (WASAPI's E_ACCESSDENIED, PipeWire/Pulse permission errors).
+fn is_microphone_permission_error(e: &cpal::BackendSpecificError) -> bool {
+    let description = e.description.to_lowercase();
+    description.contains("access is denied") || description.contains("permission")
+}
+
+impl From<cpal::DefaultStreamConfigError> for AppError {
+    fn from(e: cpal::DefaultStreamConfigError) -> Self {
+        match e {
+            cpal::DefaultStreamConfigError::DeviceNotAvailable => AppError::NoInputDevice,
+            cpal::DefaultStreamConfigError::BackendSpecific { err }
+                if is_microphone_permission_error(&err) =>
+            {
+                AppError::MicrophonePermissionDenied
+            }
+            e => AppError::internal(format!("Failed to get input config: {e}")),
+        }
+    }
+}
+
+impl From<cpal::BuildStreamError> for AppError {
+    fn from(e: cpal::BuildStreamError) -> Self {
+        match e {
+            cpal::BuildStreamError::DeviceNotAvailable => AppError::NoInputDevice,
+            cpal::BuildStreamError::BackendSpecific { err }
+                if is_microphone_permission_error(&err) =>
+            {
+                AppError::MicrophonePermissionDenied
+            }
+            e => AppError::internal(format!("Failed to build input stream: {e}")),
+        }
+    }
+}
+
+impl From<cpal::PlayStreamError> for AppError {
+    fn from(e: cpal::PlayStreamError) -> Self {
+        match e {
+            cpal::PlayStreamError::DeviceNotAvailable => AppError::NoInputDevice,
+            cpal::PlayStreamError::BackendSpecific { err }
+                if is_microphone_permission_error(&err) =>
+            {
+                AppError::MicrophonePermissionDenied
+            }
+            e => AppError::internal(format!("Failed to start stream: {e}")),
         }
     }
 }
@@ -63,6 +2621,16 @@ pub enum RecoveryError {
     IoError { message: String },
     /// JSON serialization/deserialization error
     ParseError { message: String },
+    /// Recovered audio could not be transcribed (the recovery file is kept)
+    TranscriptionFailed { message: String },
+    /// Database read/write error
+    DatabaseError { message: String },
+    /// The recovery encryption key is missing from the keychain
+    KeyUnavailable,
+    /// A PHI export was attempted without the user acknowledging the consent dialog
+    ConsentRequired,
+    /// The app is locked; unlock it first
+    Locked,
 }
 
 impl std::fmt::Display for RecoveryError {
@@ -75,6 +2643,13 @@ impl std::fmt::Display for RecoveryError {
             }
             RecoveryError::IoError { message } => write!(f, "IO error: {message}"),
             RecoveryError::ParseError { message } => write!(f, "Parse error: {message}"),
+            RecoveryError::TranscriptionFailed { message } => {
+                write!(f, "Transcription failed: {message}")
+            }
+            RecoveryError::DatabaseError { message } => write!(f, "Database error: {message}"),
+            RecoveryError::KeyUnavailable => write!(f, "Recovery encryption key unavailable"),
+            RecoveryError::ConsentRequired => write!(f, "PHI export requires explicit consent"),
+            RecoveryError::Locked => write!(f, "The app is locked"),
         }
     }
 }
@@ -113,6 +2688,22 @@ pub fn validate_string_input(input: &str, max_len: usize, field_name: &str) -> R
     Ok(())
 }
 
+/// Number of whitespace-separated words, as shown in the transcript library.
+pub fn count_words(text: &str) -> u32 {
+    text.split_whitespace()
+        .count()
+        .try_into()
+        .unwrap_or(u32::MAX)
+}
+
+/// Clamps a quick pane zoom factor into the supported range (NaN resets to 1.0).
+pub fn clamp_quick_pane_zoom(zoom: f64) -> f64 {
+    if zoom.is_nan() {
+        return 1.0;
+    }
+    zoom.clamp(MIN_QUICK_PANE_ZOOM, MAX_QUICK_PANE_ZOOM)
+}
+
 /// Validates theme value.
 pub fn validate_theme(theme: &str) -> Result<(), String> {
     match theme {
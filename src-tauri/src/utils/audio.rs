@@ -0,0 +1,347 @@
+//! Shared audio processing helpers.
+//!
+//! Used by live recording and by crash recovery so both produce the same
+//! 16kHz mono 16-bit WAV that Whisper expects.
+//!
+//! Samples are speech, so buffers holding them are overwritten with zeros
+//! before their memory goes back to the allocator, where it could otherwise
+//! turn up in a memory dump or swap.
+
+use std::ops::Deref;
+use std::path::Path;
+
+use zeroize::{Zeroize, Zeroizing};
+
+/// Sample rate Whisper requires
+pub const TARGET_SAMPLE_RATE: u32 = 16_000;
+
+/// A growable sample buffer that zeroes its memory when cleared or dropped,
+/// and zeroes the old allocation whenever it grows (a plain `Vec` frees it
+/// as is). Growth doubles the capacity like `Vec`, so appending stays
+/// amortized O(1) in the audio callback.
+#[derive(Default)]
+pub struct SampleBuffer(Vec<f32>);
+
+impl SampleBuffer {
+    /// Makes room for `additional` more samples.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.0.len() + additional;
+        if needed <= self.0.capacity() {
+            return;
+        }
+        let mut grown = Vec::with_capacity(needed.max(self.0.capacity() * 2));
+        grown.extend_from_slice(&self.0);
+        std::mem::replace(&mut self.0, grown).zeroize();
+    }
+
+    /// Appends samples, growing first so the `Vec` never reallocates itself.
+    pub fn extend(&mut self, samples: impl ExactSizeIterator<Item = f32>) {
+        self.reserve(samples.len());
+        self.0.extend(samples);
+    }
+
+    /// Zeroes and empties the buffer, keeping its allocation.
+    pub fn clear(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Deref for SampleBuffer {
+    type Target = [f32];
+
+    fn deref(&self) -> &[f32] {
+        &self.0
+    }
+}
+
+impl Drop for SampleBuffer {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Averages interleaved frames down to a single channel.
+pub fn downmix_to_mono(samples: &[f32], channels: u16) -> Zeroizing<Vec<f32>> {
+    let channels = usize::from(channels.max(1));
+    let mut mono = Zeroizing::new(Vec::with_capacity(samples.len().div_ceil(channels)));
+    mono.extend(
+        samples
+            .chunks(channels)
+            .map(|frame: &[f32]| frame.iter().sum::<f32>() / frame.len() as f32),
+    );
+    mono
+}
+
+/// Resample audio from `source_rate` to 16kHz using an FFT-based resampler.
+/// The resampler's own working buffers are small and reused per chunk; only
+/// the buffers here grow with the recording.
+pub fn resample_to_16k(mono: &[f32], source_rate: u32) -> Result<Zeroizing<Vec<f32>>, String> {
+    use rubato::{FftFixedIn, Resampler};
+
+    let ratio = TARGET_SAMPLE_RATE as f64 / source_rate as f64;
+    let chunk_size = 1024;
+    let sub_chunks = 2;
+
+    let mut resampler = FftFixedIn::<f64>::new(
+        source_rate as usize,
+        TARGET_SAMPLE_RATE as usize,
+        chunk_size,
+        sub_chunks,
+        1, // mono
+    )
+    .map_err(|e| format!("Failed to create resampler: {e}"))?;
+
+    let mut mono_f64 = Zeroizing::new(Vec::with_capacity(mono.len()));
+    mono_f64.extend(mono.iter().map(|&s| s as f64));
+    // Sized up front so extending never reallocates
+    let mut output = Zeroizing::new(Vec::with_capacity(
+        (mono.len() as f64 * ratio * 1.1) as usize + chunk_size,
+    ));
+
+    let frames_needed = resampler.input_frames_next();
+    let mut pos = 0;
+
+    while pos + frames_needed <= mono_f64.len() {
+        let chunk = &mono_f64[pos..pos + frames_needed];
+        let result = resampler
+            .process(&[chunk], None)
+            .map_err(|e| format!("Resample error: {e}"))?;
+        if let Some(channel) = result.first() {
+            output.extend(channel.iter().map(|&s| s as f32));
+        }
+        pos += frames_needed;
+    }
+
+    // Process remaining samples with zero-padding
+    if pos < mono_f64.len() {
+        let remaining = &mono_f64[pos..];
+        let mut padded = Zeroizing::new(remaining.to_vec());
+        padded.resize(frames_needed, 0.0);
+        let result = resampler
+            .process(&[&padded], None)
+            .map_err(|e| format!("Resample error (tail): {e}"))?;
+        if let Some(channel) = result.first() {
+            let expected_tail = ((mono_f64.len() - pos) as f64 * ratio).ceil() as usize;
+            let take = expected_tail.min(channel.len());
+            output.extend(channel[..take].iter().map(|&s| s as f32));
+        }
+    }
+
+    log::info!(
+        "Resampled {} -> {} samples ({}Hz -> {}Hz)",
+        mono.len(),
+        output.len(),
+        source_rate,
+        TARGET_SAMPLE_RATE
+    );
+    Ok(output)
+}
+
+/// Writes 16kHz mono samples as a 16-bit PCM WAV file.
+pub fn write_wav_16k(path: &Path, samples: &[f32]) -> Result<(), String> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: TARGET_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer =
+        hound::WavWriter::create(path, spec).map_err(|e| format!("Failed to create WAV: {e}"))?;
+
+    for &sample in samples {
+        let clamped: f32 = sample.clamp(-1.0, 1.0);
+        let as_i16 = (clamped * i16::MAX as f32) as i16;
+        writer
+            .write_sample(as_i16)
+            .map_err(|e| format!("Failed to write sample: {e}"))?;
+    }
+
+    writer
+        .finalize()
+        .map_err(|e| format!("Failed to finalize WAV: {e}"))
+}
+
+/// Where the parts of a WAV header that `repair_wav` needs live.
+#[derive(Debug, PartialEq, Eq)]
+struct WavLayout {
+    /// Offset of the data chunk's size field
+    data_size_offset: usize,
+    /// Offset of the first sample byte
+    data_start: u64,
+    /// Size the data chunk header claims
+    claimed_data_len: u32,
+    block_align: u16,
+}
+
+/// Walks the RIFF chunks in `header` up to the start of the data chunk.
+fn parse_wav_layout(header: &[u8]) -> Option<WavLayout> {
+    if header.len() < 12 || &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+        return None;
+    }
+
+    let mut block_align = None;
+    let mut pos = 12;
+    while pos + 8 <= header.len() {
+        let id = &header[pos..pos + 4];
+        let size = u32::from_le_bytes(header[pos + 4..pos + 8].try_into().ok()?);
+        match id {
+            b"fmt " => {
+                let field = header.get(pos + 20..pos + 22)?;
+                block_align = Some(u16::from_le_bytes(field.try_into().ok()?));
+            }
+            b"data" => {
+                return Some(WavLayout {
+                    data_size_offset: pos + 4,
+                    data_start: (pos + 8) as u64,
+                    claimed_data_len: size,
+                    block_align: block_align.filter(|&align| align > 0)?,
+                });
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even length
+        pos += 8 + size as usize + (size as usize & 1);
+    }
+    None
+}
+
+/// Repairs a WAV whose writer never finalized (e.g. the app crashed mid-write),
+/// leaving RIFF/data sizes that don't match the samples on disk. Sizes are
+/// rewritten from the file length, dropping any partial trailing frame, and the
+/// result is checked with hound. Returns the recovered duration in seconds.
+pub fn repair_wav(path: &Path) -> Result<f64, String> {
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open WAV: {e}"))?;
+    let file_len = file
+        .metadata()
+        .map_err(|e| format!("Failed to read WAV metadata: {e}"))?
+        .len();
+
+    let mut header = Vec::new();
+    Read::by_ref(&mut file)
+        .take(4096)
+        .read_to_end(&mut header)
+        .map_err(|e| format!("Failed to read WAV header: {e}"))?;
+    let layout = parse_wav_layout(&header).ok_or("Not a repairable WAV file")?;
+
+    let block_align = u64::from(layout.block_align);
+    let data_len = file_len.saturating_sub(layout.data_start) / block_align * block_align;
+    let data_len = u32::try_from(data_len).map_err(|_| "WAV data exceeds 4 GB")?;
+    let riff_len = u32::try_from(layout.data_start + u64::from(data_len) - 8)
+        .map_err(|_| "WAV file exceeds 4 GB")?;
+
+    if layout.claimed_data_len != data_len {
+        log::info!(
+            "Repairing WAV {path:?}: header claims {} data bytes, {data_len} on disk",
+            layout.claimed_data_len
+        );
+        let write_sizes = |file: &mut std::fs::File| -> std::io::Result<()> {
+            file.seek(SeekFrom::Start(4))?;
+            file.write_all(&riff_len.to_le_bytes())?;
+            file.seek(SeekFrom::Start(layout.data_size_offset as u64))?;
+            file.write_all(&data_len.to_le_bytes())?;
+            file.set_len(layout.data_start + u64::from(data_len))?;
+            file.sync_all()
+        };
+        write_sizes(&mut file).map_err(|e| format!("Failed to rewrite WAV header: {e}"))?;
+    }
+    drop(file);
+
+    wav_duration_secs(path).map_err(|e| format!("Repaired WAV is unreadable: {e}"))
+}
+
+/// Duration of a WAV file in seconds, from its header.
+pub fn wav_duration_secs(path: &Path) -> Result<f64, String> {
+    let reader = hound::WavReader::open(path).map_err(|e| format!("Failed to open WAV: {e}"))?;
+    let sample_rate = reader.spec().sample_rate;
+    if sample_rate == 0 {
+        return Err("WAV has no sample rate".to_string());
+    }
+    Ok(f64::from(reader.duration()) / f64::from(sample_rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_buffer_keeps_samples_across_growth() {
+        let mut buffer = SampleBuffer::default();
+        for chunk in 0..100 {
+            buffer.extend([chunk as f32; 7].into_iter());
+        }
+        assert_eq!(buffer.len(), 700);
+        assert!(buffer.0.capacity() < 2 * 700 + 7);
+        assert_eq!(buffer[699], 99.0);
+    }
+
+    #[test]
+    fn clearing_a_sample_buffer_zeroes_its_allocation() {
+        let mut buffer = SampleBuffer::default();
+        buffer.extend([0.5f32; 64].into_iter());
+        let (ptr, capacity) = (buffer.0.as_ptr(), buffer.0.capacity());
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.0.as_ptr(), ptr);
+        // SAFETY: clear keeps the allocation and zeroize wrote every slot of it
+        let memory = unsafe { std::slice::from_raw_parts(ptr, capacity) };
+        assert!(memory.iter().all(|&sample| sample == 0.0));
+    }
+
+    /// Header as hound leaves it before `finalize()`: all sizes zero.
+    fn unfinalized_header() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(b"WAVE");
+        bytes.extend_from_slice(b"fmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+        bytes.extend_from_slice(&TARGET_SAMPLE_RATE.to_le_bytes());
+        bytes.extend_from_slice(&(TARGET_SAMPLE_RATE * 2).to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes()); // block align
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_parse_wav_layout() {
+        let layout = parse_wav_layout(&unfinalized_header()).unwrap();
+        assert_eq!(
+            layout,
+            WavLayout {
+                data_size_offset: 40,
+                data_start: 44,
+                claimed_data_len: 0,
+                block_align: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_wav_layout_skips_unknown_chunks() {
+        let mut header = unfinalized_header();
+        let list_chunk = [b"LIST".as_slice(), &3u32.to_le_bytes(), b"abc\0"].concat();
+        header.splice(36..36, list_chunk);
+        let layout = parse_wav_layout(&header).unwrap();
+        assert_eq!(layout.data_start, 56);
+    }
+
+    #[test]
+    fn test_parse_wav_layout_rejects_non_wav() {
+        assert_eq!(parse_wav_layout(b"not a wav file at all"), None);
+        // fmt chunk missing before data
+        let mut header = unfinalized_header();
+        header.drain(12..36);
+        assert_eq!(parse_wav_layout(&header), None);
+    }
+}
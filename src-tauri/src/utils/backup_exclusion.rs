@@ -0,0 +1,132 @@
+//! Keeping PHI-bearing directories out of backups.
+//!
+//! - macOS: `NSURLIsExcludedFromBackupKey`, honoured by Time Machine and
+//!   iCloud backup. Set on a directory, it covers everything inside.
+//! - Windows: there's no per-folder opt-out. Directories inside a OneDrive
+//!   folder are synced to the cloud, so those are detected and reported.
+//! - Linux: backup tools each have their own exclude lists.
+
+use std::path::{Path, PathBuf};
+
+/// Why exclusion has no effect on this platform; None where it's supported
+#[cfg(target_os = "macos")]
+pub const UNSUPPORTED_REASON: Option<&str> = None;
+#[cfg(target_os = "windows")]
+pub const UNSUPPORTED_REASON: Option<&str> = Some(
+    "Windows has no per-folder backup exclusion; File History and OneDrive follow their own settings",
+);
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub const UNSUPPORTED_REASON: Option<&str> =
+    Some("Linux backup tools keep their own exclude lists");
+
+/// Marks `path` as excluded from backups, or includes it again.
+#[cfg(target_os = "macos")]
+pub fn set_excluded(path: &Path, excluded: bool) -> Result<(), String> {
+    use objc2::runtime::AnyObject;
+    use objc2_foundation::{NSNumber, NSString, NSURLIsExcludedFromBackupKey, NSURL};
+
+    let url = NSURL::fileURLWithPath(&NSString::from_str(&path.to_string_lossy()));
+    let value = NSNumber::new_bool(excluded);
+    let value: &AnyObject = &value;
+    // SAFETY: the key is a framework constant and the value the NSNumber it expects
+    unsafe { url.setResourceValue_forKey_error(Some(value), NSURLIsExcludedFromBackupKey) }.map_err(
+        |e| {
+            format!(
+                "Failed to set backup exclusion: {}",
+                e.localizedDescription()
+            )
+        },
+    )
+}
+
+/// Whether `path` is excluded from backups.
+#[cfg(target_os = "macos")]
+pub fn is_excluded(path: &Path) -> Result<bool, String> {
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2_foundation::{NSNumber, NSString, NSURLIsExcludedFromBackupKey, NSURL};
+
+    let url = NSURL::fileURLWithPath(&NSString::from_str(&path.to_string_lossy()));
+    let mut value: Option<Retained<AnyObject>> = None;
+    // SAFETY: the key is a framework constant; value outlives the call
+    unsafe { url.getResourceValue_forKey_error(&mut value, NSURLIsExcludedFromBackupKey) }
+        .map_err(|e| {
+            format!(
+                "Failed to read backup exclusion: {}",
+                e.localizedDescription()
+            )
+        })?;
+    Ok(value
+        .and_then(|value| value.downcast::<NSNumber>().ok())
+        .is_some_and(|number| number.boolValue()))
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn set_excluded(_path: &Path, excluded: bool) -> Result<(), String> {
+    if excluded {
+        Err(UNSUPPORTED_REASON.unwrap_or_default().to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn is_excluded(_path: &Path) -> Result<bool, String> {
+    Ok(false)
+}
+
+/// OneDrive folders of the signed-in user, with the provider's name.
+#[cfg(target_os = "windows")]
+fn cloud_sync_roots() -> Vec<(&'static str, PathBuf)> {
+    [
+        ("OneDrive", "OneDriveCommercial"),
+        ("OneDrive", "OneDriveConsumer"),
+        ("OneDrive", "OneDrive"),
+    ]
+    .into_iter()
+    .filter_map(|(provider, var)| {
+        std::env::var_os(var)
+            .filter(|root| !root.is_empty())
+            .map(|root| (provider, PathBuf::from(root)))
+    })
+    .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn cloud_sync_roots() -> Vec<(&'static str, PathBuf)> {
+    Vec::new()
+}
+
+/// The provider among `roots` whose folder contains `path`. Windows paths
+/// compare case-insensitively.
+fn synced_by(path: &Path, roots: &[(&'static str, PathBuf)]) -> Option<&'static str> {
+    let path = PathBuf::from(path.to_string_lossy().to_lowercase());
+    roots.iter().find_map(|(provider, root)| {
+        path.starts_with(root.to_string_lossy().to_lowercase())
+            .then_some(*provider)
+    })
+}
+
+/// The cloud sync service whose folder contains `path`, if any.
+pub fn cloud_synced_by(path: &Path) -> Option<&'static str> {
+    synced_by(path, &cloud_sync_roots())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_inside_a_sync_root_are_synced() {
+        let roots = [("OneDrive", PathBuf::from("/Users/dana/OneDrive"))];
+        assert_eq!(
+            synced_by(Path::new("/users/Dana/onedrive/AppData/scribe"), &roots),
+            Some("OneDrive")
+        );
+        assert_eq!(
+            synced_by(Path::new("/Users/dana/OneDriveOld/scribe"), &roots),
+            None
+        );
+        assert_eq!(synced_by(Path::new("/Users/dana/scribe"), &[]), None);
+    }
+}
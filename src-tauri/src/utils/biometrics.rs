@@ -0,0 +1,164 @@
+//! OS biometric prompts for unlocking the app.
+//!
+//! - macOS: LocalAuthentication with the biometrics-only policy (Touch ID,
+//!   Face ID, Optic ID). The OS's own password fallback isn't offered; the
+//!   app passcode is the fallback.
+//! - Windows: Windows Hello (`UserConsentVerifier`), which may also accept
+//!   the Hello PIN.
+//! - Linux: unsupported.
+//!
+//! Both calls block (the prompt waits for the user), so callers run them on
+//! a blocking thread.
+
+use crate::types::{BiometricAvailability, BiometricFallbackReason};
+
+#[cfg(target_os = "macos")]
+fn fallback_reason(error: &objc2_foundation::NSError) -> BiometricFallbackReason {
+    use objc2_local_authentication::LAError;
+
+    match LAError(error.code()) {
+        LAError::BiometryNotEnrolled | LAError::PasscodeNotSet => {
+            BiometricFallbackReason::NotEnrolled
+        }
+        LAError::BiometryLockout => BiometricFallbackReason::LockedOut,
+        LAError::UserCancel
+        | LAError::UserFallback
+        | LAError::SystemCancel
+        | LAError::AppCancel => BiometricFallbackReason::Cancelled,
+        LAError::AuthenticationFailed => BiometricFallbackReason::Failed,
+        _ => BiometricFallbackReason::Unavailable,
+    }
+}
+
+/// Whether biometric unlock can be offered, and with which sensor.
+#[cfg(target_os = "macos")]
+pub fn availability() -> BiometricAvailability {
+    use crate::types::BiometricKind;
+    use objc2_local_authentication::{LABiometryType, LAContext, LAPolicy};
+
+    // SAFETY: a fresh context, queried on this thread only
+    unsafe {
+        let context = LAContext::new();
+        if let Err(error) =
+            context.canEvaluatePolicy_error(LAPolicy::DeviceOwnerAuthenticationWithBiometrics)
+        {
+            return BiometricAvailability::Unavailable {
+                reason: fallback_reason(&error),
+            };
+        }
+        // biometryType is only set once canEvaluatePolicy has run
+        let kind = match context.biometryType() {
+            LABiometryType::FaceID => BiometricKind::FaceId,
+            LABiometryType::OpticID => BiometricKind::OpticId,
+            _ => BiometricKind::TouchId,
+        };
+        BiometricAvailability::Available { kind }
+    }
+}
+
+/// Shows the OS biometric prompt with `reason` and waits for the result.
+#[cfg(target_os = "macos")]
+pub fn authenticate(reason: &str) -> Result<(), BiometricFallbackReason> {
+    use block2::RcBlock;
+    use objc2::runtime::Bool;
+    use objc2_foundation::{NSError, NSString};
+    use objc2_local_authentication::{LAContext, LAPolicy};
+    use std::sync::{mpsc, Mutex};
+
+    let (sender, receiver) = mpsc::channel();
+    // The reply block is `Fn` but called once, on a private queue
+    let sender = Mutex::new(Some(sender));
+    let reply = RcBlock::new(move |success: Bool, error: *mut NSError| {
+        // SAFETY: LocalAuthentication passes a valid NSError or null
+        let result = match unsafe { error.as_ref() } {
+            _ if success.as_bool() => Ok(()),
+            Some(error) => Err(fallback_reason(error)),
+            None => Err(BiometricFallbackReason::Failed),
+        };
+        if let Some(sender) = sender.lock().ok().and_then(|mut s| s.take()) {
+            let _ = sender.send(result);
+        }
+    });
+
+    // SAFETY: the context outlives the wait below; the block is retained by
+    // LocalAuthentication until it has been called
+    let context = unsafe { LAContext::new() };
+    unsafe {
+        context.evaluatePolicy_localizedReason_reply(
+            LAPolicy::DeviceOwnerAuthenticationWithBiometrics,
+            &NSString::from_str(reason),
+            &reply,
+        );
+    }
+    receiver
+        .recv()
+        .unwrap_or(Err(BiometricFallbackReason::Failed))
+}
+
+#[cfg(target_os = "windows")]
+fn hello_availability() -> windows::core::Result<BiometricAvailability> {
+    use crate::types::BiometricKind;
+    use windows::Security::Credentials::UI::{
+        UserConsentVerifier, UserConsentVerifierAvailability,
+    };
+
+    let reason = match UserConsentVerifier::CheckAvailabilityAsync()?.get()? {
+        UserConsentVerifierAvailability::Available => {
+            return Ok(BiometricAvailability::Available {
+                kind: BiometricKind::WindowsHello,
+            })
+        }
+        UserConsentVerifierAvailability::NotConfiguredForUser => {
+            BiometricFallbackReason::NotEnrolled
+        }
+        _ => BiometricFallbackReason::Unavailable,
+    };
+    Ok(BiometricAvailability::Unavailable { reason })
+}
+
+/// Whether biometric unlock can be offered, and with which sensor.
+#[cfg(target_os = "windows")]
+pub fn availability() -> BiometricAvailability {
+    hello_availability().unwrap_or_else(|e| {
+        log::warn!("Failed to check Windows Hello availability: {e}");
+        BiometricAvailability::Unavailable {
+            reason: BiometricFallbackReason::Unavailable,
+        }
+    })
+}
+
+/// Shows the Windows Hello prompt with `reason` and waits for the result.
+#[cfg(target_os = "windows")]
+pub fn authenticate(reason: &str) -> Result<(), BiometricFallbackReason> {
+    use windows::core::HSTRING;
+    use windows::Security::Credentials::UI::{UserConsentVerificationResult, UserConsentVerifier};
+
+    let result = UserConsentVerifier::RequestVerificationAsync(&HSTRING::from(reason))
+        .and_then(|operation| operation.get())
+        .map_err(|e| {
+            log::warn!("Windows Hello verification failed: {e}");
+            BiometricFallbackReason::Unavailable
+        })?;
+    match result {
+        UserConsentVerificationResult::Verified => Ok(()),
+        UserConsentVerificationResult::NotConfiguredForUser => {
+            Err(BiometricFallbackReason::NotEnrolled)
+        }
+        UserConsentVerificationResult::RetriesExhausted => Err(BiometricFallbackReason::LockedOut),
+        UserConsentVerificationResult::Canceled => Err(BiometricFallbackReason::Cancelled),
+        _ => Err(BiometricFallbackReason::Unavailable),
+    }
+}
+
+/// Biometric unlock is never available on this platform.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn availability() -> BiometricAvailability {
+    BiometricAvailability::Unavailable {
+        reason: BiometricFallbackReason::Unsupported,
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn authenticate(_reason: &str) -> Result<(), BiometricFallbackReason> {
+    Err(BiometricFallbackReason::Unsupported)
+}
@@ -0,0 +1,104 @@
+//! Excluding app windows from screenshots, screen recording and screen sharing.
+//!
+//! - macOS: `NSWindow.sharingType = .none`.
+//! - Windows: `SetWindowDisplayAffinity(WDA_EXCLUDEFROMCAPTURE)`, which needs
+//!   Windows 10 version 2004 or later; earlier versions leave the window
+//!   capturable.
+//! - Linux: windows can't opt out; the compositor decides.
+//!
+//! Both functions must run on the main thread.
+
+use tauri::WebviewWindow;
+
+/// Why exclusion has no effect on this platform, if known up front
+#[cfg(target_os = "windows")]
+pub const UNSUPPORTED_REASON: &str = "Requires Windows 10 version 2004 or later";
+#[cfg(target_os = "macos")]
+pub const UNSUPPORTED_REASON: &str = "The window could not be excluded from capture";
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub const UNSUPPORTED_REASON: &str = "Linux doesn't let apps exclude windows from capture";
+
+#[cfg(target_os = "macos")]
+fn with_ns_window<T>(
+    window: &WebviewWindow,
+    f: impl FnOnce(&objc2_app_kit::NSWindow) -> T,
+) -> Result<T, String> {
+    let ns_window = window.ns_window().map_err(|e| e.to_string())?;
+    // SAFETY: ns_window() returns the live NSWindow backing this webview
+    // window, and callers run on the main thread
+    Ok(f(unsafe {
+        &*(ns_window as *const objc2_app_kit::NSWindow)
+    }))
+}
+
+/// Excludes `window` from capture, or makes it capturable again.
+#[cfg(target_os = "macos")]
+pub fn set_excluded(window: &WebviewWindow, excluded: bool) -> Result<(), String> {
+    use objc2_app_kit::NSWindowSharingType;
+
+    let sharing = if excluded {
+        NSWindowSharingType::None
+    } else {
+        NSWindowSharingType::ReadOnly
+    };
+    with_ns_window(window, |ns_window| ns_window.setSharingType(sharing))
+}
+
+/// Whether `window` is currently excluded from capture.
+#[cfg(target_os = "macos")]
+pub fn is_excluded(window: &WebviewWindow) -> Result<bool, String> {
+    use objc2_app_kit::NSWindowSharingType;
+
+    with_ns_window(window, |ns_window| {
+        ns_window.sharingType() == NSWindowSharingType::None
+    })
+}
+
+/// Excludes `window` from capture, or makes it capturable again.
+#[cfg(target_os = "windows")]
+pub fn set_excluded(window: &WebviewWindow, excluded: bool) -> Result<(), String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SetWindowDisplayAffinity, WDA_EXCLUDEFROMCAPTURE, WDA_NONE,
+    };
+
+    let hwnd = HWND(window.hwnd().map_err(|e| e.to_string())?.0);
+    let affinity = if excluded {
+        WDA_EXCLUDEFROMCAPTURE
+    } else {
+        WDA_NONE
+    };
+    // SAFETY: hwnd belongs to our live window
+    unsafe { SetWindowDisplayAffinity(hwnd, affinity) }
+        .map_err(|e| format!("Failed to set display affinity: {e}"))
+}
+
+/// Whether `window` is currently excluded from capture.
+#[cfg(target_os = "windows")]
+pub fn is_excluded(window: &WebviewWindow) -> Result<bool, String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetWindowDisplayAffinity, WDA_EXCLUDEFROMCAPTURE,
+    };
+
+    let hwnd = HWND(window.hwnd().map_err(|e| e.to_string())?.0);
+    let mut affinity = 0u32;
+    // SAFETY: hwnd belongs to our live window; affinity outlives the call
+    unsafe { GetWindowDisplayAffinity(hwnd, &mut affinity) }
+        .map_err(|e| format!("Failed to read display affinity: {e}"))?;
+    Ok(affinity == WDA_EXCLUDEFROMCAPTURE.0)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn set_excluded(_window: &WebviewWindow, excluded: bool) -> Result<(), String> {
+    if excluded {
+        Err(UNSUPPORTED_REASON.to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn is_excluded(_window: &WebviewWindow) -> Result<bool, String> {
+    Ok(false)
+}
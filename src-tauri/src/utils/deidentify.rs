@@ -0,0 +1,310 @@
+//! Rule-based de-identification of transcript text.
+//!
+//! Identifiers are found with regular expressions plus a user-maintained name
+//! list and replaced with typed placeholders (`[NAME]`, `[DOB]`, ...). This is
+//! a safety net for research exports, not a guarantee: names not on the list
+//! and identifiers in unusual formats pass through.
+
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex, RegexBuilder};
+use sha2::{Digest, Sha256};
+
+use crate::types::{DeidentificationReport, DeidentificationRules, DEIDENTIFICATION_RULES_VERSION};
+
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)*\.[a-z]{2,}\b")
+        .expect("email pattern is valid")
+});
+
+const DATE: &str = r"\d{1,2}[./-]\d{1,2}[./-]\d{2,4}|\d{4}-\d{2}-\d{2}|\d{1,2}\.?\s+(?:jan|feb|mar|apr|ma[iy]|jun|jul|aug|sep|o[ck]t|nov|de[cs])[a-z]*\.?,?\s+\d{4}|(?:jan|feb|mar|apr|ma[iy]|jun|jul|aug|sep|o[ck]t|nov|de[cs])[a-z]*\.?\s+\d{1,2}(?:st|nd|rd|th)?,?\s+\d{4}";
+
+/// A date after a birth-date cue. Only the date (group 2) is replaced, so
+/// "DOB: 01.02.1980" becomes "DOB: [DOB]".
+static DATE_OF_BIRTH: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?i)(\b(?:dob|d\.o\.b\.?|date of birth|birth ?date|born(?: on)?|født|fødselsdato)\s*[:\-]?\s*)({DATE})"
+    ))
+    .expect("date of birth pattern is valid")
+});
+
+/// Matches that are really dates, which the phone pattern would otherwise take.
+static DATE_ONLY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!(r"(?i)^(?:{DATE})$")).expect("date pattern is valid"));
+
+/// US SSNs, Nordic 11-digit personal numbers (fødselsnummer) and Swedish
+/// personnummer.
+static NATIONAL_ID: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b\d{3}-\d{2}-\d{4}\b|\b\d{6}[ -]?\d{5}\b|\b(?:19|20)?\d{6}[-+]\d{4}\b")
+        .expect("national id pattern is valid")
+});
+
+/// A parenthesized area code, grouped digits or one long digit run, each
+/// with an optional country code. Candidates are checked for digit count and
+/// date shape before replacing.
+static PHONE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"(?:\+\d{1,3}[ .-]?)?\(\d{1,4}\)[ .-]?\d{2,4}(?:[ .-]\d{2,4}){0,4}\b",
+        r"|(?:\+\d{1,3}[ .-]?|\b)\d{2,4}(?:[ .-]\d{2,4}){1,4}\b",
+        r"|(?:\+\d{1,3}[ .-]?|\b)\d{7,15}\b",
+    ))
+    .expect("phone pattern is valid")
+});
+
+const MIN_PHONE_DIGITS: usize = 7;
+const MAX_PHONE_DIGITS: usize = 15;
+
+/// Replaces every non-empty match of `pattern` with `placeholder`, counting
+/// replacements. `keep` lets a rule leave a candidate untouched.
+fn replace(
+    text: &str,
+    pattern: &Regex,
+    placeholder: &str,
+    keep: impl Fn(&str) -> bool,
+    count: &mut u32,
+) -> String {
+    pattern
+        .replace_all(text, |caps: &Captures| {
+            let matched = &caps[0];
+            if matched.is_empty() || keep(matched) {
+                matched.to_string()
+            } else {
+                *count += 1;
+                placeholder.to_string()
+            }
+        })
+        .into_owned()
+}
+
+fn is_phone_number(candidate: &str) -> bool {
+    let digits = candidate.chars().filter(char::is_ascii_digit).count();
+    (MIN_PHONE_DIGITS..=MAX_PHONE_DIGITS).contains(&digits) && !DATE_ONLY.is_match(candidate)
+}
+
+/// One case-insensitive, whole-word pattern for the name list, longest names
+/// first so "Anna Berg" wins over "Anna". Whitespace inside a name matches
+/// any whitespace.
+fn names_pattern(names: &[String]) -> Option<Regex> {
+    let mut names: Vec<&str> = names
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .collect();
+    if names.is_empty() {
+        return None;
+    }
+    names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+    let alternatives: Vec<String> = names
+        .iter()
+        .map(|name| {
+            name.split_whitespace()
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(r"\s+")
+        })
+        .collect();
+    RegexBuilder::new(&format!(r"\b(?:{})\b", alternatives.join("|")))
+        .case_insensitive(true)
+        .build()
+        .ok()
+}
+
+fn custom_pattern(pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("Invalid de-identification pattern {pattern:?}: {e}"))
+}
+
+/// Checks that every custom pattern compiles.
+pub fn validate_rules(rules: &DeidentificationRules) -> Result<(), String> {
+    for pattern in &rules.custom_patterns {
+        custom_pattern(pattern)?;
+    }
+    Ok(())
+}
+
+/// Identifies a rule set: the built-in pattern version plus a short hash of
+/// the user's settings, so transcripts scrubbed with different name lists or
+/// patterns can be told apart without storing the names.
+pub fn rule_set_version(rules: &DeidentificationRules) -> String {
+    let mut hasher = Sha256::new();
+    for flag in [
+        rules.dates_of_birth,
+        rules.phone_numbers,
+        rules.national_ids,
+        rules.email_addresses,
+    ] {
+        hasher.update([u8::from(flag)]);
+    }
+    for value in rules.names.iter().chain(&rules.custom_patterns) {
+        hasher.update(value.as_bytes());
+        hasher.update([0]);
+    }
+    let hash: String = hasher.finalize()[..4]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    format!("v{DEIDENTIFICATION_RULES_VERSION}-{hash}")
+}
+
+/// Applies `rules` to `text`, accumulating counts into `report`. Emails go
+/// first since they contain digits, and dates of birth before phone numbers.
+pub fn deidentify(
+    text: &str,
+    rules: &DeidentificationRules,
+    report: &mut DeidentificationReport,
+) -> Result<String, String> {
+    let mut text = text.to_string();
+    if rules.email_addresses {
+        text = replace(
+            &text,
+            &EMAIL,
+            "[EMAIL]",
+            |_| false,
+            &mut report.email_addresses,
+        );
+    }
+    if rules.dates_of_birth {
+        text = DATE_OF_BIRTH
+            .replace_all(&text, |caps: &Captures| {
+                report.dates_of_birth += 1;
+                format!("{}[DOB]", &caps[1])
+            })
+            .into_owned();
+    }
+    if rules.national_ids {
+        text = replace(
+            &text,
+            &NATIONAL_ID,
+            "[ID]",
+            |_| false,
+            &mut report.national_ids,
+        );
+    }
+    if rules.phone_numbers {
+        text = replace(
+            &text,
+            &PHONE,
+            "[PHONE]",
+            |candidate| !is_phone_number(candidate),
+            &mut report.phone_numbers,
+        );
+    }
+    if let Some(names) = names_pattern(&rules.names) {
+        text = replace(&text, &names, "[NAME]", |_| false, &mut report.names);
+    }
+    for pattern in &rules.custom_patterns {
+        text = replace(
+            &text,
+            &custom_pattern(pattern)?,
+            "[REDACTED]",
+            |_| false,
+            &mut report.custom,
+        );
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scrub(text: &str, rules: &DeidentificationRules) -> (String, DeidentificationReport) {
+        let mut report = DeidentificationReport::default();
+        let text = deidentify(text, rules, &mut report).unwrap();
+        (text, report)
+    }
+
+    #[test]
+    fn replaces_each_category_with_its_placeholder() {
+        let rules = DeidentificationRules {
+            names: vec!["Anna Berg".to_string()],
+            ..Default::default()
+        };
+        let (text, report) = scrub(
+            "Anna  Berg, DOB: 03.04.1980, SSN 123-45-6789, call +47 912 34 567 \
+             or mail anna.berg@example.no. Seen 12.05.2024.",
+            &rules,
+        );
+        assert_eq!(
+            text,
+            "[NAME], DOB: [DOB], SSN [ID], call [PHONE] or mail [EMAIL]. Seen 12.05.2024."
+        );
+        assert_eq!(
+            report,
+            DeidentificationReport {
+                names: 1,
+                dates_of_birth: 1,
+                phone_numbers: 1,
+                national_ids: 1,
+                email_addresses: 1,
+                custom: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn leaves_dates_and_short_numbers_alone() {
+        let (text, report) = scrub(
+            "Follow up on 2024-06-01, BP 140 90, dose 500 mg.",
+            &DeidentificationRules::default(),
+        );
+        assert_eq!(text, "Follow up on 2024-06-01, BP 140 90, dose 500 mg.");
+        assert_eq!(report, DeidentificationReport::default());
+    }
+
+    #[test]
+    fn names_match_whole_words_case_insensitively() {
+        let rules = DeidentificationRules {
+            names: vec!["Berg".to_string(), "  ".to_string()],
+            ..Default::default()
+        };
+        let (text, report) = scrub("berg met Bergen's BERG", &rules);
+        assert_eq!(text, "[NAME] met Bergen's [NAME]");
+        assert_eq!(report.names, 2);
+    }
+
+    #[test]
+    fn disabled_categories_are_kept() {
+        let rules = DeidentificationRules {
+            email_addresses: false,
+            ..Default::default()
+        };
+        let (text, _) = scrub("a@example.com", &rules);
+        assert_eq!(text, "a@example.com");
+    }
+
+    #[test]
+    fn custom_patterns_are_validated_and_skip_empty_matches() {
+        let rules = DeidentificationRules {
+            custom_patterns: vec![r"MRN\d*".to_string()],
+            ..Default::default()
+        };
+        let (text, report) = scrub("MRN4411 and MRN", &rules);
+        assert_eq!(text, "[REDACTED] and [REDACTED]");
+        assert_eq!(report.custom, 2);
+
+        let invalid = DeidentificationRules {
+            custom_patterns: vec!["(".to_string()],
+            ..Default::default()
+        };
+        assert!(validate_rules(&invalid).is_err());
+    }
+
+    #[test]
+    fn rule_set_version_tracks_settings_but_not_auto_save() {
+        let rules = DeidentificationRules::default();
+        let version = rule_set_version(&rules);
+        assert!(version.starts_with(&format!("v{DEIDENTIFICATION_RULES_VERSION}-")));
+
+        let auto_saved = DeidentificationRules {
+            apply_to_auto_saved: true,
+            ..rules.clone()
+        };
+        assert_eq!(rule_set_version(&auto_saved), version);
+
+        let with_names = DeidentificationRules {
+            names: vec!["Berg".to_string()],
+            ..rules
+        };
+        assert_ne!(rule_set_version(&with_names), version);
+    }
+}
@@ -0,0 +1,158 @@
+//! Probes of the machine and the app's files for the diagnostics report.
+//!
+//! Every probe is allowed to fail: `probe` turns its error into an `unknown`
+//! reading (and a log line) so one unreadable value never hides the rest.
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
+
+use crate::types::{Probe, SystemDiagnostics};
+
+/// Most directory entries `dir_size` visits before giving up
+const MAX_WALK_ENTRIES: usize = 100_000;
+
+/// Deepest folder level `dir_size` descends into
+const MAX_WALK_DEPTH: usize = 16;
+
+/// A reading of `what`, or `unknown` with the reason it couldn't be taken.
+pub fn probe<T, E: Display>(what: &str, result: Result<T, E>) -> Probe<T> {
+    match result {
+        Ok(value) => Probe::Known { value },
+        Err(e) => {
+            log::warn!("Diagnostics could not read {what}: {e}");
+            Probe::Unknown {
+                reason: e.to_string(),
+            }
+        }
+    }
+}
+
+/// Total size of the files under `dir`, without following symlinks. A
+/// missing directory is empty. Gives up past `MAX_WALK_ENTRIES` entries, and
+/// doesn't look below `MAX_WALK_DEPTH` levels.
+pub fn dir_size(dir: &Path) -> Result<u64, String> {
+    let mut total = 0;
+    let mut visited = 0;
+    let mut pending: Vec<(PathBuf, usize)> = vec![(dir.to_path_buf(), 0)];
+    while let Some((dir, depth)) = pending.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("Failed to list a directory: {e}")),
+        };
+        for entry in entries.flatten() {
+            visited += 1;
+            if visited > MAX_WALK_ENTRIES {
+                return Err(format!("More than {MAX_WALK_ENTRIES} files"));
+            }
+            let Ok(metadata) = entry.path().symlink_metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                if depth < MAX_WALK_DEPTH {
+                    pending.push((entry.path(), depth + 1));
+                }
+            } else if metadata.is_file() {
+                total += metadata.len();
+            }
+        }
+    }
+    Ok(total)
+}
+
+/// Whether a Metal device is present for whisper and llama (macOS builds
+/// link them with Metal).
+#[cfg(target_os = "macos")]
+fn metal_available() -> bool {
+    use std::ffi::c_void;
+
+    #[link(name = "Metal", kind = "framework")]
+    extern "C" {
+        fn MTLCreateSystemDefaultDevice() -> *mut c_void;
+    }
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFRelease(cf: *const c_void);
+    }
+
+    // SAFETY: returns a retained device or null; a device is released right away.
+    unsafe {
+        let device = MTLCreateSystemDefaultDevice();
+        if device.is_null() {
+            return false;
+        }
+        CFRelease(device);
+    }
+    true
+}
+
+/// Other platforms build whisper and llama without Metal.
+#[cfg(not(target_os = "macos"))]
+fn metal_available() -> bool {
+    false
+}
+
+/// OS, CPU, memory and GPU of this machine.
+pub fn system_diagnostics() -> SystemDiagnostics {
+    let system = System::new_with_specifics(
+        RefreshKind::nothing()
+            .with_cpu(CpuRefreshKind::nothing())
+            .with_memory(MemoryRefreshKind::nothing().with_ram()),
+    );
+    let cpu_model = system
+        .cpus()
+        .first()
+        .map(|cpu| cpu.brand().trim().to_string())
+        .filter(|brand| !brand.is_empty())
+        .ok_or("The OS didn't report a CPU model");
+    let cores = std::thread::available_parallelism().map(|cores| cores.get() as u32);
+    // sysinfo reports 0 where it can't read memory
+    let total_memory = Some(system.total_memory())
+        .filter(|&bytes| bytes > 0)
+        .ok_or("The OS didn't report total memory");
+    let available_memory = Some(system.available_memory())
+        .filter(|&bytes| bytes > 0)
+        .ok_or("The OS didn't report available memory");
+
+    SystemDiagnostics {
+        os: tauri_plugin_os::platform().to_string(),
+        os_version: tauri_plugin_os::version().to_string(),
+        arch: tauri_plugin_os::arch().to_string(),
+        cpu_model: probe("the CPU model", cpu_model),
+        cpu_cores: probe("the CPU core count", cores),
+        total_memory_bytes: probe("total memory", total_memory.map(|bytes| bytes as f64)),
+        available_memory_bytes: probe(
+            "available memory",
+            available_memory.map(|bytes| bytes as f64),
+        ),
+        metal_available: Probe::Known {
+            value: metal_available(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_probes_report_unknown_with_the_reason() {
+        assert_eq!(
+            probe("memory", Ok::<u32, String>(8)),
+            Probe::Known { value: 8 }
+        );
+        assert_eq!(
+            probe("memory", Err::<u32, _>("not reported")),
+            Probe::Unknown {
+                reason: "not reported".into()
+            }
+        );
+    }
+
+    #[test]
+    fn a_missing_directory_is_empty() {
+        assert_eq!(dir_size(Path::new("/nonexistent/privacyscribe")), Ok(0));
+    }
+}
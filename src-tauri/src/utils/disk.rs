@@ -0,0 +1,81 @@
+//! Free disk space checks before writing large files.
+//!
+//! Recording and model downloads both fail badly when the disk fills up
+//! mid-write (a corrupt WAV, half a gigabyte of wasted download), so callers
+//! check headroom up front and warn early.
+
+use std::path::Path;
+
+use crate::utils::audio::TARGET_SAMPLE_RATE;
+
+/// How much headroom a pending write has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiskHeadroom {
+    Sufficient,
+    /// Enough to start, but below the comfortable threshold
+    Low,
+    /// Not enough for even the minimum; the write should not start
+    Insufficient,
+}
+
+/// Bytes available to the current user on the volume containing `path`.
+pub fn available_bytes(path: &Path) -> std::io::Result<u64> {
+    fs2::available_space(path)
+}
+
+/// Whether `e` means the volume ran out of space. `ErrorKind::StorageFull`
+/// needs Rust 1.83, above our MSRV, so this matches the OS error codes.
+pub fn is_disk_full(e: &std::io::Error) -> bool {
+    #[cfg(windows)]
+    const DISK_FULL_CODES: &[i32] = &[39, 112]; // ERROR_HANDLE_DISK_FULL, ERROR_DISK_FULL
+    #[cfg(not(windows))]
+    const DISK_FULL_CODES: &[i32] = &[28]; // ENOSPC
+
+    e.raw_os_error()
+        .is_some_and(|code| DISK_FULL_CODES.contains(&code))
+}
+
+/// Disk bytes one minute of recording needs: the 16-bit recovery file at the
+/// native rate plus the 16kHz mono WAV written when recording stops.
+pub fn recording_bytes_per_minute(sample_rate: u32, channels: u16) -> u64 {
+    let recovery = u64::from(sample_rate) * u64::from(channels) * 2;
+    let wav = u64::from(TARGET_SAMPLE_RATE) * 2;
+    (recovery + wav) * 60
+}
+
+/// Classifies `available` against the warning and blocking thresholds.
+pub fn classify_headroom(available: u64, warn_below: u64, block_below: u64) -> DiskHeadroom {
+    if available < block_below {
+        DiskHeadroom::Insufficient
+    } else if available < warn_below {
+        DiskHeadroom::Low
+    } else {
+        DiskHeadroom::Sufficient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_bytes_per_minute() {
+        // 48kHz stereo recovery (192KB/s) + 16kHz mono WAV (32KB/s)
+        assert_eq!(recording_bytes_per_minute(48_000, 2), 224_000 * 60);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_is_disk_full() {
+        assert!(is_disk_full(&std::io::Error::from_raw_os_error(28)));
+        assert!(!is_disk_full(&std::io::Error::from_raw_os_error(2)));
+        assert!(!is_disk_full(&std::io::Error::other("full")));
+    }
+
+    #[test]
+    fn test_classify_headroom() {
+        assert_eq!(classify_headroom(100, 50, 10), DiskHeadroom::Sufficient);
+        assert_eq!(classify_headroom(49, 50, 10), DiskHeadroom::Low);
+        assert_eq!(classify_headroom(9, 50, 10), DiskHeadroom::Insufficient);
+    }
+}
@@ -0,0 +1,74 @@
+//! Detection of the OS Do Not Disturb / Focus state.
+//!
+//! - macOS: an active Focus leaves assertion records in the user's
+//!   DoNotDisturb database. Focus modes that only run on a schedule, and
+//!   systems where the database is unreadable, are reported as not in DND.
+//! - Windows: `SHQueryUserNotificationState` reports quiet time, presentation
+//!   mode, full-screen apps and (on recent builds) Focus Assist.
+//! - Linux: notification daemons apply DND themselves, so nothing is detected.
+
+/// Whether the OS is in Do Not Disturb. Detection failure counts as "not in
+/// DND" so a broken check never suppresses every notification.
+pub fn do_not_disturb_active() -> bool {
+    detect_do_not_disturb().unwrap_or_else(|e| {
+        log::debug!("Do Not Disturb detection failed: {e}");
+        false
+    })
+}
+
+/// Detects the DND state, reporting failures (for diagnostics).
+#[cfg(target_os = "macos")]
+pub fn detect_do_not_disturb() -> Result<bool, String> {
+    let home = std::env::var_os("HOME").ok_or("HOME is not set")?;
+    let path = std::path::Path::new(&home).join("Library/DoNotDisturb/DB/Assertions.json");
+    let contents =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {path:?}: {e}"))?;
+    let assertions: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse Focus assertions: {e}"))?;
+    Ok(focus_assertions_active(&assertions))
+}
+
+/// Detects the DND state, reporting failures (for diagnostics).
+#[cfg(target_os = "windows")]
+pub fn detect_do_not_disturb() -> Result<bool, String> {
+    use windows::Win32::UI::Shell::{SHQueryUserNotificationState, QUNS_ACCEPTS_NOTIFICATIONS};
+
+    // SAFETY: no arguments; returns the current user's notification state.
+    let state = unsafe { SHQueryUserNotificationState() }
+        .map_err(|e| format!("SHQueryUserNotificationState failed: {e}"))?;
+    Ok(state != QUNS_ACCEPTS_NOTIFICATIONS)
+}
+
+/// Detects the DND state, reporting failures (for diagnostics).
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn detect_do_not_disturb() -> Result<bool, String> {
+    Ok(false)
+}
+
+/// Whether a parsed `Assertions.json` holds any active Focus assertion.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn focus_assertions_active(assertions: &serde_json::Value) -> bool {
+    assertions["data"].as_array().is_some_and(|data| {
+        data.iter().any(|entry| {
+            entry["storeAssertionRecords"]
+                .as_array()
+                .is_some_and(|records| !records.is_empty())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_assertions_active() {
+        let active = serde_json::json!({
+            "data": [{ "storeAssertionRecords": [{ "assertionDetails": {} }] }]
+        });
+        let inactive = serde_json::json!({ "data": [{}] });
+        assert!(focus_assertions_active(&active));
+        assert!(!focus_assertions_active(&inactive));
+        assert!(!focus_assertions_active(&serde_json::json!({})));
+    }
+}
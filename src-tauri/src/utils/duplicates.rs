@@ -0,0 +1,108 @@
+//! Near-duplicate detection for transcript text.
+//!
+//! Push-to-talk retries and double-clicks save the same dictation twice,
+//! sometimes with slightly different punctuation or a word or two changed by
+//! a second transcription run. Texts are compared as lowercase word tokens:
+//! identical token sequences share a content hash (indexed, so exact matches
+//! are a lookup), and near-identical ones are caught by token overlap.
+
+use std::collections::HashMap;
+
+use sha2::{Digest, Sha256};
+
+/// Token overlap above which two transcripts count as duplicates.
+pub const FUZZY_DUPLICATE_THRESHOLD: f64 = 0.9;
+
+/// Lowercase words with punctuation stripped.
+pub fn tokens(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// SHA-256 of the normalized tokens, so case, punctuation and spacing
+/// differences hash the same.
+pub fn content_hash(text: &str) -> String {
+    Sha256::digest(tokens(text).join(" ").as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Shared tokens (counting repeats) over the longer text's token count, from
+/// 0.0 to 1.0.
+pub fn token_overlap(a: &[String], b: &[String]) -> f64 {
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 0.0;
+    }
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for token in a {
+        *counts.entry(token).or_default() += 1;
+    }
+    let mut shared = 0;
+    for token in b {
+        if let Some(count) = counts.get_mut(token.as_str()).filter(|count| **count > 0) {
+            *count -= 1;
+            shared += 1;
+        }
+    }
+    shared as f64 / longest as f64
+}
+
+/// Whether two recordings are close enough in length to be the same
+/// dictation: within 10% or 2 seconds. Unknown durations (0) always match.
+pub fn similar_duration(a: f64, b: f64) -> bool {
+    if a <= 0.0 || b <= 0.0 {
+        return true;
+    }
+    (a - b).abs() <= (a.max(b) * 0.1).max(2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_ignores_case_punctuation_and_spacing() {
+        assert_eq!(
+            content_hash("Pain improved.  Continue physio!"),
+            content_hash("pain improved continue\nphysio")
+        );
+        assert_ne!(
+            content_hash("Pain improved."),
+            content_hash("Pain not improved.")
+        );
+    }
+
+    #[test]
+    fn overlap_counts_repeated_tokens_once_each() {
+        let a = tokens("the knee the knee");
+        let b = tokens("the knee");
+        assert_eq!(token_overlap(&a, &b), 0.5);
+        assert_eq!(token_overlap(&a, &a), 1.0);
+        assert_eq!(token_overlap(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn one_changed_word_in_twenty_is_a_duplicate() {
+        let original = "one two three four five six seven eight nine ten \
+                        eleven twelve thirteen fourteen fifteen sixteen seventeen eighteen nineteen twenty";
+        let retry = original.replacen("seven", "heaven", 1);
+        assert!(token_overlap(&tokens(original), &tokens(&retry)) > FUZZY_DUPLICATE_THRESHOLD);
+    }
+
+    #[test]
+    fn durations_match_within_tolerance() {
+        assert!(similar_duration(30.0, 31.5));
+        assert!(similar_duration(100.0, 109.0));
+        assert!(!similar_duration(30.0, 40.0));
+        assert!(similar_duration(0.0, 40.0));
+    }
+}
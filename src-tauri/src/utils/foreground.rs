@@ -0,0 +1,326 @@
+//! The foreground application: finding it, bringing it back, and sending it
+//! a paste keystroke or typed text.
+//!
+//! - macOS: the frontmost app from `NSWorkspace`, identified by pid; keys are
+//!   synthesized with `CGEventPost` (text via
+//!   `CGEventKeyboardSetUnicodeString`), which needs Accessibility access.
+//! - Windows: the foreground window handle; keys go through `SendInput`
+//!   (text as `KEYEVENTF_UNICODE`).
+//! - Linux: not supported, so no app is ever identified.
+//!
+//! Our own windows are never reported as the foreground app.
+
+/// Whether this platform can identify and paste into other applications.
+pub const SUPPORTED: bool = cfg!(any(target_os = "macos", target_os = "windows"));
+
+/// An application that had focus, as identified by the OS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+pub struct ForegroundApp {
+    #[cfg(target_os = "macos")]
+    pid: i32,
+    /// `HWND` as an integer, so the value can be held across threads
+    #[cfg(target_os = "windows")]
+    hwnd: isize,
+    /// Owner of `hwnd` when it was recorded, to detect a reused handle
+    #[cfg(target_os = "windows")]
+    pid: u32,
+}
+
+/// A key to synthesize when typing text out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keystroke {
+    /// A character, sent as Unicode rather than a key of the current layout
+    Char(char),
+    Return,
+    Tab,
+}
+
+/// The application that has focus now, unless it's this one.
+#[cfg(target_os = "macos")]
+pub fn frontmost_app() -> Option<ForegroundApp> {
+    use objc2_app_kit::NSWorkspace;
+
+    // SAFETY: reading the shared workspace's frontmost application
+    let pid = unsafe {
+        NSWorkspace::sharedWorkspace()
+            .frontmostApplication()?
+            .processIdentifier()
+    };
+    (pid > 0 && pid as u32 != std::process::id()).then_some(ForegroundApp { pid })
+}
+
+/// Whether the application is still running.
+#[cfg(target_os = "macos")]
+pub fn is_running(app: &ForegroundApp) -> bool {
+    use objc2_app_kit::NSRunningApplication;
+
+    // SAFETY: looking up a running application by pid
+    unsafe {
+        NSRunningApplication::runningApplicationWithProcessIdentifier(app.pid)
+            .is_some_and(|running| !running.isTerminated())
+    }
+}
+
+/// Brings the application to the front.
+#[cfg(target_os = "macos")]
+pub fn activate(app: &ForegroundApp) -> Result<(), String> {
+    use objc2_app_kit::{NSApplicationActivationOptions, NSRunningApplication};
+
+    // SAFETY: looking up and activating a running application by pid
+    let activated = unsafe {
+        NSRunningApplication::runningApplicationWithProcessIdentifier(app.pid)
+            .is_some_and(|running| running.activateWithOptions(NSApplicationActivationOptions(0)))
+    };
+    if activated {
+        Ok(())
+    } else {
+        Err("The application could not be activated".into())
+    }
+}
+
+/// CoreGraphics keyboard event functions.
+#[cfg(target_os = "macos")]
+mod core_graphics {
+    use std::ffi::c_void;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        pub fn CGEventSourceCreate(state_id: i32) -> *mut c_void;
+        pub fn CGEventCreateKeyboardEvent(
+            source: *mut c_void,
+            key: u16,
+            key_down: bool,
+        ) -> *mut c_void;
+        pub fn CGEventSetFlags(event: *mut c_void, flags: u64);
+        pub fn CGEventKeyboardSetUnicodeString(
+            event: *mut c_void,
+            length: usize,
+            string: *const u16,
+        );
+        pub fn CGEventPost(tap: u32, event: *mut c_void);
+    }
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        pub fn CFRelease(object: *const c_void);
+    }
+
+    pub const HID_SYSTEM_STATE: i32 = 1;
+    pub const HID_EVENT_TAP: u32 = 0;
+    pub const FLAG_MASK_COMMAND: u64 = 0x0010_0000;
+    pub const KEY_V: u16 = 0x09;
+    pub const KEY_RETURN: u16 = 0x24;
+    pub const KEY_TAB: u16 = 0x30;
+}
+
+/// Posts a key press (down and up) with `flags`. With `unicode`, the event
+/// types that text instead of the key's own character.
+#[cfg(target_os = "macos")]
+fn post_key(key_code: u16, flags: u64, unicode: Option<&[u16]>) -> Result<(), String> {
+    use core_graphics::*;
+
+    // SAFETY: every created object is checked for null and released once
+    unsafe {
+        let source = CGEventSourceCreate(HID_SYSTEM_STATE);
+        if source.is_null() {
+            return Err("Failed to create keyboard event source".into());
+        }
+        for key_down in [true, false] {
+            let event = CGEventCreateKeyboardEvent(source, key_code, key_down);
+            if event.is_null() {
+                CFRelease(source);
+                return Err("Failed to create keyboard event".into());
+            }
+            CGEventSetFlags(event, flags);
+            if let Some(units) = unicode {
+                CGEventKeyboardSetUnicodeString(event, units.len(), units.as_ptr());
+            }
+            CGEventPost(HID_EVENT_TAP, event);
+            CFRelease(event);
+        }
+        CFRelease(source);
+    }
+    Ok(())
+}
+
+/// Sends Cmd+V to the frontmost application. Uses the key position of "V" on
+/// an ANSI keyboard, like the system's own shortcut handling.
+#[cfg(target_os = "macos")]
+pub fn send_paste() -> Result<(), String> {
+    post_key(core_graphics::KEY_V, core_graphics::FLAG_MASK_COMMAND, None)
+}
+
+/// Types one keystroke into the frontmost application.
+#[cfg(target_os = "macos")]
+pub fn send_keystroke(keystroke: Keystroke) -> Result<(), String> {
+    match keystroke {
+        Keystroke::Char(c) => {
+            let mut units = [0; 2];
+            post_key(0, 0, Some(c.encode_utf16(&mut units)))
+        }
+        Keystroke::Return => post_key(core_graphics::KEY_RETURN, 0, None),
+        Keystroke::Tab => post_key(core_graphics::KEY_TAB, 0, None),
+    }
+}
+
+/// The application that has focus now, unless it's this one.
+#[cfg(target_os = "windows")]
+pub fn frontmost_app() -> Option<ForegroundApp> {
+    use windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
+
+    // SAFETY: trivially safe Win32 call
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_invalid() {
+        return None;
+    }
+    let pid = window_process_id(hwnd);
+    (pid != 0 && pid != std::process::id()).then_some(ForegroundApp {
+        hwnd: hwnd.0 as isize,
+        pid,
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn window_process_id(hwnd: windows::Win32::Foundation::HWND) -> u32 {
+    use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
+
+    let mut pid = 0;
+    // SAFETY: writes the owning process id into `pid`
+    unsafe { GetWindowThreadProcessId(hwnd, Some(&mut pid)) };
+    pid
+}
+
+/// Whether the window still exists and belongs to the same process.
+#[cfg(target_os = "windows")]
+pub fn is_running(app: &ForegroundApp) -> bool {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::IsWindow;
+
+    let hwnd = HWND(app.hwnd as *mut _);
+    // SAFETY: IsWindow accepts any handle value
+    unsafe { IsWindow(Some(hwnd)) }.as_bool() && window_process_id(hwnd) == app.pid
+}
+
+/// Brings the window to the front.
+#[cfg(target_os = "windows")]
+pub fn activate(app: &ForegroundApp) -> Result<(), String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::SetForegroundWindow;
+
+    // SAFETY: the handle was checked with is_running; a stale one just fails
+    if unsafe { SetForegroundWindow(HWND(app.hwnd as *mut _)) }.as_bool() {
+        Ok(())
+    } else {
+        Err("Windows refused to bring the window to the front".into())
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn key_input(
+    vk: windows::Win32::UI::Input::KeyboardAndMouse::VIRTUAL_KEY,
+    scan: u16,
+    flags: windows::Win32::UI::Input::KeyboardAndMouse::KEYBD_EVENT_FLAGS,
+) -> windows::Win32::UI::Input::KeyboardAndMouse::INPUT {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT};
+
+    INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: vk,
+                wScan: scan,
+                dwFlags: flags,
+                ..Default::default()
+            },
+        },
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn send_inputs(
+    inputs: &[windows::Win32::UI::Input::KeyboardAndMouse::INPUT],
+) -> Result<(), String> {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{SendInput, INPUT};
+
+    // SAFETY: inputs is a valid slice of keyboard INPUT structs
+    let sent = unsafe { SendInput(inputs, std::mem::size_of::<INPUT>() as i32) };
+    if sent as usize == inputs.len() {
+        Ok(())
+    } else {
+        Err("The keystroke was blocked".into())
+    }
+}
+
+/// Sends Ctrl+V to the foreground window.
+#[cfg(target_os = "windows")]
+pub fn send_paste() -> Result<(), String> {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP, VIRTUAL_KEY, VK_CONTROL,
+    };
+
+    const VK_V: VIRTUAL_KEY = VIRTUAL_KEY(0x56);
+    send_inputs(&[
+        key_input(VK_CONTROL, 0, KEYBD_EVENT_FLAGS(0)),
+        key_input(VK_V, 0, KEYBD_EVENT_FLAGS(0)),
+        key_input(VK_V, 0, KEYEVENTF_KEYUP),
+        key_input(VK_CONTROL, 0, KEYEVENTF_KEYUP),
+    ])
+}
+
+/// Types one keystroke into the foreground window. Characters are sent as
+/// Unicode input, so the keyboard layout doesn't matter.
+#[cfg(target_os = "windows")]
+pub fn send_keystroke(keystroke: Keystroke) -> Result<(), String> {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP, KEYEVENTF_UNICODE, VIRTUAL_KEY, VK_RETURN, VK_TAB,
+    };
+
+    let vk = match keystroke {
+        Keystroke::Char(c) => {
+            let mut units = [0; 2];
+            let inputs: Vec<_> = c
+                .encode_utf16(&mut units)
+                .iter()
+                .flat_map(|&unit| {
+                    [
+                        key_input(VIRTUAL_KEY(0), unit, KEYEVENTF_UNICODE),
+                        key_input(VIRTUAL_KEY(0), unit, KEYEVENTF_UNICODE | KEYEVENTF_KEYUP),
+                    ]
+                })
+                .collect();
+            return send_inputs(&inputs);
+        }
+        Keystroke::Return => VK_RETURN,
+        Keystroke::Tab => VK_TAB,
+    };
+    send_inputs(&[
+        key_input(vk, 0, KEYBD_EVENT_FLAGS(0)),
+        key_input(vk, 0, KEYEVENTF_KEYUP),
+    ])
+}
+
+/// The application that has focus now (never identified on this platform).
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn frontmost_app() -> Option<ForegroundApp> {
+    None
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn is_running(_app: &ForegroundApp) -> bool {
+    false
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn activate(_app: &ForegroundApp) -> Result<(), String> {
+    Err("Pasting into other applications is not supported on this platform".into())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn send_paste() -> Result<(), String> {
+    Err("Pasting into other applications is not supported on this platform".into())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn send_keystroke(_keystroke: Keystroke) -> Result<(), String> {
+    Err("Typing into other applications is not supported on this platform".into())
+}
@@ -0,0 +1,195 @@
+//! Formatting profiles applied to transcripts before they're copied or
+//! pasted.
+//!
+//! Whisper returns text in timed segments. Joined with spaces they give the
+//! verbatim transcript; the gaps between them give `paragraphs-on-pause` its
+//! paragraph breaks. Plain text has no timing, so that profile leaves it as is.
+
+use crate::types::{FormattingOptions, FormattingProfile, TranscriptContent, TranscriptSegment};
+
+/// Segment texts, trimmed and joined with single spaces. Empty segments are
+/// skipped.
+pub fn join_segments(segments: &[TranscriptSegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| segment.text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// All whitespace, including line breaks, collapsed to single spaces.
+fn single_paragraph(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn capitalize_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Starts a new paragraph wherever the silence between two spoken segments is
+/// longer than `pause_secs`. The gap is measured from the end of the last
+/// non-empty segment, so empty segments don't hide a pause; overlapping
+/// segments (negative gaps) never split. Paragraphs start with a capital.
+fn paragraphs_on_pause(segments: &[TranscriptSegment], pause_secs: f64) -> String {
+    let mut paragraphs: Vec<Vec<&str>> = Vec::new();
+    let mut last_end: Option<f64> = None;
+    for segment in segments {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        match (paragraphs.last_mut(), last_end) {
+            (Some(paragraph), Some(end)) if segment.start_secs - end <= pause_secs => {
+                paragraph.push(text)
+            }
+            _ => paragraphs.push(vec![text]),
+        }
+        last_end = Some(segment.end_secs);
+    }
+    paragraphs
+        .iter()
+        .map(|paragraph| capitalize_first(&paragraph.join(" ")))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Splits after `.`, `!` or `?` followed by whitespace, and at line breaks.
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let next_is_space = chars.peek().is_none_or(|&(_, next)| next.is_whitespace());
+        let end = if c == '\n' {
+            Some(index)
+        } else if matches!(c, '.' | '!' | '?') && next_is_space {
+            Some(index + c.len_utf8())
+        } else {
+            None
+        };
+        if let Some(end) = end {
+            sentences.push(text[start..end].trim());
+            start = end;
+        }
+    }
+    sentences.push(text[start..].trim());
+    sentences.retain(|sentence| !sentence.is_empty());
+    sentences
+}
+
+fn bulleted_sentences(text: &str) -> String {
+    sentences(text)
+        .iter()
+        .map(|sentence| format!("- {}", single_paragraph(sentence)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Applies `options` to plain text.
+pub fn format_text(text: &str, options: &FormattingOptions) -> String {
+    match options.profile {
+        FormattingProfile::Verbatim | FormattingProfile::ParagraphsOnPause => text.to_string(),
+        FormattingProfile::SingleParagraph => single_paragraph(text),
+        FormattingProfile::BulletedSentences => bulleted_sentences(text),
+    }
+}
+
+/// Applies `options` to timed segments.
+pub fn format_segments(segments: &[TranscriptSegment], options: &FormattingOptions) -> String {
+    match options.profile {
+        FormattingProfile::ParagraphsOnPause => {
+            paragraphs_on_pause(segments, options.paragraph_pause_secs)
+        }
+        _ => format_text(&join_segments(segments), options),
+    }
+}
+
+/// Applies `options` to a transcript.
+pub fn format(content: &TranscriptContent, options: &FormattingOptions) -> String {
+    match content {
+        TranscriptContent::Text(text) => format_text(text, options),
+        TranscriptContent::Segments(segments) => format_segments(segments, options),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_secs: f64, end_secs: f64, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            start_secs,
+            end_secs,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn paragraphs_split_only_on_gaps_over_the_pause() {
+        let segments = [
+            // A late start is not a paragraph break
+            segment(4.0, 6.0, " Patient seen today."),
+            // Overlaps the previous segment
+            segment(5.8, 8.0, "knee pain improved."),
+            // Gap of exactly 1.5s does not split
+            segment(9.5, 11.0, "Continue physio."),
+            segment(14.2, 16.0, "plan:"),
+            // Empty segment inside a pause doesn't reset the gap
+            segment(16.1, 17.0, "  "),
+            segment(18.0, 19.0, "Review in two weeks."),
+            segment(19.1, 20.0, "Thanks."),
+        ];
+        assert_eq!(
+            paragraphs_on_pause(&segments, 1.5),
+            "Patient seen today. knee pain improved. Continue physio.\n\n\
+             Plan:\n\n\
+             Review in two weeks. Thanks."
+        );
+    }
+
+    #[test]
+    fn paragraphs_of_nothing_are_empty() {
+        assert_eq!(paragraphs_on_pause(&[], 1.5), "");
+        assert_eq!(paragraphs_on_pause(&[segment(0.0, 1.0, " ")], 1.5), "");
+    }
+
+    #[test]
+    fn single_paragraph_collapses_line_breaks() {
+        let options = FormattingOptions {
+            profile: FormattingProfile::SingleParagraph,
+            ..Default::default()
+        };
+        let content = TranscriptContent::Text("Line one.\n\n  Line\ttwo. ".to_string());
+        assert_eq!(format(&content, &options), "Line one. Line two.");
+    }
+
+    #[test]
+    fn bullets_one_sentence_each() {
+        let options = FormattingOptions {
+            profile: FormattingProfile::BulletedSentences,
+            ..Default::default()
+        };
+        let content = TranscriptContent::Text(
+            "Order labs. Is BP 140/90? Dose 2.5 mg!\nFollow up...later".to_string(),
+        );
+        assert_eq!(
+            format(&content, &options),
+            "- Order labs.\n- Is BP 140/90?\n- Dose 2.5 mg!\n- Follow up...later"
+        );
+    }
+
+    #[test]
+    fn plain_text_is_unchanged_by_pause_profile() {
+        let options = FormattingOptions {
+            profile: FormattingProfile::ParagraphsOnPause,
+            ..Default::default()
+        };
+        let content = TranscriptContent::Text("no timing here".to_string());
+        assert_eq!(format(&content, &options), "no timing here");
+    }
+}
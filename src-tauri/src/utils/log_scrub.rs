@@ -0,0 +1,133 @@
+//! Scrubbing of log files before they leave the machine in a support bundle.
+//!
+//! Logs shouldn't hold PHI to begin with, but they do hold paths, and a path
+//! under a home folder names the user. Scrubbing:
+//!
+//! - replaces the account name in home-folder paths, and the OS account name
+//!   wherever else it appears, with `<user>`
+//! - redacts whole lines a call site marked with `PHI_MARKER`
+//! - drops lines forwarded from the webview console, which are noisy and can
+//!   echo what's on screen
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Marks a log line that may carry PHI; it's never exported
+pub const PHI_MARKER: &str = "[phi]";
+
+/// What replaces an account name
+const USER_PLACEHOLDER: &str = "<user>";
+
+/// What replaces a redacted line
+const REDACTED_LINE: &str = "[line redacted]";
+
+/// Account names shorter than this aren't replaced outside paths; they'd
+/// match ordinary words
+const MIN_ACCOUNT_NAME_LEN: usize = 3;
+
+/// The account folder of a macOS, Linux or Windows home path (group 2)
+static HOME_PATH: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)(/Users/|/home/|[a-z]:\\Users\\)([^/\\\s"':<>]+)"#)
+        .expect("home path pattern is valid")
+});
+
+/// What scrubbing changed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ScrubCounts {
+    /// Account names replaced
+    pub account_names: u32,
+    pub redacted_lines: u32,
+    /// Webview console lines left out
+    pub dropped_lines: u32,
+}
+
+/// Whether the log plugin forwarded `line` from the webview console. Its
+/// lines read `[date][time][target][level] message`, with a `webview` target.
+fn is_webview_line(line: &str) -> bool {
+    line.contains("][webview")
+}
+
+/// Scrubs `text`, a log file, adding what changed to `counts`. `account` is
+/// the OS account name, replaced wherever it appears.
+pub fn scrub(text: &str, account: Option<&str>, counts: &mut ScrubCounts) -> String {
+    let account = account
+        .filter(|name| name.len() >= MIN_ACCOUNT_NAME_LEN)
+        .and_then(|name| Regex::new(&format!(r"\b{}\b", regex::escape(name))).ok());
+
+    let mut scrubbed = String::with_capacity(text.len());
+    for line in text.lines() {
+        if is_webview_line(line) {
+            counts.dropped_lines += 1;
+            continue;
+        }
+        if line.contains(PHI_MARKER) {
+            counts.redacted_lines += 1;
+            scrubbed.push_str(REDACTED_LINE);
+            scrubbed.push('\n');
+            continue;
+        }
+        let mut replaced = 0;
+        let mut line = HOME_PATH
+            .replace_all(line, |caps: &regex::Captures| {
+                replaced += 1;
+                format!("{}{USER_PLACEHOLDER}", &caps[1])
+            })
+            .into_owned();
+        if let Some(account) = &account {
+            replaced += account.find_iter(&line).count() as u32;
+            line = account.replace_all(&line, USER_PLACEHOLDER).into_owned();
+        }
+        counts.account_names += replaced;
+        scrubbed.push_str(&line);
+        scrubbed.push('\n');
+    }
+    scrubbed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_names_in_home_paths_are_replaced() {
+        let mut counts = ScrubCounts::default();
+        let scrubbed = scrub(
+            "[2026-10-15][09:00:00][app][INFO] Saved /Users/dana.k/Desktop/a.zip and C:\\Users\\Dana\\x.wav\n",
+            None,
+            &mut counts,
+        );
+        assert_eq!(
+            scrubbed,
+            "[2026-10-15][09:00:00][app][INFO] Saved /Users/<user>/Desktop/a.zip and C:\\Users\\<user>\\x.wav\n"
+        );
+        assert_eq!(counts.account_names, 2);
+    }
+
+    #[test]
+    fn the_account_name_is_replaced_anywhere() {
+        let mut counts = ScrubCounts::default();
+        let scrubbed = scrub(
+            "Keychain entry for danak found\nTook 12ms\n",
+            Some("danak"),
+            &mut counts,
+        );
+        assert_eq!(scrubbed, "Keychain entry for <user> found\nTook 12ms\n");
+        assert_eq!(counts.account_names, 1);
+    }
+
+    #[test]
+    fn marked_lines_are_redacted_and_webview_lines_dropped() {
+        let mut counts = ScrubCounts::default();
+        let scrubbed = scrub(
+            "[d][t][app][INFO] ok\n\
+             [d][t][app][DEBUG] [phi] title was X\n\
+             [d][t][webview:info@http://localhost:1420/src/App.tsx:12:3][INFO] render\n",
+            None,
+            &mut counts,
+        );
+        assert_eq!(scrubbed, "[d][t][app][INFO] ok\n[line redacted]\n");
+        assert_eq!(counts.redacted_lines, 1);
+        assert_eq!(counts.dropped_lines, 1);
+    }
+}
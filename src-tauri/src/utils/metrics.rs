@@ -0,0 +1,351 @@
+//! Command timing, so "the app feels slow" comes with numbers.
+//!
+//! Every registered command carries `#[metrics::timed]`, which records how
+//! long it took and the error variant when it failed. Commands returning
+//! transcript text use `#[metrics::timed(response_size)]`, which also keeps a
+//! coarse size of the response, read off `ResponseSize` rather than
+//! serialized. Arguments and content are never kept. Samples live in a
+//! bounded in-memory ring (and debug logs) and are summarized per command by
+//! `summarize`. With the `command_metrics_enabled` preference off, commands
+//! just run.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::types::{
+    CommandMetrics, DeidentifiedText, PayloadSize, SavedTranscript, Transcript, TranscriptDraft,
+    TranscriptRevision,
+};
+
+pub use tauri_app_macros::timed;
+
+/// Samples kept; the oldest are dropped first
+const MAX_SAMPLES: usize = 1000;
+
+/// Whether commands are timed (the `command_metrics_enabled` preference)
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Most recent samples, oldest first
+static SAMPLES: Mutex<VecDeque<Sample>> = Mutex::new(VecDeque::new());
+
+struct Sample {
+    command: &'static str,
+    duration: Duration,
+    /// Error variant (the `type` tag), None on success
+    error: Option<String>,
+    /// None unless the command is timed with `response_size`
+    size: Option<PayloadSize>,
+}
+
+/// Switches timing on or off. Switching off drops the samples kept so far.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+    if !enabled {
+        SAMPLES.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+/// Rough size in bytes of a response as sent to the frontend: the text it
+/// carries, leaving out fixed-size fields. Cheap enough to run on every call.
+pub trait ResponseSize {
+    fn response_len(&self) -> usize;
+}
+
+impl ResponseSize for String {
+    fn response_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: ResponseSize> ResponseSize for Vec<T> {
+    fn response_len(&self) -> usize {
+        self.iter().map(ResponseSize::response_len).sum()
+    }
+}
+
+impl ResponseSize for Transcript {
+    fn response_len(&self) -> usize {
+        self.title.len() + self.text.len()
+    }
+}
+
+impl ResponseSize for SavedTranscript {
+    fn response_len(&self) -> usize {
+        self.transcript.response_len()
+    }
+}
+
+impl ResponseSize for TranscriptRevision {
+    fn response_len(&self) -> usize {
+        self.title.len() + self.text.len()
+    }
+}
+
+impl ResponseSize for TranscriptDraft {
+    fn response_len(&self) -> usize {
+        self.content.len()
+    }
+}
+
+impl ResponseSize for DeidentifiedText {
+    fn response_len(&self) -> usize {
+        self.text.len()
+    }
+}
+
+fn payload_size(bytes: usize) -> PayloadSize {
+    match bytes {
+        0..1_024 => PayloadSize::Tiny,
+        1_024..65_536 => PayloadSize::Small,
+        65_536..1_048_576 => PayloadSize::Medium,
+        _ => PayloadSize::Large,
+    }
+}
+
+/// The variant of a typed error (its `type` tag); plain string errors are
+/// reported as "Error" since their text may carry content.
+fn error_variant<E: Serialize>(error: &E) -> String {
+    serde_json::to_value(error)
+        .ok()
+        .and_then(|value| value.get("type")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| "Error".to_string())
+}
+
+/// What a timed command returned, as far as its sample goes. The macro
+/// picks the constructor from the return type and `response_size`.
+pub struct Outcome {
+    error: Option<String>,
+    size: Option<PayloadSize>,
+}
+
+impl Outcome {
+    pub fn of_value<T>(_value: &T) -> Self {
+        Self {
+            error: None,
+            size: None,
+        }
+    }
+
+    pub fn of_sized_value<T: ResponseSize>(value: &T) -> Self {
+        Self {
+            error: None,
+            size: Some(payload_size(value.response_len())),
+        }
+    }
+
+    pub fn of_result<T, E: Serialize>(result: &Result<T, E>) -> Self {
+        Self {
+            error: result.as_ref().err().map(error_variant),
+            size: None,
+        }
+    }
+
+    pub fn of_sized_result<T: ResponseSize, E: Serialize>(result: &Result<T, E>) -> Self {
+        match result {
+            Ok(value) => Self::of_sized_value(value),
+            Err(_) => Self::of_result(result),
+        }
+    }
+}
+
+fn record(command: &'static str, duration: Duration, outcome: Outcome) {
+    let sample = Sample {
+        command,
+        duration,
+        error: outcome.error,
+        size: outcome.size,
+    };
+    log::debug!(
+        "Command {} took {}ms ({}, {:?} response)",
+        sample.command,
+        sample.duration.as_millis(),
+        sample.error.as_deref().unwrap_or("ok"),
+        sample.size
+    );
+    let mut samples = SAMPLES.lock().unwrap_or_else(|e| e.into_inner());
+    if samples.len() == MAX_SAMPLES {
+        samples.pop_front();
+    }
+    samples.push_back(sample);
+}
+
+/// Runs the body of the sync command `command`, timing it when metrics are
+/// on. Called by `#[metrics::timed]`.
+pub fn time<R>(
+    command: &'static str,
+    work: impl FnOnce() -> R,
+    outcome: impl FnOnce(&R) -> Outcome,
+) -> R {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return work();
+    }
+    let started = Instant::now();
+    let result = work();
+    record(command, started.elapsed(), outcome(&result));
+    result
+}
+
+/// `time` for async commands.
+pub async fn time_async<R>(
+    command: &'static str,
+    work: impl Future<Output = R>,
+    outcome: impl FnOnce(&R) -> Outcome,
+) -> R {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return work.await;
+    }
+    let started = Instant::now();
+    let result = work.await;
+    record(command, started.elapsed(), outcome(&result));
+    result
+}
+
+/// Nearest-rank percentile of `sorted` (ascending, not empty), in ms.
+fn percentile_ms(sorted: &[Duration], percentile: f64) -> f64 {
+    let rank = (percentile * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1].as_secs_f64() * 1000.0
+}
+
+fn summarize_samples<'a>(samples: impl Iterator<Item = &'a Sample>) -> Vec<CommandMetrics> {
+    let mut by_command: BTreeMap<&'static str, Vec<&Sample>> = BTreeMap::new();
+    for sample in samples {
+        by_command.entry(sample.command).or_default().push(sample);
+    }
+    by_command
+        .into_iter()
+        .map(|(command, samples)| {
+            let mut durations: Vec<Duration> = samples.iter().map(|s| s.duration).collect();
+            durations.sort();
+            let errors = samples.iter().filter(|s| s.error.is_some()).count();
+            CommandMetrics {
+                command: command.to_string(),
+                count: samples.len() as u32,
+                p50_ms: percentile_ms(&durations, 0.5),
+                p95_ms: percentile_ms(&durations, 0.95),
+                error_rate: errors as f64 / samples.len() as f64,
+                last_error: samples.iter().rev().find_map(|s| s.error.clone()),
+                largest_response: samples.iter().filter_map(|s| s.size).max(),
+            }
+        })
+        .collect()
+}
+
+/// Per-command aggregates over the samples kept, by command name.
+pub fn summarize() -> Vec<CommandMetrics> {
+    let samples = SAMPLES.lock().unwrap_or_else(|e| e.into_inner());
+    summarize_samples(samples.iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(command: &'static str, ms: u64, error: Option<&str>) -> Sample {
+        Sample {
+            command,
+            duration: Duration::from_millis(ms),
+            error: error.map(str::to_string),
+            size: None,
+        }
+    }
+
+    #[test]
+    fn aggregates_per_command() {
+        let mut samples: Vec<Sample> = (1..=20)
+            .map(|ms| sample("list_transcripts", ms * 10, None))
+            .collect();
+        samples.push(sample("stop_recording", 40, Some("NotRecording")));
+        samples.push(sample("stop_recording", 60, None));
+
+        let metrics = summarize_samples(samples.iter());
+        assert_eq!(metrics.len(), 2);
+        let list = &metrics[0];
+        assert_eq!(list.command, "list_transcripts");
+        assert_eq!(list.count, 20);
+        assert_eq!(list.p50_ms, 100.0);
+        assert_eq!(list.p95_ms, 190.0);
+        assert_eq!(list.error_rate, 0.0);
+        let stop = &metrics[1];
+        assert_eq!(stop.error_rate, 0.5);
+        assert_eq!(stop.last_error.as_deref(), Some("NotRecording"));
+    }
+
+    #[test]
+    fn errors_report_their_variant_only() {
+        let typed = crate::types::AppError::ModelNotDownloaded {
+            model_id: "base".into(),
+        };
+        assert_eq!(error_variant(&typed), "ModelNotDownloaded");
+        assert_eq!(
+            error_variant(&"Failed to open /Users/dana".to_string()),
+            "Error"
+        );
+    }
+
+    #[test]
+    fn response_sizes_are_bucketed_when_asked_for() {
+        let size = |text: String| Outcome::of_sized_value(&vec![text]).size;
+        assert_eq!(size(String::new()), Some(PayloadSize::Tiny));
+        assert_eq!(size("x".repeat(2_000)), Some(PayloadSize::Small));
+        assert_eq!(size("x".repeat(2_000_000)), Some(PayloadSize::Large));
+        assert_eq!(Outcome::of_value(&"x".repeat(2_000)).size, None);
+        let failed: Result<String, String> = Err("x".repeat(2_000));
+        assert_eq!(Outcome::of_sized_result(&failed).size, None);
+    }
+
+    #[timed]
+    fn timed_sample_command(fail: bool) -> Result<u32, crate::types::AppError> {
+        if fail {
+            return Err(crate::types::AppError::NotRecording);
+        }
+        Ok(1)
+    }
+
+    #[test]
+    fn timed_commands_are_recorded() {
+        assert_eq!(timed_sample_command(false), Ok(1));
+        assert!(timed_sample_command(true).is_err());
+        let metrics = summarize();
+        let timed = metrics
+            .iter()
+            .find(|m| m.command == "timed_sample_command")
+            .unwrap();
+        assert_eq!(timed.count, 2);
+        assert_eq!(timed.last_error.as_deref(), Some("NotRecording"));
+        assert_eq!(timed.largest_response, None);
+    }
+
+    /// Commands are timed by attribute, so a new command without one would
+    /// silently go missing from the diagnostics screen.
+    #[test]
+    fn every_command_is_timed() {
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut files = vec![src.join("bindings.rs")];
+        for entry in std::fs::read_dir(src.join("commands")).unwrap() {
+            files.push(entry.unwrap().path());
+        }
+        for path in files {
+            let source = std::fs::read_to_string(&path).unwrap();
+            let mut lines = source.lines().map(str::trim);
+            while let Some(line) = lines.next() {
+                if !line.starts_with("#[tauri::command") {
+                    continue;
+                }
+                let attributes: Vec<&str> = lines
+                    .by_ref()
+                    .take_while(|line| line.starts_with("#["))
+                    .collect();
+                assert!(
+                    attributes.iter().any(|a| a.starts_with("#[metrics::timed")),
+                    "{} has a command without #[metrics::timed]",
+                    path.display()
+                );
+            }
+        }
+    }
+}
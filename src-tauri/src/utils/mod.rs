@@ -1,3 +1,25 @@
 //! Utility modules for cross-platform support and common operations.
 
+pub mod audio;
+pub mod backup_exclusion;
+pub mod biometrics;
+pub mod capture;
+pub mod deidentify;
+pub mod diagnostics;
+pub mod disk;
+pub mod dnd;
+pub mod duplicates;
+pub mod envelope;
+pub mod foreground;
+pub mod formatting;
+pub mod log_scrub;
+pub mod metrics;
+pub mod model_integrity;
+pub mod pasteboard;
+pub mod paths;
+pub mod pdf;
+pub mod permissions;
 pub mod platform;
+pub mod rich_text;
+pub mod secrets;
+pub mod secure_delete;
@@ -0,0 +1,200 @@
+//! Integrity of downloaded model files.
+//!
+//! After a download the model's SHA-256 is recorded, with its size and
+//! modification time, in `verified-models.json` next to the models. Before a
+//! model is loaded, a changed size or mtime triggers a re-hash; a hash that
+//! no longer matches means bit rot or tampering, and the model isn't loaded.
+//! Loading an unchanged file costs one `stat`.
+//!
+//! Models downloaded before hashes were recorded are trusted and recorded on
+//! their first check.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Record of verified models, in the models directory
+const MANIFEST_FILENAME: &str = "verified-models.json";
+
+/// Serializes read-modify-write of the manifest
+static MANIFEST_LOCK: Mutex<()> = Mutex::new(());
+
+/// What was verified about one model file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct VerifiedModel {
+    sha256: String,
+    size: u64,
+    modified_ms: u64,
+}
+
+/// Result of checking a model against its record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// Matches the recorded hash (or was recorded just now)
+    Intact,
+    /// The hash differs from the recorded one
+    Corrupt,
+}
+
+fn manifest_path(model_path: &Path) -> PathBuf {
+    model_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(MANIFEST_FILENAME)
+}
+
+fn manifest_key(model_path: &Path) -> String {
+    model_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn read_manifest(path: &Path) -> HashMap<String, VerifiedModel> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn write_manifest(path: &Path, manifest: &HashMap<String, VerifiedModel>) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, json)
+        .and_then(|()| std::fs::rename(&tmp_path, path))
+        .map_err(|e| format!("Failed to save model checksums: {e}"))
+}
+
+/// Size and mtime (ms since epoch) of a file.
+fn stat(path: &Path) -> Result<(u64, u64), String> {
+    let metadata =
+        std::fs::metadata(path).map_err(|e| format!("Failed to read model file: {e}"))?;
+    let modified_ms = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |since| since.as_millis() as u64);
+    Ok((metadata.len(), modified_ms))
+}
+
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file =
+        std::fs::File::open(path).map_err(|e| format!("Failed to open model file: {e}"))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read model file: {e}"))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+/// Hashes `model_path` and compares with its record. With `rehash` false, an
+/// unchanged size and mtime is trusted without hashing. A missing record, or a
+/// changed mtime with the same content, is (re)recorded.
+fn verify(model_path: &Path, rehash: bool) -> Result<Verdict, String> {
+    let (size, modified_ms) = stat(model_path)?;
+    let manifest_path = manifest_path(model_path);
+    let key = manifest_key(model_path);
+
+    let recorded = {
+        let _guard = MANIFEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        read_manifest(&manifest_path).remove(&key)
+    };
+    if let Some(recorded) = &recorded {
+        if !rehash && recorded.size == size && recorded.modified_ms == modified_ms {
+            return Ok(Verdict::Intact);
+        }
+    }
+
+    // Hashing a model takes seconds; don't hold the lock meanwhile
+    let sha256 = sha256_file(model_path)?;
+    if recorded
+        .as_ref()
+        .is_some_and(|recorded| recorded.sha256 != sha256)
+    {
+        return Ok(Verdict::Corrupt);
+    }
+    if recorded.is_none() {
+        log::info!("Recording checksum of model {key} on first verification");
+    }
+    let _guard = MANIFEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut manifest = read_manifest(&manifest_path);
+    manifest.insert(
+        key,
+        VerifiedModel {
+            sha256,
+            size,
+            modified_ms,
+        },
+    );
+    write_manifest(&manifest_path, &manifest)?;
+    Ok(Verdict::Intact)
+}
+
+/// Records the hash of a freshly downloaded model, replacing any earlier record.
+pub fn record(model_path: &Path) -> Result<(), String> {
+    forget(model_path)?;
+    verify(model_path, true).map(|_| ())
+}
+
+/// Drops the record of a model, e.g. after deleting a corrupt file.
+pub fn forget(model_path: &Path) -> Result<(), String> {
+    let _guard = MANIFEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let manifest_path = manifest_path(model_path);
+    let mut manifest = read_manifest(&manifest_path);
+    if manifest.remove(&manifest_key(model_path)).is_none() {
+        return Ok(());
+    }
+    write_manifest(&manifest_path, &manifest)
+}
+
+/// Checks a model before it's loaded: re-hashes only when its size or mtime
+/// changed since it was verified. Blocks while hashing.
+pub fn check_before_load(model_path: &Path) -> Result<Verdict, String> {
+    verify(model_path, false)
+}
+
+/// Whether a model has a record matching its current size and mtime, i.e.
+/// would load without re-hashing. Doesn't hash.
+pub fn is_recorded(model_path: &Path) -> Result<bool, String> {
+    let (size, modified_ms) = stat(model_path)?;
+    let _guard = MANIFEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(read_manifest(&manifest_path(model_path))
+        .get(&manifest_key(model_path))
+        .is_some_and(|recorded| recorded.size == size && recorded.modified_ms == modified_ms))
+}
+
+/// Re-hashes a model and compares it with its record. Blocks.
+pub fn recheck(model_path: &Path) -> Result<Verdict, String> {
+    verify(model_path, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_sits_next_to_the_model() {
+        let model = Path::new("/data/models/ggml-large-v3-turbo-q5_0.bin");
+        assert_eq!(
+            manifest_path(model),
+            Path::new("/data/models/verified-models.json")
+        );
+        assert_eq!(manifest_key(model), "ggml-large-v3-turbo-q5_0.bin");
+    }
+}
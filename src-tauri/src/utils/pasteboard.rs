@@ -0,0 +1,128 @@
+//! Native clipboard writes marked as concealed and transient, so clipboard
+//! history tools that follow the conventions don't record them.
+//!
+//! - macOS: the text plus the empty `org.nspasteboard.ConcealedType` and
+//!   `org.nspasteboard.TransientType` markers (<http://nspasteboard.org>).
+//! - Windows: `CF_UNICODETEXT` plus `ExcludeClipboardContentFromMonitorProcessing`
+//!   and `CanIncludeInClipboardHistory` / `CanUploadToCloudClipboard` set to
+//!   0, which Win+V history and cloud sync honour.
+//! - Linux: no native path; callers write through the clipboard plugin.
+//!
+//! With rich text, an HTML flavor (`public.html` / `HTML Format`) is written
+//! alongside the plain text. Only writes go through here. Reads and clears
+//! still use the plugin; a clear empties every flavor at once.
+
+/// Whether this platform can write marked clipboard contents.
+pub const SUPPORTED: bool = cfg!(any(target_os = "macos", target_os = "windows"));
+
+/// Replaces the clipboard contents with `text` (and `html`, if given),
+/// marked as concealed and transient.
+#[cfg(target_os = "macos")]
+pub fn write_concealed(text: &str, html: Option<&str>) -> Result<(), String> {
+    use objc2_app_kit::{NSPasteboard, NSPasteboardTypeHTML, NSPasteboardTypeString};
+    use objc2_foundation::{ns_string, NSData, NSString};
+
+    // SAFETY: general pasteboard access; the type constant is a static NSString
+    unsafe {
+        let pasteboard = NSPasteboard::generalPasteboard();
+        pasteboard.clearContents();
+        if !pasteboard.setString_forType(&NSString::from_str(text), NSPasteboardTypeString) {
+            return Err("The pasteboard refused the text".into());
+        }
+        if let Some(html) = html {
+            if !pasteboard.setString_forType(&NSString::from_str(html), NSPasteboardTypeHTML) {
+                return Err("The pasteboard refused the HTML".into());
+            }
+        }
+        let marker = NSData::new();
+        for marker_type in [
+            ns_string!("org.nspasteboard.ConcealedType"),
+            ns_string!("org.nspasteboard.TransientType"),
+        ] {
+            pasteboard.setData_forType(Some(&marker), marker_type);
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the clipboard contents with `text` (and `html`, if given),
+/// excluded from clipboard history, cloud clipboard and clipboard monitors.
+#[cfg(target_os = "windows")]
+pub fn write_concealed(text: &str, html: Option<&str>) -> Result<(), String> {
+    use windows::core::w;
+    use windows::Win32::Foundation::{HANDLE, HGLOBAL};
+    use windows::Win32::System::DataExchange::{
+        CloseClipboard, EmptyClipboard, OpenClipboard, RegisterClipboardFormatW, SetClipboardData,
+    };
+    use windows::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+    use windows::Win32::System::Ole::CF_UNICODETEXT;
+
+    /// Copies `bytes` into movable global memory, which the clipboard takes
+    /// ownership of once `SetClipboardData` succeeds.
+    fn global_copy(bytes: &[u8]) -> Result<HGLOBAL, String> {
+        // SAFETY: the allocation is at least bytes.len() and locked while written
+        unsafe {
+            let memory = GlobalAlloc(GMEM_MOVEABLE, bytes.len().max(1))
+                .map_err(|e| format!("Failed to allocate clipboard memory: {e}"))?;
+            let target = GlobalLock(memory) as *mut u8;
+            if target.is_null() {
+                return Err("Failed to lock clipboard memory".into());
+            }
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), target, bytes.len());
+            let _ = GlobalUnlock(memory);
+            Ok(memory)
+        }
+    }
+
+    fn set(format: u32, bytes: &[u8]) -> Result<(), String> {
+        let memory = global_copy(bytes)?;
+        // SAFETY: the clipboard is open and owns `memory` on success
+        unsafe { SetClipboardData(format, Some(HANDLE(memory.0))) }
+            .map(|_| ())
+            .map_err(|e| format!("Failed to set clipboard data: {e}"))
+    }
+
+    /// Empties the open clipboard and fills it with the text and markers.
+    fn fill(text: &str, html: Option<&str>) -> Result<(), String> {
+        let text_bytes: Vec<u8> = text
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let disallowed = 0u32.to_le_bytes();
+
+        // SAFETY: the caller opened the clipboard
+        unsafe { EmptyClipboard() }.map_err(|e| format!("Failed to empty the clipboard: {e}"))?;
+        set(u32::from(CF_UNICODETEXT.0), &text_bytes)?;
+        if let Some(html) = html {
+            let mut payload = crate::utils::rich_text::cf_html(html).into_bytes();
+            payload.push(0);
+            // SAFETY: registering a named format has no preconditions
+            let html_format = unsafe { RegisterClipboardFormatW(w!("HTML Format")) };
+            set(html_format, &payload)?;
+        }
+        // SAFETY: registering a named format has no preconditions
+        let (exclude, history, cloud) = unsafe {
+            (
+                RegisterClipboardFormatW(w!("ExcludeClipboardContentFromMonitorProcessing")),
+                RegisterClipboardFormatW(w!("CanIncludeInClipboardHistory")),
+                RegisterClipboardFormatW(w!("CanUploadToCloudClipboard")),
+            )
+        };
+        set(exclude, &[])?;
+        set(history, &disallowed)?;
+        set(cloud, &disallowed)
+    }
+
+    // SAFETY: the clipboard is opened for this thread and always closed below
+    unsafe { OpenClipboard(None) }.map_err(|e| format!("Failed to open the clipboard: {e}"))?;
+    let result = fill(text, html);
+    // SAFETY: opened above
+    let _ = unsafe { CloseClipboard() };
+    result
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn write_concealed(_text: &str, _html: Option<&str>) -> Result<(), String> {
+    Err("Concealed clipboard writes are not supported on this platform".into())
+}
@@ -0,0 +1,168 @@
+//! Where the app keeps its files.
+//!
+//! Normally these are the platform directories from Tauri's path resolver.
+//! In portable mode, switched on by a `portable.flag` file beside the
+//! executable or by `--portable` on the command line, everything lives in a
+//! `PrivacyScribeData` folder beside the executable instead, so the app runs
+//! from a USB stick or a shared folder without touching the user profile.
+//!
+//! Every path the backend resolves goes through here, as do the log,
+//! window-state and SQL plugins (see `lib.rs` and `commands::storage`).
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use tauri::{AppHandle, Manager, Runtime};
+
+/// File beside the executable that switches on portable mode
+pub const PORTABLE_FLAG_FILENAME: &str = "portable.flag";
+
+/// Command-line argument that switches on portable mode
+pub const PORTABLE_ARG: &str = "--portable";
+
+/// Folder beside the executable holding all data in portable mode
+const PORTABLE_DATA_DIRNAME: &str = "PrivacyScribeData";
+
+/// The portable data folder; None outside portable mode. Set once by `init`.
+static PORTABLE_ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// The portable data folder for an executable in `exe_dir`, when portable
+/// mode is switched on.
+fn detect(
+    exe_dir: &Path,
+    flag_file_exists: bool,
+    mut args: impl Iterator<Item = String>,
+) -> Option<PathBuf> {
+    (flag_file_exists || args.any(|arg| arg == PORTABLE_ARG))
+        .then(|| exe_dir.join(PORTABLE_DATA_DIRNAME))
+}
+
+/// Subfolders of the portable data folder, in place of the platform directories
+const PORTABLE_SUBDIRS: [&str; 4] = ["data", "config", "cache", "logs"];
+
+/// Creates `root` with its subfolders and checks a file can be written in it.
+fn verify_writable(root: &Path) -> Result<(), String> {
+    for subdir in PORTABLE_SUBDIRS {
+        std::fs::create_dir_all(root.join(subdir)).map_err(|e| {
+            format!(
+                "The portable data folder {} could not be created: {e}",
+                root.display()
+            )
+        })?;
+    }
+    let probe = root.join(".write-test");
+    std::fs::write(&probe, b"")
+        .and_then(|()| std::fs::remove_file(&probe))
+        .map_err(|e| {
+            format!(
+                "The portable data folder {} is not writable: {e}",
+                root.display()
+            )
+        })
+}
+
+/// Decides between portable and installed mode. Call once at startup, before
+/// any plugin resolves a path. Errors when portable mode is on but its data
+/// folder can't be written.
+pub fn init() -> Result<(), String> {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    let root = exe_dir.and_then(|exe_dir| {
+        let flag_file_exists = exe_dir.join(PORTABLE_FLAG_FILENAME).is_file();
+        detect(&exe_dir, flag_file_exists, std::env::args().skip(1))
+    });
+    let root = PORTABLE_ROOT.get_or_init(|| root);
+    match root {
+        Some(root) => verify_writable(root),
+        None => Ok(()),
+    }
+}
+
+/// The portable data folder, when running in portable mode.
+pub fn portable_root() -> Option<&'static Path> {
+    PORTABLE_ROOT.get().and_then(|root| root.as_deref())
+}
+
+/// Whether the app runs in portable mode.
+pub fn is_portable() -> bool {
+    portable_root().is_some()
+}
+
+/// The portable subfolder `name`, or the platform directory.
+fn resolve(
+    name: &str,
+    platform_dir: impl FnOnce() -> tauri::Result<PathBuf>,
+) -> tauri::Result<PathBuf> {
+    match portable_root() {
+        Some(root) => Ok(root.join(name)),
+        None => platform_dir(),
+    }
+}
+
+/// App data: preferences, models, recovery files.
+pub fn app_data_dir<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<PathBuf> {
+    resolve("data", || app.path().app_data_dir())
+}
+
+/// App config: the databases and the window state.
+pub fn app_config_dir<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<PathBuf> {
+    resolve("config", || app.path().app_config_dir())
+}
+
+/// Cache: temporary recordings and audio.
+pub fn app_cache_dir<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<PathBuf> {
+    resolve("cache", || app.path().app_cache_dir())
+}
+
+/// Log folder, where the log plugin writes its files (macOS).
+pub fn app_log_dir<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<PathBuf> {
+    resolve("logs", || app.path().app_log_dir())
+}
+
+/// Log folder in portable mode; None otherwise, where the log plugin uses the
+/// platform's log directory.
+#[cfg(target_os = "macos")]
+pub fn portable_log_dir() -> Option<PathBuf> {
+    portable_root().map(|root| root.join("logs"))
+}
+
+/// Window-state file in portable mode. The plugin joins it onto the config
+/// directory, which an absolute path replaces.
+pub fn portable_window_state_file() -> Option<String> {
+    portable_root().map(|root| {
+        root.join("config")
+            .join(".window-state.json")
+            .to_string_lossy()
+            .into_owned()
+    })
+}
+
+/// WebView2 profile folder in portable mode, so cookies and local storage
+/// don't land in the user profile either.
+#[cfg(target_os = "windows")]
+pub fn portable_webview_dir() -> Option<PathBuf> {
+    portable_root().map(|root| root.join("webview"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn portable_mode_needs_the_flag_file_or_argument() {
+        let exe_dir = Path::new("/Volumes/USB/PrivacyScribe");
+        let root = Some(exe_dir.join("PrivacyScribeData"));
+        assert_eq!(detect(exe_dir, false, args(&[])), None);
+        assert_eq!(detect(exe_dir, false, args(&["--minimized"])), None);
+        assert_eq!(detect(exe_dir, true, args(&[])), root);
+        assert_eq!(detect(exe_dir, false, args(&["--portable"])), root);
+    }
+}
@@ -0,0 +1,199 @@
+//! Minimal PDF rendering for transcript exports.
+//!
+//! A4 pages with a title, a few metadata lines and the body, paragraphs
+//! preserved. Text is set in a TrueType font found on the system (the PDF
+//! base fonts only cover Latin-1), so letters like æøå and long dashes render
+//! correctly. Lines are wrapped by an average glyph width, which is
+//! conservative enough for proportional sans-serif fonts.
+
+use std::sync::OnceLock;
+
+use printpdf::{IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference};
+
+const PAGE_WIDTH_MM: f32 = 210.0;
+const PAGE_HEIGHT_MM: f32 = 297.0;
+const MARGIN_MM: f32 = 20.0;
+
+const TITLE_PT: f32 = 18.0;
+const META_PT: f32 = 9.0;
+const BODY_PT: f32 = 11.0;
+
+/// Line height as a multiple of the font size
+const LINE_SPACING: f32 = 1.4;
+
+/// Average glyph advance as a fraction of the font size, used for wrapping
+const AVERAGE_GLYPH_EM: f32 = 0.52;
+
+const MM_PER_PT: f32 = 25.4 / 72.0;
+
+/// Unicode TrueType fonts shipped with each desktop OS, in order of preference
+const FONT_CANDIDATES: &[&str] = &[
+    // macOS
+    "/System/Library/Fonts/Supplemental/Arial Unicode.ttf",
+    "/Library/Fonts/Arial Unicode.ttf",
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    // Windows
+    "C:\\Windows\\Fonts\\arial.ttf",
+    "C:\\Windows\\Fonts\\segoeui.ttf",
+    // Linux
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "/usr/share/fonts/dejavu-sans-fonts/DejaVuSans.ttf",
+    "/usr/share/fonts/truetype/noto/NotoSans-Regular.ttf",
+    "/usr/share/fonts/noto/NotoSans-Regular.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+];
+
+/// Font file contents, read once (bulk exports render many documents)
+static FONT: OnceLock<Option<Vec<u8>>> = OnceLock::new();
+
+fn load_font() -> Result<&'static [u8], String> {
+    FONT.get_or_init(|| {
+        FONT_CANDIDATES
+            .iter()
+            .find_map(|path| std::fs::read(path).ok())
+    })
+    .as_deref()
+    .ok_or_else(|| "No Unicode font found for PDF export".to_string())
+}
+
+/// Characters of `font_pt` text that fit on one line.
+fn chars_per_line(font_pt: f32) -> usize {
+    let usable_mm = PAGE_WIDTH_MM - 2.0 * MARGIN_MM;
+    (usable_mm / (font_pt * AVERAGE_GLYPH_EM * MM_PER_PT)) as usize
+}
+
+/// Word-wraps one line of text to at most `max_chars` characters per line,
+/// breaking words that are longer than a whole line. Empty input is one empty line.
+pub(crate) fn wrap_line(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        // Hard-break words that can't fit on any line
+        while word.len() > max_chars {
+            if current_len > 0 {
+                lines.push(std::mem::take(&mut current));
+                current_len = 0;
+            }
+            let rest = word.split_off(max_chars);
+            lines.push(word.into_iter().collect());
+            word = rest;
+        }
+
+        let separator = usize::from(current_len > 0);
+        if current_len + separator + word.len() > max_chars {
+            lines.push(std::mem::take(&mut current));
+            current_len = 0;
+        }
+        if current_len > 0 {
+            current.push(' ');
+            current_len += 1;
+        }
+        current.extend(word.iter());
+        current_len += word.len();
+    }
+
+    if current_len > 0 || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Writes lines top to bottom, starting new pages as needed.
+struct PageWriter<'a> {
+    doc: &'a PdfDocumentReference,
+    layer: PdfLayerReference,
+    font: IndirectFontRef,
+    /// Baseline of the next line, in mm from the bottom of the page
+    y_mm: f32,
+}
+
+impl PageWriter<'_> {
+    fn line(&mut self, text: &str, font_pt: f32) {
+        let height_mm = font_pt * LINE_SPACING * MM_PER_PT;
+        if self.y_mm - height_mm < MARGIN_MM {
+            let (page, layer) = self
+                .doc
+                .add_page(Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Text");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y_mm = PAGE_HEIGHT_MM - MARGIN_MM;
+        }
+        self.y_mm -= height_mm;
+        if !text.is_empty() {
+            self.layer
+                .use_text(text, font_pt, Mm(MARGIN_MM), Mm(self.y_mm), &self.font);
+        }
+    }
+
+    fn wrapped(&mut self, text: &str, font_pt: f32) {
+        for line in wrap_line(text, chars_per_line(font_pt)) {
+            self.line(&line, font_pt);
+        }
+    }
+
+    fn gap(&mut self, mm: f32) {
+        self.y_mm -= mm;
+    }
+}
+
+/// Renders a titled document to PDF bytes. Blank lines in `body` separate
+/// paragraphs; every other line break is kept.
+pub fn render_document(title: &str, metadata: &[String], body: &str) -> Result<Vec<u8>, String> {
+    let font_bytes = load_font()?;
+    let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Text");
+    let font = doc
+        .add_external_font(std::io::Cursor::new(font_bytes))
+        .map_err(|e| format!("Failed to load PDF font: {e}"))?;
+
+    let mut writer = PageWriter {
+        doc: &doc,
+        layer: doc.get_page(page).get_layer(layer),
+        font,
+        y_mm: PAGE_HEIGHT_MM - MARGIN_MM,
+    };
+
+    writer.wrapped(title, TITLE_PT);
+    writer.gap(2.0);
+    for line in metadata {
+        writer.wrapped(line, META_PT);
+    }
+    writer.gap(6.0);
+    for line in body.lines() {
+        if line.trim().is_empty() {
+            writer.gap(BODY_PT * MM_PER_PT);
+        } else {
+            writer.wrapped(line, BODY_PT);
+        }
+    }
+
+    doc.save_to_bytes()
+        .map_err(|e| format!("Failed to write PDF: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_breaks_between_words() {
+        assert_eq!(
+            wrap_line("the quick brown fox", 10),
+            vec!["the quick", "brown fox"]
+        );
+    }
+
+    #[test]
+    fn wrap_counts_characters_not_bytes() {
+        assert_eq!(wrap_line("blåbær — øl", 11), vec!["blåbær — øl"]);
+    }
+
+    #[test]
+    fn wrap_hard_breaks_long_words() {
+        assert_eq!(wrap_line("a abcdefgh", 4), vec!["a", "abcd", "efgh"]);
+        assert_eq!(wrap_line("", 10), vec![""]);
+    }
+}
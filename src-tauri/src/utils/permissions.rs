@@ -0,0 +1,79 @@
+//! OS permission checks for features that observe or synthesize input.
+//!
+//! On macOS, global event monitors and synthesized keystrokes require the app
+//! to be trusted for Accessibility. Windows and Linux have no equivalent gate
+//! for the APIs we use, so checks report granted there.
+//!
+//! Microphone access is only read here, for diagnostics; recording asks for
+//! it by opening the input stream.
+
+use crate::types::PermissionStatus;
+
+/// Returns whether the app is trusted for Accessibility (macOS).
+/// When `prompt` is true and access is missing, macOS shows its permission dialog.
+#[cfg(target_os = "macos")]
+pub fn accessibility_trusted(prompt: bool) -> bool {
+    use objc2_foundation::{ns_string, NSDictionary, NSNumber, NSString};
+    use std::ffi::c_void;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrustedWithOptions(options: *const c_void) -> bool;
+    }
+
+    let prompt_value = NSNumber::new_bool(prompt);
+    let options = NSDictionary::<NSString, NSNumber>::from_slices(
+        &[ns_string!("AXTrustedCheckOptionPrompt")],
+        &[&*prompt_value],
+    );
+
+    // SAFETY: NSDictionary is toll-free bridged to CFDictionaryRef and outlives the call.
+    unsafe { AXIsProcessTrustedWithOptions(&*options as *const _ as *const c_void) }
+}
+
+/// Returns whether the app is trusted for Accessibility (always true off macOS).
+#[cfg(not(target_os = "macos"))]
+pub fn accessibility_trusted(_prompt: bool) -> bool {
+    true
+}
+
+/// Microphone access as macOS records it, without asking for it.
+#[cfg(target_os = "macos")]
+pub fn microphone_permission() -> Result<PermissionStatus, String> {
+    use objc2::{class, msg_send};
+    use objc2_foundation::NSString;
+
+    #[link(name = "AVFoundation", kind = "framework")]
+    extern "C" {
+        static AVMediaTypeAudio: &'static NSString;
+    }
+
+    // SAFETY: AVMediaTypeAudio is a framework constant; the class method only reads it.
+    let status: isize = unsafe {
+        msg_send![
+            class!(AVCaptureDevice),
+            authorizationStatusForMediaType: AVMediaTypeAudio
+        ]
+    };
+    // AVAuthorizationStatus
+    match status {
+        0 => Ok(PermissionStatus::NotDetermined),
+        1 => Ok(PermissionStatus::Restricted),
+        2 => Ok(PermissionStatus::Denied),
+        3 => Ok(PermissionStatus::Granted),
+        other => Err(format!("Unexpected authorization status {other}")),
+    }
+}
+
+/// Windows keeps microphone consent for desktop apps in a setting it doesn't
+/// expose to them.
+#[cfg(target_os = "windows")]
+pub fn microphone_permission() -> Result<PermissionStatus, String> {
+    Err("Windows doesn't report microphone access to desktop apps".to_string())
+}
+
+/// Linux has no microphone permission.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn microphone_permission() -> Result<PermissionStatus, String> {
+    Ok(PermissionStatus::Granted)
+}
@@ -0,0 +1,120 @@
+//! Minimal HTML for the rich-text clipboard flavor.
+//!
+//! Only `<p>`, `<br>` and `<strong>` are produced, and every character of the
+//! transcript is escaped, so nothing dictated can become markup in the
+//! destination. Blank lines separate paragraphs, single line breaks become
+//! `<br>`, and a short line ending in a colon on its own (a template section
+//! header such as "Assessment:") is bolded.
+
+/// Words a line may have and still count as a section header
+const MAX_HEADER_WORDS: usize = 6;
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn is_header(line: &str) -> bool {
+    line.len() > 1 && line.ends_with(':') && line.split_whitespace().count() <= MAX_HEADER_WORDS
+}
+
+/// The transcript as an HTML fragment.
+pub fn to_html(text: &str) -> String {
+    let normalized = text.replace("\r\n", "\n");
+    normalized
+        .split("\n\n")
+        .map(|paragraph| {
+            paragraph
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(|line| {
+                    if is_header(line) {
+                        format!("<strong>{}</strong>", escape(line))
+                    } else {
+                        escape(line)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("<br>")
+        })
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| format!("<p>{paragraph}</p>"))
+        .collect()
+}
+
+/// Wraps an HTML fragment in the Windows `CF_HTML` clipboard format, whose
+/// header gives byte offsets into the UTF-8 payload.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn cf_html(fragment: &str) -> String {
+    const HEADER: &str = "Version:0.9\r\nStartHTML:{0000000000}\r\nEndHTML:{1111111111}\r\n\
+                          StartFragment:{2222222222}\r\nEndFragment:{3333333333}\r\n";
+    const PREFIX: &str = "<html><body>\r\n<!--StartFragment-->";
+    const SUFFIX: &str = "<!--EndFragment-->\r\n</body></html>";
+
+    // Every placeholder is as long as the 10-digit number replacing it
+    let header_len = HEADER.len() - 4 * 2;
+    let start_html = header_len;
+    let start_fragment = start_html + PREFIX.len();
+    let end_fragment = start_fragment + fragment.len();
+    let end_html = end_fragment + SUFFIX.len();
+    let header = HEADER
+        .replace("{0000000000}", &format!("{start_html:010}"))
+        .replace("{1111111111}", &format!("{end_html:010}"))
+        .replace("{2222222222}", &format!("{start_fragment:010}"))
+        .replace("{3333333333}", &format!("{end_fragment:010}"));
+    format!("{header}{PREFIX}{fragment}{SUFFIX}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paragraphs_breaks_and_headers() {
+        assert_eq!(
+            to_html("Assessment:\nKnee pain.\nImproving.\n\n\nPlan:\r\nPhysio."),
+            "<p><strong>Assessment:</strong><br>Knee pain.<br>Improving.</p>\
+             <p><strong>Plan:</strong><br>Physio.</p>"
+        );
+    }
+
+    #[test]
+    fn everything_is_escaped() {
+        assert_eq!(
+            to_html("<b>x</b> & \"y\" 'z'"),
+            "<p>&lt;b&gt;x&lt;/b&gt; &amp; &quot;y&quot; &#39;z&#39;</p>"
+        );
+        // A long sentence ending in a colon is not a header
+        assert_eq!(
+            to_html("The patient reported the following symptoms today:"),
+            "<p>The patient reported the following symptoms today:</p>"
+        );
+    }
+
+    #[test]
+    fn cf_html_offsets_point_at_the_fragment() {
+        let fragment = "<p>Blåbær</p>";
+        let payload = cf_html(fragment);
+        let offset = |name: &str| -> usize {
+            let start = payload.find(name).unwrap() + name.len() + 1;
+            payload[start..start + 10].parse().unwrap()
+        };
+        assert_eq!(
+            &payload[offset("StartFragment")..offset("EndFragment")],
+            fragment
+        );
+        assert!(payload[offset("StartHTML")..].starts_with("<html>"));
+        assert_eq!(offset("EndHTML"), payload.len());
+    }
+}
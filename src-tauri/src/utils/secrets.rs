@@ -0,0 +1,71 @@
+//! Encryption keys stored in the OS keychain.
+//!
+//! Uses the macOS Keychain, Windows Credential Manager, or the Secret Service
+//! on Linux. Keys never touch the app's data directory, so copying that
+//! directory alone doesn't expose encrypted data.
+
+/// Keychain service name all app secrets are stored under
+const KEYCHAIN_SERVICE: &str = "org.privacyscribe.app";
+
+/// Length of the symmetric keys we store (256-bit)
+pub const KEY_LEN: usize = 32;
+
+fn entry(name: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, name)
+        .map_err(|e| format!("Failed to access keychain entry {name}: {e}"))
+}
+
+/// Reads a key from the keychain. Returns None if it was never created or was removed.
+pub fn get_key(name: &str) -> Result<Option<[u8; KEY_LEN]>, String> {
+    match entry(name)?.get_secret() {
+        Ok(secret) => {
+            let key: [u8; KEY_LEN] = secret
+                .try_into()
+                .map_err(|_| format!("Keychain entry {name} has an unexpected length"))?;
+            Ok(Some(key))
+        }
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read keychain entry {name}: {e}")),
+    }
+}
+
+/// Reads a key from the keychain, generating and storing a random one on first use.
+pub fn get_or_create_key(name: &str) -> Result<[u8; KEY_LEN], String> {
+    if let Some(key) = get_key(name)? {
+        return Ok(key);
+    }
+
+    use chacha20poly1305::aead::{rand_core::RngCore, OsRng};
+    let mut key = [0u8; KEY_LEN];
+    OsRng.fill_bytes(&mut key);
+
+    entry(name)?
+        .set_secret(&key)
+        .map_err(|e| format!("Failed to store keychain entry {name}: {e}"))?;
+    log::info!("Created keychain entry {name}");
+    Ok(key)
+}
+
+/// Reads a text secret (e.g. a password hash). Returns None if it was never set.
+pub fn get_secret_string(name: &str) -> Result<Option<String>, String> {
+    match entry(name)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read keychain entry {name}: {e}")),
+    }
+}
+
+/// Stores a text secret, replacing any previous value.
+pub fn set_secret_string(name: &str, secret: &str) -> Result<(), String> {
+    entry(name)?
+        .set_password(secret)
+        .map_err(|e| format!("Failed to store keychain entry {name}: {e}"))
+}
+
+/// Removes a secret. Removing one that doesn't exist is not an error.
+pub fn delete_secret(name: &str) -> Result<(), String> {
+    match entry(name)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to delete keychain entry {name}: {e}")),
+    }
+}
@@ -0,0 +1,75 @@
+//! Best-effort secure deletion for files that held PHI.
+//!
+//! Files are overwritten with zeros and synced before being unlinked. On SSDs
+//! and copy-on-write filesystems (APFS, Btrfs) the old blocks may survive the
+//! overwrite, so this complements - not replaces - full-disk encryption.
+
+use std::io::Write;
+use std::path::Path;
+
+/// Size of the zero buffer used when overwriting
+const OVERWRITE_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Overwrites a file with zeros, syncs it, then removes it.
+/// A missing file is treated as already deleted.
+pub fn secure_delete(path: &Path) -> std::io::Result<()> {
+    let len = match std::fs::metadata(path) {
+        Ok(metadata) => metadata.len(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+
+    // Overwrite failures shouldn't leave the file behind - still unlink it
+    if let Err(e) = overwrite_with_zeros(path, len) {
+        log::warn!("Failed to overwrite {path:?} before deletion: {e}");
+    }
+
+    std::fs::remove_file(path)
+}
+
+/// Securely deletes every file under `dir`, then removes the directory.
+/// Symlinks are unlinked, not followed. Keeps going past failures and
+/// returns the first one. A missing directory is treated as already deleted.
+pub fn secure_delete_dir(dir: &Path) -> std::io::Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+
+    let mut first_error = None;
+    for entry in entries {
+        let result = entry.and_then(|entry| {
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+            if file_type.is_symlink() {
+                std::fs::remove_file(&path)
+            } else if file_type.is_dir() {
+                secure_delete_dir(&path)
+            } else {
+                secure_delete(&path)
+            }
+        });
+        if let Err(e) = result {
+            first_error.get_or_insert(e);
+        }
+    }
+
+    let removed = std::fs::remove_dir(dir);
+    match first_error {
+        Some(e) => Err(e),
+        None => removed,
+    }
+}
+
+fn overwrite_with_zeros(path: &Path, len: u64) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+    let zeros = [0u8; OVERWRITE_CHUNK_BYTES];
+    let mut remaining = len;
+    while remaining > 0 {
+        let chunk = remaining.min(OVERWRITE_CHUNK_BYTES as u64) as usize;
+        file.write_all(&zeros[..chunk])?;
+        remaining -= chunk as u64;
+    }
+    file.sync_all()
+}
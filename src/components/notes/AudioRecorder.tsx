@@ -10,6 +10,7 @@ import {
   SelectTrigger,
   SelectValue,
 } from '@/components/ui/select'
+import { formatAppError } from '@/lib/app-error'
 import { notifications } from '@/lib/notifications'
 import { WHISPER_LANGUAGES } from '@/lib/whisper-languages'
 import { commands } from '@/lib/tauri-bindings'
@@ -156,7 +157,7 @@ export function AudioRecorder({ onTranscriptionReady }: AudioRecorderProps) {
         }
       } catch (err) {
         notifications.error(
-          t('notes.recording.errorToast', { error: String(err) })
+          t('notes.recording.errorToast', { error: formatAppError(err) })
         )
       } finally {
         setState('idle')
@@ -170,9 +171,7 @@ export function AudioRecorder({ onTranscriptionReady }: AudioRecorderProps) {
         setElapsed(0)
         timerRef.current = setInterval(() => setElapsed(s => s + 1), 1000)
       } catch (err) {
-        notifications.error(
-          t('notes.recording.noMicError') + '\n' + String(err)
-        )
+        notifications.error(formatAppError(err))
       }
     }
   }
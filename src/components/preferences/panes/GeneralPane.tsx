@@ -8,6 +8,7 @@ import { Label } from '@/components/ui/label'
 import { ShortcutPicker } from '../ShortcutPicker'
 import { SettingsField, SettingsSection } from '../shared/SettingsComponents'
 import { usePreferences, useSavePreferences } from '@/services/preferences'
+import { formatAppError } from '@/lib/app-error'
 import { commands } from '@/lib/tauri-bindings'
 import { logger } from '@/lib/logger'
 
@@ -47,7 +48,7 @@ export function GeneralPane() {
     if (result.status === 'error') {
       logger.error('Failed to register shortcut', { error: result.error })
       toast.error(t('toast.error.shortcutFailed'), {
-        description: result.error,
+        description: formatAppError(result.error),
       })
       return
     }
@@ -0,0 +1,33 @@
+import { i18n } from '@/i18n/config'
+import type { AppError } from '@/lib/tauri-bindings'
+
+function isAppError(error: unknown): error is AppError {
+  return (
+    typeof error === 'object' &&
+    error !== null &&
+    'type' in error &&
+    typeof error.type === 'string'
+  )
+}
+
+/**
+ * Convert an AppError from a command to a message in the current language.
+ * Anything else (e.g. a raw `invoke` rejection) is shown as is.
+ */
+export function formatAppError(error: unknown): string {
+  if (!isAppError(error)) {
+    return String(error)
+  }
+  switch (error.type) {
+    case 'ModelNotDownloaded':
+    case 'ChecksumMismatch':
+      return i18n.t(`errors.${error.type}`, { model: error.model_id })
+    case 'DiskFull':
+      return i18n.t('errors.DiskFull')
+    case 'InvalidInput':
+    case 'Internal':
+      return i18n.t(`errors.${error.type}`, { message: error.message })
+    default:
+      return i18n.t(`errors.${error.type}`)
+  }
+}
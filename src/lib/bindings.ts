@@ -1,5 +1,6 @@
 // @ts-nocheck
 // Auto-generated by tauri-specta. DO NOT EDIT.
+// Schema hash: a2f6a65bb619ca06
 
 
 // This file was generated by [tauri-specta](https://github.com/oscartbeaumont/tauri-specta). Do not edit this file manually.
@@ -11,7 +12,7 @@ export const commands = {
 /**
  * Simple greeting command for demonstration purposes.
  */
-async greet(name: string) : Promise<Result<string, string>> {
+async greet(name: string) : Promise<Result<string, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("greet", { name }) };
 } catch (e) {
@@ -21,9 +22,10 @@ async greet(name: string) : Promise<Result<string, string>> {
 },
 /**
  * Loads user preferences from disk.
- * Returns default preferences if the file doesn't exist.
+ * Returns default preferences if the file doesn't exist. While the app is
+ * locked, the de-identification names and patterns come back empty.
  */
-async loadPreferences() : Promise<Result<AppPreferences, string>> {
+async loadPreferences() : Promise<Result<AppPreferences, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("load_preferences") };
 } catch (e) {
@@ -35,7 +37,7 @@ async loadPreferences() : Promise<Result<AppPreferences, string>> {
  * Saves user preferences to disk.
  * Uses atomic write (temp file + rename) to prevent corruption.
  */
-async savePreferences(preferences: AppPreferences) : Promise<Result<null, string>> {
+async savePreferences(preferences: AppPreferences) : Promise<Result<null, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("save_preferences", { preferences }) };
 } catch (e) {
@@ -43,11 +45,83 @@ async savePreferences(preferences: AppPreferences) : Promise<Result<null, string
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Returns whether a passcode is set, whether the app is locked, and how
+ * long until another unlock attempt is accepted.
+ */
+async getAppLockStatus() : Promise<AppLockStatus> {
+    return await TAURI_INVOKE("get_app_lock_status");
+},
+/**
+ * Sets, changes or (with `passcode` None) removes the app lock passcode.
+ * When one is already set, `current_passcode` must match it, with the same
+ * attempt limit as `unlock_app`.
+ */
+async setAppLockPasscode(currentPasscode: string | null, passcode: string | null) : Promise<Result<null, AppLockError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_app_lock_passcode", { currentPasscode, passcode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Unlocks the app. Wrong passcodes are counted; after a few, attempts are
+ * refused with `RateLimited` until the delay has passed.
+ */
+async unlockApp(passcode: string) : Promise<Result<null, AppLockError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unlock_app", { passcode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Whether biometric unlock can be offered on this device, and with which
+ * sensor. The settings screen only shows the `biometric_unlock` toggle when
+ * this is `available`.
+ */
+async getBiometricAvailability() : Promise<BiometricAvailability> {
+    return await TAURI_INVOKE("get_biometric_availability");
+},
+/**
+ * Unlocks the app with the OS biometric prompt. Fails with
+ * `BiometricsUnavailable` (and the reason) when the preference is off, the
+ * device can't do it, or the prompt is cancelled or fails; the lock screen
+ * then asks for the passcode. Success also clears passcode attempt delays.
+ */
+async unlockWithBiometrics() : Promise<Result<null, AppLockError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unlock_with_biometrics") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Locks the app now and emits `app-locked`.
+ */
+async lockApp() : Promise<Result<null, AppLockError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lock_app") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Resets the idle timers. The frontend calls this (throttled) on focus and
+ * input events. Ignored while locked.
+ */
+async reportUserActivity() : Promise<void> {
+    await TAURI_INVOKE("report_user_activity");
+},
 /**
  * Sends a native system notification.
  * On mobile platforms, returns an error as notifications are not yet supported.
  */
-async sendNativeNotification(title: string, body: string | null) : Promise<Result<null, string>> {
+async sendNativeNotification(title: string, body: string | null) : Promise<Result<null, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("send_native_notification", { title, body }) };
 } catch (e) {
@@ -55,6 +129,33 @@ async sendNativeNotification(title: string, body: string | null) : Promise<Resul
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Handles a click on a transcription-complete notification. `action_id` is
+ * `"copy"` to put the transcript on the clipboard; anything else (including a
+ * plain click, which carries no action id) opens it in the main window.
+ * The held transcript is released either way.
+ */
+async handleTranscriptionNotification(jobId: string, actionId: string | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("handle_transcription_notification", { jobId, actionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Sends a sample notification for `category` so the settings screen can show
+ * what it looks and sounds like. Ignores mutes and quiet hours, and reports
+ * whether the OS allows notifications so the UI can link to system settings.
+ */
+async sendTestNotification(category: NotificationCategory) : Promise<Result<TestNotificationResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("send_test_notification", { category }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Saves emergency data to a JSON file for later recovery.
  * Validates filename and enforces a 10MB size limit.
@@ -92,250 +193,3193 @@ async cleanupOldRecoveryFiles() : Promise<Result<number, RecoveryError>> {
 }
 },
 /**
- * Shows the quick pane window and makes it the key window (for keyboard input).
+ * Lists recoverable recordings in the recovery directory, newest first.
+ * The recording currently in progress is excluded.
  */
-async showQuickPane() : Promise<Result<null, string>> {
+async listRecoverySessions() : Promise<Result<RecoverySessionInfo[], RecoveryError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("show_quick_pane") };
+    return { status: "ok", data: await TAURI_INVOKE("list_recovery_sessions") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Dismisses the quick pane window.
- * On macOS, resigns key window status before hiding to avoid activating main window.
+ * Transcribes the audio in a recording recovery file.
+ * Publishes the usual `pipeline-status` events with `recovered` set. On success the
+ * temporary WAV and the recovery file are securely deleted; on failure the recovery
+ * file is kept so the user can retry. `auto_copy_to_clipboard` and
+ * `formatting` work as in `transcribe_and_delete`'s delivery options.
  */
-async dismissQuickPane() : Promise<Result<null, string>> {
+async recoverSession(id: string, language: string | null, autoCopyToClipboard: boolean | null, formatting: FormattingOptions | null) : Promise<Result<string, RecoveryError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("dismiss_quick_pane") };
+    return { status: "ok", data: await TAURI_INVOKE("recover_session", { id, language, autoCopyToClipboard, formatting }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Toggles the quick pane window visibility.
+ * Deletes one recording recovery session, or all of them.
+ * The recording currently in progress is never purged. Returns the count removed.
  */
-async toggleQuickPane() : Promise<Result<null, string>> {
+async purgeRecovery(target: RecoveryPurgeTarget) : Promise<Result<number, RecoveryError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("toggle_quick_pane") };
+    return { status: "ok", data: await TAURI_INVOKE("purge_recovery", { target }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Returns the default shortcut constant for frontend use.
+ * Reports how much disk space recovery data uses, for the storage settings screen.
  */
-async getDefaultQuickPaneShortcut() : Promise<string> {
-    return await TAURI_INVOKE("get_default_quick_pane_shortcut");
+async getRecoveryUsage() : Promise<Result<RecoveryUsage, RecoveryError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_recovery_usage") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 },
 /**
- * Updates the global shortcut for the quick pane.
- * Pass None to reset to default.
+ * Repairs a recording's WAV left unfinalized by a crash and returns its
+ * recovered duration and path.
  */
-async updateQuickPaneShortcut(shortcut: string | null) : Promise<Result<null, string>> {
+async repairRecording(sessionId: string) : Promise<Result<RepairedRecording, RecoveryError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_quick_pane_shortcut", { shortcut }) };
+    return { status: "ok", data: await TAURI_INVOKE("repair_recording", { sessionId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Start recording from the default microphone.
- * Spawns a dedicated thread that creates and owns the cpal::Stream.
+ * Exports a recovery session's audio as a standard 16kHz mono WAV, e.g. for a human
+ * transcriptionist when it won't transcribe. `destination_path` must come from the
+ * save dialog (which grants fs scope) and `acknowledge_phi_export` must be true,
+ * i.e. the user accepted the PHI consent dialog. The export is audited with its
+ * destination; the recovery file itself is kept.
  */
-async startRecording() : Promise<Result<null, string>> {
+async exportRecoveryAudio(id: string, destinationPath: string, acknowledgePhiExport: boolean) : Promise<Result<null, RecoveryError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("start_recording") };
+    return { status: "ok", data: await TAURI_INVOKE("export_recovery_audio", { id, destinationPath, acknowledgePhiExport }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Stop recording, resample to 16kHz mono WAV, and return the file path.
+ * Saves (or replaces) a transcript draft. The frontend calls this debounced
+ * every few seconds while editing. Saving beyond `MAX_DRAFTS` evicts the
+ * least recently updated drafts.
  */
-async stopRecording() : Promise<Result<string, string>> {
+async saveDraft(draftId: string, content: string, cursorMeta: JsonValue | null) : Promise<Result<null, RecoveryError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("stop_recording") };
+    return { status: "ok", data: await TAURI_INVOKE("save_draft", { draftId, content, cursorMeta }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Return the model catalogue with `downloaded` status filled in.
+ * Loads all saved drafts, most recently updated first.
  */
-async listWhisperModels() : Promise<Result<WhisperModelInfo[], string>> {
+async loadDrafts() : Promise<Result<TranscriptDraft[], RecoveryError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_whisper_models") };
+    return { status: "ok", data: await TAURI_INVOKE("load_drafts") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Download a model by id. Emits `whisper-model-download-progress` events:
- * `{ "model_id": "...", "percent": 0..100 }`.
+ * Deletes a draft after the transcript was explicitly saved or discarded.
  */
-async downloadWhisperModel(modelId: string) : Promise<Result<null, string>> {
+async deleteDraft(draftId: string) : Promise<Result<null, RecoveryError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("download_whisper_model", { modelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_draft", { draftId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Transcribe a 16kHz mono WAV file and delete it immediately after.
- * 
- * `language` is an optional ISO 639-1 code (e.g. "en", "no").
- * Pass `None` to auto-detect. `model_id` selects which model to use;
- * if the model is not downloaded it falls back to `whisper-large-v3-turbo`.
+ * Reports whether the transcripts database opened, and if not, why.
+ */
+async getDatabaseStatus() : Promise<DatabaseStatus> {
+    return await TAURI_INVOKE("get_database_status");
+},
+/**
+ * Connection URL for the frontend's SQL plugin database. The plugin joins
+ * the path onto the platform config directory, so in portable mode the URL
+ * carries an absolute path, which replaces it.
  */
-async transcribeAndDelete(filePath: string, language: string | null, modelId: string) : Promise<Result<string, string>> {
+async getFrontendDatabaseUrl() : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("transcribe_and_delete", { filePath, language, modelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_frontend_database_url") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Check whether the LLM model file exists locally and is valid.
+ * Bytes the app uses per category, and the folders in effect. Measured at
+ * most once per `USAGE_CACHE_TTL`; clearing a category measures again.
  */
-async checkLlmModel() : Promise<Result<boolean, string>> {
+async getStorageUsage() : Promise<Result<StorageUsage, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_llm_model") };
+    return { status: "ok", data: await TAURI_INVOKE("get_storage_usage") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Download the LLM model from HuggingFace if it doesn't exist locally.
- * Emits `llm-model-download-progress` events with `{ percent: number }`.
+ * Empties a category that holds nothing the user keeps, securely deleting
+ * its files. Files in use (a running recording's audio, the current log
+ * file) are skipped.
  */
-async downloadLlmModel() : Promise<Result<null, string>> {
+async clearCategory(kind: ClearableStorage) : Promise<Result<StorageCleared, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("download_llm_model") };
+    return { status: "ok", data: await TAURI_INVOKE("clear_category", { kind }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Generate a structured clinical note from a system prompt and user content.
- * 
- * Streams tokens via `llm-chunk` events. Emits `llm-done` on completion
- * or `llm-error` on failure. Uses GBNF grammar to force valid JSON output.
+ * Saves a new transcript and returns it with its assigned id, plus the
+ * de-identification report when `deidentify` was set and the id of an
+ * earlier duplicate from today, if there is one.
  */
-async generateNoteStream(systemPrompt: string, userContent: string) : Promise<Result<null, string>> {
+async saveTranscript(transcript: TranscriptNew) : Promise<Result<SavedTranscript, TranscriptError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("generate_note_stream", { systemPrompt, userContent }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_transcript", { transcript }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+/**
+ * Applies the saved de-identification rules to `text` without storing
+ * anything, so the result can be previewed before saving.
+ */
+async deidentifyText(text: string) : Promise<Result<DeidentifiedText, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("deidentify_text", { text }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
 }
+},
+/**
+ * Loads a single transcript.
+ */
+async getTranscript(id: string) : Promise<Result<Transcript, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_transcript", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
 }
-
-/** user-defined events **/
-
-
-
-/** user-defined constants **/
-
-
-
-/** user-defined types **/
-
+},
 /**
- * Application preferences that persist to disk.
- * Only contains settings that should be saved between sessions.
+ * Lists transcripts matching `filter`, most recently created first. Results
+ * carry the session metadata but not the text; `get_transcript` loads that.
  */
-export type AppPreferences = { theme: string; 
+async listTranscripts(filter: TranscriptFilter | null, pagination: Pagination | null) : Promise<Result<TranscriptSummaryPage, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_transcripts", { filter, pagination }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Global shortcut for quick pane (e.g., "CommandOrControl+Shift+.")
- * If None, uses the default shortcut
+ * Applies a partial update and returns the updated transcript.
  */
-quick_pane_shortcut: string | null; 
+async updateTranscript(id: string, patch: TranscriptPatch) : Promise<Result<Transcript, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_transcript", { id, patch }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * User's preferred language (e.g., "en", "es", "de")
- * If None, uses system locale detection
+ * Sets or clears (with None or an empty string) a transcript's session label.
  */
-language: string | null }
-export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+async updateSessionLabel(id: string, label: string | null) : Promise<Result<Transcript, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_session_label", { id, label }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Error types for recovery operations (typed for frontend matching)
+ * Lists a transcript's earlier versions, newest first.
  */
-export type RecoveryError = 
+async listRevisions(id: string) : Promise<Result<TranscriptRevision[], TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_revisions", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * File does not exist (expected case, not a failure)
+ * Restores a transcript's title and text from one of its revisions and
+ * returns the updated transcript. The replaced version is kept as a revision.
  */
-{ type: "FileNotFound" } | 
+async restoreRevision(id: string, revisionId: number) : Promise<Result<Transcript, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_revision", { id, revisionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Filename validation failed
+ * Moves a transcript to the trash. It disappears from lists and search but
+ * can be restored until it's purged.
  */
-{ type: "ValidationError"; message: string } | 
+async deleteTranscript(id: string) : Promise<Result<null, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_transcript", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Data exceeds size limit
+ * Lists transcripts in the trash, most recently deleted first.
  */
-{ type: "DataTooLarge"; max_bytes: number } | 
+async listTrash() : Promise<Result<TranscriptSummary[], TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_trash") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * File system read/write error
+ * Takes a transcript back out of the trash.
  */
-{ type: "IoError"; message: string } | 
+async restoreTranscript(id: string) : Promise<Result<null, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_transcript", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * JSON serialization/deserialization error
+ * Permanently deletes a transcript, with its tags and revisions. The database
+ * runs with `secure_delete`, so the text is overwritten rather than left in
+ * free pages.
  */
-{ type: "ParseError"; message: string }
+async purgeTranscript(id: string) : Promise<Result<null, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("purge_transcript", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Metadata for a downloadable Whisper model.
+ * Dictation volume and transcription speed over `period` (default: the last
+ * week), with a per-day histogram. Transcripts in the trash aren't counted.
  */
-export type WhisperModelInfo = { id: string; name: string; filename: string; url: string; 
+async getUsageStats(period: UsagePeriod | null) : Promise<Result<UsageStats, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_usage_stats", { period }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Approximate size in bytes (used for UI display only). u32 supports up to ~4 GB.
+ * Full-text search over transcript titles and text, best match first.
+ * Accepts FTS5 syntax (`"exact phrase"`, `prefix*`, `AND`/`OR`/`NOT`); a query
+ * that isn't valid FTS5 is retried as plain words. `filter` narrows the
+ * results like `list_transcripts`.
  */
-size_bytes: number; 
+async searchTranscripts(query: string, filter: TranscriptFilter | null, limit: number | null, offset: number | null) : Promise<Result<TranscriptSearchHit[], TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_transcripts", { query, filter, limit, offset }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * ISO 639-1 codes this model supports. Empty = all languages.
+ * Replaces a transcript's tags. Returns the normalized tags now attached.
  */
-languages: string[]; 
+async setTranscriptTags(id: string, tags: string[]) : Promise<Result<string[], TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_transcript_tags", { id, tags }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Whether the model file is present on disk (set dynamically).
+ * Lists all tags alphabetically with how many transcripts (outside the trash) use each.
  */
-downloaded: boolean }
-
-/** tauri-specta globals **/
-
-import {
-	invoke as TAURI_INVOKE,
-	Channel as TAURI_CHANNEL,
-} from "@tauri-apps/api/core";
-import * as TAURI_API_EVENT from "@tauri-apps/api/event";
-import { type WebviewWindow as __WebviewWindow__ } from "@tauri-apps/api/webviewWindow";
-
-type __EventObj__<T> = {
-	listen: (
-		cb: TAURI_API_EVENT.EventCallback<T>,
-	) => ReturnType<typeof TAURI_API_EVENT.listen<T>>;
-	once: (
-		cb: TAURI_API_EVENT.EventCallback<T>,
-	) => ReturnType<typeof TAURI_API_EVENT.once<T>>;
-	emit: null extends T
-		? (payload?: T) => ReturnType<typeof TAURI_API_EVENT.emit>
-		: (payload: T) => ReturnType<typeof TAURI_API_EVENT.emit>;
-};
-
-export type Result<T, E> =
-	| { status: "ok"; data: T }
-	| { status: "error"; error: E };
-
-function __makeEvents__<T extends Record<string, any>>(
+async listTags() : Promise<Result<TagUsage[], TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_tags") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Renames a tag on every transcript. Renaming onto an existing tag merges the two.
+ */
+async renameTag(name: string, newName: string) : Promise<Result<null, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rename_tag", { name, newName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Deletes a tag and removes it from every transcript.
+ */
+async deleteTag(name: string) : Promise<Result<null, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_tag", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Exports a saved transcript to `path` (from the save dialog, ending in the
+ * format's extension, or `.psenc` when `encrypted`). Returns the number of
+ * bytes written.
+ */
+async exportTranscript(id: string, format: TranscriptExportFormat, path: string, encrypted: ExportEncryption | null) : Promise<Result<number, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_transcript", { id, format, path, encrypted }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Exports all transcripts created within `date_range` into a ZIP archive at
+ * `path` (from the save dialog), one file per transcript in `format`. With a
+ * `passphrase`, entries are AES-256 encrypted. With `encrypted`, the whole
+ * archive, entry names included, is sealed into a `.psenc` envelope at
+ * `path` instead. Emits `transcript-export-progress` after each entry;
+ * `cancel_transcript_export` stops the export and deletes the partial archive.
+ */
+async exportAllTranscripts(path: string, format: TranscriptExportFormat, dateRange: DateRange | null, passphrase: string | null, encrypted: ExportEncryption | null) : Promise<Result<BulkExportSummary, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_all_transcripts", { path, format, dateRange, passphrase, encrypted }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Cancels the running bulk export, if any.
+ */
+async cancelTranscriptExport() : Promise<void> {
+    await TAURI_INVOKE("cancel_transcript_export");
+},
+/**
+ * Decrypts a `.psenc` export at `path` (from the open dialog) and writes the
+ * file it holds to `destination` (from the save dialog, ending in the
+ * wrapped file's extension). Fails with `WrongPassphrase` when the
+ * passphrase doesn't open it. Returns the number of bytes written.
+ */
+async decryptExport(path: string, passphrase: string, destination: string) : Promise<Result<number, TranscriptError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("decrypt_export", { path, passphrase, destination }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Writes a timestamped backup of the database into `destination_path`
+ * (a directory from the folder picker) and returns the backup's path.
+ */
+async backupDatabase(destinationPath: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("backup_database", { destinationPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replaces the database with the backup at `source_path` (from the file
+ * picker). `confirm` must be true, i.e. the user accepted the warning that
+ * current data will be replaced. The replaced database is kept next to the
+ * live one as `.pre-restore`.
+ */
+async restoreDatabase(sourcePath: string, confirm: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_database", { sourcePath, confirm }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Runs a full integrity check on the database for the diagnostics screen.
+ * Slower than the startup quick check; runs off the main thread.
+ */
+async runIntegrityCheck() : Promise<Result<IntegrityReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_integrity_check") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Writes a report of the current privacy configuration to `path` (from the
+ * save dialog, ending in `.pdf` or `.md`). Returns the number of bytes
+ * written.
+ */
+async generateComplianceReport(path: string) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("generate_compliance_report", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Securely wipes the cache directory now and reports what was removed.
+ * Audio in use by a recording or transcription, and the webview's cache,
+ * are skipped.
+ */
+async secureWipeCache() : Promise<Result<CacheWipeReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("secure_wipe_cache") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Wipes all PHI from this machine and restarts the app into its first-run
+ * state. Requires `confirmation_phrase` to be exactly `EMERGENCY_WIPE_PHRASE`.
+ * Returns how each category went; a failed category doesn't stop the rest.
+ */
+async emergencyWipe(confirmationPhrase: string) : Promise<Result<EmergencyWipeReport, EmergencyWipeError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("emergency_wipe", { confirmationPhrase }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists audit entries matching `filter`, newest first.
+ */
+async listAuditEvents(filter: AuditFilter | null, pagination: Pagination | null) : Promise<Result<AuditEventPage, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_audit_events", { filter, pagination }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Exports the whole audit log, newest first. `path` must come from the save
+ * dialog (which grants fs scope) and end in the format's extension. The export
+ * is itself audited. Returns the number of entries written.
+ */
+async exportAuditLog(path: string, format: AuditExportFormat) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_audit_log", { path, format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Shows the quick pane window.
+ * When `focus_keyboard` is true the pane becomes the key window (for keyboard input);
+ * pass None to use the `quick_pane_focus_keyboard` preference.
+ */
+async showQuickPane(focusKeyboard: boolean | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("show_quick_pane", { focusKeyboard }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Copies `text` and pastes it into the application that had focus when the
+ * quick pane was shown. Falls back to copy-only (with `paste-fallback`) when
+ * that application can't be identified, has quit, or Accessibility access is
+ * missing on macOS.
+ */
+async pasteIntoPreviousApp(text: string) : Promise<Result<PasteOutcome, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("paste_into_previous_app", { text }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns whether the OS permission needed to paste into other apps is
+ * granted. When `prompt` is true, macOS shows the Accessibility permission
+ * dialog if needed.
+ */
+async checkPastePermission(prompt: boolean) : Promise<boolean> {
+    return await TAURI_INVOKE("check_paste_permission", { prompt });
+},
+/**
+ * Types `text` into the application that had focus when the quick pane was
+ * shown, as individual keystrokes, for fields that block pasting. Returns the
+ * number of keystrokes sent.
+ * 
+ * `chars_per_second` defaults to the `type_out_chars_per_second` preference
+ * and is clamped to a range apps keep up with. Texts over 2,000 characters
+ * need `confirmed`; texts over 20,000 are refused. Stops early when
+ * `cancel_type_out` is called or another app takes focus.
+ */
+async typeOutText(text: string, charsPerSecond: number | null, confirmed: boolean | null) : Promise<Result<number, TypeOutError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("type_out_text", { text, charsPerSecond, confirmed }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Formats a transcript with a formatting profile, as auto-copy and
+ * auto-paste would. `paragraphs-on-pause` needs `content` as segments; plain
+ * text is returned unchanged by it.
+ */
+async formatTranscript(content: TranscriptContent, options: FormattingOptions) : Promise<string> {
+    return await TAURI_INVOKE("format_transcript", { content, options });
+},
+/**
+ * Stops a running `type_out_text` before its next keystroke. Does nothing
+ * when nothing is being typed.
+ */
+async cancelTypeOut() : Promise<void> {
+    await TAURI_INVOKE("cancel_type_out");
+},
+/**
+ * Dismisses the quick pane window.
+ * On macOS, resigns key window status before hiding to avoid activating main window.
+ * Blur auto-hide and Escape in the pane both route through here, so an active
+ * recording or unsaved result can hold the hide back.
+ */
+async dismissQuickPane() : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("dismiss_quick_pane") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Dismisses the quick pane even with unsaved work (after the frontend confirmed).
+ */
+async forceDismissQuickPane() : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("force_dismiss_quick_pane") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Marks whether the pane holds a transcript that hasn't been copied or saved yet.
+ */
+async setQuickPaneDirty(dirty: boolean) : Promise<void> {
+    await TAURI_INVOKE("set_quick_pane_dirty", { dirty });
+},
+/**
+ * Toggles the quick pane window visibility.
+ */
+async toggleQuickPane() : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("toggle_quick_pane") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns whether the quick pane is currently visible (for menu state on startup).
+ */
+async getQuickPaneVisible() : Promise<boolean> {
+    return await TAURI_INVOKE("get_quick_pane_visible");
+},
+/**
+ * Returns the default shortcut constant for frontend use.
+ */
+async getDefaultQuickPaneShortcut() : Promise<string> {
+    return await TAURI_INVOKE("get_default_quick_pane_shortcut");
+},
+/**
+ * Updates the global shortcut for the quick pane.
+ * Pass None to reset to default.
+ */
+async updateQuickPaneShortcut(shortcut: string | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_quick_pane_shortcut", { shortcut }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Updates double-tap modifier activation. Pass None to disable it entirely.
+ */
+async updateDoubleTapModifier(config: DoubleTapModifier | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_double_tap_modifier", { config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns whether the OS permission needed for double-tap activation is granted.
+ * When `prompt` is true, macOS shows the Accessibility permission dialog if needed.
+ */
+async checkDoubleTapPermission(prompt: boolean) : Promise<boolean> {
+    return await TAURI_INVOKE("check_double_tap_permission", { prompt });
+},
+/**
+ * Updates quick pane appearance immediately (the frontend persists the preference).
+ */
+async updateQuickPaneAppearance(appearance: QuickPaneAppearance) : Promise<void> {
+    await TAURI_INVOKE("update_quick_pane_appearance", { appearance });
+},
+/**
+ * Sets the quick pane zoom factor (clamped to 0.75-2.0) and persists it.
+ */
+async setQuickPaneZoom(zoom: number) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_quick_pane_zoom", { zoom }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Zooms the quick pane in by one step (bound to Cmd/Ctrl+= in the pane).
+ */
+async zoomQuickPaneIn() : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("zoom_quick_pane_in") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Zooms the quick pane out by one step (bound to Cmd/Ctrl+- in the pane).
+ */
+async zoomQuickPaneOut() : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("zoom_quick_pane_out") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Reports whether each protected window is actually excluded from capture.
+ * Windows not created yet are left out. Sync, so it runs on the main thread.
+ */
+async getScreenCaptureState() : Promise<WindowScreenCapture[]> {
+    return await TAURI_INVOKE("get_screen_capture_state");
+},
+/**
+ * What the OS reports for each data directory, and which of them are
+ * inside a cloud-synced folder.
+ */
+async getBackupExclusionStatus() : Promise<BackupExclusionStatus> {
+    return await TAURI_INVOKE("get_backup_exclusion_status");
+},
+/**
+ * Start recording from the default microphone.
+ * Spawns a dedicated thread that creates and owns the cpal::Stream.
+ */
+async startRecording() : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_recording") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop recording, resample to 16kHz mono WAV, and return the file path.
+ */
+async stopRecording() : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_recording") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns the current pipeline snapshot so a reloaded webview can rehydrate.
+ */
+async getPipelineStatus() : Promise<PipelineStatus> {
+    return await TAURI_INVOKE("get_pipeline_status");
+},
+/**
+ * Return the model catalogue with `downloaded` status filled in.
+ */
+async listWhisperModels() : Promise<Result<WhisperModelInfo[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_whisper_models") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Re-hashes every downloaded model against the checksum recorded at
+ * download and reports each one. Corrupt models are deleted so they can be
+ * downloaded again. Slow: every model file is read in full.
+ */
+async verifyAllModels() : Promise<Result<ModelVerification[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("verify_all_models") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Download a model by id, as a cancellable `model_download` job. Also emits
+ * the older `whisper-model-download-progress` events:
+ * `{ "model_id": "...", "percent": 0..100 }`.
+ */
+async downloadWhisperModel(modelId: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_whisper_model", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Continue an interrupted download from its `.tmp` partial using a Range request.
+ * Falls back to a full download if the server ignores the range.
+ */
+async resumeModelDownload(modelId: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resume_model_download", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Transcribe a 16kHz mono WAV file and delete it immediately after.
+ * 
+ * `language` is an optional ISO 639-1 code (e.g. "en", "no").
+ * Pass `None` to auto-detect. `model_id` selects which model to use;
+ * if the model is not downloaded it falls back to `whisper-large-v3-turbo`.
+ * With `auto_save`, the transcript is also saved to the `transcripts` table
+ * before returning (announced via `transcript-saved`), so it survives a
+ * webview crash between this call returning and the frontend saving it.
+ * The saved transcript records the audio duration, language, the model that
+ * actually ran and `session_label`, and is de-identified first when the
+ * `apply_to_auto_saved` de-identification preference is on. The returned
+ * text is never scrubbed.
+ * While the app is locked the text is saved (whatever `auto_save` says) but
+ * neither delivered nor returned; the call fails with `Locked`.
+ * `delivery` decides whether the text is put on the clipboard as soon as
+ * it's ready (announced via `transcript-copied`), pasted into the app that
+ * had focus when the quick pane opened, and which formatting profile it gets
+ * on the way (see `output`). The returned and saved text is never formatted.
+ */
+async transcribeAndDelete(filePath: string, language: string | null, modelId: string, autoSave: boolean | null, sessionLabel: string | null, delivery: TranscriptDelivery | null) : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("transcribe_and_delete", { filePath, language, modelId, autoSave, sessionLabel, delivery }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check whether the LLM model file exists locally and is valid.
+ */
+async checkLlmModel() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_llm_model") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Download the LLM model from HuggingFace if it doesn't exist locally.
+ * Emits `llm-model-download-progress` events with `{ percent: number }`.
+ */
+async downloadLlmModel() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_llm_model") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Generate a structured clinical note from a system prompt and user content.
+ * 
+ * Streams tokens via `llm-chunk` events. Emits `llm-done` on completion
+ * or `llm-error` on failure. Uses GBNF grammar to force valid JSON output.
+ */
+async generateNoteStream(systemPrompt: string, userContent: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("generate_note_stream", { systemPrompt, userContent }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Opens the main window (creating it if it was closed) and navigates to `route`.
+ * The optional `payload` is forwarded untouched with the `navigate` event.
+ */
+async openMainWindow(route: string, payload: JsonValue | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_main_window", { route, payload }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Called by the main window once its event listeners are registered.
+ * Flushes any events and navigation that were queued while the window was loading.
+ */
+async mainWindowReady() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("main_window_ready") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Checks for an update. The result is kept so
+ * `download_and_install_update` installs exactly what was shown.
+ */
+async checkForUpdates() : Promise<Result<UpdateInfo, UpdateError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_for_updates") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Downloads and installs the update from the last check (checking first if
+ * there was none), emitting `update-download-progress`. Doesn't restart;
+ * call `restart_to_update`. Returns the installed version.
+ * 
+ * While a recording or transcription is in progress this returns `Busy`
+ * and the install (and restart) runs by itself once it's done, unless
+ * `force` is set: then a recording is stopped first (see `prepare_forced`).
+ */
+async downloadAndInstallUpdate(force: boolean) : Promise<Result<string, UpdateError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_and_install_update", { force }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Switches `update_channel` and checks the new channel right away. Going
+ * from beta to stable reports no update until stable passes the installed
+ * version.
+ */
+async setUpdateChannel(channel: UpdateChannel) : Promise<Result<UpdateInfo, UpdateError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_update_channel", { channel }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Restarts into the installed update. Deferred like
+ * `download_and_install_update` while the pipeline is busy, unless `force`.
+ */
+async restartToUpdate(force: boolean) : Promise<Result<null, UpdateError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restart_to_update", { force }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Cancels an install waiting for the pipeline or counting down. Returns
+ * whether one was pending.
+ */
+async cancelPendingUpdateInstall() : Promise<boolean> {
+    return await TAURI_INVOKE("cancel_pending_update_install");
+},
+/**
+ * Reports the schema hash of the commands, types and events this build
+ * exposes, to compare with `BINDINGS_VERSION` in the frontend's bindings.
+ */
+async getBindingsVersion() : Promise<string> {
+    return await TAURI_INVOKE("get_bindings_version");
+},
+/**
+ * Count, p50/p95 duration and error rate of each timed command, over the
+ * most recent calls. Empty while command metrics are off.
+ */
+async getCommandMetrics() : Promise<CommandMetrics[]> {
+    return await TAURI_INVOKE("get_command_metrics");
+},
+/**
+ * App version and channel, OS and hardware, the default model, database and
+ * disk usage, permissions and privacy-relevant preferences, for support.
+ * Readings that fail are reported as unknown; the command itself only fails
+ * if the collection task dies.
+ */
+async getDiagnostics() : Promise<Result<DiagnosticsReport, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_diagnostics") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Writes a ZIP for a bug report to `destination_path`, which must come from
+ * the save dialog and end in `.zip`: the last `LOG_BUNDLE_DAYS` days of log
+ * files, scrubbed (see `utils::log_scrub`), and the `get_diagnostics` report
+ * as `diagnostics.json`. Older log content is left out past
+ * `MAX_BUNDLE_LOG_BYTES`. The export is audited. Returns what went in, so
+ * the user can see what they're about to send.
+ */
+async exportLogBundle(destinationPath: string) : Promise<Result<LogBundleSummary, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_log_bundle", { destinationPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns a running or recently finished job.
+ */
+async getJob(id: string) : Promise<Result<Job, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_job", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists running and recently finished jobs, oldest first.
+ */
+async listJobs() : Promise<Job[]> {
+    return await TAURI_INVOKE("list_jobs");
+},
+/**
+ * Asks a running job to stop. The job finishes as cancelled once its work
+ * notices, announced with `job-progress`. Cancelling a finished job does
+ * nothing.
+ */
+async cancelJob(id: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_job", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Checks, one after the other, everything a dictation depends on. Each
+ * result is also sent as `health-check-progress` when its check finishes.
+ */
+async runHealthCheck() : Promise<Result<HealthCheckResult[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_health_check") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
+export const events = __makeEvents__<{
+appLocked: AppLocked,
+autoStoppedAndLocked: AutoStoppedAndLocked,
+clipboardCleared: ClipboardCleared,
+dataDirCloudSynced: DataDirCloudSynced,
+databaseRecovered: DatabaseRecovered,
+databaseTransferProgress: DatabaseTransferProgress,
+databaseUnavailable: DatabaseUnavailable,
+draftsAvailable: DraftsAvailable,
+fileAppendFailed: FileAppendFailed,
+healthCheckProgress: HealthCheckProgress,
+jobProgress: JobProgress,
+llmChunk: LlmChunk,
+llmDone: LlmDone,
+llmError: LlmError,
+llmModelDownloadProgress: LlmModelDownloadProgress,
+lowDiskSpace: LowDiskSpace,
+modelDownloadComplete: ModelDownloadComplete,
+modelDownloadFailed: ModelDownloadFailed,
+modelDownloadResumable: ModelDownloadResumable,
+navigate: Navigate,
+notificationSuppressed: NotificationSuppressed,
+pasteFallback: PasteFallback,
+pipelineStatus: PipelineStatus,
+preferencesChanged: PreferencesChanged,
+previousSessionCrashed: PreviousSessionCrashed,
+quickPaneHideRequested: QuickPaneHideRequested,
+quickPaneVisibilityChanged: QuickPaneVisibilityChanged,
+recordingsRepaired: RecordingsRepaired,
+recoveryDegraded: RecoveryDegraded,
+recoveryEvicted: RecoveryEvicted,
+recoveryExpired: RecoveryExpired,
+shutdownBlocked: ShutdownBlocked,
+transcriptCopied: TranscriptCopied,
+transcriptExportProgress: TranscriptExportProgress,
+transcriptSaved: TranscriptSaved,
+updateAvailable: UpdateAvailable,
+updateDownloadProgress: UpdateDownloadProgress,
+updateInstallCountdown: UpdateInstallCountdown,
+whisperModelDownloadProgress: WhisperModelDownloadProgress
+}>({
+appLocked: "app-locked",
+autoStoppedAndLocked: "auto-stopped-and-locked",
+clipboardCleared: "clipboard-cleared",
+dataDirCloudSynced: "data-dir-cloud-synced",
+databaseRecovered: "database-recovered",
+databaseTransferProgress: "database-transfer-progress",
+databaseUnavailable: "database-unavailable",
+draftsAvailable: "drafts-available",
+fileAppendFailed: "file-append-failed",
+healthCheckProgress: "health-check-progress",
+jobProgress: "job-progress",
+llmChunk: "llm-chunk",
+llmDone: "llm-done",
+llmError: "llm-error",
+llmModelDownloadProgress: "llm-model-download-progress",
+lowDiskSpace: "low-disk-space",
+modelDownloadComplete: "model-download-complete",
+modelDownloadFailed: "model-download-failed",
+modelDownloadResumable: "model-download-resumable",
+navigate: "navigate",
+notificationSuppressed: "notification-suppressed",
+pasteFallback: "paste-fallback",
+pipelineStatus: "pipeline-status",
+preferencesChanged: "preferences-changed",
+previousSessionCrashed: "previous-session-crashed",
+quickPaneHideRequested: "quick-pane-hide-requested",
+quickPaneVisibilityChanged: "quick-pane-visibility-changed",
+recordingsRepaired: "recordings-repaired",
+recoveryDegraded: "recovery-degraded",
+recoveryEvicted: "recovery-evicted",
+recoveryExpired: "recovery-expired",
+shutdownBlocked: "shutdown-blocked",
+transcriptCopied: "transcript-copied",
+transcriptExportProgress: "transcript-export-progress",
+transcriptSaved: "transcript-saved",
+updateAvailable: "update-available",
+updateDownloadProgress: "update-download-progress",
+updateInstallCountdown: "update-install-countdown",
+whisperModelDownloadProgress: "whisper-model-download-progress"
+})
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+/**
+ * The app build and how it runs.
+ */
+export type AppDiagnostics = { version: string; update_channel: UpdateChannel; portable: boolean }
+/**
+ * Error type for recording, transcription, preferences, quick pane and
+ * notification commands (typed for frontend matching)
+ */
+export type AppError = 
+/**
+ * There's no microphone to record from
+ */
+{ type: "NoInputDevice" } | 
+/**
+ * A recording is already in progress
+ */
+{ type: "AlreadyRecording" } | 
+/**
+ * There's no recording in progress to stop
+ */
+{ type: "NotRecording" } | 
+/**
+ * The recording or file holds no audio
+ */
+{ type: "NoAudio" } | 
+/**
+ * The OS denied access to the microphone
+ */
+{ type: "MicrophonePermissionDenied" } | 
+/**
+ * macOS Accessibility access isn't granted
+ */
+{ type: "AccessibilityPermissionDenied" } | 
+/**
+ * The model isn't downloaded; download it first
+ */
+{ type: "ModelNotDownloaded"; model_id: string } | 
+/**
+ * A model file doesn't match its expected size or checksum. A corrupt
+ * model is deleted so it can be downloaded again.
+ */
+{ type: "ChecksumMismatch"; model_id: string } | 
+/**
+ * The network is unreachable or the connection kept dropping
+ */
+{ type: "Offline" } | 
+/**
+ * Not enough free disk space. Sizes are in bytes, None when the OS only
+ * reported the disk as full.
+ */
+{ type: "DiskFull"; required: number | null; available: number | null } | 
+/**
+ * The app is locked; unlock it first
+ */
+{ type: "Locked" } | 
+/**
+ * The operation was cancelled before it finished
+ */
+{ type: "Cancelled" } | 
+/**
+ * Not available on this platform
+ */
+{ type: "Unsupported" } | 
+/**
+ * Input validation failed
+ */
+{ type: "InvalidInput"; message: string } | 
+/**
+ * Any other failure
+ */
+{ type: "Internal"; message: string }
+/**
+ * Error types for app lock operations (typed for frontend matching)
+ */
+export type AppLockError = 
+/**
+ * No passcode is set
+ */
+{ type: "NotEnabled" } | 
+/**
+ * The passcode didn't match. Further attempts wait `retry_after_secs`.
+ */
+{ type: "WrongPasscode"; failed_attempts: number; retry_after_secs: number } | 
+/**
+ * Too many failed attempts; try again after `retry_after_secs`
+ */
+{ type: "RateLimited"; retry_after_secs: number } | 
+/**
+ * The new passcode is too short
+ */
+{ type: "PasscodeTooShort"; min_length: number } | 
+/**
+ * The keychain couldn't be read or written
+ */
+{ type: "StorageError"; message: string } | 
+/**
+ * Biometric unlock isn't available or didn't succeed; use the passcode
+ */
+{ type: "BiometricsUnavailable"; reason: BiometricFallbackReason }
+/**
+ * Why the app locked.
+ */
+export type AppLockReason = 
+/**
+ * `lock_app` was called
+ */
+"manual" | 
+/**
+ * No activity for `auto_lock_minutes`
+ */
+"idle" | 
+/**
+ * A recording ran `max_idle_while_recording_minutes` without activity
+ */
+"idle_while_recording"
+/**
+ * Result of `get_app_lock_status`.
+ */
+export type AppLockStatus = { 
+/**
+ * A passcode is set
+ */
+enabled: boolean; locked: boolean; 
+/**
+ * Seconds before another unlock attempt is accepted (0 when allowed now)
+ */
+retry_after_secs: number }
+/**
+ * Payload for the `app-locked` event.
+ */
+export type AppLocked = { reason: AppLockReason }
+/**
+ * Application preferences that persist to disk.
+ * Only contains settings that should be saved between sessions.
+ * Missing fields fall back to their defaults so older preference files keep loading.
+ */
+export type AppPreferences = { theme: string; 
+/**
+ * Global shortcut for quick pane (e.g., "CommandOrControl+Shift+.")
+ * If None, uses the default shortcut
+ */
+quick_pane_shortcut: string | null; 
+/**
+ * User's preferred language (e.g., "en", "es", "de")
+ * If None, uses system locale detection
+ */
+language: string | null; 
+/**
+ * Whether showing the quick pane makes it the key window so typing works immediately.
+ * When false the pane is ordered front without taking keyboard focus.
+ */
+quick_pane_focus_keyboard: boolean; 
+/**
+ * Double-tap modifier that toggles the quick pane, in addition to the shortcut.
+ * If None, double-tap activation is disabled and no event listener runs.
+ */
+double_tap_modifier: DoubleTapModifier | null; 
+/**
+ * Show/hide animation and background material for the quick pane
+ */
+quick_pane_appearance: QuickPaneAppearance; 
+/**
+ * Webview zoom factor for the quick pane only (0.75-2.0)
+ */
+quick_pane_zoom: number; 
+/**
+ * Whether the quick pane window grows/shrinks with its zoom so content doesn't overflow
+ */
+quick_pane_scale_window_with_zoom: boolean; 
+/**
+ * Where the quick pane appears when shown
+ */
+quick_pane_position: QuickPanePosition; 
+/**
+ * What happens when the pane would auto-hide with an active recording or unsaved result
+ */
+quick_pane_dirty_hide: DirtyHideBehavior; 
+/**
+ * Hours before recovery sessions, drafts and stranded recordings are securely deleted
+ */
+recovery_retention_hours: number; 
+/**
+ * Size (MB) at which a recording's recovery file keeps only recent audio
+ */
+recovery_session_cap_mb: number; 
+/**
+ * Minutes of recent audio kept once a recovery file hits its cap
+ */
+recovery_rolling_window_minutes: number; 
+/**
+ * Size (MB) of all recovery files combined before the oldest are evicted
+ */
+recovery_dir_cap_mb: number; 
+/**
+ * Which system notifications are sent, and when
+ */
+notifications: NotificationPreferences; 
+/**
+ * Minutes of recording the free disk space must cover before a low-disk warning
+ */
+low_disk_warning_minutes: number; 
+/**
+ * Minutes of recording the free disk space must cover for recording to start at all
+ */
+low_disk_block_minutes: number; 
+/**
+ * Minutes between "still recording" reminders. 0 disables them.
+ */
+recording_reminder_minutes: number; 
+/**
+ * Check for app updates in the background (at most once a day)
+ */
+check_updates_automatically: boolean; 
+/**
+ * Which releases update checks offer
+ */
+update_channel: UpdateChannel; 
+/**
+ * Start with only the tray icon and shortcuts, leaving the main window closed
+ */
+start_hidden: boolean; 
+/**
+ * Time commands in memory for the diagnostics screen (never their arguments or results)
+ */
+command_metrics_enabled: boolean; 
+/**
+ * Days audit log entries are kept (never less than `MIN_AUDIT_RETENTION_DAYS`)
+ */
+audit_retention_days: number; 
+/**
+ * Days deleted transcripts stay in the trash before they're purged (at least 1)
+ */
+trash_retention_days: number; 
+/**
+ * Rules for the optional de-identification pass on save
+ */
+deidentification: DeidentificationRules; 
+/**
+ * Copy transcription results to the clipboard as soon as they're ready
+ */
+auto_copy_to_clipboard: boolean; 
+/**
+ * Paste transcription results into the app that had focus when the quick pane opened
+ */
+auto_paste_into_previous_app: boolean; 
+/**
+ * Seconds before a transcript the app copied is cleared from the clipboard. 0 disables.
+ */
+clipboard_clear_seconds: number; 
+/**
+ * Typing speed for `type_out_text` when the call doesn't give one
+ */
+type_out_chars_per_second: number; 
+/**
+ * How transcripts are shaped before they're copied or pasted
+ */
+output_formatting: FormattingOptions; 
+/**
+ * Appending finished transcriptions to a plain-text daily note
+ */
+append_to_file: AppendToFileOptions; 
+/**
+ * Mark copied transcripts so clipboard history tools skip them
+ */
+conceal_clipboard_from_history: boolean; 
+/**
+ * Flavors written when the app copies a transcript
+ */
+clipboard_format: ClipboardFormat; 
+/**
+ * Minutes without activity before the app locks, when a passcode is set. 0 disables.
+ */
+auto_lock_minutes: number; 
+/**
+ * Offer Touch ID / Windows Hello on the lock screen. The passcode always works too.
+ */
+biometric_unlock: boolean; 
+/**
+ * Hide the main window and quick pane from screenshots, recording and screen sharing
+ */
+block_screen_capture: boolean; 
+/**
+ * Minutes a recording may run without user input or commands before it's
+ * stopped, saved as a draft and the app locked. 0 disables.
+ */
+max_idle_while_recording_minutes: number; 
+/**
+ * Also keep the database directory out of Time Machine and iCloud
+ * backups (macOS). The cache and recovery directories always are.
+ */
+exclude_database_from_backups: boolean }
+/**
+ * Where and how transcriptions are appended to a plain-text file.
+ */
+export type AppendToFileOptions = { enabled: boolean; 
+/**
+ * Target file, chosen through a file dialog. A `{date}` in the path is
+ * replaced with today's date (YYYY-MM-DD), starting a new file daily; its
+ * folder must then have been chosen through the folder picker.
+ */
+path: string | null; 
+/**
+ * Entry written per transcription. `{date}`, `{time}` and `{text}` are
+ * replaced; `{text}` is added at the end if missing.
+ */
+template: string }
+/**
+ * An audit log entry. `actor` is the OS account the app ran under.
+ */
+export type AuditEvent = { timestamp_ms: number; actor: string; kind: AuditEventKind; subject_id: string | null; detail: string }
+/**
+ * Kind of PHI-touching operation recorded in the audit log.
+ */
+export type AuditEventKind = "recovery_purged" | 
+/**
+ * Recovery audio was exported outside the app (detail holds the destination)
+ */
+"recovery_exported" | 
+/**
+ * Summary of one retention sweep (counts only)
+ */
+"retention_sweep" | "transcript_saved" | "transcript_viewed" | 
+/**
+ * A transcript was edited (detail lists the changed fields)
+ */
+"transcript_edited" | 
+/**
+ * A transcript was moved to the trash
+ */
+"transcript_deleted" | 
+/**
+ * A transcript was taken back out of the trash
+ */
+"transcript_restored" | 
+/**
+ * A transcript was permanently deleted (detail says whether by schedule)
+ */
+"transcript_purged" | 
+/**
+ * A transcript was exported to a file (detail holds the format, whether it
+ * was encrypted, and the destination)
+ */
+"transcript_exported" | 
+/**
+ * An encrypted export was decrypted (detail holds the source and destination)
+ */
+"export_decrypted" | 
+/**
+ * A compliance report was written (detail holds the format and destination)
+ */
+"compliance_report_generated" | 
+/**
+ * A database backup was written (detail holds the destination)
+ */
+"database_backed_up" | 
+/**
+ * The database was replaced from a backup (detail holds the source)
+ */
+"database_restored" | 
+/**
+ * The audit log was exported (detail holds the destination)
+ */
+"audit_log_exported" | 
+/**
+ * Audit entries past their retention were removed (detail holds the count)
+ */
+"audit_log_trimmed" | 
+/**
+ * A setting affecting data retention or exposure changed (detail holds old and new values)
+ */
+"privacy_setting_changed" | 
+/**
+ * A support log bundle was written (detail holds the destination and file count)
+ */
+"log_bundle_exported"
+/**
+ * One page of audit entries, newest first.
+ */
+export type AuditEventPage = { events: AuditEvent[]; 
+/**
+ * Number of entries matching the filter across all pages
+ */
+total: number }
+/**
+ * File format for `export_audit_log`.
+ */
+export type AuditExportFormat = "csv" | "json"
+/**
+ * Filter for `list_audit_events`. All fields are optional and combined with AND.
+ */
+export type AuditFilter = { kind: AuditEventKind | null; subject_id: string | null; 
+/**
+ * Only entries at or after this time (ms since epoch)
+ */
+from_ms: number | null; 
+/**
+ * Only entries before this time (ms since epoch)
+ */
+to_ms: number | null }
+/**
+ * Payload for the `auto-stopped-and-locked` event, sent after an unattended
+ * recording was stopped.
+ */
+export type AutoStoppedAndLocked = { 
+/**
+ * Draft holding the transcription; None when nothing could be saved
+ */
+draft_id: string | null; 
+/**
+ * Minutes without activity that triggered it
+ */
+idle_minutes: number; 
+/**
+ * False when no passcode is set, so the app couldn't lock
+ */
+locked: boolean }
+/**
+ * Whether a directory is kept out of backups.
+ */
+export type BackupExclusionState = 
+/**
+ * Excluded from Time Machine and iCloud backups
+ */
+{ type: "excluded" } | 
+/**
+ * Backed up; `exclude_database_from_backups` is off, or the flag was cleared
+ */
+{ type: "included" } | 
+/**
+ * The platform has no exclusion the app can set
+ */
+{ type: "unsupported"; reason: string } | { type: "error"; message: string }
+/**
+ * Result of `get_backup_exclusion_status`.
+ */
+export type BackupExclusionStatus = { directories: DirectoryBackupExclusion[]; 
+/**
+ * Directories that leave the device through a sync service
+ */
+cloud_synced: CloudSyncedDirectory[] }
+/**
+ * Result of `get_biometric_availability`.
+ */
+export type BiometricAvailability = { type: "available"; kind: BiometricKind } | { type: "unavailable"; reason: BiometricFallbackReason }
+/**
+ * Why biometric unlock isn't offered or didn't unlock. The passcode still works.
+ */
+export type BiometricFallbackReason = 
+/**
+ * The OS has no supported biometric API (Linux)
+ */
+"unsupported" | 
+/**
+ * The `biometric_unlock` preference is off
+ */
+"disabled" | 
+/**
+ * No biometric hardware, or it's turned off by policy
+ */
+"unavailable" | 
+/**
+ * No fingerprint or face is enrolled
+ */
+"not_enrolled" | 
+/**
+ * Too many failed matches; the OS requires its password first
+ */
+"locked_out" | 
+/**
+ * The prompt was cancelled by the user or the system
+ */
+"cancelled" | 
+/**
+ * The biometric didn't match
+ */
+"failed"
+/**
+ * The OS biometric prompt behind `unlock_with_biometrics`.
+ */
+export type BiometricKind = "touch_id" | "face_id" | "optic_id" | "windows_hello"
+/**
+ * Outcome of `export_all_transcripts`.
+ */
+export type BulkExportSummary = { exported: number; total: number; 
+/**
+ * The export was cancelled; the partial archive was deleted
+ */
+cancelled: boolean }
+/**
+ * Result of a secure wipe of the cache directory.
+ */
+export type CacheWipeReport = { files_wiped: number; bytes_wiped: number; 
+/**
+ * Files left in place: audio of a running recording or transcription,
+ * webview files while the app runs, and files that couldn't be deleted
+ */
+files_skipped: number; duration_ms: number; 
+/**
+ * False when the time budget ran out; the rest is wiped next start
+ */
+complete: boolean }
+/**
+ * A category `clear_category` can empty without losing anything the user
+ * keeps.
+ */
+export type ClearableStorage = "cache" | "logs"
+/**
+ * Payload for the `clipboard-cleared` event, sent when a transcript the app
+ * copied was cleared from the clipboard.
+ */
+export type ClipboardCleared = { 
+/**
+ * Delay the clear ran after
+ */
+after_secs: number }
+/**
+ * What the app puts on the clipboard when it copies a transcript.
+ */
+export type ClipboardFormat = 
+/**
+ * Plain text only
+ */
+"plain" | 
+/**
+ * Plain text plus minimal HTML (paragraphs, line breaks, bold section
+ * headers), so the destination picks
+ */
+"rich"
+/**
+ * A data directory inside a cloud-synced folder such as OneDrive.
+ */
+export type CloudSyncedDirectory = { directory: DataDirectory; path: string; 
+/**
+ * Name of the sync service, e.g. `OneDrive`
+ */
+provider: string }
+/**
+ * How one command performed over the recent calls kept in memory.
+ */
+export type CommandMetrics = { command: string; 
+/**
+ * Calls recorded
+ */
+count: number; p50_ms: number; p95_ms: number; 
+/**
+ * Share of calls that failed, 0–1
+ */
+error_rate: number; 
+/**
+ * Error variant of the most recent failure
+ */
+last_error: string | null; 
+/**
+ * Largest response seen (None for commands whose responses aren't sized)
+ */
+largest_response: PayloadSize | null }
+/**
+ * Saved transcripts on one local calendar day.
+ */
+export type DailyUsage = { 
+/**
+ * `YYYY-MM-DD`
+ */
+date: string; transcripts: number; audio_secs: number; words: number }
+/**
+ * Payload for the `data-dir-cloud-synced` event, sent at startup when data
+ * directories are inside a cloud-synced folder.
+ */
+export type DataDirCloudSynced = { directories: CloudSyncedDirectory[] }
+/**
+ * A directory holding PHI.
+ */
+export type DataDirectory = 
+/**
+ * Downloads and other cached files
+ */
+"cache" | 
+/**
+ * Encrypted in-progress recordings and drafts
+ */
+"recovery" | 
+/**
+ * The transcript database, alongside preferences
+ */
+"database"
+/**
+ * Database operation reported by `database-transfer-progress`.
+ */
+export type DatabaseOperation = "backup" | "restore"
+/**
+ * Payload for the `database-recovered` event, sent at startup when the
+ * database failed its integrity check and was rebuilt from readable rows.
+ */
+export type DatabaseRecovered = { transcripts_salvaged: number; drafts_salvaged: number; audit_entries_salvaged: number; 
+/**
+ * Where the damaged original was kept
+ */
+corrupt_copy_path: string; 
+/**
+ * First problems reported by `PRAGMA integrity_check`
+ */
+problems: string[] }
+/**
+ * State of the encrypted backend database, checked once at startup.
+ */
+export type DatabaseStatus = 
+/**
+ * Opened and decrypted
+ */
+{ type: "Ready" } | 
+/**
+ * The OS keychain couldn't be read (locked, or no Secret Service on Linux)
+ */
+{ type: "KeychainUnavailable"; message: string } | 
+/**
+ * The database exists but its key is gone from the keychain
+ */
+{ type: "KeyMissing" } | 
+/**
+ * The keychain key doesn't decrypt the database
+ */
+{ type: "KeyMismatch" } | 
+/**
+ * Any other failure opening the database
+ */
+{ type: "Failed"; message: string } | 
+/**
+ * Not opened yet: the app started locked, and the key is read on unlock
+ */
+{ type: "Locked" }
+/**
+ * Payload for the `database-transfer-progress` event, sent while a backup
+ * or restore copies the database.
+ */
+export type DatabaseTransferProgress = { operation: DatabaseOperation; percent: number }
+/**
+ * The `database-unavailable` event, sent at startup when the encrypted
+ * database couldn't be opened.
+ */
+export type DatabaseUnavailable = DatabaseStatus
+/**
+ * Creation-time window for bulk exports. Either bound may be omitted.
+ */
+export type DateRange = { 
+/**
+ * Inclusive start (ms since epoch)
+ */
+from_ms: number | null; 
+/**
+ * Exclusive end (ms since epoch)
+ */
+to_ms: number | null }
+/**
+ * How many matches of each category were replaced. The removed values are
+ * never returned.
+ */
+export type DeidentificationReport = { names: number; dates_of_birth: number; phone_numbers: number; national_ids: number; email_addresses: number; custom: number }
+/**
+ * Which identifiers the de-identification pass replaces.
+ */
+export type DeidentificationRules = { 
+/**
+ * Dates following "DOB", "date of birth", "born" and the like
+ */
+dates_of_birth: boolean; phone_numbers: boolean; 
+/**
+ * SSN-style (123-45-6789) and Nordic personal numbers
+ */
+national_ids: boolean; email_addresses: boolean; 
+/**
+ * Names to replace wherever they appear (case-insensitive, whole words)
+ */
+names: string[]; 
+/**
+ * Extra regular expressions; matches become `[REDACTED]`
+ */
+custom_patterns: string[]; 
+/**
+ * Also de-identify transcripts auto-saved after transcription
+ */
+apply_to_auto_saved: boolean }
+/**
+ * Result of `deidentify_text`.
+ */
+export type DeidentifiedText = { text: string; report: DeidentificationReport; 
+/**
+ * Identifies the rules applied, as stored on de-identified transcripts
+ */
+rule_set: string }
+/**
+ * Result of `get_diagnostics`: what support asks first. Carries no
+ * transcript content.
+ */
+export type DiagnosticsReport = { 
+/**
+ * Milliseconds since the Unix epoch
+ */
+generated_at_ms: number; app: AppDiagnostics; system: SystemDiagnostics; model: ModelDiagnostics; storage: StorageDiagnostics; permissions: PermissionDiagnostics; privacy: PrivacyDiagnostics }
+/**
+ * One directory in `get_backup_exclusion_status`.
+ */
+export type DirectoryBackupExclusion = { directory: DataDirectory; path: string; state: BackupExclusionState }
+/**
+ * Auto-hide behavior while the quick pane has an active recording or unsaved result.
+ */
+export type DirtyHideBehavior = 
+/**
+ * Keep the pane open; blur and Escape are ignored
+ */
+"block" | 
+/**
+ * Emit `quick-pane-hide-requested` and let the frontend decide
+ */
+"ask_frontend"
+/**
+ * What was about to write to disk when a low-disk-space check failed.
+ */
+export type DiskSpaceContext = "recording" | "model_download"
+/**
+ * Double-tap modifier activation settings for the quick pane.
+ */
+export type DoubleTapModifier = { key: ModifierKey; 
+/**
+ * Maximum time between the two taps in milliseconds
+ */
+interval_ms: number }
+/**
+ * Payload for the `drafts-available` event, emitted at startup when unsaved drafts exist.
+ */
+export type DraftsAvailable = { draft_ids: string[] }
+/**
+ * Error types for the emergency wipe (typed for frontend matching)
+ */
+export type EmergencyWipeError = 
+/**
+ * The confirmation phrase didn't match `expected`; nothing was wiped
+ */
+{ type: "ConfirmationMismatch"; expected: string } | 
+/**
+ * A wipe has already run; the app is restarting
+ */
+{ type: "AlreadyRunning" } | 
+/**
+ * The app is locked; unlock it first
+ */
+{ type: "Locked" }
+/**
+ * Result of `emergency_wipe`. The app restarts shortly after returning it.
+ */
+export type EmergencyWipeReport = { results: WipeCategoryResult[] }
+/**
+ * Passphrase encryption for an export, which is then written as a `.psenc`
+ * envelope (see `utils::envelope`) that `decrypt_export` opens.
+ */
+export type ExportEncryption = { passphrase: string }
+/**
+ * Payload for the `file-append-failed` event, sent when a transcription
+ * couldn't be appended to the daily note file. The transcript was copied to
+ * the clipboard instead.
+ */
+export type FileAppendFailed = { path: string; message: string }
+/**
+ * A formatting profile and its settings.
+ */
+export type FormattingOptions = { profile: FormattingProfile; 
+/**
+ * Gap between segments (seconds) that `paragraphs-on-pause` splits on
+ */
+paragraph_pause_secs: number }
+/**
+ * Shape given to a transcript before it's copied or pasted.
+ */
+export type FormattingProfile = 
+/**
+ * Exactly as transcribed
+ */
+"verbatim" | 
+/**
+ * All whitespace and line breaks collapsed to single spaces
+ */
+"single-paragraph" | 
+/**
+ * A new paragraph wherever the speaker paused; needs segment timestamps
+ */
+"paragraphs-on-pause" | 
+/**
+ * One "- " bullet per sentence
+ */
+"bulleted-sentences"
+/**
+ * A check run by `run_health_check`, in the order they run.
+ */
+export type HealthCheckKind = 
+/**
+ * Microphone permission and an input device that delivers audio
+ */
+"microphone" | 
+/**
+ * The default model is downloaded and matches its checksum
+ */
+"model" | 
+/**
+ * whisper loads the default model
+ */
+"whisper" | 
+/**
+ * The database is open and passes `quick_check`
+ */
+"database" | 
+/**
+ * Crash recovery can write its files
+ */
+"recovery_directory" | 
+/**
+ * Free space against the low-disk thresholds
+ */
+"disk_space" | 
+/**
+ * The quick pane shortcut is registered with the OS
+ */
+"shortcut" | "notifications"
+/**
+ * The `health-check-progress` event, sent as each check of
+ * `run_health_check` finishes.
+ */
+export type HealthCheckProgress = { result: HealthCheckResult; 
+/**
+ * Checks finished so far, this one included
+ */
+done: number; total: number }
+export type HealthCheckResult = { check: HealthCheckKind; status: HealthStatus; 
+/**
+ * What was found, for display
+ */
+detail: string }
+export type HealthStatus = "pass" | 
+/**
+ * Works, but something needs the user's attention
+ */
+"warn" | "fail"
+/**
+ * Result of `run_integrity_check`.
+ */
+export type IntegrityReport = { ok: boolean; 
+/**
+ * First problems reported by `PRAGMA integrity_check` (empty when ok)
+ */
+problems: string[] }
+/**
+ * A long-running job, from `get_job` and `list_jobs`. Timestamps are
+ * milliseconds since the Unix epoch.
+ */
+export type Job = { id: string; kind: JobKind; status: JobStatus; 
+/**
+ * Whether `cancel_job` can stop it
+ */
+cancellable: boolean; created_at_ms: number; updated_at_ms: number }
+/**
+ * What a long-running job does.
+ */
+export type JobKind = "transcription" | "model_download"
+/**
+ * What a finished job produced. Never carries transcript text.
+ */
+export type JobOutput = { type: "transcription"; model_id: string; text_length: number } | { type: "model_download"; model_id: string }
+/**
+ * The `job-progress` event, sent when a job starts, progresses and finishes.
+ */
+export type JobProgress = Job
+/**
+ * Where a job is.
+ */
+export type JobStatus = 
+/**
+ * `percent` is 0–100, None until the job reports any
+ */
+{ status: "running"; percent: number | null } | { status: "succeeded"; output: JobOutput } | { status: "failed"; error: AppError } | { status: "cancelled" }
+export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+/**
+ * The `llm-chunk` event, carrying one generated token.
+ */
+export type LlmChunk = string
+/**
+ * The `llm-done` event, sent once generation finished.
+ */
+export type LlmDone = null
+/**
+ * The `llm-error` event, carrying why generation failed.
+ */
+export type LlmError = string
+/**
+ * Payload for the `llm-model-download-progress` event.
+ */
+export type LlmModelDownloadProgress = { 
+/**
+ * 0–100
+ */
+percent: number }
+/**
+ * Result of `export_log_bundle`: what went into the ZIP.
+ */
+export type LogBundleSummary = { 
+/**
+ * Log files included, by name, newest first
+ */
+files: string[]; 
+/**
+ * Size of the written ZIP in bytes
+ */
+bytes: number; 
+/**
+ * Account names replaced in paths and text
+ */
+account_names_scrubbed: number; 
+/**
+ * Lines marked as possibly holding PHI, replaced whole
+ */
+lines_redacted: number; 
+/**
+ * Webview console lines left out
+ */
+lines_dropped: number; 
+/**
+ * Whether older log content was left out to stay under the size cap
+ */
+truncated: boolean }
+/**
+ * Payload for the `low-disk-space` event.
+ */
+export type LowDiskSpace = { context: DiskSpaceContext; available_bytes: number; 
+/**
+ * Bytes needed to be comfortable (the warning threshold)
+ */
+required_bytes: number; 
+/**
+ * True when the operation was refused rather than just warned about
+ */
+blocked: boolean }
+/**
+ * The model transcriptions use unless the caller picks another.
+ */
+export type ModelDiagnostics = { model_id: string; name: string; downloaded: boolean; 
+/**
+ * Whether the file still matches the checksum recorded at download
+ * (by size and modification time; nothing is re-hashed)
+ */
+verified: Probe<boolean>; size_bytes: Probe<number> }
+/**
+ * Payload for the `model-download-complete` event.
+ */
+export type ModelDownloadComplete = { model_id: string }
+/**
+ * Why a model download ultimately failed.
+ */
+export type ModelDownloadErrorKind = 
+/**
+ * Network unreachable or the connection kept dropping
+ */
+"offline" | 
+/**
+ * The downloaded file didn't match the expected length
+ */
+"checksum" | 
+/**
+ * Not enough disk space for the model
+ */
+"disk_full" | "other"
+/**
+ * Payload for the `model-download-failed` event, sent once retries are exhausted.
+ */
+export type ModelDownloadFailed = { model_id: string; kind: ModelDownloadErrorKind; message: string }
+/**
+ * Payload for the `model-download-resumable` event, sent at startup for each
+ * interrupted model download that `resume_model_download` can continue.
+ */
+export type ModelDownloadResumable = { model_id: string; bytes_done: number; total_bytes: number }
+/**
+ * Integrity of a model file, from `verify_all_models`.
+ */
+export type ModelIntegrity = 
+/**
+ * Matches the checksum recorded when it was downloaded
+ */
+{ type: "verified" } | { type: "not_downloaded" } | 
+/**
+ * Changed since download; it was deleted and has to be downloaded again
+ */
+{ type: "corrupt" } | 
+/**
+ * The file couldn't be read
+ */
+{ type: "error"; message: string }
+/**
+ * A file in the models folder.
+ */
+export type ModelStorage = { file_name: string; bytes: number }
+/**
+ * How many transcriptions a model ran in the period.
+ */
+export type ModelUsage = { model_id: string; transcriptions: number }
+/**
+ * One model's entry in the `verify_all_models` report.
+ */
+export type ModelVerification = { model_id: string; status: ModelIntegrity }
+/**
+ * A specific (sided) modifier key. Command maps to the Windows key and
+ * Option to Alt on non-macOS platforms.
+ */
+export type ModifierKey = "left_command" | "right_command" | "left_option" | "right_option" | "left_shift" | "right_shift" | "left_control" | "right_control"
+/**
+ * The `navigate` event, carrying a route to the main window.
+ */
+export type Navigate = NavigatePayload
+/**
+ * Payload for the `navigate` event sent to the main window.
+ */
+export type NavigatePayload = { 
+/**
+ * Frontend route to open (e.g. "/notes/123")
+ */
+route: string; 
+/**
+ * Optional data for the target view, passed through untouched
+ */
+payload: JsonValue | null }
+/**
+ * Kind of system notification, so users can mute each independently.
+ */
+export type NotificationCategory = 
+/**
+ * A transcription finished while the app was in the background
+ */
+"transcription_complete" | 
+/**
+ * Free disk space is too low for a recording or model download
+ */
+"low_disk_space" | 
+/**
+ * The microphone has been recording for a long time
+ */
+"recording_reminder" | 
+/**
+ * A speech model download finished or failed
+ */
+"model_download" | 
+/**
+ * A new app version is available
+ */
+"update_available" | 
+/**
+ * A command-line or link request was queued or couldn't run
+ */
+"automation"
+/**
+ * System notification settings.
+ */
+export type NotificationPreferences = { 
+/**
+ * Master switch for all system notifications
+ */
+enabled: boolean; 
+/**
+ * Categories the user turned off
+ */
+muted_categories: NotificationCategory[]; 
+/**
+ * If set, notifications are suppressed during this window
+ */
+quiet_hours: QuietHours | null; 
+/**
+ * Categories still sent during quiet hours. Defaults to the recording
+ * reminder, since a forgotten open microphone is a privacy problem.
+ */
+quiet_hours_exempt: NotificationCategory[]; 
+/**
+ * Sound played with notifications
+ */
+sound: NotificationSound }
+/**
+ * Notification sound. Named system sounds (e.g. "Glass") are macOS-only and
+ * fall back to the default sound elsewhere.
+ */
+export type NotificationSound = { type: "default" } | 
+/**
+ * Silent notifications (e.g. clinic exam rooms)
+ */
+{ type: "none" } | { type: "named"; name: string }
+/**
+ * Payload for the `notification-suppressed` event, sent instead of a system
+ * notification while the OS is in Do Not Disturb so the app can show it in-app.
+ */
+export type NotificationSuppressed = { category: NotificationCategory; title: string; body: string }
+/**
+ * Page window for list queries.
+ */
+export type Pagination = { limit: number; offset: number }
+/**
+ * Payload for the `paste-fallback` event.
+ */
+export type PasteFallback = { reason: PasteFallbackReason; 
+/**
+ * User-facing explanation
+ */
+message: string }
+/**
+ * Why a paste into the previous app fell back to copy-only.
+ */
+export type PasteFallbackReason = 
+/**
+ * The OS has no supported way to paste into other apps (Linux)
+ */
+"unsupported" | 
+/**
+ * No other app had focus when the quick pane was shown
+ */
+"no_previous_app" | 
+/**
+ * That app has quit or its window closed
+ */
+"previous_app_gone" | 
+/**
+ * macOS Accessibility access isn't granted
+ */
+"accessibility_denied" | 
+/**
+ * The app couldn't be brought to the front
+ */
+"activation_failed" | 
+/**
+ * The keystroke couldn't be synthesized
+ */
+"keystroke_failed"
+/**
+ * Result of `paste_into_previous_app`. The transcript is on the clipboard
+ * either way.
+ */
+export type PasteOutcome = { type: "pasted" } | { type: "copied_only"; reason: PasteFallbackReason }
+/**
+ * Coarse size of a command's response.
+ */
+export type PayloadSize = 
+/**
+ * Under 1 KB
+ */
+"tiny" | 
+/**
+ * Under 64 KB
+ */
+"small" | 
+/**
+ * Under 1 MB
+ */
+"medium" | 
+/**
+ * 1 MB or more
+ */
+"large"
+/**
+ * OS permissions the app depends on.
+ */
+export type PermissionDiagnostics = { microphone: Probe<PermissionStatus>; notifications: Probe<PermissionStatus> }
+/**
+ * Whether the OS lets the app use something.
+ */
+export type PermissionStatus = "granted" | "denied" | 
+/**
+ * The user hasn't been asked yet
+ */
+"not_determined" | 
+/**
+ * Blocked by policy (parental controls, MDM)
+ */
+"restricted"
+/**
+ * Category of a pipeline failure, for frontend matching.
+ */
+export type PipelineErrorKind = "recording" | "resampling" | "transcription" | 
+/**
+ * The model file failed its integrity check and was deleted; download it again
+ */
+"model_corrupt"
+/**
+ * A stage of the record → transcribe pipeline.
+ */
+export type PipelineStage = { stage: "idle" } | { stage: "recording" } | { stage: "stopping" } | { stage: "resampling" } | { stage: "transcribing"; percent: number } | { stage: "done"; text_length: number } | { stage: "error"; kind: PipelineErrorKind }
+/**
+ * Payload for the `pipeline-status` event and `get_pipeline_status` snapshot.
+ * Timestamps are milliseconds since the Unix epoch.
+ */
+export type PipelineStatus = { 
+/**
+ * Id shared by every transition of one recording/transcription job
+ */
+job_id: string | null; stage: PipelineStage; 
+/**
+ * True when the job transcribes audio salvaged from a recovery file
+ */
+recovered: boolean; started_at_ms: number; updated_at_ms: number }
+/**
+ * The `preferences-changed` event, carrying the saved preferences.
+ */
+export type PreferencesChanged = AppPreferences
+/**
+ * Payload for the `previous-session-crashed` event, emitted at startup when the
+ * last run ended with a recording or transcription in flight.
+ */
+export type PreviousSessionCrashed = { recovery_sessions: RecoverySessionInfo[]; drafts: TranscriptDraft[] }
+/**
+ * Preferences that decide where PHI can end up and how long it stays.
+ */
+export type PrivacyDiagnostics = { app_lock_enabled: boolean; auto_lock_minutes: number; biometric_unlock: boolean; block_screen_capture: boolean; auto_copy_to_clipboard: boolean; auto_paste_into_previous_app: boolean; clipboard_clear_seconds: number; conceal_clipboard_from_history: boolean; append_to_file: boolean; deidentify_auto_saved: boolean; exclude_database_from_backups: boolean; recovery_retention_hours: number; trash_retention_days: number; audit_retention_days: number; max_idle_while_recording_minutes: number; check_updates_automatically: boolean; command_metrics_enabled: boolean }
+/**
+ * One diagnostics reading. A probe that fails reports `unknown` with why,
+ * rather than failing the whole report.
+ */
+export type Probe<T> = { status: "known"; value: T } | { status: "unknown"; reason: string }
+/**
+ * Quick pane appearance options.
+ */
+export type QuickPaneAppearance = { 
+/**
+ * Fade (and scale on macOS) duration for show/hide in milliseconds. 0 disables it.
+ */
+animation_ms: number; 
+/**
+ * Vibrancy material. Ignored on platforms without window materials.
+ */
+material: QuickPaneMaterial }
+/**
+ * Payload for the `quick-pane-hide-requested` event, sent when a hide was held back.
+ */
+export type QuickPaneHideRequested = { 
+/**
+ * A recording is active or starting/stopping
+ */
+recording: boolean; 
+/**
+ * The frontend flagged a result that hasn't been copied/saved
+ */
+unsaved_result: boolean; 
+/**
+ * The hide came from the shortcut/toggle; pressing again will hide anyway
+ */
+explicit: boolean }
+/**
+ * Background material for the quick pane's vibrancy effect (macOS only).
+ */
+export type QuickPaneMaterial = "hud" | "popover" | "sidebar"
+/**
+ * Quick pane placement mode.
+ */
+export type QuickPanePosition = 
+/**
+ * Centered on the monitor containing the cursor
+ */
+"cursor-monitor" | 
+/**
+ * Dropped down from the tray/menu-bar icon like a MenuBarExtra
+ */
+"menubar-anchored"
+/**
+ * Payload for the `quick-pane-visibility-changed` event.
+ * Emitted from every show/hide path so menu checkmarks reflect the real state.
+ */
+export type QuickPaneVisibilityChanged = { visible: boolean }
+/**
+ * Daily window in local time during which notifications are held back.
+ * Minutes since midnight; `start_minute > end_minute` spans midnight.
+ */
+export type QuietHours = { start_minute: number; end_minute: number }
+/**
+ * Payload for the `recordings-repaired` event, sent at startup when WAVs left
+ * unfinalized by a crash were repaired.
+ */
+export type RecordingsRepaired = { recordings: RepairedRecording[] }
+/**
+ * Payload for the `recovery-degraded` event.
+ * Sent when a recording's recovery file can't be written (e.g. disk full);
+ * the recording continues in memory only.
+ */
+export type RecoveryDegraded = { session_id: string; message: string }
+/**
+ * Error types for recovery operations (typed for frontend matching)
+ */
+export type RecoveryError = 
+/**
+ * File does not exist (expected case, not a failure)
+ */
+{ type: "FileNotFound" } | 
+/**
+ * Filename validation failed
+ */
+{ type: "ValidationError"; message: string } | 
+/**
+ * Data exceeds size limit
+ */
+{ type: "DataTooLarge"; max_bytes: number } | 
+/**
+ * File system read/write error
+ */
+{ type: "IoError"; message: string } | 
+/**
+ * JSON serialization/deserialization error
+ */
+{ type: "ParseError"; message: string } | 
+/**
+ * Recovered audio could not be transcribed (the recovery file is kept)
+ */
+{ type: "TranscriptionFailed"; message: string } | 
+/**
+ * Database read/write error
+ */
+{ type: "DatabaseError"; message: string } | 
+/**
+ * The recovery encryption key is missing from the keychain
+ */
+{ type: "KeyUnavailable" } | 
+/**
+ * A PHI export was attempted without the user acknowledging the consent dialog
+ */
+{ type: "ConsentRequired" } | 
+/**
+ * The app is locked; unlock it first
+ */
+{ type: "Locked" }
+/**
+ * Payload for the `recovery-evicted` event, sent when the oldest recovery
+ * sessions were deleted to keep the recovery directory under its cap.
+ */
+export type RecoveryEvicted = { session_ids: string[]; budget_bytes: number }
+/**
+ * Payload for the `recovery-expired` event, sent when the retention sweep
+ * deleted recovery data older than `recovery_retention_hours`.
+ */
+export type RecoveryExpired = { 
+/**
+ * Recording recovery sessions, including damaged ones
+ */
+sessions_removed: number; drafts_removed: number; 
+/**
+ * Stranded recording WAVs and interrupted temp files
+ */
+files_removed: number }
+/**
+ * Which recovery sessions `purge_recovery` deletes.
+ */
+export type RecoveryPurgeTarget = { type: "session"; id: string } | { type: "all" }
+/**
+ * A recoverable recording found in the recovery directory.
+ * Timestamps are milliseconds since the Unix epoch; sizes are f64 because specta forbids u64.
+ */
+export type RecoverySessionInfo = { id: string; created_at_ms: number; modified_at_ms: number; 
+/**
+ * Approximate audio duration from sample count and recorded rate (0 when damaged)
+ */
+duration_secs: number; size_bytes: number; status: RecoverySessionStatus; 
+/**
+ * The session hit its size cap, so only its most recent audio was kept
+ */
+rolled: boolean }
+/**
+ * Integrity of a recording recovery file.
+ */
+export type RecoverySessionStatus = 
+/**
+ * Recording stopped cleanly and every sample is on disk
+ */
+"complete" | 
+/**
+ * Recording was interrupted; audio up to the last flush is recoverable
+ */
+"truncated" | 
+/**
+ * Header is missing or corrupt; the audio can't be interpreted
+ */
+"damaged" | 
+/**
+ * The encryption key is missing from the keychain; the file can only be purged
+ */
+"unrecoverable"
+/**
+ * Disk usage of the recovery directory, for the storage settings screen.
+ * Sizes are f64 because specta forbids u64.
+ */
+export type RecoveryUsage = { 
+/**
+ * Everything in the recovery directory (recordings plus emergency data)
+ */
+total_bytes: number; 
+/**
+ * Recording recovery files only
+ */
+session_bytes: number; session_count: number; session_cap_bytes: number; dir_cap_bytes: number }
+/**
+ * A cached recording WAV repaired after a crash, ready for `transcribe_and_delete`.
+ */
+export type RepairedRecording = { session_id: string; file_path: string; duration_secs: number }
+/**
+ * Result of `save_transcript`.
+ */
+export type SavedTranscript = { transcript: Transcript; 
+/**
+ * What the de-identification pass replaced, when it was requested
+ */
+deidentification: DeidentificationReport | null; 
+/**
+ * Earlier transcript from today that this one appears to duplicate
+ * (same or nearly the same text, similar duration)
+ */
+duplicate_of: string | null }
+/**
+ * Whether a window is kept out of screen capture.
+ */
+export type ScreenCaptureState = 
+/**
+ * Excluded from screenshots, recording and screen sharing
+ */
+{ type: "blocked" } | 
+/**
+ * Capturable; `block_screen_capture` is off
+ */
+{ type: "allowed" } | 
+/**
+ * `block_screen_capture` is on but the platform can't honour it
+ */
+{ type: "unsupported"; reason: string }
+/**
+ * What a quit is waiting for.
+ */
+export type ShutdownBlockReason = 
+/**
+ * The recording in progress is being stopped and kept for recovery
+ */
+"recording" | 
+/**
+ * A transcription gets a grace period to finish
+ */
+"transcribing"
+/**
+ * Payload for the `shutdown-blocked` event, sent while a quit waits for
+ * in-flight work.
+ */
+export type ShutdownBlocked = { reason: ShutdownBlockReason; 
+/**
+ * When the app quits regardless (ms since the Unix epoch)
+ */
+deadline_ms: number }
+/**
+ * A run of snippet text, highlighted when it matched the search query.
+ */
+export type SnippetSegment = { text: string; highlighted: boolean }
+/**
+ * Result of `clear_category`.
+ */
+export type StorageCleared = { files_deleted: number; bytes_freed: number; 
+/**
+ * Files in use or that couldn't be deleted
+ */
+files_skipped: number }
+/**
+ * Disk use of the app's data. Sizes are in bytes.
+ */
+export type StorageDiagnostics = { database_bytes: Probe<number>; row_counts: Probe<TableRowCount[]>; recovery_bytes: Probe<number>; cache_bytes: Probe<number>; 
+/**
+ * Free space on the volume holding the app data
+ */
+free_disk_bytes: Probe<number> }
+/**
+ * The folders in effect, which portable mode moves beside the executable.
+ */
+export type StorageLocations = { portable: boolean; 
+/**
+ * Preferences, models and recovery files
+ */
+data_dir: string; 
+/**
+ * The databases
+ */
+config_dir: string; 
+/**
+ * Temporary recordings
+ */
+cache_dir: string; log_dir: string }
+/**
+ * Result of `get_storage_usage`: bytes used per category. Drafts live in
+ * the database and show as its `drafts` table.
+ */
+export type StorageUsage = { 
+/**
+ * Milliseconds since the Unix epoch
+ */
+measured_at_ms: number; 
+/**
+ * Largest first
+ */
+models: Probe<ModelStorage[]>; 
+/**
+ * The encrypted database with its journal files
+ */
+database_bytes: Probe<number>; 
+/**
+ * Largest first
+ */
+database_tables: Probe<TableStorage[]>; recovery_bytes: Probe<number>; cache_bytes: Probe<number>; log_bytes: Probe<number>; locations: StorageLocations }
+/**
+ * The machine the app runs on. Sizes are in bytes.
+ */
+export type SystemDiagnostics = { 
+/**
+ * "macos", "windows" or "linux"
+ */
+os: string; os_version: string; arch: string; cpu_model: Probe<string>; 
+/**
+ * Logical cores
+ */
+cpu_cores: Probe<number>; total_memory_bytes: Probe<number>; available_memory_bytes: Probe<number>; 
+/**
+ * Whether whisper and llama can run on the GPU through Metal (macOS)
+ */
+metal_available: Probe<boolean> }
+/**
+ * Rows in one database table.
+ */
+export type TableRowCount = { table: string; rows: number }
+/**
+ * Space a database table takes, indexes included, estimated from the pages
+ * it occupies.
+ */
+export type TableStorage = { table: string; bytes: number }
+/**
+ * How a tag filter combines several tags.
+ */
+export type TagMatch = 
+/**
+ * Transcripts with at least one of the tags
+ */
+"any" | 
+/**
+ * Transcripts with every one of the tags
+ */
+"all"
+/**
+ * A tag and how many transcripts carry it.
+ */
+export type TagUsage = { name: string; transcript_count: number }
+/**
+ * Result of `send_test_notification`.
+ */
+export type TestNotificationResult = { 
+/**
+ * Whether the OS allows this app to show notifications
+ */
+permission_granted: boolean; 
+/**
+ * Whether the sample notification was shown
+ */
+sent: boolean }
+/**
+ * A saved transcript.
+ * Timestamps are milliseconds since the Unix epoch.
+ */
+export type Transcript = { id: string; title: string; text: string; 
+/**
+ * Language the transcript was produced in (None for auto-detect)
+ */
+language: string | null; 
+/**
+ * Whisper model that produced the transcript
+ */
+model_id: string | null; 
+/**
+ * Length of the source audio in seconds (0 when unknown)
+ */
+duration_secs: number; 
+/**
+ * Whitespace-separated words in `text`, kept current on edit
+ */
+word_count: number; 
+/**
+ * Free-text label for the session ("Ward round", "Dr. Berg consult")
+ */
+session_label: string | null; 
+/**
+ * Note this transcript belongs to, if any
+ */
+note_id: string | null; 
+/**
+ * Normalized (lowercase) tag names, sorted
+ */
+tags: string[]; 
+/**
+ * Rule set the transcript was de-identified with when saved (None if it wasn't)
+ */
+deidentified_with: string | null; created_at_ms: number; updated_at_ms: number; 
+/**
+ * When the transcript was moved to the trash (None when it isn't there)
+ */
+deleted_at_ms: number | null }
+/**
+ * Input to `format_transcript`: plain text, or segments when timestamps are
+ * available.
+ */
+export type TranscriptContent = { text: string } | { segments: TranscriptSegment[] }
+/**
+ * Payload for the `transcript-copied` event, sent when the backend put a
+ * transcript on the clipboard. Carries no content.
+ */
+export type TranscriptCopied = { characters: number }
+/**
+ * How a finished transcription is handed over. Unset fields fall back to
+ * the preferences of the same name.
+ */
+export type TranscriptDelivery = { 
+/**
+ * Default: `auto_copy_to_clipboard`
+ */
+copy_to_clipboard: boolean | null; 
+/**
+ * Default: `auto_paste_into_previous_app`. Pasting always copies too.
+ */
+paste_into_previous_app: boolean | null; 
+/**
+ * Default: `output_formatting`
+ */
+formatting: FormattingOptions | null; 
+/**
+ * Default: `append_to_file.enabled`
+ */
+append_to_file: boolean | null }
+/**
+ * An autosaved transcript draft.
+ * Timestamps are milliseconds since the Unix epoch.
+ */
+export type TranscriptDraft = { draft_id: string; content: string; 
+/**
+ * Editor cursor/selection state, passed through untouched
+ */
+cursor_meta: JsonValue | null; created_at_ms: number; updated_at_ms: number }
+/**
+ * Error types for transcript operations (typed for frontend matching)
+ */
+export type TranscriptError = 
+/**
+ * No transcript with this id
+ */
+{ type: "NotFound"; id: string } | 
+/**
+ * No tag with this name
+ */
+{ type: "TagNotFound"; name: string } | 
+/**
+ * No revision with this id on the transcript
+ */
+{ type: "RevisionNotFound"; id: number } | 
+/**
+ * Not saved: transcript `id` has the same content
+ */
+{ type: "Duplicate"; id: string } | 
+/**
+ * The app is locked; unlock it first
+ */
+{ type: "Locked" } | 
+/**
+ * Input validation failed
+ */
+{ type: "ValidationError"; message: string } | 
+/**
+ * Transcript text exceeds size limit
+ */
+{ type: "DataTooLarge"; max_bytes: number } | 
+/**
+ * Database read/write error
+ */
+{ type: "DatabaseError"; message: string } | 
+/**
+ * Export file could not be written
+ */
+{ type: "IoError"; message: string } | 
+/**
+ * An encrypted export didn't open: wrong passphrase, or the file was altered
+ */
+{ type: "WrongPassphrase" }
+/**
+ * File format for transcript exports.
+ */
+export type TranscriptExportFormat = "txt" | "md" | "pdf"
+/**
+ * Payload for the `transcript-export-progress` event, sent after each
+ * transcript is written to a bulk export archive.
+ */
+export type TranscriptExportProgress = { done: number; total: number }
+/**
+ * Filter for `list_transcripts` and `search_transcripts`. All fields are
+ * optional and combined with AND.
+ */
+export type TranscriptFilter = { note_id: string | null; 
+/**
+ * Only transcripts created at or after this time (ms since epoch)
+ */
+created_after_ms: number | null; 
+/**
+ * Only transcripts created before this time (ms since epoch)
+ */
+created_before_ms: number | null; 
+/**
+ * Only transcripts with these tags (empty means no tag filter)
+ */
+tags: string[]; 
+/**
+ * Whether `tags` must all match or any one is enough
+ */
+tag_match: TagMatch; 
+/**
+ * Also match transcripts in the trash
+ */
+include_deleted: boolean }
+/**
+ * Fields for a new transcript; the id and timestamps are assigned on save.
+ */
+export type TranscriptNew = { title?: string; text: string; language?: string | null; model_id?: string | null; duration_secs?: number; note_id?: string | null; session_label?: string | null; 
+/**
+ * Tags to attach; normalized like `set_transcript_tags`
+ */
+tags?: string[]; 
+/**
+ * Scrub identifiers from the title and text with the saved
+ * de-identification rules before anything is stored
+ */
+deidentify?: boolean; 
+/**
+ * Fail with `Duplicate` instead of saving when an equivalent transcript
+ * was saved earlier today
+ */
+reject_duplicates?: boolean }
+/**
+ * Partial update for a transcript. Omitted fields are left unchanged.
+ */
+export type TranscriptPatch = { title: string | null; text: string | null; 
+/**
+ * New owning note; an empty string detaches the transcript from its note
+ */
+note_id: string | null }
+/**
+ * A transcript's title and text as they were before an edit.
+ */
+export type TranscriptRevision = { revision_id: number; transcript_id: string; title: string; text: string; word_count: number; 
+/**
+ * When this version was replaced (ms since epoch)
+ */
+created_at_ms: number }
+/**
+ * Payload for the `transcript-saved` event, sent when `transcribe_and_delete`
+ * auto-saved its result.
+ */
+export type TranscriptSaved = { transcript_id: string; 
+/**
+ * Earlier transcript from today with the same content, if any
+ */
+duplicate_of: string | null }
+/**
+ * A transcript matching a search, best match first.
+ */
+export type TranscriptSearchHit = { id: string; title: string; 
+/**
+ * Excerpt around the match, split into plain and highlighted runs
+ * (structured rather than HTML so transcript text is never rendered as markup)
+ */
+snippet: SnippetSegment[]; created_at_ms: number }
+/**
+ * One timed piece of a transcription, as whisper splits it.
+ */
+export type TranscriptSegment = { start_secs: number; end_secs: number; text: string }
+/**
+ * A transcript without its text, for library lists.
+ */
+export type TranscriptSummary = { id: string; title: string; language: string | null; model_id: string | null; duration_secs: number; word_count: number; session_label: string | null; note_id: string | null; tags: string[]; deidentified_with: string | null; created_at_ms: number; updated_at_ms: number; deleted_at_ms: number | null }
+/**
+ * One page of `list_transcripts` results, most recently created first.
+ */
+export type TranscriptSummaryPage = { transcripts: TranscriptSummary[]; 
+/**
+ * Number of transcripts matching the filter across all pages
+ */
+total: number }
+/**
+ * Transcription speed over the period, from every transcription run
+ * (saved or not).
+ */
+export type TranscriptionPerformance = { transcriptions: number; 
+/**
+ * Mean processing time per transcription (None without any)
+ */
+average_processing_secs: number | null; 
+/**
+ * Mean real-time factor, processing time over audio length (below 1 is
+ * faster than real time)
+ */
+average_rtf: number | null; 
+/**
+ * Most used first
+ */
+models: ModelUsage[] }
+/**
+ * Why `type_out_text` didn't type (all of) the text.
+ */
+export type TypeOutError = 
+/**
+ * macOS Accessibility access isn't granted
+ */
+{ type: "PermissionDenied" } | 
+/**
+ * Long text; call again with `confirmed` to type it anyway
+ */
+{ type: "ConfirmationRequired"; characters: number; estimated_secs: number } | 
+/**
+ * Longer than type-out ever sends; paste it instead
+ */
+{ type: "TooLong"; max_chars: number } | 
+/**
+ * There's no app to type into
+ */
+{ type: "NoTarget"; reason: PasteFallbackReason } | 
+/**
+ * Another type-out is still running
+ */
+{ type: "AlreadyRunning" } | 
+/**
+ * Stopped by `cancel_type_out` after `typed` characters
+ */
+{ type: "Cancelled"; typed: number } | 
+/**
+ * Another app took focus after `typed` characters, so typing stopped
+ */
+{ type: "FocusLost"; typed: number } | 
+/**
+ * A keystroke couldn't be sent
+ */
+{ type: "Failed"; message: string } | 
+/**
+ * The app is locked; unlock it first
+ */
+{ type: "Locked" }
+/**
+ * Payload for the `update-available` event from the background update check.
+ */
+export type UpdateAvailable = { version: string; current_version: string; 
+/**
+ * Full release notes, if the update manifest has any
+ */
+notes: string | null }
+/**
+ * Which releases the updater offers.
+ */
+export type UpdateChannel = 
+/**
+ * Stable releases only
+ */
+"stable" | 
+/**
+ * Pre-releases as well as stable releases
+ */
+"beta"
+/**
+ * Payload for the `update-download-progress` event.
+ */
+export type UpdateDownloadProgress = { downloaded_bytes: number; 
+/**
+ * None when the server doesn't send a length
+ */
+total_bytes: number | null }
+/**
+ * Error types for update operations (typed for frontend matching)
+ */
+export type UpdateError = 
+/**
+ * The update server couldn't be reached or returned an error
+ */
+{ type: "Network"; message: string } | 
+/**
+ * The download doesn't match the app's update signing key
+ */
+{ type: "InvalidSignature" } | 
+/**
+ * There's no update to download
+ */
+{ type: "NoUpdate" } | 
+/**
+ * No update has been installed, so there's nothing to restart into
+ */
+{ type: "NotInstalled" } | 
+/**
+ * A recording or transcription is in progress; the update installs once
+ * it finishes, after a countdown on `update-install-countdown`
+ */
+{ type: "Busy" } | 
+/**
+ * In-app updates aren't available on this platform
+ */
+{ type: "Unsupported" } | 
+/**
+ * Any other updater failure
+ */
+{ type: "Failed"; message: string }
+/**
+ * Result of `check_for_updates`.
+ */
+export type UpdateInfo = { available: boolean; current_version: string; 
+/**
+ * Version on offer; None when there's no update
+ */
+latest_version: string | null; 
+/**
+ * Release notes, if the update manifest has any
+ */
+notes: string | null; 
+/**
+ * Release date (RFC 3339), if the manifest has one
+ */
+pub_date: string | null; 
+/**
+ * Size of the download in bytes, if the manifest lists it
+ */
+download_size: number | null }
+/**
+ * Payload for the `update-install-countdown` event, sent while an install
+ * deferred by a recording or transcription waits to run.
+ */
+export type UpdateInstallCountdown = { 
+/**
+ * Seconds until the app installs and restarts; None while it waits for
+ * the recording or transcription to finish
+ */
+seconds_remaining: number | null }
+/**
+ * Range covered by `get_usage_stats`, ending today (local time).
+ */
+export type UsagePeriod = 
+/**
+ * The last 7 days
+ */
+"week" | 
+/**
+ * The last 30 days
+ */
+"month" | 
+/**
+ * The last 365 days
+ */
+"year"
+/**
+ * Result of `get_usage_stats`.
+ */
+export type UsageStats = { period: UsagePeriod; 
+/**
+ * Start of the first day in the period (ms since epoch)
+ */
+from_ms: number; transcripts: number; total_audio_secs: number; total_words: number; 
+/**
+ * One entry per day in the period, oldest first, including empty days
+ */
+daily: DailyUsage[]; performance: TranscriptionPerformance }
+/**
+ * Payload for the `whisper-model-download-progress` event.
+ */
+export type WhisperModelDownloadProgress = { model_id: string; 
+/**
+ * 0–100
+ */
+percent: number }
+/**
+ * Metadata for a downloadable Whisper model.
+ */
+export type WhisperModelInfo = { id: string; name: string; filename: string; url: string; 
+/**
+ * Approximate size in bytes (used for UI display only). u32 supports up to ~4 GB.
+ */
+size_bytes: number; 
+/**
+ * ISO 639-1 codes this model supports. Empty = all languages.
+ */
+languages: string[]; 
+/**
+ * Whether the model file is present on disk (set dynamically).
+ */
+downloaded: boolean }
+/**
+ * One entry of `get_screen_capture_state`.
+ */
+export type WindowScreenCapture = { 
+/**
+ * Window label (`main` or `quick-pane`)
+ */
+window: string; state: ScreenCaptureState }
+/**
+ * One kind of data the emergency wipe removes, in the order it's wiped.
+ */
+export type WipeCategory = 
+/**
+ * The recording in progress, stopped without saving
+ */
+"recording" | "drafts" | 
+/**
+ * Transcripts, revisions, tags and the audit log
+ */
+"database" | 
+/**
+ * Recording recovery files and emergency data
+ */
+"recovery" | 
+/**
+ * Cached WAVs and temp files
+ */
+"cache" | 
+/**
+ * A transcript the app copied, if still on the clipboard
+ */
+"clipboard" | 
+/**
+ * Encryption keys and the app lock passcode
+ */
+"keychain" | 
+/**
+ * Preferences and other app state
+ */
+"settings"
+/**
+ * Outcome of wiping one category.
+ */
+export type WipeCategoryResult = { category: WipeCategory; success: boolean; 
+/**
+ * Why it failed
+ */
+message: string | null }
+
+/** tauri-specta globals **/
+
+import {
+	invoke as TAURI_INVOKE,
+	Channel as TAURI_CHANNEL,
+} from "@tauri-apps/api/core";
+import * as TAURI_API_EVENT from "@tauri-apps/api/event";
+import { type WebviewWindow as __WebviewWindow__ } from "@tauri-apps/api/webviewWindow";
+
+type __EventObj__<T> = {
+	listen: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.listen<T>>;
+	once: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.once<T>>;
+	emit: null extends T
+		? (payload?: T) => ReturnType<typeof TAURI_API_EVENT.emit>
+		: (payload: T) => ReturnType<typeof TAURI_API_EVENT.emit>;
+};
+
+export type Result<T, E> =
+	| { status: "ok"; data: T }
+	| { status: "error"; error: E };
+
+function __makeEvents__<T extends Record<string, any>>(
 	mappings: Record<keyof T, string>,
 ) {
 	return new Proxy(
@@ -369,3 +3413,5 @@ function __makeEvents__<T extends Record<string, any>>(
 		},
 	);
 }
+
+export const BINDINGS_VERSION = "a2f6a65bb619ca06";
@@ -2,10 +2,12 @@
  * SQLite database connection and schema management.
  *
  * All note data is stored locally on the device — never transmitted.
- * The database file lives in the platform app-data directory managed by Tauri.
+ * The database file lives in the platform app-data directory managed by Tauri,
+ * or in the portable data folder; the backend supplies the connection URL.
  */
 import Database from '@tauri-apps/plugin-sql'
 import { logger } from './logger'
+import { commands, unwrapResult } from './tauri-bindings'
 import type { TemplateSection } from '@/types/templates'
 
 let _db: Database | null = null
@@ -16,7 +18,8 @@ let _db: Database | null = null
  */
 export async function getDb(): Promise<Database> {
   if (_db) return _db
-  _db = await Database.load('sqlite:privacyscribe.db')
+  const url = unwrapResult(await commands.getFrontendDatabaseUrl())
+  _db = await Database.load(url)
   return _db
 }
 
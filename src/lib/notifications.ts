@@ -3,6 +3,7 @@
  */
 
 import { toast } from 'sonner'
+import { formatAppError } from './app-error'
 import { logger } from './logger'
 import { commands } from './tauri-bindings'
 
@@ -52,7 +53,7 @@ export async function notify(
         message ?? null
       )
       if (result.status === 'error') {
-        throw new Error(result.error)
+        throw new Error(formatAppError(result.error))
       }
     } else {
       // Send in-app toast notification
@@ -23,6 +23,7 @@
 
 export { commands, type Result } from './bindings'
 export type {
+  AppError,
   AppPreferences,
   JsonValue,
   RecoveryError,
@@ -1,5 +1,6 @@
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query'
 import { toast } from 'sonner'
+import { formatAppError } from '@/lib/app-error'
 import { logger } from '@/lib/logger'
 import { commands, type AppPreferences } from '@/lib/tauri-bindings'
 
@@ -48,8 +49,9 @@ export function useSavePreferences() {
           error: result.error,
           preferences,
         })
-        toast.error('Failed to save preferences', { description: result.error })
-        throw new Error(result.error)
+        const message = formatAppError(result.error)
+        toast.error('Failed to save preferences', { description: message })
+        throw new Error(message)
       }
 
       logger.info('Preferences saved successfully')